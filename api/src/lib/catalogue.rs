@@ -0,0 +1,358 @@
+use std::{convert::TryFrom, str::FromStr};
+
+use book::models::CatalogueEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Separator of the values of a list in a column of a CSV file
+const LIST_SEPARATOR: char = ';';
+/// Escape of the separator and of itself in the values of a list
+const LIST_ESCAPE: char = '\\';
+
+/// Maximum size of the JSON of the entries imported by one call, so that it fits the frame
+/// length of the book service
+pub const IMPORT_BATCH_SIZE: usize = 4 * 1024 * 1024;
+
+/// Format of a catalogue export, which is imported in the same format
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogueFormat {
+    Csv,
+    Json,
+    /// JSON Lines, one book per line
+    Jsonl,
+}
+
+impl CatalogueFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            CatalogueFormat::Csv => "text/csv; charset=utf-8",
+            CatalogueFormat::Json => "application/json",
+            CatalogueFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CatalogueFormat::Csv => "csv",
+            CatalogueFormat::Json => "json",
+            CatalogueFormat::Jsonl => "jsonl",
+        }
+    }
+
+    /// Writes a page of an export, the first one starts the file
+    pub fn write_page(&self, entries: &[CatalogueEntry], first: bool) -> Result<Vec<u8>, ()> {
+        match self {
+            CatalogueFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(first)
+                    .from_writer(vec![]);
+                if first && entries.is_empty() {
+                    writer.write_record(CSV_HEADER).map_err(|_| ())?;
+                }
+                for entry in entries {
+                    writer.serialize(Row::from(entry)).map_err(|_| ())?;
+                }
+                writer.into_inner().map_err(|_| ())
+            }
+            CatalogueFormat::Json => {
+                let mut data = if first { b"[".to_vec() } else { vec![] };
+                for (i, entry) in entries.iter().enumerate() {
+                    if !first || i > 0 {
+                        data.push(b',');
+                    }
+                    serde_json::to_writer(&mut data, entry).map_err(|_| ())?;
+                }
+                Ok(data)
+            }
+            CatalogueFormat::Jsonl => {
+                let mut data = vec![];
+                for entry in entries {
+                    serde_json::to_writer(&mut data, entry).map_err(|_| ())?;
+                    data.push(b'\n');
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    /// Ends the file of an export after its last page
+    pub fn end(&self) -> &'static [u8] {
+        match self {
+            CatalogueFormat::Json => b"]",
+            _ => b"",
+        }
+    }
+
+    /// Reads the books of a file of an export
+    pub fn read(&self, data: &[u8]) -> Result<Vec<CatalogueEntry>, ()> {
+        match self {
+            CatalogueFormat::Csv => csv::Reader::from_reader(data)
+                .deserialize::<Row>()
+                .map(|row| row.map_err(|_| ()).and_then(CatalogueEntry::try_from))
+                .collect(),
+            CatalogueFormat::Json => serde_json::from_slice(data).map_err(|_| ()),
+            CatalogueFormat::Jsonl => data
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+                .map(|line| serde_json::from_slice(line).map_err(|_| ()))
+                .collect(),
+        }
+    }
+}
+
+/// Splits the entries of an import into batches, whose JSON doesn't exceed the given size
+/// unless a batch holds a single entry
+pub fn batches(entries: Vec<CatalogueEntry>, max_size: usize) -> Vec<Vec<CatalogueEntry>> {
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut size = 0;
+    for entry in entries {
+        let entry_size = serde_json::to_vec(&entry).map_or(0, |data| data.len()) + 1;
+        if !batch.is_empty() && size + entry_size > max_size {
+            batches.push(batch);
+            batch = vec![];
+            size = 0;
+        }
+        size += entry_size;
+        batch.push(entry);
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+/// Columns of a CSV file, in the order of the fields of a row
+const CSV_HEADER: [&str; 22] = [
+    "id",
+    "code_identifier",
+    "isbn",
+    "issn",
+    "release_year",
+    "edition",
+    "pages",
+    "title",
+    "subtitle",
+    "description",
+    "category_id",
+    "language_id",
+    "publisher_id",
+    "series_id",
+    "author_ids",
+    "authors",
+    "editor_ids",
+    "subject_area_ids",
+    "tag_ids",
+    "tags",
+    "copies",
+    "created_at",
+];
+
+/// Book in a row of a CSV file, whose lists are joined into one column each
+#[derive(Debug, Deserialize, Serialize)]
+struct Row {
+    id: Uuid,
+    code_identifier: i32,
+    isbn: Option<String>,
+    issn: Option<String>,
+    release_year: i16,
+    edition: Option<i32>,
+    pages: Option<i32>,
+    title: String,
+    subtitle: Option<String>,
+    description: Option<String>,
+    category_id: Uuid,
+    language_id: Uuid,
+    publisher_id: Uuid,
+    series_id: Option<Uuid>,
+    author_ids: String,
+    authors: String,
+    editor_ids: String,
+    subject_area_ids: String,
+    tag_ids: String,
+    tags: String,
+    #[serde(default)]
+    copies: i64,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|value| escape(&value.to_string()))
+        .collect::<Vec<_>>()
+        .join(&LIST_SEPARATOR.to_string())
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == LIST_SEPARATOR || c == LIST_ESCAPE {
+            escaped.push(LIST_ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn split<T: FromStr>(values: &str) -> Result<Vec<T>, ()> {
+    let mut items = vec![];
+    let mut item = String::new();
+    let mut chars = values.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            LIST_ESCAPE => item.push(chars.next().ok_or(())?),
+            LIST_SEPARATOR => items.push(std::mem::take(&mut item)),
+            _ => item.push(c),
+        }
+    }
+    items.push(item);
+
+    items
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse().map_err(|_| ()))
+        .collect()
+}
+
+impl From<&CatalogueEntry> for Row {
+    fn from(entry: &CatalogueEntry) -> Self {
+        Row {
+            id: entry.id,
+            code_identifier: entry.code_identifier,
+            isbn: entry.isbn.clone(),
+            issn: entry.issn.clone(),
+            release_year: entry.release_year,
+            edition: entry.edition,
+            pages: entry.pages,
+            title: entry.title.clone(),
+            subtitle: entry.subtitle.clone(),
+            description: entry.description.clone(),
+            category_id: entry.category_id,
+            language_id: entry.language_id,
+            publisher_id: entry.publisher_id,
+            series_id: entry.series_id,
+            author_ids: join(&entry.author_ids),
+            authors: join(&entry.authors),
+            editor_ids: join(&entry.editor_ids),
+            subject_area_ids: join(&entry.subject_area_ids),
+            tag_ids: join(&entry.tag_ids),
+            tags: join(&entry.tags),
+            copies: entry.copies,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+impl TryFrom<Row> for CatalogueEntry {
+    type Error = ();
+
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        Ok(CatalogueEntry {
+            id: row.id,
+            code_identifier: row.code_identifier,
+            isbn: row.isbn,
+            issn: row.issn,
+            release_year: row.release_year,
+            edition: row.edition,
+            pages: row.pages,
+            title: row.title,
+            subtitle: row.subtitle,
+            description: row.description,
+            category_id: row.category_id,
+            language_id: row.language_id,
+            publisher_id: row.publisher_id,
+            series_id: row.series_id,
+            author_ids: split(&row.author_ids)?,
+            authors: split(&row.authors)?,
+            editor_ids: split(&row.editor_ids)?,
+            subject_area_ids: split(&row.subject_area_ids)?,
+            tag_ids: split(&row.tag_ids)?,
+            tags: split(&row.tags)?,
+            copies: row.copies,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str) -> CatalogueEntry {
+        CatalogueEntry {
+            id: Uuid::new_v4(),
+            code_identifier: 7,
+            isbn: Some("9783161484100".into()),
+            issn: None,
+            release_year: 2021,
+            edition: Some(2),
+            pages: None,
+            title: title.into(),
+            subtitle: None,
+            description: Some("Line one,\n\"line\" two".into()),
+            category_id: Uuid::new_v4(),
+            language_id: Uuid::new_v4(),
+            publisher_id: Uuid::new_v4(),
+            series_id: Some(Uuid::new_v4()),
+            author_ids: vec![Uuid::new_v4(), Uuid::new_v4()],
+            authors: vec!["Doe, Jane".into(), "Roe, Richard".into()],
+            editor_ids: vec![],
+            subject_area_ids: vec![Uuid::new_v4()],
+            tag_ids: vec![Uuid::new_v4()],
+            tags: vec!["new".into(), "sci-fi; fantasy".into()],
+            copies: 3,
+            created_at: Some(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn ut_catalogue_round_trip() {
+        let entries = vec![entry("First"), entry("Second"), entry("Third")];
+        for format in [
+            CatalogueFormat::Csv,
+            CatalogueFormat::Json,
+            CatalogueFormat::Jsonl,
+        ] {
+            let mut data = format.write_page(&entries[..2], true).unwrap();
+            data.extend(format.write_page(&entries[2..], false).unwrap());
+            data.extend(format.end());
+            assert_eq!(format.read(&data), Ok(entries.clone()), "{:?}", format);
+
+            let mut data = format.write_page(&[], true).unwrap();
+            data.extend(format.end());
+            assert_eq!(format.read(&data), Ok(vec![]), "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn ut_catalogue_batches() {
+        let entries = vec![entry("First"), entry("Second"), entry("Third")];
+        let size = serde_json::to_vec(&entry("Longest")).unwrap().len() + 1;
+
+        assert_eq!(
+            vec![entries[..2].to_vec(), entries[2..].to_vec()],
+            batches(entries.clone(), 2 * size)
+        );
+
+        let single = batches(entries.clone(), size / 2);
+        assert_eq!(3, single.len());
+        assert_eq!(entries, single.concat());
+
+        assert!(batches(vec![], size).is_empty());
+    }
+
+    #[test]
+    fn ut_catalogue_list_separator() {
+        let tags = vec!["sci-fi; fantasy".to_string(), "back\\slash".into()];
+
+        let joined = join(&tags);
+        assert_eq!("sci-fi\\; fantasy;back\\\\slash", joined);
+        assert_eq!(Ok(tags), split::<String>(&joined));
+        assert_eq!(Err(()), split::<String>("trailing\\"));
+    }
+}
@@ -12,6 +12,8 @@ pub struct Configuration {
     service_socket: SocketAddr,
     identity_socket: SocketAddr,
     book_socket: SocketAddr,
+    borrow_socket: SocketAddr,
+    notification_socket: SocketAddr,
 }
 
 #[derive(Debug, PartialEq)]
@@ -66,6 +68,8 @@ impl Configuration {
             service_socket: Configuration::init_service_socket()?,
             book_socket: Configuration::init_book_socket()?,
             identity_socket: Configuration::init_identity_socket()?,
+            borrow_socket: Configuration::init_borrow_socket()?,
+            notification_socket: Configuration::init_notification_socket()?,
         })
     }
 
@@ -136,6 +140,54 @@ impl Configuration {
         }
     }
 
+    fn init_borrow_socket() -> Result<SocketAddr, ConfigurationError> {
+        let key = "BORROW_SOCKET";
+        match var(key) {
+            Ok(socket) => {
+                let sockets = socket
+                    .to_socket_addrs()
+                    .map_err(|_| ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid))?
+                    .filter(|socket| socket.is_ipv4())
+                    .collect::<Vec<SocketAddr>>();
+                Ok(*sockets.get(0).ok_or_else(|| {
+                    ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid)
+                })?)
+            }
+            Err(VarError::NotPresent) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueRequired,
+            )),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_notification_socket() -> Result<SocketAddr, ConfigurationError> {
+        let key = "NOTIFICATION_SOCKET";
+        match var(key) {
+            Ok(socket) => {
+                let sockets = socket
+                    .to_socket_addrs()
+                    .map_err(|_| ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid))?
+                    .filter(|socket| socket.is_ipv4())
+                    .collect::<Vec<SocketAddr>>();
+                Ok(*sockets.get(0).ok_or_else(|| {
+                    ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid)
+                })?)
+            }
+            Err(VarError::NotPresent) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueRequired,
+            )),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
     pub fn get_service_socket(&self) -> SocketAddr {
         self.service_socket
     }
@@ -147,6 +199,14 @@ impl Configuration {
     pub fn get_book_socket(&self) -> SocketAddr {
         self.book_socket
     }
+
+    pub fn get_borrow_socket(&self) -> SocketAddr {
+        self.borrow_socket
+    }
+
+    pub fn get_notification_socket(&self) -> SocketAddr {
+        self.notification_socket
+    }
 }
 
 pub fn get_configuration() -> Configuration {
@@ -165,11 +225,15 @@ mod tests {
         remove_var("SERVICE_SOCKET");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
             book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
         };
         let result = Configuration::init();
 
@@ -182,11 +246,15 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8000");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000),
             identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
             book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
         };
         let result = Configuration::init();
 
@@ -199,6 +267,8 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result =
             ConfigurationError::new("SERVICE_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -213,11 +283,15 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8080");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
             book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
         };
         let result = Configuration::init();
 
@@ -230,6 +304,8 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8082");
         set_var("IDENTITY_SOCKET", "127.0.0.1");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result =
             ConfigurationError::new("IDENTITY_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -244,6 +320,8 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8080");
         remove_var("IDENTITY_SOCKET");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result =
             ConfigurationError::new("IDENTITY_SOCKET".into(), ErrorKind::EnvVarValueRequired);
@@ -258,11 +336,15 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8080");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
             book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
         };
         let result = Configuration::init();
 
@@ -275,6 +357,8 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8082");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         set_var("BOOK_SOCKET", "127.0.0.1");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result =
             ConfigurationError::new("BOOK_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -289,6 +373,8 @@ mod tests {
         set_var("SERVICE_SOCKET", "127.0.0.1:8080");
         set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
         remove_var("BOOK_SOCKET");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
 
         let expected_result =
             ConfigurationError::new("BOOK_SOCKET".into(), ErrorKind::EnvVarValueRequired);
@@ -296,4 +382,110 @@ mod tests {
 
         assert_eq!(Err(expected_result), result)
     }
+
+    #[test]
+    #[ignore]
+    fn uts_borrow_socket_input_valid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_borrow_socket_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
+
+        let expected_result =
+            ConfigurationError::new("BORROW_SOCKET".into(), ErrorKind::SocketAddrInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_borrow_socket_input_not_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        remove_var("BORROW_SOCKET");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
+
+        let expected_result =
+            ConfigurationError::new("BORROW_SOCKET".into(), ErrorKind::EnvVarValueRequired);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_input_valid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8084");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            identity_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            book_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8082),
+            borrow_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8083),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8084),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1");
+
+        let expected_result =
+            ConfigurationError::new("NOTIFICATION_SOCKET".into(), ErrorKind::SocketAddrInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_input_not_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("IDENTITY_SOCKET", "127.0.0.1:8081");
+        set_var("BOOK_SOCKET", "127.0.0.1:8082");
+        set_var("BORROW_SOCKET", "127.0.0.1:8083");
+        remove_var("NOTIFICATION_SOCKET");
+
+        let expected_result =
+            ConfigurationError::new("NOTIFICATION_SOCKET".into(), ErrorKind::EnvVarValueRequired);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
 }
@@ -1,25 +1,193 @@
-use std::net::SocketAddr;
+use std::{io, net::SocketAddr};
 
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use tarpc::context;
 use uuid::Uuid;
-use warp::{reject::Rejection, Reply};
+use warp::{
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+        Response, StatusCode,
+    },
+    hyper::{body::Bytes, Body},
+    reject::Rejection,
+    reply::{with_status, Reply},
+};
 
-use book::init_rpc_client;
-use helpers::{filters, rpc::Error};
+use book::{
+    init_rpc_client,
+    models::{
+        Book, BookRelations, CatalogueEntry, ImportFile, ImportFormat, ImportOptions, ImportReport,
+        NameDetails, PersonDetails, PersonPatch, RetirementReason, SeriesDetails, SeriesPatch,
+    },
+    service::BookServiceClient,
+};
+use borrow::rpc::get_rpc_client as get_borrow_client;
+use helpers::{
+    filters::{self, Cursor, Items, Paginated},
+    rpc::{Error, RpcResult},
+};
+use notification::rpc::{get_rpc_client as get_notification_client, models::Announcement};
 
 use crate::{
-    rejections::{not_found, InternalServerError},
-    responses::{json_object_reply, json_vector_reply},
+    catalogue::{self, CatalogueFormat},
+    filters::authorization::Session,
+    rejections::{not_found, BadRequest, Conflict, InternalServerError},
+    responses::{json_object_reply, json_page_reply, json_vector_reply},
 };
 
+/// Book with its relations, which were requested to be included
+#[derive(Serialize)]
+pub struct ExpandedBook {
+    #[serde(flatten)]
+    book: Book,
+    #[serde(flatten)]
+    relations: Option<BookRelations>,
+}
+
+#[derive(Deserialize)]
+pub struct PersonMerge {
+    duplicate_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct CopyAcquisition {
+    count: u32,
+}
+
+#[derive(Deserialize)]
+pub struct CopyRetirement {
+    reason: RetirementReason,
+}
+
+/// Format of an import file and values of the books it creates,
+/// which are only written if it isn't a dry run
+#[derive(Deserialize)]
+pub struct BookImport {
+    format: ImportFormat,
+    category_id: Uuid,
+    code_identifier: i32,
+    #[serde(default = "dry_run")]
+    dry_run: bool,
+}
+
+/// Imports are dry runs unless stated otherwise, so that their report can be checked first
+fn dry_run() -> bool {
+    true
+}
+
+/// Format of a catalogue export
+#[derive(Deserialize)]
+pub struct CatalogueExport {
+    format: CatalogueFormat,
+}
+
+/// Format of a catalogue export, which is imported unless it is a dry run
+#[derive(Deserialize)]
+pub struct CatalogueImport {
+    format: CatalogueFormat,
+    #[serde(default = "dry_run")]
+    dry_run: bool,
+}
+
+/// Number of books read by one call while an export is streamed
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+/// Maps the error of a write to a rejection, which tells the client what went wrong
+fn write_rejection(error: Error, entity: &str) -> Rejection {
+    match error {
+        Error::NotFound => not_found(),
+        Error::InvalidInput => {
+            BadRequest(format!("Invalid {} or unknown reference", entity)).into()
+        }
+        Error::AlreadyExists => Conflict(format!("The {} already exists", entity)).into(),
+        Error::InUse => Conflict(format!("The {} is still referenced", entity)).into(),
+        _ => InternalServerError().into(),
+    }
+}
+
+/// Maps the error of a paginated read to a rejection
+fn page_rejection(error: Error) -> Rejection {
+    match error {
+        Error::NotFound => not_found(),
+        Error::InvalidInput => BadRequest("The cursor or the items are invalid".into()).into(),
+        _ => InternalServerError().into(),
+    }
+}
+
+/// Reads the requested relations of the books by one call for all of them
+async fn get_relations(
+    client: &BookServiceClient,
+    books: &[Book],
+    include: filters::Include,
+) -> Result<Option<Vec<BookRelations>>, Rejection> {
+    if include.is_empty() {
+        return Ok(None);
+    }
+
+    let ids = books.iter().map(|book| book.id).collect();
+    match client
+        .get_book_relations(context::current(), ids, include)
+        .await
+    {
+        Ok(Ok(relations)) => Ok(Some(relations)),
+        _ => Err(InternalServerError().into()),
+    }
+}
+
+fn expand(books: Vec<Book>, relations: Option<Vec<BookRelations>>) -> Vec<ExpandedBook> {
+    let mut relations = relations.map(Vec::into_iter);
+    books
+        .into_iter()
+        .map(|book| ExpandedBook {
+            book,
+            relations: relations.as_mut().and_then(Iterator::next),
+        })
+        .collect()
+}
+
 pub async fn get_books(
     addr: SocketAddr,
-    page: filters::Page,
+    page: filters::KeysetPage,
     book: filters::Book,
+    include: filters::Include,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(books) = client.get_books(context::current(), page, book).await {
-            return Ok(json_vector_reply(&books.unwrap()));
+        if let Ok(rpc_result) = client.get_books(context::current(), page, book).await {
+            match rpc_result {
+                Ok(books) => {
+                    let relations = get_relations(&client, books.get_items(), include).await?;
+                    return Ok(json_page_reply(
+                        &books.map(|books| expand(books, relations)),
+                    ));
+                }
+                Err(Error::InvalidInput) => return Err(BadRequest(
+                    "The cursor does not belong to the sort order or the items are less than one"
+                        .into(),
+                )
+                .into()),
+                Err(_) => return Err(InternalServerError().into()),
+            }
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn search_books(
+    addr: SocketAddr,
+    search: filters::Search,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.search_books(context::current(), search).await {
+            match rpc_result {
+                Ok(books) => return Ok(json_vector_reply(&books)),
+                Err(Error::InvalidInput) => return Err(BadRequest(
+                    "The search needs 1 to 16 terms within 200 characters and at least one item"
+                        .into(),
+                )
+                .into()),
+                _ => {}
+            }
         }
     }
     Err(InternalServerError().into())
@@ -27,8 +195,11 @@ pub async fn get_books(
 
 pub async fn get_authors(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(authors) = client.get_authors(context::current(), page).await {
-            return Ok(json_vector_reply(&authors.unwrap()));
+        if let Ok(rpc_result) = client.get_authors(context::current(), page).await {
+            return match rpc_result {
+                Ok(authors) => Ok(json_page_reply(&authors)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -39,8 +210,11 @@ pub async fn get_categories(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(categories) = client.get_categories(context::current(), page).await {
-            return Ok(json_vector_reply(&categories.unwrap()));
+        if let Ok(rpc_result) = client.get_categories(context::current(), page).await {
+            return match rpc_result {
+                Ok(categories) => Ok(json_page_reply(&categories)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -48,8 +222,11 @@ pub async fn get_categories(
 
 pub async fn get_copies(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(copies) = client.get_copies(context::current(), page).await {
-            return Ok(json_vector_reply(&copies.unwrap()));
+        if let Ok(rpc_result) = client.get_copies(context::current(), page).await {
+            return match rpc_result {
+                Ok(copies) => Ok(json_page_reply(&copies)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -57,8 +234,11 @@ pub async fn get_copies(addr: SocketAddr, page: filters::Page) -> Result<impl Re
 
 pub async fn get_editors(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(editors) = client.get_editors(context::current(), page).await {
-            return Ok(json_vector_reply(&editors.unwrap()));
+        if let Ok(rpc_result) = client.get_editors(context::current(), page).await {
+            return match rpc_result {
+                Ok(editors) => Ok(json_page_reply(&editors)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -66,8 +246,11 @@ pub async fn get_editors(addr: SocketAddr, page: filters::Page) -> Result<impl R
 
 pub async fn get_languages(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(languages) = client.get_languages(context::current(), page).await {
-            return Ok(json_vector_reply(&languages.unwrap()));
+        if let Ok(rpc_result) = client.get_languages(context::current(), page).await {
+            return match rpc_result {
+                Ok(languages) => Ok(json_page_reply(&languages)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -78,8 +261,11 @@ pub async fn get_publishers(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(publishers) = client.get_publishers(context::current(), page).await {
-            return Ok(json_vector_reply(&publishers.unwrap()));
+        if let Ok(rpc_result) = client.get_publishers(context::current(), page).await {
+            return match rpc_result {
+                Ok(publishers) => Ok(json_page_reply(&publishers)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -87,8 +273,11 @@ pub async fn get_publishers(
 
 pub async fn get_series(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(series) = client.get_series(context::current(), page).await {
-            return Ok(json_vector_reply(&series.unwrap()));
+        if let Ok(rpc_result) = client.get_series(context::current(), page).await {
+            return match rpc_result {
+                Ok(series) => Ok(json_page_reply(&series)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -99,8 +288,11 @@ pub async fn get_subject_areas(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(subject_areas) = client.get_subject_areas(context::current(), page).await {
-            return Ok(json_vector_reply(&subject_areas.unwrap()));
+        if let Ok(rpc_result) = client.get_subject_areas(context::current(), page).await {
+            return match rpc_result {
+                Ok(subject_areas) => Ok(json_page_reply(&subject_areas)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -108,18 +300,29 @@ pub async fn get_subject_areas(
 
 pub async fn get_tags(addr: SocketAddr, page: filters::Page) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(tags) = client.get_tags(context::current(), page).await {
-            return Ok(json_vector_reply(&tags.unwrap()));
+        if let Ok(rpc_result) = client.get_tags(context::current(), page).await {
+            return match rpc_result {
+                Ok(tags) => Ok(json_page_reply(&tags)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
 }
 
-pub async fn get_book_by_id(id: Uuid, addr: SocketAddr) -> Result<impl Reply, Rejection> {
+pub async fn get_book_by_id(
+    id: Uuid,
+    addr: SocketAddr,
+    include: filters::Include,
+) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
         if let Ok(rpc_result) = client.get_book_by_id(context::current(), id).await {
             match rpc_result {
-                Ok(book) => return Ok(json_object_reply(&book)),
+                Ok(book) => {
+                    let books = vec![book];
+                    let relations = get_relations(&client, &books, include).await?;
+                    return Ok(json_object_reply(&expand(books, relations)[0]));
+                }
                 Err(Error::NotFound) => return Err(not_found()),
                 _ => {}
             }
@@ -167,6 +370,25 @@ pub async fn get_copy_by_id(id: Uuid, addr: SocketAddr) -> Result<impl Reply, Re
     Err(InternalServerError().into())
 }
 
+pub async fn get_copy_by_label(label: String, addr: SocketAddr) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.get_copy_by_label(context::current(), label).await {
+            match rpc_result {
+                Ok(copy) => return Ok(json_object_reply(&copy)),
+                Err(Error::NotFound) => return Err(not_found()),
+                Err(Error::InvalidInput) => {
+                    return Err(BadRequest("The label is not of the form code-copy".into()).into())
+                }
+                Err(Error::InvalidData) => {
+                    return Err(Conflict("The label matches more than one copy".into()).into())
+                }
+                _ => {}
+            }
+        }
+    }
+    Err(InternalServerError().into())
+}
+
 pub async fn get_editor_by_id(id: Uuid, addr: SocketAddr) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
         if let Ok(rpc_result) = client.get_editor_by_id(context::current(), id).await {
@@ -251,11 +473,14 @@ pub async fn get_authors_by_book_id(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(authors) = client
+        if let Ok(rpc_result) = client
             .get_authors_by_book_id(context::current(), id, page)
             .await
         {
-            return Ok(json_object_reply(&authors));
+            return match rpc_result {
+                Ok(authors) => Ok(json_page_reply(&authors)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -276,11 +501,14 @@ pub async fn get_copies_by_book_id(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(copies) = client
+        if let Ok(rpc_result) = client
             .get_copies_by_book_id(context::current(), id, page)
             .await
         {
-            return Ok(json_object_reply(&copies));
+            return match rpc_result {
+                Ok(copies) => Ok(json_page_reply(&copies)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -292,11 +520,14 @@ pub async fn get_editors_by_book_id(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(editors) = client
+        if let Ok(rpc_result) = client
             .get_editors_by_book_id(context::current(), id, page)
             .await
         {
-            return Ok(json_object_reply(&editors));
+            return match rpc_result {
+                Ok(editors) => Ok(json_page_reply(&editors)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -338,11 +569,14 @@ pub async fn get_subject_areas_by_book_id(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(authors) = client
+        if let Ok(rpc_result) = client
             .get_subject_areas_by_book_id(context::current(), id, page)
             .await
         {
-            return Ok(json_vector_reply(&authors.unwrap()));
+            return match rpc_result {
+                Ok(subject_areas) => Ok(json_page_reply(&subject_areas)),
+                Err(e) => Err(page_rejection(e)),
+            };
         }
     }
     Err(InternalServerError().into())
@@ -354,11 +588,573 @@ pub async fn get_tags_by_book_id(
     page: filters::Page,
 ) -> Result<impl Reply, Rejection> {
     if let Ok(client) = init_rpc_client(&addr).await {
-        if let Ok(tags) = client
+        if let Ok(rpc_result) = client
             .get_tags_by_book_id(context::current(), id, page)
             .await
         {
-            return Ok(json_vector_reply(&tags.unwrap()));
+            return match rpc_result {
+                Ok(tags) => Ok(json_page_reply(&tags)),
+                Err(e) => Err(page_rejection(e)),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn import_books(
+    import: BookImport,
+    body: Bytes,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client
+            .import_books(
+                context::current(),
+                import.format,
+                ImportFile(body.to_vec()),
+                ImportOptions {
+                    category_id: import.category_id,
+                    code_identifier: import.code_identifier,
+                },
+                import.dry_run,
+            )
+            .await
+        {
+            return match rpc_result {
+                Ok(report) => Ok(json_object_reply(&report)),
+                Err(Error::InvalidInput) => Err(BadRequest(
+                    "Invalid file, unknown category or negative code identifier".into(),
+                )
+                .into()),
+                Err(e) => Err(write_rejection(e, "book")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+async fn export_page(
+    client: &BookServiceClient,
+    cursor: Cursor,
+) -> RpcResult<Paginated<CatalogueEntry>> {
+    client
+        .export_books(
+            context::current(),
+            filters::Page::new(cursor, Items::new(EXPORT_PAGE_SIZE)),
+        )
+        .await
+        .map_err(|_| Error::InternalError)?
+}
+
+/// Streams the books page by page, so that the whole catalogue is never held in memory.
+/// The first page is read before the response starts, so that its failure is reported.
+pub async fn export_books(
+    export: CatalogueExport,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    let format = export.format;
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(page) = export_page(&client, Cursor::default()).await {
+            let first = format
+                .write_page(page.get_items(), true)
+                .map_err(|_| InternalServerError())?;
+            let pages = stream::try_unfold(page.get_next().cloned(), move |cursor| {
+                let client = client.clone();
+                async move {
+                    let cursor = match cursor {
+                        Some(cursor) => cursor,
+                        None => return Ok(None),
+                    };
+                    // a failure ends the response early, as its status is already sent
+                    let failed = || io::Error::new(io::ErrorKind::Other, "export failed");
+                    let page = export_page(&client, cursor).await.map_err(|_| failed())?;
+                    let data = format
+                        .write_page(page.get_items(), false)
+                        .map_err(|_| failed())?;
+                    Ok(Some((data, page.get_next().cloned())))
+                }
+            });
+            let body = stream::iter(vec![Ok::<_, io::Error>(first)])
+                .chain(pages)
+                .chain(stream::iter(vec![Ok(format.end().to_vec())]));
+
+            return Response::builder()
+                .header(CONTENT_TYPE, format.content_type())
+                .header(
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"books.{}\"", format.extension()),
+                )
+                .body(Body::wrap_stream(body))
+                .map_err(|_| InternalServerError().into());
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn import_catalogue(
+    import: CatalogueImport,
+    body: Bytes,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    let entries = import
+        .format
+        .read(&body)
+        .map_err(|_| BadRequest("Invalid file".into()))?;
+    if let Ok(client) = init_rpc_client(&addr).await {
+        // the entries are sent in batches, which each fit the frame length of the book service
+        let mut batches = catalogue::batches(entries, catalogue::IMPORT_BATCH_SIZE).into_iter();
+        let mut report =
+            import_catalogue_batch(&client, batches.next().unwrap_or_default(), import.dry_run)
+                .await?;
+        for batch in batches {
+            report.extend(import_catalogue_batch(&client, batch, import.dry_run).await?);
+        }
+        return Ok(json_object_reply(&report));
+    }
+    Err(InternalServerError().into())
+}
+
+/// Imports a batch of the entries of a catalogue, which the book service writes in one
+/// transaction
+async fn import_catalogue_batch(
+    client: &BookServiceClient,
+    entries: Vec<CatalogueEntry>,
+    dry_run: bool,
+) -> Result<ImportReport, Rejection> {
+    match client
+        .import_catalogue(context::current(), entries, dry_run)
+        .await
+    {
+        Ok(Ok(report)) => Ok(report),
+        Ok(Err(e)) => Err(write_rejection(e, "book")),
+        Err(_) => Err(InternalServerError().into()),
+    }
+}
+
+pub async fn create_category(
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_category(context::current(), body).await {
+            return match rpc_result {
+                Ok(category) => Ok(with_status(
+                    json_object_reply(&category),
+                    StatusCode::CREATED,
+                )),
+                Err(e) => Err(write_rejection(e, "category")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_category(
+    id: Uuid,
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.update_category(context::current(), id, body).await {
+            return match rpc_result {
+                Ok(category) => Ok(json_object_reply(&category)),
+                Err(e) => Err(write_rejection(e, "category")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_category(
+    id: Uuid,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_category(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "category")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_publisher(
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_publisher(context::current(), body).await {
+            return match rpc_result {
+                Ok(publisher) => Ok(with_status(
+                    json_object_reply(&publisher),
+                    StatusCode::CREATED,
+                )),
+                Err(e) => Err(write_rejection(e, "publisher")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_publisher(
+    id: Uuid,
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.update_publisher(context::current(), id, body).await {
+            return match rpc_result {
+                Ok(publisher) => Ok(json_object_reply(&publisher)),
+                Err(e) => Err(write_rejection(e, "publisher")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_publisher(
+    id: Uuid,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_publisher(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "publisher")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_series(
+    body: SeriesDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_series(context::current(), body).await {
+            return match rpc_result {
+                Ok(series) => Ok(with_status(json_object_reply(&series), StatusCode::CREATED)),
+                Err(e) => Err(write_rejection(e, "series")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_series(
+    id: Uuid,
+    body: SeriesPatch,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.update_series(context::current(), id, body).await {
+            return match rpc_result {
+                Ok(series) => Ok(json_object_reply(&series)),
+                Err(e) => Err(write_rejection(e, "series")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_series(
+    id: Uuid,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_series(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "series")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_subject_area(
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_subject_area(context::current(), body).await {
+            return match rpc_result {
+                Ok(subject_area) => Ok(with_status(
+                    json_object_reply(&subject_area),
+                    StatusCode::CREATED,
+                )),
+                Err(e) => Err(write_rejection(e, "subject area")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_subject_area(
+    id: Uuid,
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client
+            .update_subject_area(context::current(), id, body)
+            .await
+        {
+            return match rpc_result {
+                Ok(subject_area) => Ok(json_object_reply(&subject_area)),
+                Err(e) => Err(write_rejection(e, "subject area")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_subject_area(
+    id: Uuid,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_subject_area(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "subject area")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_tag(
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_tag(context::current(), body).await {
+            return match rpc_result {
+                Ok(tag) => Ok(with_status(json_object_reply(&tag), StatusCode::CREATED)),
+                Err(e) => Err(write_rejection(e, "tag")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_tag(
+    id: Uuid,
+    body: NameDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.update_tag(context::current(), id, body).await {
+            return match rpc_result {
+                Ok(tag) => Ok(json_object_reply(&tag)),
+                Err(e) => Err(write_rejection(e, "tag")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_tag(id: Uuid, addr: SocketAddr, _: Session) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_tag(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "tag")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_person(
+    body: PersonDetails,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.create_person(context::current(), body).await {
+            return match rpc_result {
+                Ok(person) => Ok(with_status(json_object_reply(&person), StatusCode::CREATED)),
+                Err(e) => Err(write_rejection(e, "person")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_person(
+    id: Uuid,
+    body: PersonPatch,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.update_person(context::current(), id, body).await {
+            return match rpc_result {
+                Ok(person) => Ok(json_object_reply(&person)),
+                Err(e) => Err(write_rejection(e, "person")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn delete_person(
+    id: Uuid,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client.delete_person(context::current(), id).await {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(e) => Err(write_rejection(e, "person")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn merge_persons(
+    id: Uuid,
+    body: PersonMerge,
+    addr: SocketAddr,
+    _: Session,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client
+            .merge_persons(context::current(), id, body.duplicate_id)
+            .await
+        {
+            return match rpc_result {
+                Ok(person) => Ok(json_object_reply(&person)),
+                Err(e) => Err(write_rejection(e, "person")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_copies(
+    book_id: Uuid,
+    body: CopyAcquisition,
+    addr: SocketAddr,
+    borrow_addr: SocketAddr,
+    notification_addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client
+            .create_copies(context::current(), book_id, body.count, user_id)
+            .await
+        {
+            return match rpc_result {
+                Ok(copies) => {
+                    assign_acquisition(book_id, borrow_addr).await;
+                    announce_acquisition(book_id, body.count, addr, notification_addr).await;
+                    Ok(with_status(json_vector_reply(&copies), StatusCode::CREATED))
+                }
+                Err(Error::InvalidInput) => {
+                    Err(BadRequest("The number of copies must be between 1 and 100".into()).into())
+                }
+                Err(e) => Err(write_rejection(e, "copy")),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+/// Holds new copies of a book for the users waiting in its queue, failures don't fail the
+/// acquisition, as the borrow scheduler assigns the copies later on
+async fn assign_acquisition(book_id: Uuid, borrow_addr: SocketAddr) {
+    match get_borrow_client(borrow_addr).await {
+        Ok(client) => match client
+            .assign_available_copies(context::current(), book_id)
+            .await
+        {
+            Ok(Ok(_)) => {}
+            _ => log::error!("Failed to assign the acquired copies of book '{}'", book_id),
+        },
+        Err(e) => log::error!("Borrow service error: {}", e),
+    }
+}
+
+/// Announces new copies of a book on the shared channels, failures don't fail the acquisition
+async fn announce_acquisition(
+    book_id: Uuid,
+    copies: u32,
+    addr: SocketAddr,
+    notification_addr: SocketAddr,
+) {
+    let title = match init_rpc_client(&addr).await {
+        Ok(client) => match client.get_book_by_id(context::current(), book_id).await {
+            Ok(Ok(book)) => book.title,
+            _ => {
+                log::error!("Failed to get book '{}' for its announcement", book_id);
+                return;
+            }
+        },
+        Err(e) => {
+            log::error!("Book service error: {}", e);
+            return;
+        }
+    };
+    let announcement = Announcement::NewAcquisition {
+        book_id,
+        title,
+        copies,
+    };
+    match get_notification_client(notification_addr).await {
+        Ok(client) => match client.announce(context::current(), announcement).await {
+            Ok(Ok(())) => {}
+            _ => log::error!("Failed to announce the acquisition of book '{}'", book_id),
+        },
+        Err(e) => log::error!("Notification service error: {}", e),
+    }
+}
+
+pub async fn retire_copy(
+    id: Uuid,
+    body: CopyRetirement,
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = init_rpc_client(&addr).await {
+        if let Ok(rpc_result) = client
+            .retire_copy(context::current(), id, body.reason, user_id)
+            .await
+        {
+            return match rpc_result {
+                Ok(copy) => Ok(json_object_reply(&copy)),
+                Err(Error::AlreadyExists) => {
+                    Err(Conflict("The copy is already retired".into()).into())
+                }
+                Err(Error::InUse) => Err(Conflict("The copy is lent or held".into()).into()),
+                Err(e) => Err(write_rejection(e, "copy")),
+            };
         }
     }
     Err(InternalServerError().into())
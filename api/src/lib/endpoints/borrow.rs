@@ -0,0 +1,21 @@
+use std::net::SocketAddr;
+
+use tarpc::context;
+use uuid::Uuid;
+use warp::{reject::Rejection, Reply};
+
+use borrow::rpc::get_rpc_client;
+
+use crate::{rejections::InternalServerError, responses::json_vector_reply};
+
+pub async fn get_availability_by_book_id(
+    id: Uuid,
+    addr: SocketAddr,
+) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(Ok(copies)) = client.list_copy_availability(context::current(), id).await {
+            return Ok(json_vector_reply(&copies));
+        }
+    }
+    Err(InternalServerError().into())
+}
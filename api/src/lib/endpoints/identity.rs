@@ -1,11 +1,28 @@
 use std::{collections::HashMap, net::SocketAddr};
 
+use serde::Deserialize;
 use tarpc::context;
-use warp::{reject::Rejection, Reply};
+use warp::{
+    http::StatusCode,
+    reject::Rejection,
+    reply::{with_status, Reply},
+};
 
+use helpers::rpc::Error;
 use identity::rpc::get_rpc_client;
 
-use crate::{rejections::InternalServerError, responses::json_object_reply};
+use crate::{
+    filters::authorization::Session,
+    rejections::{not_found, BadRequest, Conflict, InternalServerError, Unauthorized},
+    responses::{json_object_reply, json_vector_reply},
+};
+
+/// Scope of a logout, which ends all sessions of the user instead of the current one
+#[derive(Deserialize)]
+pub struct Logout {
+    #[serde(default)]
+    everywhere: bool,
+}
 
 pub async fn get_oauth_client_identifier(addr: SocketAddr) -> Result<impl Reply, Rejection> {
     if let Ok(client) = get_rpc_client(addr).await {
@@ -16,25 +33,45 @@ pub async fn get_oauth_client_identifier(addr: SocketAddr) -> Result<impl Reply,
     Err(InternalServerError().into())
 }
 
+pub async fn get_oauth_providers(addr: SocketAddr) -> Result<impl Reply, Rejection> {
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(Ok(providers)) = client.oauth_providers(context::current()).await {
+            return Ok(json_vector_reply(&providers));
+        }
+    }
+    Err(InternalServerError().into())
+}
+
 pub async fn create_oauth_authentication(
     body: HashMap<String, String>,
     addr: SocketAddr,
 ) -> Result<impl Reply, Rejection> {
+    let code = body
+        .get("code")
+        .cloned()
+        .ok_or_else(|| BadRequest("Authorization code missing".into()))?;
     if let Ok(client) = get_rpc_client(addr).await {
-        if let Ok(authentication) = client
-            .oauth_authentication(context::current(), body["code"].clone())
+        if let Ok(rpc_result) = client
+            .oauth_authentication(context::current(), code, body.get("provider").cloned())
             .await
         {
-            return Ok(json_object_reply(&authentication.unwrap()));
+            return match rpc_result {
+                Ok(session) => Ok(json_object_reply(&session)),
+                Err(Error::NotFound) | Err(Error::InvalidData) => {
+                    Err(BadRequest("Unknown provider or invalid authorization code".into()).into())
+                }
+                Err(Error::InvalidInput) => Err(Unauthorized(
+                    "ID token invalid or account not allowed to sign in".into(),
+                )
+                .into()),
+                Err(_) => Err(InternalServerError().into()),
+            };
         }
     }
     Err(InternalServerError().into())
 }
 
-pub async fn get_session_info(
-    addr: SocketAddr,
-    session: crate::filters::authorization::Session,
-) -> Result<impl Reply, Rejection> {
+pub async fn get_session_info(addr: SocketAddr, session: Session) -> Result<impl Reply, Rejection> {
     if let Ok(client) = get_rpc_client(addr).await {
         if let Ok(session) = client.session_info(context::current(), session.token).await {
             return Ok(json_object_reply(&session.unwrap()));
@@ -42,3 +79,122 @@ pub async fn get_session_info(
     }
     Err(InternalServerError().into())
 }
+
+pub async fn create_session_refresh(
+    body: HashMap<String, String>,
+    addr: SocketAddr,
+) -> Result<impl Reply, Rejection> {
+    let refresh_token = body
+        .get("refresh_token")
+        .cloned()
+        .ok_or_else(|| BadRequest("Refresh token missing".into()))?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(rpc_result) = client
+            .refresh_session(context::current(), refresh_token)
+            .await
+        {
+            return match rpc_result {
+                Ok(session) => Ok(json_object_reply(&session)),
+                Err(Error::InvalidData) => {
+                    Err(Unauthorized("Refresh token invalid, expired or revoked".into()).into())
+                }
+                Err(_) => Err(InternalServerError().into()),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn create_session_logout(
+    logout: Logout,
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        let rpc_result = if logout.everywhere {
+            client.logout_everywhere(context::current(), user_id).await
+        } else {
+            client.logout(context::current(), session.token).await
+        };
+        if let Ok(rpc_result) = rpc_result {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(Error::InvalidData) => {
+                    Err(Unauthorized("Session token invalid or revoked".into()).into())
+                }
+                Err(_) => Err(InternalServerError().into()),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn get_identities(addr: SocketAddr, session: Session) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(Ok(identities)) = client.list_identities(context::current(), user_id).await {
+            return Ok(json_vector_reply(&identities));
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn link_identity(
+    provider: String,
+    body: HashMap<String, String>,
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    let code = body
+        .get("code")
+        .cloned()
+        .ok_or_else(|| BadRequest("Authorization code missing".into()))?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(rpc_result) = client
+            .link_identity(context::current(), user_id, provider, code)
+            .await
+        {
+            return match rpc_result {
+                Ok(identity) => Ok(with_status(
+                    json_object_reply(&identity),
+                    StatusCode::CREATED,
+                )),
+                Err(Error::NotFound) | Err(Error::InvalidInput) | Err(Error::InvalidData) => {
+                    Err(BadRequest("Unknown provider or invalid authorization code".into()).into())
+                }
+                Err(Error::AlreadyExists) => Err(Conflict(
+                    "The account is already linked to this or another user".into(),
+                )
+                .into()),
+                Err(_) => Err(InternalServerError().into()),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn unlink_identity(
+    provider: String,
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(rpc_result) = client
+            .unlink_identity(context::current(), user_id, provider)
+            .await
+        {
+            return match rpc_result {
+                Ok(()) => Ok(StatusCode::NO_CONTENT),
+                Err(Error::NotFound) => Err(not_found()),
+                Err(Error::PolicyViolation) => {
+                    Err(Conflict("The last linked account can't be unlinked".into()).into())
+                }
+                Err(_) => Err(InternalServerError().into()),
+            };
+        }
+    }
+    Err(InternalServerError().into())
+}
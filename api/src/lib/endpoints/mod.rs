@@ -1,3 +1,5 @@
 pub mod book;
+pub mod borrow;
 pub mod identity;
+pub mod notification;
 pub mod root;
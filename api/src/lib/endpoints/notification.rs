@@ -0,0 +1,56 @@
+use std::net::SocketAddr;
+
+use tarpc::context;
+use warp::{reject::Rejection, Reply};
+
+use helpers::rpc::Error;
+use notification::rpc::{get_rpc_client, models::NotificationPreferences};
+
+use crate::{
+    filters::authorization::Session,
+    rejections::{BadRequest, InternalServerError},
+    responses::json_object_reply,
+};
+
+pub async fn get_notification_preferences(
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(Ok(preferences)) = client
+            .get_notification_preferences(context::current(), user_id)
+            .await
+        {
+            return Ok(json_object_reply(&preferences));
+        }
+    }
+    Err(InternalServerError().into())
+}
+
+pub async fn update_notification_preferences(
+    body: NotificationPreferences,
+    addr: SocketAddr,
+    session: Session,
+) -> Result<impl Reply, Rejection> {
+    let user_id = session.user_id()?;
+    if let Ok(client) = get_rpc_client(addr).await {
+        if let Ok(rpc_result) = client
+            .update_notification_preferences(context::current(), user_id, body)
+            .await
+        {
+            match rpc_result {
+                Ok(preferences) => return Ok(json_object_reply(&preferences)),
+                Err(Error::InvalidInput) => {
+                    return Err(BadRequest(
+                        "Unknown notification kind, unavailable channel or invalid quiet hours"
+                            .into(),
+                    )
+                    .into())
+                }
+                _ => {}
+            }
+        }
+    }
+    Err(InternalServerError().into())
+}
@@ -1,17 +1,28 @@
-use crate::rejections::{InternalServerError, Unauthorized};
+use crate::rejections::{Forbidden, InternalServerError, Unauthorized};
 
 use std::net::SocketAddr;
 
 use tarpc::context;
+use uuid::Uuid;
 use warp::{reject, Filter, Rejection};
 
 use identity::rpc::get_rpc_client;
 
+/// Roles, of which the users manage the catalogue
+const LIBRARIAN_ROLES: [&str; 2] = ["Manager", "Administrator"];
+
 pub struct Session {
     pub token: String,
     pub sub: String,
 }
 
+impl Session {
+    /// Id of the user, who is the subject of the session
+    pub fn user_id(&self) -> Result<Uuid, Rejection> {
+        Uuid::parse_str(&self.sub).map_err(|_| reject::custom(InternalServerError()))
+    }
+}
+
 pub fn authorization(
     addr: SocketAddr,
 ) -> impl Filter<Extract = (Session,), Error = Rejection> + Clone {
@@ -45,3 +56,37 @@ pub fn authorization(
         },
     )
 }
+
+/// Session of an user, whose role allows managing the catalogue
+pub fn librarian(addr: SocketAddr) -> impl Filter<Extract = (Session,), Error = Rejection> + Clone {
+    authorization(addr).and_then(move |session: Session| async move {
+        let client = get_rpc_client(addr).await.map_err(|e| {
+            log::error!("Identity service error: {}", e);
+            reject::custom(InternalServerError())
+        })?;
+
+        let user = client
+            .get_user(context::current(), session.user_id()?)
+            .await
+            .map_err(|e| {
+                log::error!("Identity service communication error: {}", e);
+                reject::custom(InternalServerError())
+            })?
+            .map_err(|_e| reject::custom(Unauthorized("User unknown".into())))?;
+        let role = client
+            .get_role(context::current(), user.role_id)
+            .await
+            .map_err(|e| {
+                log::error!("Identity service communication error: {}", e);
+                reject::custom(InternalServerError())
+            })?
+            .map_err(|_e| reject::custom(InternalServerError()))?;
+
+        match LIBRARIAN_ROLES.contains(&role.name.as_str()) {
+            true => Ok::<Session, Rejection>(session),
+            false => Err(reject::custom(Forbidden(
+                "Role isn't allowed to manage the catalogue".into(),
+            ))),
+        }
+    })
+}
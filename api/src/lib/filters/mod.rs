@@ -8,6 +8,14 @@ pub fn book_service(book_addr: SocketAddr) -> BoxedFilter<(SocketAddr,)> {
     warp::any().map(move || book_addr).boxed()
 }
 
+pub fn borrow_service(borrow_addr: SocketAddr) -> BoxedFilter<(SocketAddr,)> {
+    warp::any().map(move || borrow_addr).boxed()
+}
+
 pub fn identity_service(identity_addr: SocketAddr) -> BoxedFilter<(SocketAddr,)> {
     warp::any().map(move || identity_addr).boxed()
 }
+
+pub fn notification_service(notification_addr: SocketAddr) -> BoxedFilter<(SocketAddr,)> {
+    warp::any().map(move || notification_addr).boxed()
+}
@@ -52,6 +52,24 @@ pub async fn rejection(err: Rejection) -> Result<impl Reply, Infallible> {
             }),
             StatusCode::UNAUTHORIZED,
         )
+    } else if let Some(error) = err.find::<Forbidden>() {
+        with_status(
+            error_reply(&Error {
+                code: 403,
+                status: "FORBIDDEN",
+                detail: error.detail(),
+            }),
+            StatusCode::FORBIDDEN,
+        )
+    } else if let Some(error) = err.find::<Conflict>() {
+        with_status(
+            error_reply(&Error {
+                code: 409,
+                status: "CONFLICT",
+                detail: error.detail(),
+            }),
+            StatusCode::CONFLICT,
+        )
     } else if err.find::<InternalServerError>().is_some() {
         with_status(
             error_reply(&Error {
@@ -79,7 +97,7 @@ pub async fn rejection(err: Rejection) -> Result<impl Reply, Infallible> {
             }),
             StatusCode::BAD_REQUEST,
         )
-    } else if err.find::<InvalidQuery>().is_some() {
+    } else if err.find::<InvalidQuery>().is_some() || err.find::<serde_qs::Error>().is_some() {
         with_status(
             error_reply(&Error {
                 code: 400,
@@ -2,6 +2,7 @@ use std::net::SocketAddr as Addr;
 
 use warp::{filters::BoxedFilter, Reply, Server};
 
+mod catalogue;
 pub mod config;
 mod endpoints;
 mod filters;
@@ -9,6 +10,16 @@ mod rejections;
 mod responses;
 mod router;
 
-pub fn server(book_addr: Addr, identity_addr: Addr) -> Server<BoxedFilter<(impl Reply,)>> {
-    warp::serve(crate::router::init_router(book_addr, identity_addr))
+pub fn server(
+    book_addr: Addr,
+    borrow_addr: Addr,
+    identity_addr: Addr,
+    notification_addr: Addr,
+) -> Server<BoxedFilter<(impl Reply,)>> {
+    warp::serve(crate::router::init_router(
+        book_addr,
+        borrow_addr,
+        identity_addr,
+        notification_addr,
+    ))
 }
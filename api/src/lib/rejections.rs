@@ -26,6 +26,28 @@ impl Unauthorized {
 
 impl Reject for Unauthorized {}
 
+#[derive(Debug)]
+pub struct Forbidden(pub String);
+
+impl Forbidden {
+    pub fn detail(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Reject for Forbidden {}
+
+#[derive(Debug)]
+pub struct Conflict(pub String);
+
+impl Conflict {
+    pub fn detail(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Reject for Conflict {}
+
 #[derive(Debug)]
 pub struct InternalServerError();
 
@@ -1,3 +1,4 @@
+use helpers::filters::Paginated;
 use serde::Serialize;
 use warp::reply::{json, Json};
 
@@ -30,3 +31,45 @@ where
 {
     json(&JsonVectorReply { data })
 }
+
+/// Cursors of the pages after and before the listed items and whether more items follow
+/// in the direction of the requested cursor
+#[derive(Serialize)]
+pub struct Pagination<'a, C>
+where
+    C: Serialize,
+{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<&'a C>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous: Option<&'a C>,
+    has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct JsonPageReply<'a, T, C>
+where
+    T: Serialize,
+    C: Serialize,
+{
+    data: &'a [T],
+    pagination: Pagination<'a, C>,
+}
+
+pub fn json_page_reply<T, C>(page: &Paginated<T, C>) -> Json
+where
+    T: Serialize,
+    C: Serialize,
+{
+    json(&JsonPageReply {
+        data: page.get_items(),
+        pagination: Pagination {
+            next: page.get_next(),
+            previous: page.get_previous(),
+            has_more: page.has_more(),
+            total: page.get_total(),
+        },
+    })
+}
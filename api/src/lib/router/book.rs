@@ -1,25 +1,47 @@
-use crate::{endpoints::book::*, filters::book_service};
+use crate::{
+    endpoints::{book::*, borrow::get_availability_by_book_id},
+    filters::{authorization::librarian, book_service, borrow_service, notification_service},
+};
 use helpers::filters;
 use serde_qs::{warp::query, Config};
 use std::net::SocketAddr;
 use uuid::Uuid;
 use warp::{filters::BoxedFilter, Filter, Reply};
 
-pub fn book(book_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
+/// Maximum size of an import file in bytes, of which the base64 encoding still fits the frame
+/// length of the book service
+const IMPORT_MAX_SIZE: u64 = 5 * 1024 * 1024;
+
+pub fn book(
+    book_addr: SocketAddr,
+    borrow_addr: SocketAddr,
+    identity_addr: SocketAddr,
+    notification_addr: SocketAddr,
+) -> BoxedFilter<(impl Reply,)> {
     warp::path("book")
         .and(
             // GET /book
             warp::path::end()
                 .and(book_service(book_addr))
-                .and(query::<filters::Page>(Config::default()))
+                .and(query::<filters::KeysetPage>(Config::default()))
                 .and(query::<filters::Book>(Config::default()))
+                .and(query::<filters::Include>(Config::default()))
                 .and(warp::get())
                 .and_then(get_books)
                 .boxed()
+                // GET /book/search
+                .or(warp::path("search")
+                    .and(warp::path::end())
+                    .and(book_service(book_addr))
+                    .and(query::<filters::Search>(Config::default()))
+                    .and(warp::get())
+                    .and_then(search_books)
+                    .boxed())
                 // GET /book/{book_id}
                 .or(warp::path::param::<Uuid>()
                     .and(warp::path::end())
                     .and(book_service(book_addr))
+                    .and(query::<filters::Include>(Config::default()))
                     .and(warp::get())
                     .and_then(get_book_by_id)
                     .boxed())
@@ -32,6 +54,14 @@ pub fn book(book_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
                     .and(warp::get())
                     .and_then(get_authors_by_book_id)
                     .boxed())
+                // GET /book/{book_id}/availability
+                .or(warp::path::param::<Uuid>()
+                    .and(warp::path("availability"))
+                    .and(warp::path::end())
+                    .and(borrow_service(borrow_addr))
+                    .and(warp::get())
+                    .and_then(get_availability_by_book_id)
+                    .boxed())
                 // GET /book/{book_id}/category
                 .or(warp::path::param::<Uuid>()
                     .and(warp::path("category"))
@@ -243,7 +273,272 @@ pub fn book(book_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
                     .and(book_service(book_addr))
                     .and(warp::get())
                     .and_then(get_tag_by_id)
-                    .boxed()),
+                    .boxed())
+                // POST /book/import
+                .or(warp::path("import")
+                    .and(warp::path::end())
+                    .and(warp::post())
+                    .and(query::<BookImport>(Config::default()))
+                    .and(warp::body::content_length_limit(IMPORT_MAX_SIZE))
+                    .and(warp::body::bytes())
+                    .and(book_service(book_addr))
+                    .and(librarian(identity_addr))
+                    .and_then(import_books)
+                    .boxed())
+                // POST /book/import/catalogue
+                .or(warp::path("import")
+                    .and(warp::path("catalogue"))
+                    .and(warp::path::end())
+                    .and(warp::post())
+                    .and(query::<CatalogueImport>(Config::default()))
+                    .and(warp::body::content_length_limit(IMPORT_MAX_SIZE))
+                    .and(warp::body::bytes())
+                    .and(book_service(book_addr))
+                    .and(librarian(identity_addr))
+                    .and_then(import_catalogue)
+                    .boxed())
+                // GET /book/export
+                .or(warp::path("export")
+                    .and(warp::path::end())
+                    .and(warp::get())
+                    .and(query::<CatalogueExport>(Config::default()))
+                    .and(book_service(book_addr))
+                    .and(librarian(identity_addr))
+                    .and_then(export_books)
+                    .boxed())
+                .or(reference_data(book_addr, identity_addr))
+                .or(copies(
+                    book_addr,
+                    borrow_addr,
+                    identity_addr,
+                    notification_addr,
+                )),
         )
         .boxed()
 }
+
+/// Routes writing the catalogue reference data, which require the session of a librarian
+fn reference_data(book_addr: SocketAddr, identity_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
+    // POST /book/categories
+    warp::path("categories")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(book_service(book_addr))
+        .and(librarian(identity_addr))
+        .and_then(create_category)
+        .boxed()
+        // PATCH /book/categories/{category_id}
+        .or(warp::path("categories")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_category)
+            .boxed())
+        // DELETE /book/categories/{category_id}
+        .or(warp::path("categories")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_category)
+            .boxed())
+        // POST /book/persons
+        .or(warp::path("persons")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_person)
+            .boxed())
+        // PATCH /book/persons/{person_id}
+        .or(warp::path("persons")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_person)
+            .boxed())
+        // DELETE /book/persons/{person_id}
+        .or(warp::path("persons")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_person)
+            .boxed())
+        // POST /book/persons/{person_id}/merge
+        .or(warp::path("persons")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path("merge"))
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(merge_persons)
+            .boxed())
+        // POST /book/publishers
+        .or(warp::path("publishers")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_publisher)
+            .boxed())
+        // PATCH /book/publishers/{publisher_id}
+        .or(warp::path("publishers")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_publisher)
+            .boxed())
+        // DELETE /book/publishers/{publisher_id}
+        .or(warp::path("publishers")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_publisher)
+            .boxed())
+        // POST /book/series
+        .or(warp::path("series")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_series)
+            .boxed())
+        // PATCH /book/series/{series_id}
+        .or(warp::path("series")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_series)
+            .boxed())
+        // DELETE /book/series/{series_id}
+        .or(warp::path("series")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_series)
+            .boxed())
+        // POST /book/subject_areas
+        .or(warp::path("subject_areas")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_subject_area)
+            .boxed())
+        // PATCH /book/subject_areas/{subject_area_id}
+        .or(warp::path("subject_areas")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_subject_area)
+            .boxed())
+        // DELETE /book/subject_areas/{subject_area_id}
+        .or(warp::path("subject_areas")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_subject_area)
+            .boxed())
+        // POST /book/tags
+        .or(warp::path("tags")
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_tag)
+            .boxed())
+        // PATCH /book/tags/{tag_id}
+        .or(warp::path("tags")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::patch())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(update_tag)
+            .boxed())
+        // DELETE /book/tags/{tag_id}
+        .or(warp::path("tags")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path::end())
+            .and(warp::delete())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(delete_tag)
+            .boxed())
+        .boxed()
+}
+
+/// Routes managing the copies of books, of which the writes require the session of a librarian
+fn copies(
+    book_addr: SocketAddr,
+    borrow_addr: SocketAddr,
+    identity_addr: SocketAddr,
+    notification_addr: SocketAddr,
+) -> BoxedFilter<(impl Reply,)> {
+    // GET /book/copies/label/{label}
+    warp::path("copies")
+        .and(warp::path("label"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(book_service(book_addr))
+        .and(warp::get())
+        .and_then(get_copy_by_label)
+        .boxed()
+        // POST /book/{book_id}/copies
+        .or(warp::path::param::<Uuid>()
+            .and(warp::path("copies"))
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(borrow_service(borrow_addr))
+            .and(notification_service(notification_addr))
+            .and(librarian(identity_addr))
+            .and_then(create_copies)
+            .boxed())
+        // POST /book/copies/{copy_id}/retire
+        .or(warp::path("copies")
+            .and(warp::path::param::<Uuid>())
+            .and(warp::path("retire"))
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(book_service(book_addr))
+            .and(librarian(identity_addr))
+            .and_then(retire_copy)
+            .boxed())
+        .boxed()
+}
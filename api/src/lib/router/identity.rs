@@ -2,6 +2,7 @@ use crate::{
     endpoints::identity::*,
     filters::{authorization::authorization, identity_service},
 };
+use serde_qs::{warp::query, Config};
 use std::net::SocketAddr;
 use warp::{filters::BoxedFilter, Filter, Reply};
 
@@ -16,6 +17,12 @@ pub fn identity(identity_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
                         .and(warp::get())
                         .and(identity_service(identity_addr))
                         .and_then(get_oauth_client_identifier)
+                        // GET - /identity/oauth/providers
+                        .or(warp::path("providers")
+                            .and(warp::path::end())
+                            .and(warp::get())
+                            .and(identity_service(identity_addr))
+                            .and_then(get_oauth_providers))
                         // POST - /identity/oauth/authentication
                         .or(warp::path("authentication")
                             .and(warp::path::end())
@@ -30,7 +37,22 @@ pub fn identity(identity_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
                     .and(warp::get())
                     .and(identity_service(identity_addr))
                     .and(authorization(identity_addr))
-                    .and_then(get_session_info)),
+                    .and_then(get_session_info))
+                // POST - /identity/session/refresh
+                .or(warp::path!("session" / "refresh")
+                    .and(warp::path::end())
+                    .and(warp::post())
+                    .and(warp::body::json())
+                    .and(identity_service(identity_addr))
+                    .and_then(create_session_refresh))
+                // POST - /identity/session/logout
+                .or(warp::path!("session" / "logout")
+                    .and(warp::path::end())
+                    .and(warp::post())
+                    .and(query::<Logout>(Config::default()))
+                    .and(identity_service(identity_addr))
+                    .and(authorization(identity_addr))
+                    .and_then(create_session_logout)),
         )
         .boxed()
 }
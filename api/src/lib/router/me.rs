@@ -0,0 +1,50 @@
+use crate::{
+    endpoints::{identity::*, notification::*},
+    filters::{authorization::authorization, identity_service, notification_service},
+};
+use std::net::SocketAddr;
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+pub fn me(identity_addr: SocketAddr, notification_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
+    warp::path("me")
+        .and(
+            // GET /me/notification-preferences
+            warp::path("notification-preferences")
+                .and(warp::path::end())
+                .and(warp::get())
+                .and(notification_service(notification_addr))
+                .and(authorization(identity_addr))
+                .and_then(get_notification_preferences)
+                // PUT /me/notification-preferences
+                .or(warp::path("notification-preferences")
+                    .and(warp::path::end())
+                    .and(warp::put())
+                    .and(warp::body::json())
+                    .and(notification_service(notification_addr))
+                    .and(authorization(identity_addr))
+                    .and_then(update_notification_preferences))
+                // GET /me/identities
+                .or(warp::path("identities")
+                    .and(warp::path::end())
+                    .and(warp::get())
+                    .and(identity_service(identity_addr))
+                    .and(authorization(identity_addr))
+                    .and_then(get_identities))
+                // POST /me/identities/{provider}
+                .or(warp::path!("identities" / String)
+                    .and(warp::path::end())
+                    .and(warp::post())
+                    .and(warp::body::json())
+                    .and(identity_service(identity_addr))
+                    .and(authorization(identity_addr))
+                    .and_then(link_identity))
+                // DELETE /me/identities/{provider}
+                .or(warp::path!("identities" / String)
+                    .and(warp::path::end())
+                    .and(warp::delete())
+                    .and(identity_service(identity_addr))
+                    .and(authorization(identity_addr))
+                    .and_then(unlink_identity)),
+        )
+        .boxed()
+}
@@ -1,5 +1,6 @@
 mod book;
 mod identity;
+mod me;
 mod root;
 
 use std::net::SocketAddr;
@@ -8,10 +9,21 @@ use crate::filters::rejection::rejection;
 
 use warp::{filters::BoxedFilter, Filter, Reply};
 
-pub fn init_router(book_addr: SocketAddr, identity_addr: SocketAddr) -> BoxedFilter<(impl Reply,)> {
+pub fn init_router(
+    book_addr: SocketAddr,
+    borrow_addr: SocketAddr,
+    identity_addr: SocketAddr,
+    notification_addr: SocketAddr,
+) -> BoxedFilter<(impl Reply,)> {
     root::root()
         .or(identity::identity(identity_addr))
-        .or(book::book(book_addr))
+        .or(me::me(identity_addr, notification_addr))
+        .or(book::book(
+            book_addr,
+            borrow_addr,
+            identity_addr,
+            notification_addr,
+        ))
         .recover(rejection)
         .boxed()
 }
@@ -14,7 +14,9 @@ async fn main() {
 
     let server = api::server(
         configuration.get_book_socket(),
+        configuration.get_borrow_socket(),
         configuration.get_identity_socket(),
+        configuration.get_notification_socket(),
     )
     .bind(configuration.get_service_socket());
     log::info!(
@@ -0,0 +1,4 @@
+ALTER TABLE copies
+    DROP COLUMN retirement_reason,
+    DROP COLUMN retired_by,
+    DROP COLUMN retired_at;
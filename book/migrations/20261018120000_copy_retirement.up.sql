@@ -0,0 +1,6 @@
+-- Retired copies are kept, so that the loans of them are still traceable
+ALTER TABLE copies
+    ADD COLUMN retired_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN retired_by UUID,
+    ADD COLUMN retirement_reason VARCHAR(20) CHECK (retirement_reason IN ('lost', 'damaged', 'withdrawn')),
+    ADD CHECK ((retired_at IS NULL) = (retired_by IS NULL) AND (retired_at IS NULL) = (retirement_reason IS NULL));
@@ -0,0 +1,16 @@
+DROP TRIGGER tags_search_vector_refresh ON tags;
+DROP FUNCTION tags_search_vector_refresh;
+DROP TRIGGER persons_search_vector_refresh ON persons;
+DROP FUNCTION persons_search_vector_refresh;
+DROP TRIGGER books_tags_search_vector_refresh ON books_tags;
+DROP TRIGGER books_editors_search_vector_refresh ON books_editors;
+DROP TRIGGER books_authors_search_vector_refresh ON books_authors;
+DROP FUNCTION books_links_search_vector_refresh;
+DROP FUNCTION books_search_vector_refresh;
+DROP TRIGGER books_search_vector_update ON books;
+DROP FUNCTION books_search_vector_update;
+DROP FUNCTION books_search_query;
+DROP FUNCTION books_search_vector;
+ALTER TABLE books DROP COLUMN search_vector;
+DROP FUNCTION books_search_config;
+DROP FUNCTION search_config_name;
@@ -0,0 +1,154 @@
+-- Text search configuration of a language, by its ISO 639-2 code
+CREATE FUNCTION search_config_name(iso_code VARCHAR) RETURNS TEXT AS
+  $$
+    SELECT CASE iso_code
+      WHEN 'ara' THEN 'arabic'
+      WHEN 'arm' THEN 'armenian'
+      WHEN 'baq' THEN 'basque'
+      WHEN 'cat' THEN 'catalan'
+      WHEN 'dan' THEN 'danish'
+      WHEN 'dut' THEN 'dutch'
+      WHEN 'eng' THEN 'english'
+      WHEN 'fin' THEN 'finnish'
+      WHEN 'fre' THEN 'french'
+      WHEN 'ger' THEN 'german'
+      WHEN 'gle' THEN 'irish'
+      WHEN 'gre' THEN 'greek'
+      WHEN 'hin' THEN 'hindi'
+      WHEN 'hun' THEN 'hungarian'
+      WHEN 'ind' THEN 'indonesian'
+      WHEN 'ita' THEN 'italian'
+      WHEN 'lit' THEN 'lithuanian'
+      WHEN 'nep' THEN 'nepali'
+      WHEN 'nob' THEN 'norwegian'
+      WHEN 'nor' THEN 'norwegian'
+      WHEN 'por' THEN 'portuguese'
+      WHEN 'rum' THEN 'romanian'
+      WHEN 'rus' THEN 'russian'
+      WHEN 'spa' THEN 'spanish'
+      WHEN 'srp' THEN 'serbian'
+      WHEN 'swe' THEN 'swedish'
+      WHEN 'tam' THEN 'tamil'
+      WHEN 'tur' THEN 'turkish'
+      WHEN 'yid' THEN 'yiddish'
+      ELSE 'simple'
+    END
+  $$ LANGUAGE sql IMMUTABLE;
+
+-- Text search configuration of a book, without stemming if the server lacks the one of its language
+CREATE FUNCTION books_search_config(language_id UUID) RETURNS REGCONFIG AS
+  $$
+    SELECT COALESCE(
+      (SELECT c.oid::regconfig
+        FROM languages l
+        INNER JOIN pg_ts_config c ON c.cfgname = search_config_name(l.iso_code)
+        WHERE l.id = language_id),
+      'simple'::regconfig
+    )
+  $$ LANGUAGE sql STABLE;
+
+ALTER TABLE books ADD COLUMN search_vector TSVECTOR NOT NULL DEFAULT ''::tsvector;
+
+-- Document of a book, weighted from title and identifiers (A) over subtitle and persons (B) and tags (C) to description (D)
+CREATE FUNCTION books_search_vector(book books) RETURNS TSVECTOR AS
+  $$
+    SELECT
+      setweight(to_tsvector(config, book.title), 'A')
+      || setweight(to_tsvector('simple', concat_ws(' ', book.isbn, book.issn)), 'A')
+      || setweight(to_tsvector(config, COALESCE(book.subtitle, '')), 'B')
+      || setweight(to_tsvector(config, COALESCE(
+        (SELECT string_agg(p.first_name || ' ' || p.last_name, ' ')
+          FROM persons p
+          WHERE p.id IN (
+            SELECT person_id FROM books_authors WHERE book_id = book.id
+            UNION
+            SELECT person_id FROM books_editors WHERE book_id = book.id
+          )),
+        ''
+      )), 'B')
+      || setweight(to_tsvector(config, COALESCE(
+        (SELECT string_agg(t.name, ' ')
+          FROM tags t
+          INNER JOIN books_tags bt ON bt.tag_id = t.id
+          WHERE bt.book_id = book.id),
+        ''
+      )), 'C')
+      || setweight(to_tsvector(config, COALESCE(book.description, '')), 'D')
+    FROM books_search_config(book.language_id) AS config
+  $$ LANGUAGE sql STABLE;
+
+-- Query in every configuration used by the languages, so that the index serves books of all languages
+CREATE FUNCTION books_search_query(query TEXT) RETURNS TSQUERY AS
+  $$
+    SELECT string_agg('(' || q::text || ')', ' | ')::tsquery
+    FROM (
+      SELECT DISTINCT to_tsquery(c.oid::regconfig, query) AS q
+      FROM (SELECT DISTINCT search_config_name(iso_code) AS name FROM languages) l
+      INNER JOIN pg_ts_config c ON c.cfgname = l.name
+      UNION
+      SELECT to_tsquery('simple', query)
+    ) queries
+    WHERE numnode(q) > 0
+  $$ LANGUAGE sql STABLE;
+
+CREATE FUNCTION books_search_vector_update() RETURNS trigger AS
+  $$
+  BEGIN
+    NEW.search_vector := books_search_vector(NEW);
+    RETURN NEW;
+  END
+  $$ LANGUAGE plpgsql;
+
+CREATE TRIGGER books_search_vector_update BEFORE INSERT OR UPDATE OF title, subtitle, description, isbn, issn, language_id ON books FOR EACH ROW EXECUTE PROCEDURE books_search_vector_update();
+
+CREATE FUNCTION books_search_vector_refresh(book_id UUID) RETURNS void AS
+  $$
+    UPDATE books SET search_vector = books_search_vector(books) WHERE id = book_id
+  $$ LANGUAGE sql;
+
+CREATE FUNCTION books_links_search_vector_refresh() RETURNS trigger AS
+  $$
+  BEGIN
+    IF TG_OP <> 'INSERT' THEN
+      PERFORM books_search_vector_refresh(OLD.book_id);
+    END IF;
+    IF TG_OP <> 'DELETE' AND (TG_OP = 'INSERT' OR NEW.book_id <> OLD.book_id) THEN
+      PERFORM books_search_vector_refresh(NEW.book_id);
+    END IF;
+    RETURN NULL;
+  END
+  $$ LANGUAGE plpgsql;
+
+CREATE TRIGGER books_authors_search_vector_refresh AFTER INSERT OR UPDATE OR DELETE ON books_authors FOR EACH ROW EXECUTE PROCEDURE books_links_search_vector_refresh();
+CREATE TRIGGER books_editors_search_vector_refresh AFTER INSERT OR UPDATE OR DELETE ON books_editors FOR EACH ROW EXECUTE PROCEDURE books_links_search_vector_refresh();
+CREATE TRIGGER books_tags_search_vector_refresh AFTER INSERT OR UPDATE OR DELETE ON books_tags FOR EACH ROW EXECUTE PROCEDURE books_links_search_vector_refresh();
+
+CREATE FUNCTION persons_search_vector_refresh() RETURNS trigger AS
+  $$
+  BEGIN
+    UPDATE books SET search_vector = books_search_vector(books)
+    WHERE id IN (
+      SELECT book_id FROM books_authors WHERE person_id = NEW.id
+      UNION
+      SELECT book_id FROM books_editors WHERE person_id = NEW.id
+    );
+    RETURN NULL;
+  END
+  $$ LANGUAGE plpgsql;
+
+CREATE TRIGGER persons_search_vector_refresh AFTER UPDATE OF first_name, last_name ON persons FOR EACH ROW EXECUTE PROCEDURE persons_search_vector_refresh();
+
+CREATE FUNCTION tags_search_vector_refresh() RETURNS trigger AS
+  $$
+  BEGIN
+    UPDATE books SET search_vector = books_search_vector(books)
+    WHERE id IN (SELECT book_id FROM books_tags WHERE tag_id = NEW.id);
+    RETURN NULL;
+  END
+  $$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tags_search_vector_refresh AFTER UPDATE OF name ON tags FOR EACH ROW EXECUTE PROCEDURE tags_search_vector_refresh();
+
+UPDATE books SET search_vector = books_search_vector(books);
+
+CREATE INDEX books_search_vector_idx ON books USING GIN (search_vector);
@@ -0,0 +1,13 @@
+-- Books existing before are dated by their first copy
+ALTER TABLE books
+    ADD COLUMN created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
+
+UPDATE books
+SET created_at = copies.created_at
+FROM (SELECT book_id, min(created_at) AS created_at FROM copies GROUP BY book_id) AS copies
+WHERE copies.book_id = books.id;
+
+-- Keyset pagination of the sorted listings
+CREATE INDEX books_title_id_idx ON books (title, id);
+CREATE INDEX books_release_year_id_idx ON books (release_year, id);
+CREATE INDEX books_created_at_id_idx ON books (created_at, id);
@@ -0,0 +1 @@
+DROP FUNCTION books_search_query(TEXT, UUID);
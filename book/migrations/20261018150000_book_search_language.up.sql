@@ -0,0 +1,6 @@
+-- Query in the configuration of a book, so that a term, which is a stop word of another
+-- language, is still required. books_search_query(query) only narrows the books by the index.
+CREATE FUNCTION books_search_query(query TEXT, language_id UUID) RETURNS TSQUERY AS
+  $$
+    SELECT to_tsquery(books_search_config(language_id), query)
+  $$ LANGUAGE sql STABLE;
@@ -157,6 +157,16 @@ impl Configuration {
         self.service_socket
     }
 
+    pub fn get_db_connection_base_url(&self) -> String {
+        format!(
+            "postgres://{}:{}@{}:{}",
+            self.db_username,
+            self.db_password,
+            self.db_socket.ip(),
+            self.db_socket.port()
+        )
+    }
+
     pub fn get_db_connection_url(&self) -> String {
         format!(
             "postgres://{}:{}@{}:{}/{}",
@@ -1,4 +1,10 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    error::BoxDynError,
+    postgres::{PgRow, PgTypeInfo, PgValueRef},
+    Decode, FromRow, Postgres, Row, Type,
+};
 use uuid::Uuid;
 
 #[derive(Debug, sqlx::FromRow)]
@@ -39,6 +45,17 @@ pub struct Tag {
     pub name: String,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+pub struct Person {
+    pub id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub date_of_birth: Option<NaiveDate>,
+    pub isni: Option<String>,
+    pub orcid: Option<String>,
+    pub oclc: Option<i32>,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct Author {
     pub id: Uuid,
@@ -61,13 +78,75 @@ pub struct Editor {
     pub oclc: Option<i32>,
 }
 
+/// Entity related to a book, which is read along with the id of the book as `book_id`
+#[derive(Debug)]
+pub struct Related<T> {
+    pub book_id: Uuid,
+    pub entity: T,
+}
+
+impl<'r, T> FromRow<'r, PgRow> for Related<T>
+where
+    T: FromRow<'r, PgRow>,
+{
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Related {
+            book_id: row.try_get("book_id")?,
+            entity: T::from_row(row)?,
+        })
+    }
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct Copy {
     pub id: Uuid,
     pub book_id: Uuid,
-    pub copy_id: i64,
+    pub copy_id: i32,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub retired_at: Option<DateTime<Utc>>,
+    pub retired_by: Option<Uuid>,
+    pub retirement_reason: Option<RetirementReason>,
+}
+
+/// Reason, for which a copy left the library
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetirementReason {
+    Lost,
+    Damaged,
+    Withdrawn,
+}
+
+impl RetirementReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetirementReason::Lost => "lost",
+            RetirementReason::Damaged => "damaged",
+            RetirementReason::Withdrawn => "withdrawn",
+        }
+    }
+}
+
+impl Type<Postgres> for RetirementReason {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for RetirementReason {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match <&str as Decode<Postgres>>::decode(value)? {
+            "lost" => Ok(RetirementReason::Lost),
+            "damaged" => Ok(RetirementReason::Damaged),
+            "withdrawn" => Ok(RetirementReason::Withdrawn),
+            reason => Err(format!("Unknown retirement reason '{}'", reason).into()),
+        }
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -82,4 +161,46 @@ pub struct Book {
     pub title: String,
     pub subtitle: Option<String>,
     pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Book with the ids of its category, language, publisher and series
+#[derive(Debug, sqlx::FromRow)]
+pub struct BookEntry {
+    pub id: Uuid,
+    pub code_identifier: i32,
+    pub isbn: Option<String>,
+    pub issn: Option<String>,
+    pub release_year: i16,
+    pub edition: Option<i32>,
+    pub pages: Option<i32>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub category_id: Uuid,
+    pub language_id: Uuid,
+    pub publisher_id: Uuid,
+    pub series_id: Option<Uuid>,
+}
+
+/// Book of the catalogue export with the ids of the entities it references
+#[derive(Debug, sqlx::FromRow)]
+pub struct CatalogueEntry {
+    pub id: Uuid,
+    pub code_identifier: i32,
+    pub isbn: Option<String>,
+    pub issn: Option<String>,
+    pub release_year: i16,
+    pub edition: Option<i32>,
+    pub pages: Option<i32>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub category_id: Uuid,
+    pub language_id: Uuid,
+    pub publisher_id: Uuid,
+    pub series_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Copies in the library, without the retired ones
+    pub copies: i64,
 }
@@ -1,7 +1,15 @@
-use sea_query::{Alias, Cond, Expr, Order, Query, SelectStatement};
+use chrono::{DateTime, Utc};
+use sea_query::{
+    Alias, Cond, DeleteStatement, Expr, Iden, InsertStatement, Order, Query, SelectStatement,
+    SimpleExpr, UpdateStatement, Value,
+};
 use uuid::Uuid;
 
 use super::schema;
+use crate::rpc::models::{
+    BookDetails, NameDetails, PersonDetails, PersonPatch, RetirementReason, SeriesDetails,
+    SeriesPatch,
+};
 use helpers::filters;
 
 pub(crate) fn get_language_by_id(id: Uuid) -> SelectStatement {
@@ -16,6 +24,18 @@ pub(crate) fn get_language_by_id(id: Uuid) -> SelectStatement {
         .to_owned()
 }
 
+pub(crate) fn get_language_by_iso_code(iso_code: &str) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Languages::Id,
+            schema::Languages::IsoCode,
+            schema::Languages::Name,
+        ])
+        .from(schema::Languages::Table)
+        .and_where(Expr::col(schema::Languages::IsoCode).eq(iso_code))
+        .to_owned()
+}
+
 pub(crate) fn get_language_by_book_id(id: Uuid) -> SelectStatement {
     Query::select()
         .columns(vec![
@@ -33,6 +53,14 @@ pub(crate) fn get_language_by_book_id(id: Uuid) -> SelectStatement {
         .to_owned()
 }
 
+/// Counts the rows of a listing, which is read from the start without limit
+pub(crate) fn count(query: SelectStatement) -> SelectStatement {
+    Query::select()
+        .expr(Expr::cust("COUNT(*)"))
+        .from_subquery(query, Alias::new("t"))
+        .to_owned()
+}
+
 pub(crate) fn get_languages(page: filters::Page) -> SelectStatement {
     Query::select()
         .columns(vec![
@@ -59,7 +87,7 @@ pub(crate) fn get_languages(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -109,7 +137,7 @@ pub(crate) fn get_categories(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -125,6 +153,14 @@ pub(crate) fn get_publisher_by_id(id: Uuid) -> SelectStatement {
         .to_owned()
 }
 
+pub(crate) fn get_publisher_by_name(name: &str) -> SelectStatement {
+    Query::select()
+        .columns(vec![schema::Publishers::Id, schema::Publishers::Name])
+        .from(schema::Publishers::Table)
+        .and_where(Expr::col(schema::Publishers::Name).eq(name))
+        .to_owned()
+}
+
 pub(crate) fn get_publisher_by_book_id(id: Uuid) -> SelectStatement {
     Query::select()
         .columns(vec![
@@ -159,7 +195,7 @@ pub(crate) fn get_publishers(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -179,6 +215,19 @@ pub(crate) fn get_series_by_id(id: Uuid) -> SelectStatement {
         .to_owned()
 }
 
+pub(crate) fn get_series_by_name(publisher_id: Uuid, name: &str) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Series::Id,
+            schema::Series::PublisherId,
+            schema::Series::Name,
+        ])
+        .from(schema::Series::Table)
+        .and_where(Expr::col(schema::Series::PublisherId).eq(publisher_id))
+        .and_where(Expr::col(schema::Series::Name).eq(name))
+        .to_owned()
+}
+
 pub(crate) fn get_series_by_book_id(id: Uuid) -> SelectStatement {
     Query::select()
         .columns(vec![
@@ -222,7 +271,7 @@ pub(crate) fn get_series(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -238,6 +287,14 @@ pub(crate) fn get_subject_area_by_id(id: Uuid) -> SelectStatement {
         .to_owned()
 }
 
+pub(crate) fn get_subject_area_by_name(name: &str) -> SelectStatement {
+    Query::select()
+        .columns(vec![schema::SubjectAreas::Id, schema::SubjectAreas::Name])
+        .from(schema::SubjectAreas::Table)
+        .and_where(Expr::col(schema::SubjectAreas::Name).eq(name))
+        .to_owned()
+}
+
 pub(crate) fn get_subject_areas(page: filters::Page) -> SelectStatement {
     Query::select()
         .columns(vec![schema::SubjectAreas::Id, schema::SubjectAreas::Name])
@@ -256,7 +313,7 @@ pub(crate) fn get_subject_areas(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -295,7 +352,7 @@ pub(crate) fn get_subject_areas_by_book_id(id: Uuid, page: filters::Page) -> Sel
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -329,7 +386,7 @@ pub(crate) fn get_tags(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -368,7 +425,7 @@ pub(crate) fn get_tags_by_book_id(id: Uuid, page: filters::Page) -> SelectStatem
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -440,7 +497,7 @@ pub(crate) fn get_authors(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -497,7 +554,7 @@ pub(crate) fn get_authors_by_book_id(id: Uuid, page: filters::Page) -> SelectSta
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -569,7 +626,7 @@ pub(crate) fn get_editors(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -626,7 +683,7 @@ pub(crate) fn get_editors_by_book_id(id: Uuid, page: filters::Page) -> SelectSta
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -639,9 +696,12 @@ pub(crate) fn get_copy_by_id(id: Uuid) -> SelectStatement {
         .columns(vec![
             schema::Copies::Id,
             schema::Copies::BookId,
-            schema::Copies::CodeIdentifierCopyId,
+            schema::Copies::CopyId,
             schema::Copies::CreatedAt,
             schema::Copies::CreatedBy,
+            schema::Copies::RetiredAt,
+            schema::Copies::RetiredBy,
+            schema::Copies::RetirementReason,
         ])
         .from(schema::Copies::Table)
         .and_where(Expr::col(schema::Copies::Id).eq(id))
@@ -653,18 +713,24 @@ pub(crate) fn get_copies(page: filters::Page) -> SelectStatement {
         .columns(vec![
             schema::Copies::Id,
             schema::Copies::BookId,
-            schema::Copies::CodeIdentifierCopyId,
+            schema::Copies::CopyId,
             schema::Copies::CreatedAt,
             schema::Copies::CreatedBy,
+            schema::Copies::RetiredAt,
+            schema::Copies::RetiredBy,
+            schema::Copies::RetirementReason,
         ])
         .from_subquery(
             Query::select()
                 .columns(vec![
                     schema::Copies::Id,
                     schema::Copies::BookId,
-                    schema::Copies::CodeIdentifierCopyId,
+                    schema::Copies::CopyId,
                     schema::Copies::CreatedAt,
                     schema::Copies::CreatedBy,
+                    schema::Copies::RetiredAt,
+                    schema::Copies::RetiredBy,
+                    schema::Copies::RetirementReason,
                 ])
                 .from(schema::Copies::Table)
                 .and_where(match page.get_cursor() {
@@ -678,7 +744,7 @@ pub(crate) fn get_copies(page: filters::Page) -> SelectStatement {
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -691,18 +757,24 @@ pub(crate) fn get_copies_by_book_id(id: Uuid, page: filters::Page) -> SelectStat
         .columns(vec![
             schema::Copies::Id,
             schema::Copies::BookId,
-            schema::Copies::CodeIdentifierCopyId,
+            schema::Copies::CopyId,
             schema::Copies::CreatedAt,
             schema::Copies::CreatedBy,
+            schema::Copies::RetiredAt,
+            schema::Copies::RetiredBy,
+            schema::Copies::RetirementReason,
         ])
         .from_subquery(
             Query::select()
                 .columns(vec![
                     (schema::Copies::Table, schema::Copies::Id),
                     (schema::Copies::Table, schema::Copies::BookId),
-                    (schema::Copies::Table, schema::Copies::CodeIdentifierCopyId),
+                    (schema::Copies::Table, schema::Copies::CopyId),
                     (schema::Copies::Table, schema::Copies::CreatedAt),
                     (schema::Copies::Table, schema::Copies::CreatedBy),
+                    (schema::Copies::Table, schema::Copies::RetiredAt),
+                    (schema::Copies::Table, schema::Copies::RetiredBy),
+                    (schema::Copies::Table, schema::Copies::RetirementReason),
                 ])
                 .from(schema::Books::Table)
                 .inner_join(
@@ -726,7 +798,7 @@ pub(crate) fn get_copies_by_book_id(id: Uuid, page: filters::Page) -> SelectStat
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -747,13 +819,15 @@ pub(crate) fn get_book_by_id(id: Uuid) -> SelectStatement {
             schema::Books::Title,
             schema::Books::Subtitle,
             schema::Books::Description,
+            schema::Books::CreatedAt,
         ])
         .from(schema::Books::Table)
         .and_where(Expr::col(schema::Books::Id).eq(id))
         .to_owned()
 }
 
-pub(crate) fn get_books(page: filters::Page, book: filters::Book) -> SelectStatement {
+/// Book with the ids of the entities it references, looked up by its normalized ISBN
+pub(crate) fn get_book_entry_by_isbn(isbn: &str) -> SelectStatement {
     Query::select()
         .columns(vec![
             schema::Books::Id,
@@ -763,91 +837,94 @@ pub(crate) fn get_books(page: filters::Page, book: filters::Book) -> SelectState
             schema::Books::ReleaseYear,
             schema::Books::Edition,
             schema::Books::Pages,
+            schema::Books::Title,
             schema::Books::Subtitle,
+            schema::Books::Description,
+            schema::Books::CategoryId,
+            schema::Books::LanguageId,
+            schema::Books::PublisherId,
+            schema::Books::SeriesId,
+        ])
+        .from(schema::Books::Table)
+        .and_where(Expr::col(schema::Books::Isbn).eq(isbn))
+        .to_owned()
+}
+
+pub(crate) fn get_book_entry_by_id(id: Uuid) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Books::Id,
+            schema::Books::CodeIdentifier,
+            schema::Books::Isbn,
+            schema::Books::Issn,
+            schema::Books::ReleaseYear,
+            schema::Books::Edition,
+            schema::Books::Pages,
             schema::Books::Title,
+            schema::Books::Subtitle,
             schema::Books::Description,
+            schema::Books::CategoryId,
+            schema::Books::LanguageId,
+            schema::Books::PublisherId,
+            schema::Books::SeriesId,
         ])
+        .from(schema::Books::Table)
+        .and_where(Expr::col(schema::Books::Id).eq(id))
+        .to_owned()
+}
+
+/// Books with the ids of the entities they reference and their number of copies in the library,
+/// in the order of their ids
+pub(crate) fn get_catalogue(page: filters::Page) -> SelectStatement {
+    let columns = || {
+        vec![
+            schema::Books::Id,
+            schema::Books::CodeIdentifier,
+            schema::Books::Isbn,
+            schema::Books::Issn,
+            schema::Books::ReleaseYear,
+            schema::Books::Edition,
+            schema::Books::Pages,
+            schema::Books::Title,
+            schema::Books::Subtitle,
+            schema::Books::Description,
+            schema::Books::CategoryId,
+            schema::Books::LanguageId,
+            schema::Books::PublisherId,
+            schema::Books::SeriesId,
+            schema::Books::CreatedAt,
+        ]
+    };
+    let copies = Query::select()
+        .expr(Expr::tbl(schema::Copies::Table, schema::Copies::Id).count())
+        .from(schema::Copies::Table)
+        .and_where(
+            Expr::tbl(schema::Copies::Table, schema::Copies::BookId)
+                .equals(schema::Books::Table, schema::Books::Id),
+        )
+        .and_where(Expr::tbl(schema::Copies::Table, schema::Copies::RetiredAt).is_null())
+        .to_owned();
+
+    Query::select()
+        .columns(columns())
+        .column(Alias::new("copies"))
         .from_subquery(
             Query::select()
-                .columns(vec![
-                    (schema::Books::Table, schema::Books::Id),
-                    (schema::Books::Table, schema::Books::CodeIdentifier),
-                    (schema::Books::Table, schema::Books::Isbn),
-                    (schema::Books::Table, schema::Books::Issn),
-                    (schema::Books::Table, schema::Books::ReleaseYear),
-                    (schema::Books::Table, schema::Books::Edition),
-                    (schema::Books::Table, schema::Books::Pages),
-                    (schema::Books::Table, schema::Books::Subtitle),
-                    (schema::Books::Table, schema::Books::Title),
-                    (schema::Books::Table, schema::Books::Description),
-                ])
+                .columns(columns())
+                .expr_as(SimpleExpr::SubQuery(Box::new(copies)), Alias::new("copies"))
                 .from(schema::Books::Table)
-                .inner_join(
-                    schema::Categories::Table,
-                    Expr::tbl(schema::Books::Table, schema::Books::CategoryId)
-                        .equals(schema::Categories::Table, schema::Categories::Id),
-                )
-                .inner_join(
-                    schema::Publishers::Table,
-                    Expr::tbl(schema::Books::Table, schema::Books::PublisherId)
-                        .equals(schema::Publishers::Table, schema::Publishers::Id),
-                )
-                .left_join(
-                    schema::Series::Table,
-                    Expr::tbl(schema::Books::Table, schema::Books::SeriesId)
-                        .equals(schema::Series::Table, schema::Series::Id),
-                )
-                .left_join(
-                    schema::BooksTags::Table,
-                    Expr::tbl(schema::Books::Table, schema::Books::Id)
-                        .equals(schema::BooksTags::Table, schema::BooksTags::BookId),
-                )
-                .left_join(
-                    schema::Tags::Table,
-                    Expr::tbl(schema::BooksTags::Table, schema::BooksTags::TagId)
-                        .equals(schema::Tags::Table, schema::Tags::Id),
-                )
-                .cond_where(
-                    Cond::all()
-                        .add_option(book.get_categories().map(|categories| {
-                            Cond::all().add(
-                                Expr::tbl(schema::Categories::Table, schema::Categories::Name)
-                                    .is_in(categories),
-                            )
-                        }))
-                        .add_option(book.get_publishers().map(|publishers| {
-                            Cond::all().add(
-                                Expr::tbl(schema::Publishers::Table, schema::Publishers::Name)
-                                    .is_in(publishers),
-                            )
-                        }))
-                        .add_option(book.get_series().map(|series| {
-                            Cond::all().add(
-                                Expr::tbl(schema::Series::Table, schema::Series::Name)
-                                    .is_in(series),
-                            )
-                        }))
-                        .add_option(book.get_tags().map(|tags| {
-                            Cond::all()
-                                .add(Expr::tbl(schema::Tags::Table, schema::Tags::Name).is_in(tags))
-                        }))
-                        .add(Cond::all().add(match page.get_cursor() {
-                            filters::Cursor::After(id) => {
-                                Expr::tbl(schema::Books::Table, schema::Books::Id).gt(id)
-                            }
-                            filters::Cursor::Before(id) => {
-                                Expr::tbl(schema::Books::Table, schema::Books::Id).lt(id)
-                            }
-                        })),
-                )
+                .and_where(match page.get_cursor() {
+                    filters::Cursor::After(id) => Expr::col(schema::Books::Id).gt(id),
+                    filters::Cursor::Before(id) => Expr::col(schema::Books::Id).lt(id),
+                })
                 .order_by(
-                    (schema::Books::Table, schema::Books::Id),
+                    schema::Books::Id,
                     match page.get_cursor() {
                         filters::Cursor::After(_) => Order::Asc,
                         filters::Cursor::Before(_) => Order::Desc,
                     },
                 )
-                .limit(page.get_items() as u64)
+                .limit(page.get_limit())
                 .take(),
             Alias::new("t"),
         )
@@ -855,152 +932,1451 @@ pub(crate) fn get_books(page: filters::Page, book: filters::Book) -> SelectState
         .to_owned()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use sea_query::PostgresQueryBuilder;
-
-    #[test]
-    fn ut_get_language() {
-        let id = Uuid::new_v4();
-        let query = get_language_by_id(id);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" = '{}'"#,
-                id
-            ),
-            query.to_string(PostgresQueryBuilder)
+pub(crate) fn get_books(page: filters::KeysetPage, book: filters::Book) -> SelectStatement {
+    let position = page
+        .get_cursor()
+        .map_or(filters::Position::After, |cursor| cursor.get_position());
+    let mut books = Query::select()
+        .columns(vec![
+            (schema::Books::Table, schema::Books::Id),
+            (schema::Books::Table, schema::Books::CodeIdentifier),
+            (schema::Books::Table, schema::Books::Isbn),
+            (schema::Books::Table, schema::Books::Issn),
+            (schema::Books::Table, schema::Books::ReleaseYear),
+            (schema::Books::Table, schema::Books::Edition),
+            (schema::Books::Table, schema::Books::Pages),
+            (schema::Books::Table, schema::Books::Subtitle),
+            (schema::Books::Table, schema::Books::Title),
+            (schema::Books::Table, schema::Books::Description),
+            (schema::Books::Table, schema::Books::CreatedAt),
+        ])
+        .from(schema::Books::Table)
+        .cond_where(
+            Cond::all()
+                .add_option(book_filters(&book))
+                .add_option(page.get_cursor().map(keyset_condition)),
+        )
+        .to_owned();
+    for (field, direction) in page.get_sort().get_keys() {
+        books.order_by(
+            (schema::Books::Table, sort_column(*field)),
+            sort_order(*direction, position),
         );
     }
+    books
+        .order_by(
+            (schema::Books::Table, schema::Books::Id),
+            sort_order(filters::Direction::Asc, position),
+        )
+        .limit(page.get_limit());
 
-    #[test]
-    fn ut_get_language_by_book_id() {
-        let book_id = Uuid::new_v4();
-        let query = get_language_by_book_id(book_id);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "languages"."id", "languages"."iso_code", "languages"."name" FROM "languages" INNER JOIN "books" ON "languages"."id" = "books"."language_id" WHERE "books"."id" = '{}'"#,
-                book_id
-            ),
-            query.to_string(PostgresQueryBuilder)
+    let mut query = Query::select()
+        .columns(vec![
+            schema::Books::Id,
+            schema::Books::CodeIdentifier,
+            schema::Books::Isbn,
+            schema::Books::Issn,
+            schema::Books::ReleaseYear,
+            schema::Books::Edition,
+            schema::Books::Pages,
+            schema::Books::Subtitle,
+            schema::Books::Title,
+            schema::Books::Description,
+            schema::Books::CreatedAt,
+        ])
+        .from_subquery(books, Alias::new("t"))
+        .to_owned();
+    for (field, direction) in page.get_sort().get_keys() {
+        query.order_by(
+            sort_column(*field),
+            sort_order(*direction, filters::Position::After),
         );
     }
+    query.order_by(schema::Books::Id, Order::Asc).to_owned()
+}
 
-    #[test]
-    fn ut_get_languages_page_after() {
-        let filter_id = Uuid::new_v4();
-        let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::After(filter_id),
-            filters::Items::new(items),
-        );
-        let query = get_languages(page);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "iso_code", "name" FROM (SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
-            ),
-            query.to_string(PostgresQueryBuilder)
-        );
+fn sort_column(field: filters::SortField) -> schema::Books {
+    match field {
+        filters::SortField::Title => schema::Books::Title,
+        filters::SortField::ReleaseYear => schema::Books::ReleaseYear,
+        filters::SortField::CreatedAt => schema::Books::CreatedAt,
     }
+}
 
-    #[test]
-    fn ut_get_languages_page_before() {
-        let filter_id = Uuid::new_v4();
-        let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::Before(filter_id),
-            filters::Items::new(items),
-        );
-        let query = get_languages(page);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "iso_code", "name" FROM (SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
-            ),
-            query.to_string(PostgresQueryBuilder)
-        );
+/// Order of a sort key, which is reversed to read the page before a cursor
+fn sort_order(direction: filters::Direction, position: filters::Position) -> Order {
+    match (direction, position) {
+        (filters::Direction::Asc, filters::Position::After)
+        | (filters::Direction::Desc, filters::Position::Before) => Order::Asc,
+        (filters::Direction::Desc, filters::Position::After)
+        | (filters::Direction::Asc, filters::Position::Before) => Order::Desc,
     }
+}
 
-    #[test]
-    fn ut_get_category() {
-        let id = Uuid::new_v4();
-        let query = get_category_by_id(id);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "name" FROM "categories" WHERE "id" = '{}'"#,
-                id
-            ),
-            query.to_string(PostgresQueryBuilder)
+/// Condition of the books after or before a cursor in the order of its sort keys and the id,
+/// i.e. the first keys are equal to those of the cursor and the next one follows it
+fn keyset_condition(cursor: &filters::Keyset) -> Cond {
+    let keys = cursor.get_sort().get_keys();
+    let column = |index: usize| match keys.get(index) {
+        Some((field, _)) => sort_column(*field),
+        None => schema::Books::Id,
+    };
+    let mut values: Vec<Value> = cursor
+        .get_values()
+        .iter()
+        .map(|value| match value {
+            filters::SortValue::Text(text) => text.clone().into(),
+            filters::SortValue::Integer(integer) => (*integer).into(),
+            filters::SortValue::Timestamp(timestamp) => (*timestamp).into(),
+        })
+        .collect();
+    values.push(cursor.get_id().into());
+
+    let mut condition = Cond::any();
+    for (index, value) in values.iter().enumerate() {
+        let direction = keys
+            .get(index)
+            .map_or(filters::Direction::Asc, |(_, direction)| *direction);
+        let key = Expr::tbl(schema::Books::Table, column(index));
+        condition = condition.add(
+            values[..index]
+                .iter()
+                .enumerate()
+                .fold(Cond::all(), |equal, (previous, value)| {
+                    equal.add(Expr::tbl(schema::Books::Table, column(previous)).eq(value.clone()))
+                })
+                .add(match sort_order(direction, cursor.get_position()) {
+                    Order::Asc => key.gt(value.clone()),
+                    _ => key.lt(value.clone()),
+                }),
         );
     }
+    condition
+}
 
-    #[test]
-    fn ut_get_category_by_book_id() {
-        let book_id = Uuid::new_v4();
-        let query = get_category_by_book_id(book_id);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "categories"."id", "categories"."name" FROM "categories" INNER JOIN "books" ON "categories"."id" = "books"."category_id" WHERE "books"."id" = '{}'"#,
-                book_id
-            ),
-            query.to_string(PostgresQueryBuilder)
-        );
+/// Condition of the given book filters, which are combined as all or any of them.
+/// Linked entities are matched by subqueries, so that a book is listed once.
+fn book_filters(book: &filters::Book) -> Option<Cond> {
+    fn named<T: Iden + 'static>(
+        column: schema::Books,
+        table: T,
+        values: Vec<String>,
+    ) -> SimpleExpr {
+        Expr::tbl(schema::Books::Table, column).in_subquery(
+            Query::select()
+                .column(Alias::new("id"))
+                .from(table)
+                .and_where(Expr::col(Alias::new("name")).is_in(values))
+                .to_owned(),
+        )
     }
-
-    #[test]
-    fn ut_get_categories_page_after() {
-        let filter_id = Uuid::new_v4();
-        let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::After(filter_id),
-            filters::Items::new(items),
-        );
-        let query = get_categories(page);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "categories" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
-            ),
-            query.to_string(PostgresQueryBuilder)
-        );
+    let linked = |subquery: SelectStatement| {
+        Expr::tbl(schema::Books::Table, schema::Books::Id).in_subquery(subquery)
+    };
+    fn range(column: schema::Books, from: Option<i32>, to: Option<i32>) -> Option<SimpleExpr> {
+        let column = Expr::tbl(schema::Books::Table, column);
+        match (from, to) {
+            (Some(from), Some(to)) => Some(column.between(from, to)),
+            (Some(from), None) => Some(column.gte(from)),
+            (None, Some(to)) => Some(column.lte(to)),
+            (None, None) => None,
+        }
     }
 
-    #[test]
-    fn ut_get_categories_page_before() {
-        let filter_id = Uuid::new_v4();
-        let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::Before(filter_id),
-            filters::Items::new(items),
-        );
-        let query = get_categories(page);
-
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "categories" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
-            ),
-            query.to_string(PostgresQueryBuilder)
-        );
+    let conditions: Vec<Cond> = vec![
+        book.get_categories().map(|categories| {
+            Cond::all().add(named(
+                schema::Books::CategoryId,
+                schema::Categories::Table,
+                categories,
+            ))
+        }),
+        book.get_publishers().map(|publishers| {
+            Cond::all().add(named(
+                schema::Books::PublisherId,
+                schema::Publishers::Table,
+                publishers,
+            ))
+        }),
+        book.get_series().map(|series| {
+            Cond::all().add(named(
+                schema::Books::SeriesId,
+                schema::Series::Table,
+                series,
+            ))
+        }),
+        book.get_tags().map(|tags| {
+            Cond::all().add(linked(
+                Query::select()
+                    .column((schema::BooksTags::Table, schema::BooksTags::BookId))
+                    .from(schema::BooksTags::Table)
+                    .inner_join(
+                        schema::Tags::Table,
+                        Expr::tbl(schema::BooksTags::Table, schema::BooksTags::TagId)
+                            .equals(schema::Tags::Table, schema::Tags::Id),
+                    )
+                    .and_where(Expr::tbl(schema::Tags::Table, schema::Tags::Name).is_in(tags))
+                    .to_owned(),
+            ))
+        }),
+        book.get_subject_areas().map(|subject_areas| {
+            Cond::all().add(linked(
+                Query::select()
+                    .column((
+                        schema::BooksSubjectAreas::Table,
+                        schema::BooksSubjectAreas::BookId,
+                    ))
+                    .from(schema::BooksSubjectAreas::Table)
+                    .inner_join(
+                        schema::SubjectAreas::Table,
+                        Expr::tbl(
+                            schema::BooksSubjectAreas::Table,
+                            schema::BooksSubjectAreas::SubjectAreaId,
+                        )
+                        .equals(schema::SubjectAreas::Table, schema::SubjectAreas::Id),
+                    )
+                    .and_where(
+                        Expr::tbl(schema::SubjectAreas::Table, schema::SubjectAreas::Name)
+                            .is_in(subject_areas),
+                    )
+                    .to_owned(),
+            ))
+        }),
+        book.get_languages().map(|languages| {
+            Cond::all().add(
+                Expr::tbl(schema::Books::Table, schema::Books::LanguageId).in_subquery(
+                    Query::select()
+                        .column(schema::Languages::Id)
+                        .from(schema::Languages::Table)
+                        .and_where(Expr::col(schema::Languages::IsoCode).is_in(languages))
+                        .to_owned(),
+                ),
+            )
+        }),
+        book.get_authors().map(|authors| {
+            Cond::all().add(linked(
+                Query::select()
+                    .column(schema::BooksAuthors::BookId)
+                    .from(schema::BooksAuthors::Table)
+                    .and_where(Expr::col(schema::BooksAuthors::PersonId).is_in(authors))
+                    .to_owned(),
+            ))
+        }),
+        book.get_editors().map(|editors| {
+            Cond::all().add(linked(
+                Query::select()
+                    .column(schema::BooksEditors::BookId)
+                    .from(schema::BooksEditors::Table)
+                    .and_where(Expr::col(schema::BooksEditors::PersonId).is_in(editors))
+                    .to_owned(),
+            ))
+        }),
+        book.get_release_year()
+            .and_then(|release_year| {
+                range(
+                    schema::Books::ReleaseYear,
+                    release_year.get_from().map(i32::from),
+                    release_year.get_to().map(i32::from),
+                )
+            })
+            .map(|condition| Cond::all().add(condition)),
+        book.get_pages()
+            .and_then(|pages| range(schema::Books::Pages, pages.get_from(), pages.get_to()))
+            .map(|condition| Cond::all().add(condition)),
+        book.get_available().map(|available| {
+            let books = Expr::tbl(schema::Books::Table, schema::Books::Id);
+            Cond::all().add(if available {
+                books.in_subquery(available_books())
+            } else {
+                books.not_in_subquery(available_books())
+            })
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if conditions.is_empty() {
+        return None;
     }
+    let combined = match book.get_matching() {
+        filters::Match::All => Cond::all(),
+        filters::Match::Any => Cond::any(),
+    };
+    Some(
+        conditions
+            .into_iter()
+            .fold(combined, |combined, condition| combined.add(condition)),
+    )
+}
 
-    #[test]
-    fn ut_get_publisher() {
-        let id = Uuid::new_v4();
-        let query = get_publisher_by_id(id);
+/// Books with a copy, which is neither retired, lent nor held for pickup
+fn available_books() -> SelectStatement {
+    Query::select()
+        .column(schema::Copies::BookId)
+        .from(schema::Copies::Table)
+        .and_where(Expr::col(schema::Copies::RetiredAt).is_null())
+        .and_where(Expr::col(schema::Copies::Id).not_in_subquery(lent_copies()))
+        .and_where(Expr::col(schema::Copies::Id).not_in_subquery(held_copies()))
+        .to_owned()
+}
 
-        assert_eq!(
-            format!(
-                r#"SELECT "id", "name" FROM "publishers" WHERE "id" = '{}'"#,
-                id
+/// Books matching a text search query in their language, the best ranked first
+pub(crate) fn search_books(query: &str, items: i64) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Books::Id,
+            schema::Books::CodeIdentifier,
+            schema::Books::Isbn,
+            schema::Books::Issn,
+            schema::Books::ReleaseYear,
+            schema::Books::Edition,
+            schema::Books::Pages,
+            schema::Books::Subtitle,
+            schema::Books::Title,
+            schema::Books::Description,
+            schema::Books::CreatedAt,
+        ])
+        .from(schema::Books::Table)
+        .and_where(Expr::cust_with_values(
+            r#""search_vector" @@ books_search_query(?)"#,
+            vec![query],
+        ))
+        .and_where(Expr::cust_with_values(
+            r#""search_vector" @@ books_search_query(?, "language_id")"#,
+            vec![query],
+        ))
+        .order_by_expr(
+            Expr::cust_with_values(
+                r#"ts_rank_cd("search_vector", books_search_query(?, "language_id"))"#,
+                vec![query],
+            ),
+            Order::Desc,
+        )
+        .order_by(schema::Books::Id, Order::Asc)
+        .limit(items as u64)
+        .to_owned()
+}
+
+/// Reads the authors of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_authors_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::BooksAuthors::Table, schema::BooksAuthors::BookId),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Persons::Table, schema::Persons::Id),
+            (schema::Persons::Table, schema::Persons::FirstName),
+            (schema::Persons::Table, schema::Persons::LastName),
+            (schema::Persons::Table, schema::Persons::DateOfBirth),
+            (schema::Persons::Table, schema::Persons::Isni),
+            (schema::Persons::Table, schema::Persons::Orcid),
+            (schema::Persons::Table, schema::Persons::Oclc),
+        ])
+        .from(schema::BooksAuthors::Table)
+        .inner_join(
+            schema::Persons::Table,
+            Expr::tbl(schema::BooksAuthors::Table, schema::BooksAuthors::PersonId)
+                .equals(schema::Persons::Table, schema::Persons::Id),
+        )
+        .and_where(
+            Expr::tbl(schema::BooksAuthors::Table, schema::BooksAuthors::BookId)
+                .is_in(ids.to_vec()),
+        )
+        .order_by(
+            (schema::Persons::Table, schema::Persons::LastName),
+            Order::Asc,
+        )
+        .order_by(
+            (schema::Persons::Table, schema::Persons::FirstName),
+            Order::Asc,
+        )
+        .order_by((schema::Persons::Table, schema::Persons::Id), Order::Asc)
+        .to_owned()
+}
+
+/// Reads the editors of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_editors_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::BooksEditors::Table, schema::BooksEditors::BookId),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Persons::Table, schema::Persons::Id),
+            (schema::Persons::Table, schema::Persons::FirstName),
+            (schema::Persons::Table, schema::Persons::LastName),
+            (schema::Persons::Table, schema::Persons::DateOfBirth),
+            (schema::Persons::Table, schema::Persons::Isni),
+            (schema::Persons::Table, schema::Persons::Orcid),
+            (schema::Persons::Table, schema::Persons::Oclc),
+        ])
+        .from(schema::BooksEditors::Table)
+        .inner_join(
+            schema::Persons::Table,
+            Expr::tbl(schema::BooksEditors::Table, schema::BooksEditors::PersonId)
+                .equals(schema::Persons::Table, schema::Persons::Id),
+        )
+        .and_where(
+            Expr::tbl(schema::BooksEditors::Table, schema::BooksEditors::BookId)
+                .is_in(ids.to_vec()),
+        )
+        .order_by(
+            (schema::Persons::Table, schema::Persons::LastName),
+            Order::Asc,
+        )
+        .order_by(
+            (schema::Persons::Table, schema::Persons::FirstName),
+            Order::Asc,
+        )
+        .order_by((schema::Persons::Table, schema::Persons::Id), Order::Asc)
+        .to_owned()
+}
+
+/// Reads the tags of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_tags_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::BooksTags::Table, schema::BooksTags::BookId),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Tags::Table, schema::Tags::Id),
+            (schema::Tags::Table, schema::Tags::Name),
+        ])
+        .from(schema::BooksTags::Table)
+        .inner_join(
+            schema::Tags::Table,
+            Expr::tbl(schema::BooksTags::Table, schema::BooksTags::TagId)
+                .equals(schema::Tags::Table, schema::Tags::Id),
+        )
+        .and_where(
+            Expr::tbl(schema::BooksTags::Table, schema::BooksTags::BookId).is_in(ids.to_vec()),
+        )
+        .order_by((schema::Tags::Table, schema::Tags::Name), Order::Asc)
+        .to_owned()
+}
+
+/// Reads the subject areas of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_subject_areas_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(
+                schema::BooksSubjectAreas::Table,
+                schema::BooksSubjectAreas::BookId,
+            ),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::SubjectAreas::Table, schema::SubjectAreas::Id),
+            (schema::SubjectAreas::Table, schema::SubjectAreas::Name),
+        ])
+        .from(schema::BooksSubjectAreas::Table)
+        .inner_join(
+            schema::SubjectAreas::Table,
+            Expr::tbl(
+                schema::BooksSubjectAreas::Table,
+                schema::BooksSubjectAreas::SubjectAreaId,
+            )
+            .equals(schema::SubjectAreas::Table, schema::SubjectAreas::Id),
+        )
+        .and_where(
+            Expr::tbl(
+                schema::BooksSubjectAreas::Table,
+                schema::BooksSubjectAreas::BookId,
+            )
+            .is_in(ids.to_vec()),
+        )
+        .order_by(
+            (schema::SubjectAreas::Table, schema::SubjectAreas::Name),
+            Order::Asc,
+        )
+        .to_owned()
+}
+
+/// Reads the categories of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_categories_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::Books::Table, schema::Books::Id),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Categories::Table, schema::Categories::Id),
+            (schema::Categories::Table, schema::Categories::Name),
+        ])
+        .from(schema::Books::Table)
+        .inner_join(
+            schema::Categories::Table,
+            Expr::tbl(schema::Books::Table, schema::Books::CategoryId)
+                .equals(schema::Categories::Table, schema::Categories::Id),
+        )
+        .and_where(Expr::tbl(schema::Books::Table, schema::Books::Id).is_in(ids.to_vec()))
+        .to_owned()
+}
+
+/// Reads the languages of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_languages_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::Books::Table, schema::Books::Id),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Languages::Table, schema::Languages::Id),
+            (schema::Languages::Table, schema::Languages::IsoCode),
+            (schema::Languages::Table, schema::Languages::Name),
+        ])
+        .from(schema::Books::Table)
+        .inner_join(
+            schema::Languages::Table,
+            Expr::tbl(schema::Books::Table, schema::Books::LanguageId)
+                .equals(schema::Languages::Table, schema::Languages::Id),
+        )
+        .and_where(Expr::tbl(schema::Books::Table, schema::Books::Id).is_in(ids.to_vec()))
+        .to_owned()
+}
+
+/// Reads the publishers of the given books, each row with the id of its book as `book_id`
+pub(crate) fn get_publishers_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::Books::Table, schema::Books::Id),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Publishers::Table, schema::Publishers::Id),
+            (schema::Publishers::Table, schema::Publishers::Name),
+        ])
+        .from(schema::Books::Table)
+        .inner_join(
+            schema::Publishers::Table,
+            Expr::tbl(schema::Books::Table, schema::Books::PublisherId)
+                .equals(schema::Publishers::Table, schema::Publishers::Id),
+        )
+        .and_where(Expr::tbl(schema::Books::Table, schema::Books::Id).is_in(ids.to_vec()))
+        .to_owned()
+}
+
+/// Reads the series of the given books, each row with the id of its book as `book_id`.
+/// Books without series have no row.
+pub(crate) fn get_series_by_book_ids(ids: &[Uuid]) -> SelectStatement {
+    Query::select()
+        .expr_as(
+            Expr::tbl(schema::Books::Table, schema::Books::Id),
+            Alias::new("book_id"),
+        )
+        .columns(vec![
+            (schema::Series::Table, schema::Series::Id),
+            (schema::Series::Table, schema::Series::PublisherId),
+            (schema::Series::Table, schema::Series::Name),
+        ])
+        .from(schema::Books::Table)
+        .inner_join(
+            schema::Series::Table,
+            Expr::tbl(schema::Books::Table, schema::Books::SeriesId)
+                .equals(schema::Series::Table, schema::Series::Id),
+        )
+        .and_where(Expr::tbl(schema::Books::Table, schema::Books::Id).is_in(ids.to_vec()))
+        .to_owned()
+}
+
+pub(crate) fn create_book(id: Uuid, book: &BookDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Books::Table)
+        .columns(vec![
+            schema::Books::Id,
+            schema::Books::CodeIdentifier,
+            schema::Books::Isbn,
+            schema::Books::Issn,
+            schema::Books::ReleaseYear,
+            schema::Books::Edition,
+            schema::Books::Pages,
+            schema::Books::Subtitle,
+            schema::Books::Title,
+            schema::Books::Description,
+            schema::Books::CategoryId,
+            schema::Books::LanguageId,
+            schema::Books::PublisherId,
+            schema::Books::SeriesId,
+        ])
+        .values_panic(vec![
+            id.into(),
+            book.code_identifier.into(),
+            book.isbn.clone().into(),
+            book.issn.clone().into(),
+            book.release_year.into(),
+            book.edition.into(),
+            book.pages.into(),
+            book.subtitle.clone().into(),
+            book.title.clone().into(),
+            book.description.clone().into(),
+            book.category_id.into(),
+            book.language_id.into(),
+            book.publisher_id.into(),
+            book.series_id.into(),
+        ])
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Books::Id,
+                    schema::Books::CodeIdentifier,
+                    schema::Books::Isbn,
+                    schema::Books::Issn,
+                    schema::Books::ReleaseYear,
+                    schema::Books::Edition,
+                    schema::Books::Pages,
+                    schema::Books::Title,
+                    schema::Books::Subtitle,
+                    schema::Books::Description,
+                    schema::Books::CreatedAt,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn update_book(id: Uuid, book: &BookDetails) -> UpdateStatement {
+    Query::update()
+        .table(schema::Books::Table)
+        .values(vec![
+            (schema::Books::CodeIdentifier, book.code_identifier.into()),
+            (schema::Books::Isbn, book.isbn.clone().into()),
+            (schema::Books::Issn, book.issn.clone().into()),
+            (schema::Books::ReleaseYear, book.release_year.into()),
+            (schema::Books::Edition, book.edition.into()),
+            (schema::Books::Pages, book.pages.into()),
+            (schema::Books::Subtitle, book.subtitle.clone().into()),
+            (schema::Books::Title, book.title.clone().into()),
+            (schema::Books::Description, book.description.clone().into()),
+            (schema::Books::CategoryId, book.category_id.into()),
+            (schema::Books::LanguageId, book.language_id.into()),
+            (schema::Books::PublisherId, book.publisher_id.into()),
+            (schema::Books::SeriesId, book.series_id.into()),
+        ])
+        .and_where(Expr::col(schema::Books::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Books::Id,
+                    schema::Books::CodeIdentifier,
+                    schema::Books::Isbn,
+                    schema::Books::Issn,
+                    schema::Books::ReleaseYear,
+                    schema::Books::Edition,
+                    schema::Books::Pages,
+                    schema::Books::Title,
+                    schema::Books::Subtitle,
+                    schema::Books::Description,
+                    schema::Books::CreatedAt,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_book(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Books::Table)
+        .and_where(Expr::col(schema::Books::Id).eq(id))
+        .to_owned()
+}
+
+/// Links a book to authors, every link is given as pair of its id and the person id
+pub(crate) fn create_books_authors(book_id: Uuid, links: &[(Uuid, Uuid)]) -> InsertStatement {
+    let mut query = Query::insert()
+        .into_table(schema::BooksAuthors::Table)
+        .columns(vec![
+            schema::BooksAuthors::Id,
+            schema::BooksAuthors::BookId,
+            schema::BooksAuthors::PersonId,
+        ])
+        .to_owned();
+    for (id, person_id) in links {
+        query.values_panic(vec![(*id).into(), book_id.into(), (*person_id).into()]);
+    }
+    query
+}
+
+pub(crate) fn delete_books_authors(book_id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksAuthors::Table)
+        .and_where(Expr::col(schema::BooksAuthors::BookId).eq(book_id))
+        .to_owned()
+}
+
+/// Links a book to editors, every link is given as pair of its id and the person id
+pub(crate) fn create_books_editors(book_id: Uuid, links: &[(Uuid, Uuid)]) -> InsertStatement {
+    let mut query = Query::insert()
+        .into_table(schema::BooksEditors::Table)
+        .columns(vec![
+            schema::BooksEditors::Id,
+            schema::BooksEditors::BookId,
+            schema::BooksEditors::PersonId,
+        ])
+        .to_owned();
+    for (id, person_id) in links {
+        query.values_panic(vec![(*id).into(), book_id.into(), (*person_id).into()]);
+    }
+    query
+}
+
+pub(crate) fn delete_books_editors(book_id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksEditors::Table)
+        .and_where(Expr::col(schema::BooksEditors::BookId).eq(book_id))
+        .to_owned()
+}
+
+/// Links a book to subject areas, every link is given as pair of its id and the subject area id
+pub(crate) fn create_books_subject_areas(book_id: Uuid, links: &[(Uuid, Uuid)]) -> InsertStatement {
+    let mut query = Query::insert()
+        .into_table(schema::BooksSubjectAreas::Table)
+        .columns(vec![
+            schema::BooksSubjectAreas::Id,
+            schema::BooksSubjectAreas::BookId,
+            schema::BooksSubjectAreas::SubjectAreaId,
+        ])
+        .to_owned();
+    for (id, subject_area_id) in links {
+        query.values_panic(vec![
+            (*id).into(),
+            book_id.into(),
+            (*subject_area_id).into(),
+        ]);
+    }
+    query
+}
+
+pub(crate) fn delete_books_subject_areas(book_id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksSubjectAreas::Table)
+        .and_where(Expr::col(schema::BooksSubjectAreas::BookId).eq(book_id))
+        .to_owned()
+}
+
+/// Links a book to tags, every link is given as pair of its id and the tag id
+pub(crate) fn create_books_tags(book_id: Uuid, links: &[(Uuid, Uuid)]) -> InsertStatement {
+    let mut query = Query::insert()
+        .into_table(schema::BooksTags::Table)
+        .columns(vec![
+            schema::BooksTags::Id,
+            schema::BooksTags::BookId,
+            schema::BooksTags::TagId,
+        ])
+        .to_owned();
+    for (id, tag_id) in links {
+        query.values_panic(vec![(*id).into(), book_id.into(), (*tag_id).into()]);
+    }
+    query
+}
+
+pub(crate) fn delete_books_tags(book_id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksTags::Table)
+        .and_where(Expr::col(schema::BooksTags::BookId).eq(book_id))
+        .to_owned()
+}
+
+pub(crate) fn create_category(id: Uuid, category: &NameDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Categories::Table)
+        .columns(vec![schema::Categories::Id, schema::Categories::Name])
+        .values_panic(vec![id.into(), category.name.clone().into()])
+        .returning(
+            Query::select()
+                .columns(vec![schema::Categories::Id, schema::Categories::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn update_category(id: Uuid, category: &NameDetails) -> UpdateStatement {
+    Query::update()
+        .table(schema::Categories::Table)
+        .values(vec![(
+            schema::Categories::Name,
+            category.name.clone().into(),
+        )])
+        .and_where(Expr::col(schema::Categories::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![schema::Categories::Id, schema::Categories::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_category(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Categories::Table)
+        .and_where(Expr::col(schema::Categories::Id).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn create_publisher(id: Uuid, publisher: &NameDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Publishers::Table)
+        .columns(vec![schema::Publishers::Id, schema::Publishers::Name])
+        .values_panic(vec![id.into(), publisher.name.clone().into()])
+        .returning(
+            Query::select()
+                .columns(vec![schema::Publishers::Id, schema::Publishers::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn update_publisher(id: Uuid, publisher: &NameDetails) -> UpdateStatement {
+    Query::update()
+        .table(schema::Publishers::Table)
+        .values(vec![(
+            schema::Publishers::Name,
+            publisher.name.clone().into(),
+        )])
+        .and_where(Expr::col(schema::Publishers::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![schema::Publishers::Id, schema::Publishers::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_publisher(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Publishers::Table)
+        .and_where(Expr::col(schema::Publishers::Id).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn count_books_by_publisher_id(id: Uuid) -> SelectStatement {
+    Query::select()
+        .expr(Expr::col(schema::Books::Id).count())
+        .from(schema::Books::Table)
+        .and_where(Expr::col(schema::Books::PublisherId).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn create_series(id: Uuid, series: &SeriesDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Series::Table)
+        .columns(vec![
+            schema::Series::Id,
+            schema::Series::PublisherId,
+            schema::Series::Name,
+        ])
+        .values_panic(vec![
+            id.into(),
+            series.publisher_id.into(),
+            series.name.clone().into(),
+        ])
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Series::Id,
+                    schema::Series::PublisherId,
+                    schema::Series::Name,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+/// Changes the given fields of a series, the patch must change at least one field
+pub(crate) fn update_series(id: Uuid, patch: &SeriesPatch) -> UpdateStatement {
+    let mut values = vec![];
+    if let Some(publisher_id) = patch.publisher_id {
+        values.push((schema::Series::PublisherId, publisher_id.into()));
+    }
+    if let Some(name) = &patch.name {
+        values.push((schema::Series::Name, name.clone().into()));
+    }
+
+    Query::update()
+        .table(schema::Series::Table)
+        .values(values)
+        .and_where(Expr::col(schema::Series::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Series::Id,
+                    schema::Series::PublisherId,
+                    schema::Series::Name,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_series(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Series::Table)
+        .and_where(Expr::col(schema::Series::Id).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn create_subject_area(id: Uuid, subject_area: &NameDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::SubjectAreas::Table)
+        .columns(vec![schema::SubjectAreas::Id, schema::SubjectAreas::Name])
+        .values_panic(vec![id.into(), subject_area.name.clone().into()])
+        .returning(
+            Query::select()
+                .columns(vec![schema::SubjectAreas::Id, schema::SubjectAreas::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn update_subject_area(id: Uuid, subject_area: &NameDetails) -> UpdateStatement {
+    Query::update()
+        .table(schema::SubjectAreas::Table)
+        .values(vec![(
+            schema::SubjectAreas::Name,
+            subject_area.name.clone().into(),
+        )])
+        .and_where(Expr::col(schema::SubjectAreas::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![schema::SubjectAreas::Id, schema::SubjectAreas::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_subject_area(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::SubjectAreas::Table)
+        .and_where(Expr::col(schema::SubjectAreas::Id).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn create_tag(id: Uuid, tag: &NameDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Tags::Table)
+        .columns(vec![schema::Tags::Id, schema::Tags::Name])
+        .values_panic(vec![id.into(), tag.name.clone().into()])
+        .returning(
+            Query::select()
+                .columns(vec![schema::Tags::Id, schema::Tags::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn update_tag(id: Uuid, tag: &NameDetails) -> UpdateStatement {
+    Query::update()
+        .table(schema::Tags::Table)
+        .values(vec![(schema::Tags::Name, tag.name.clone().into())])
+        .and_where(Expr::col(schema::Tags::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![schema::Tags::Id, schema::Tags::Name])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_tag(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Tags::Table)
+        .and_where(Expr::col(schema::Tags::Id).eq(id))
+        .to_owned()
+}
+
+pub(crate) fn get_person_by_id(id: Uuid) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Persons::Id,
+            schema::Persons::FirstName,
+            schema::Persons::LastName,
+            schema::Persons::DateOfBirth,
+            schema::Persons::Isni,
+            schema::Persons::Orcid,
+            schema::Persons::Oclc,
+        ])
+        .from(schema::Persons::Table)
+        .and_where(Expr::col(schema::Persons::Id).eq(id))
+        .to_owned()
+}
+
+/// Persons with any of the given identifiers, of which at least one must be present
+pub(crate) fn get_persons_by_identifiers(
+    isni: Option<&str>,
+    orcid: Option<&str>,
+) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Persons::Id,
+            schema::Persons::FirstName,
+            schema::Persons::LastName,
+            schema::Persons::DateOfBirth,
+            schema::Persons::Isni,
+            schema::Persons::Orcid,
+            schema::Persons::Oclc,
+        ])
+        .from(schema::Persons::Table)
+        .cond_where(
+            Cond::any()
+                .add_option(isni.map(|isni| Expr::col(schema::Persons::Isni).eq(isni)))
+                .add_option(orcid.map(|orcid| Expr::col(schema::Persons::Orcid).eq(orcid))),
+        )
+        .order_by(schema::Persons::Id, Order::Asc)
+        .to_owned()
+}
+
+pub(crate) fn get_persons_by_name(first_name: &str, last_name: &str) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            schema::Persons::Id,
+            schema::Persons::FirstName,
+            schema::Persons::LastName,
+            schema::Persons::DateOfBirth,
+            schema::Persons::Isni,
+            schema::Persons::Orcid,
+            schema::Persons::Oclc,
+        ])
+        .from(schema::Persons::Table)
+        .and_where(Expr::col(schema::Persons::FirstName).eq(first_name))
+        .and_where(Expr::col(schema::Persons::LastName).eq(last_name))
+        .order_by(schema::Persons::Id, Order::Asc)
+        .to_owned()
+}
+
+pub(crate) fn create_person(id: Uuid, person: &PersonDetails) -> InsertStatement {
+    Query::insert()
+        .into_table(schema::Persons::Table)
+        .columns(vec![
+            schema::Persons::Id,
+            schema::Persons::FirstName,
+            schema::Persons::LastName,
+            schema::Persons::DateOfBirth,
+            schema::Persons::Isni,
+            schema::Persons::Orcid,
+            schema::Persons::Oclc,
+        ])
+        .values_panic(vec![
+            id.into(),
+            person.first_name.clone().into(),
+            person.last_name.clone().into(),
+            person.date_of_birth.into(),
+            person.isni.clone().into(),
+            person.orcid.clone().into(),
+            person.oclc.into(),
+        ])
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Persons::Id,
+                    schema::Persons::FirstName,
+                    schema::Persons::LastName,
+                    schema::Persons::DateOfBirth,
+                    schema::Persons::Isni,
+                    schema::Persons::Orcid,
+                    schema::Persons::Oclc,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+/// Changes the given fields of a person, the patch must change at least one field
+pub(crate) fn update_person(id: Uuid, patch: &PersonPatch) -> UpdateStatement {
+    let mut values = vec![];
+    if let Some(first_name) = &patch.first_name {
+        values.push((schema::Persons::FirstName, first_name.clone().into()));
+    }
+    if let Some(last_name) = &patch.last_name {
+        values.push((schema::Persons::LastName, last_name.clone().into()));
+    }
+    if let Some(date_of_birth) = patch.date_of_birth {
+        values.push((schema::Persons::DateOfBirth, date_of_birth.into()));
+    }
+    if let Some(isni) = &patch.isni {
+        values.push((schema::Persons::Isni, isni.clone().into()));
+    }
+    if let Some(orcid) = &patch.orcid {
+        values.push((schema::Persons::Orcid, orcid.clone().into()));
+    }
+    if let Some(oclc) = patch.oclc {
+        values.push((schema::Persons::Oclc, oclc.into()));
+    }
+
+    Query::update()
+        .table(schema::Persons::Table)
+        .values(values)
+        .and_where(Expr::col(schema::Persons::Id).eq(id))
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Persons::Id,
+                    schema::Persons::FirstName,
+                    schema::Persons::LastName,
+                    schema::Persons::DateOfBirth,
+                    schema::Persons::Isni,
+                    schema::Persons::Orcid,
+                    schema::Persons::Oclc,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+pub(crate) fn delete_person(id: Uuid) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::Persons::Table)
+        .and_where(Expr::col(schema::Persons::Id).eq(id))
+        .to_owned()
+}
+
+/// Removes the authorships of a duplicate person for books, which the person authored as well
+pub(crate) fn delete_duplicate_books_authors(
+    person_id: Uuid,
+    duplicate_id: Uuid,
+) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksAuthors::Table)
+        .and_where(Expr::col(schema::BooksAuthors::PersonId).eq(duplicate_id))
+        .and_where(
+            Expr::col(schema::BooksAuthors::BookId).in_subquery(
+                Query::select()
+                    .column(schema::BooksAuthors::BookId)
+                    .from(schema::BooksAuthors::Table)
+                    .and_where(Expr::col(schema::BooksAuthors::PersonId).eq(person_id))
+                    .take(),
+            ),
+        )
+        .to_owned()
+}
+
+/// Moves the authorships of a duplicate person to the person
+pub(crate) fn update_books_authors_person(person_id: Uuid, duplicate_id: Uuid) -> UpdateStatement {
+    Query::update()
+        .table(schema::BooksAuthors::Table)
+        .values(vec![(schema::BooksAuthors::PersonId, person_id.into())])
+        .and_where(Expr::col(schema::BooksAuthors::PersonId).eq(duplicate_id))
+        .to_owned()
+}
+
+/// Removes the editorships of a duplicate person for books, which the person edited as well
+pub(crate) fn delete_duplicate_books_editors(
+    person_id: Uuid,
+    duplicate_id: Uuid,
+) -> DeleteStatement {
+    Query::delete()
+        .from_table(schema::BooksEditors::Table)
+        .and_where(Expr::col(schema::BooksEditors::PersonId).eq(duplicate_id))
+        .and_where(
+            Expr::col(schema::BooksEditors::BookId).in_subquery(
+                Query::select()
+                    .column(schema::BooksEditors::BookId)
+                    .from(schema::BooksEditors::Table)
+                    .and_where(Expr::col(schema::BooksEditors::PersonId).eq(person_id))
+                    .take(),
+            ),
+        )
+        .to_owned()
+}
+
+/// Moves the editorships of a duplicate person to the person
+pub(crate) fn update_books_editors_person(person_id: Uuid, duplicate_id: Uuid) -> UpdateStatement {
+    Query::update()
+        .table(schema::BooksEditors::Table)
+        .values(vec![(schema::BooksEditors::PersonId, person_id.into())])
+        .and_where(Expr::col(schema::BooksEditors::PersonId).eq(duplicate_id))
+        .to_owned()
+}
+
+/// Adds copies of a book, their copy ids are assigned by the database
+pub(crate) fn create_copies(
+    book_id: Uuid,
+    ids: &[Uuid],
+    created_at: DateTime<Utc>,
+    created_by: Uuid,
+) -> InsertStatement {
+    let mut query = Query::insert()
+        .into_table(schema::Copies::Table)
+        .columns(vec![
+            schema::Copies::Id,
+            schema::Copies::BookId,
+            schema::Copies::CreatedAt,
+            schema::Copies::CreatedBy,
+        ])
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Copies::Id,
+                    schema::Copies::BookId,
+                    schema::Copies::CopyId,
+                    schema::Copies::CreatedAt,
+                    schema::Copies::CreatedBy,
+                    schema::Copies::RetiredAt,
+                    schema::Copies::RetiredBy,
+                    schema::Copies::RetirementReason,
+                ])
+                .take(),
+        )
+        .to_owned();
+    for id in ids {
+        query.values_panic(vec![
+            (*id).into(),
+            book_id.into(),
+            created_at.into(),
+            created_by.into(),
+        ]);
+    }
+    query
+}
+
+/// Copies labeled `code_identifier`-`copy_id`, which is ambiguous if books share a code identifier
+pub(crate) fn get_copies_by_label(code_identifier: i32, copy_id: i32) -> SelectStatement {
+    Query::select()
+        .columns(vec![
+            (schema::Copies::Table, schema::Copies::Id),
+            (schema::Copies::Table, schema::Copies::BookId),
+            (schema::Copies::Table, schema::Copies::CopyId),
+            (schema::Copies::Table, schema::Copies::CreatedAt),
+            (schema::Copies::Table, schema::Copies::CreatedBy),
+            (schema::Copies::Table, schema::Copies::RetiredAt),
+            (schema::Copies::Table, schema::Copies::RetiredBy),
+            (schema::Copies::Table, schema::Copies::RetirementReason),
+        ])
+        .from(schema::Copies::Table)
+        .inner_join(
+            schema::Books::Table,
+            Expr::tbl(schema::Books::Table, schema::Books::Id)
+                .equals(schema::Copies::Table, schema::Copies::BookId),
+        )
+        .and_where(
+            Expr::tbl(schema::Books::Table, schema::Books::CodeIdentifier).eq(code_identifier),
+        )
+        .and_where(Expr::tbl(schema::Copies::Table, schema::Copies::CopyId).eq(copy_id))
+        .order_by((schema::Copies::Table, schema::Copies::Id), Order::Asc)
+        .limit(2)
+        .to_owned()
+}
+
+/// Retires a copy, which is not retired yet
+pub(crate) fn retire_copy(
+    id: Uuid,
+    reason: RetirementReason,
+    retired_at: DateTime<Utc>,
+    retired_by: Uuid,
+) -> UpdateStatement {
+    Query::update()
+        .table(schema::Copies::Table)
+        .values(vec![
+            (schema::Copies::RetiredAt, retired_at.into()),
+            (schema::Copies::RetiredBy, retired_by.into()),
+            (schema::Copies::RetirementReason, reason.as_str().into()),
+        ])
+        .and_where(Expr::col(schema::Copies::Id).eq(id))
+        .and_where(Expr::col(schema::Copies::RetiredAt).is_null())
+        .and_where(Expr::col(schema::Copies::Id).not_in_subquery(lent_copies()))
+        .and_where(Expr::col(schema::Copies::Id).not_in_subquery(held_copies()))
+        .returning(
+            Query::select()
+                .columns(vec![
+                    schema::Copies::Id,
+                    schema::Copies::BookId,
+                    schema::Copies::CopyId,
+                    schema::Copies::CreatedAt,
+                    schema::Copies::CreatedBy,
+                    schema::Copies::RetiredAt,
+                    schema::Copies::RetiredBy,
+                    schema::Copies::RetirementReason,
+                ])
+                .take(),
+        )
+        .to_owned()
+}
+
+/// Copies, which are lent and not returned yet
+fn lent_copies() -> SelectStatement {
+    Query::select()
+        .column(schema::Loans::CopyId)
+        .from(schema::Loans::Table)
+        .and_where(Expr::col(schema::Loans::ReturnedAt).is_null())
+        .to_owned()
+}
+
+/// Copies, which are held for pickup
+fn held_copies() -> SelectStatement {
+    Query::select()
+        .column(schema::Holds::CopyId)
+        .from(schema::Holds::Table)
+        .and_where(Expr::col(schema::Holds::Status).eq("ready"))
+        .and_where(Expr::col(schema::Holds::CopyId).is_not_null())
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sea_query::PostgresQueryBuilder;
+
+    #[test]
+    fn ut_get_language() {
+        let id = Uuid::new_v4();
+        let query = get_language_by_id(id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" = '{}'"#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_language_by_book_id() {
+        let book_id = Uuid::new_v4();
+        let query = get_language_by_book_id(book_id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "languages"."id", "languages"."iso_code", "languages"."name" FROM "languages" INNER JOIN "books" ON "languages"."id" = "books"."language_id" WHERE "books"."id" = '{}'"#,
+                book_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_count() {
+        let query = count(get_languages(filters::Page::all()));
+
+        assert_eq!(
+            format!(
+                r#"SELECT COUNT(*) FROM (SELECT "id", "iso_code", "name" FROM (SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC) AS "t""#,
+                Uuid::nil(),
+                i64::MAX
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_languages_page_after() {
+        let filter_id = Uuid::new_v4();
+        let items = 10;
+        let page = filters::Page::new(
+            filters::Cursor::After(filter_id),
+            filters::Items::new(items),
+        );
+        let query = get_languages(page);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "iso_code", "name" FROM (SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_languages_page_before() {
+        let filter_id = Uuid::new_v4();
+        let items = 10;
+        let page = filters::Page::new(
+            filters::Cursor::Before(filter_id),
+            filters::Items::new(items),
+        );
+        let query = get_languages(page);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "iso_code", "name" FROM (SELECT "id", "iso_code", "name" FROM "languages" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_category() {
+        let id = Uuid::new_v4();
+        let query = get_category_by_id(id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "name" FROM "categories" WHERE "id" = '{}'"#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_category_by_book_id() {
+        let book_id = Uuid::new_v4();
+        let query = get_category_by_book_id(book_id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "categories"."id", "categories"."name" FROM "categories" INNER JOIN "books" ON "categories"."id" = "books"."category_id" WHERE "books"."id" = '{}'"#,
+                book_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_categories_page_after() {
+        let filter_id = Uuid::new_v4();
+        let items = 10;
+        let page = filters::Page::new(
+            filters::Cursor::After(filter_id),
+            filters::Items::new(items),
+        );
+        let query = get_categories(page);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "categories" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_categories_page_before() {
+        let filter_id = Uuid::new_v4();
+        let items = 10;
+        let page = filters::Page::new(
+            filters::Cursor::Before(filter_id),
+            filters::Items::new(items),
+        );
+        let query = get_categories(page);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "categories" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_publisher() {
+        let id = Uuid::new_v4();
+        let query = get_publisher_by_id(id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "name" FROM "publishers" WHERE "id" = '{}'"#,
+                id
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1033,7 +2409,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "publishers" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1052,7 +2429,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "publishers" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1099,7 +2477,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "publisher_id", "name" FROM (SELECT "id", "publisher_id", "name" FROM "series" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1118,7 +2497,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "publisher_id", "name" FROM (SELECT "id", "publisher_id", "name" FROM "series" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1152,7 +2532,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "subject_areas"."id", "subject_areas"."name" FROM "books_subject_areas" INNER JOIN "subject_areas" ON "subject_area_id" = "subject_areas"."id" WHERE "book_id" = '{}' AND "subject_areas"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1172,7 +2554,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "subject_areas"."id", "subject_areas"."name" FROM "books_subject_areas" INNER JOIN "subject_areas" ON "subject_area_id" = "subject_areas"."id" WHERE "book_id" = '{}' AND "subject_areas"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1191,7 +2575,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "subject_areas" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1210,7 +2595,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "subject_areas" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1241,7 +2627,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "tags"."id", "tags"."name" FROM "books_tags" INNER JOIN "tags" ON "tag_id" = "tags"."id" WHERE "book_id" = '{}' AND "tags"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1261,7 +2649,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "tags"."id", "tags"."name" FROM "books_tags" INNER JOIN "tags" ON "tag_id" = "tags"."id" WHERE "book_id" = '{}' AND "tags"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1280,7 +2670,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "tags" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1299,7 +2690,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "name" FROM (SELECT "id", "name" FROM "tags" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1333,7 +2725,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_authors" INNER JOIN "persons" ON "person_id" = "persons"."id" INNER JOIN "books" ON "book_id" = "books"."id" WHERE "books"."id" = '{}' AND "persons"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1353,7 +2747,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_authors" INNER JOIN "persons" ON "person_id" = "persons"."id" INNER JOIN "books" ON "book_id" = "books"."id" WHERE "books"."id" = '{}' AND "persons"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1372,7 +2768,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_authors" INNER JOIN "persons" ON "persons"."id" = "books_authors"."person_id" WHERE "persons"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1391,7 +2788,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_authors" INNER JOIN "persons" ON "persons"."id" = "books_authors"."person_id" WHERE "persons"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1425,7 +2823,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_editors" INNER JOIN "persons" ON "person_id" = "persons"."id" INNER JOIN "books" ON "book_id" = "books"."id" WHERE "books"."id" = '{}' AND "persons"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1445,7 +2845,9 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_editors" INNER JOIN "persons" ON "person_id" = "persons"."id" INNER JOIN "books" ON "book_id" = "books"."id" WHERE "books"."id" = '{}' AND "persons"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1464,7 +2866,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_editors" INNER JOIN "persons" ON "persons"."id" = "books_editors"."person_id" WHERE "persons"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1483,7 +2886,8 @@ mod tests {
         assert_eq!(
             format!(
                 r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM (SELECT "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_editors" INNER JOIN "persons" ON "persons"."id" = "books_editors"."person_id" WHERE "persons"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1496,7 +2900,7 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM "copies" WHERE "id" = '{}'"#,
+                r#"SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM "copies" WHERE "id" = '{}'"#,
                 id
             ),
             query.to_string(PostgresQueryBuilder)
@@ -1516,8 +2920,10 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM (SELECT "copies"."id", "copies"."book_id", "copies"."code_identifier_copy_id", "copies"."created_at", "copies"."created_by" FROM "books" INNER JOIN "copies" ON "books"."id" = "copies"."book_id" WHERE "books"."id" = '{}' AND "copies"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                r#"SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM (SELECT "copies"."id", "copies"."book_id", "copies"."copy_id", "copies"."created_at", "copies"."created_by", "copies"."retired_at", "copies"."retired_by", "copies"."retirement_reason" FROM "books" INNER JOIN "copies" ON "books"."id" = "copies"."book_id" WHERE "books"."id" = '{}' AND "copies"."id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1536,8 +2942,10 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM (SELECT "copies"."id", "copies"."book_id", "copies"."code_identifier_copy_id", "copies"."created_at", "copies"."created_by" FROM "books" INNER JOIN "copies" ON "books"."id" = "copies"."book_id" WHERE "books"."id" = '{}' AND "copies"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                book_id, filter_id, items
+                r#"SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM (SELECT "copies"."id", "copies"."book_id", "copies"."copy_id", "copies"."created_at", "copies"."created_by", "copies"."retired_at", "copies"."retired_by", "copies"."retirement_reason" FROM "books" INNER JOIN "copies" ON "books"."id" = "copies"."book_id" WHERE "books"."id" = '{}' AND "copies"."id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                book_id,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1555,8 +2963,9 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM (SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM "copies" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                r#"SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM (SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM "copies" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1574,8 +2983,9 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM (SELECT "id", "book_id", "code_identifier_copy_id", "created_at", "created_by" FROM "copies" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                r#"SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM (SELECT "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason" FROM "copies" WHERE "id" < '{}' ORDER BY "id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1588,7 +2998,7 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description" FROM "books" WHERE "id" = '{}'"#,
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description", "created_at" FROM "books" WHERE "id" = '{}'"#,
                 id
             ),
             query.to_string(PostgresQueryBuilder)
@@ -1599,8 +3009,14 @@ mod tests {
     fn ut_get_books_page_after() {
         let filter_id = Uuid::new_v4();
         let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::After(filter_id),
+        let page = filters::KeysetPage::new(
+            filters::Sort::default(),
+            Some(filters::Keyset::new(
+                filters::Position::After,
+                filters::Sort::default(),
+                vec![],
+                filter_id,
+            )),
             filters::Items::new(items),
         );
         let book = filters::Book::new(None, None, None, None);
@@ -1608,8 +3024,9 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description" FROM "books" INNER JOIN "categories" ON "books"."category_id" = "categories"."id" INNER JOIN "publishers" ON "books"."publisher_id" = "publishers"."id" LEFT JOIN "series" ON "books"."series_id" = "series"."id" LEFT JOIN "books_tags" ON "books"."id" = "books_tags"."book_id" LEFT JOIN "tags" ON "books_tags"."tag_id" = "tags"."id" WHERE "books"."id" > '{}' ORDER BY "books"."id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description", "books"."created_at" FROM "books" WHERE "books"."id" > '{}' ORDER BY "books"."id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1619,8 +3036,48 @@ mod tests {
     fn ut_get_books_page_before() {
         let filter_id = Uuid::new_v4();
         let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::Before(filter_id),
+        let page = filters::KeysetPage::new(
+            filters::Sort::default(),
+            Some(filters::Keyset::new(
+                filters::Position::Before,
+                filters::Sort::default(),
+                vec![],
+                filter_id,
+            )),
+            filters::Items::new(items),
+        );
+        let book = filters::Book::new(None, None, None, None);
+        let query = get_books(page, book);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description", "books"."created_at" FROM "books" WHERE "books"."id" < '{}' ORDER BY "books"."id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_books_sort_before() {
+        let filter_id = Uuid::new_v4();
+        let items = 10;
+        let sort = filters::Sort::new(vec![
+            (filters::SortField::ReleaseYear, filters::Direction::Desc),
+            (filters::SortField::Title, filters::Direction::Asc),
+        ]);
+        let page = filters::KeysetPage::new(
+            sort.clone(),
+            Some(filters::Keyset::new(
+                filters::Position::Before,
+                sort,
+                vec![
+                    filters::SortValue::Integer(2000),
+                    filters::SortValue::Text("title".into()),
+                ],
+                filter_id,
+            )),
             filters::Items::new(items),
         );
         let book = filters::Book::new(None, None, None, None);
@@ -1628,8 +3085,9 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description" FROM "books" INNER JOIN "categories" ON "books"."category_id" = "categories"."id" INNER JOIN "publishers" ON "books"."publisher_id" = "publishers"."id" LEFT JOIN "series" ON "books"."series_id" = "series"."id" LEFT JOIN "books_tags" ON "books"."id" = "books_tags"."book_id" LEFT JOIN "tags" ON "books_tags"."tag_id" = "tags"."id" WHERE "books"."id" < '{}' ORDER BY "books"."id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description", "books"."created_at" FROM "books" WHERE "books"."release_year" > 2000 OR ("books"."release_year" = 2000 AND "books"."title" < 'title') OR ("books"."release_year" = 2000 AND "books"."title" = 'title' AND "books"."id" < '{}') ORDER BY "books"."release_year" ASC, "books"."title" DESC, "books"."id" DESC LIMIT {}) AS "t" ORDER BY "release_year" DESC, "title" ASC, "id" ASC"#,
+                filter_id,
+                items + 1
             ),
             query.to_string(PostgresQueryBuilder)
         );
@@ -1639,8 +3097,14 @@ mod tests {
     fn ut_get_books_filter_books() {
         let filter_id = Uuid::new_v4();
         let items = 10;
-        let page = filters::Page::new(
-            filters::Cursor::Before(filter_id),
+        let page = filters::KeysetPage::new(
+            filters::Sort::default(),
+            Some(filters::Keyset::new(
+                filters::Position::Before,
+                filters::Sort::default(),
+                vec![],
+                filter_id,
+            )),
             filters::Items::new(items),
         );
         let book = filters::Book::new(
@@ -1653,10 +3117,390 @@ mod tests {
 
         assert_eq!(
             format!(
-                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description" FROM "books" INNER JOIN "categories" ON "books"."category_id" = "categories"."id" INNER JOIN "publishers" ON "books"."publisher_id" = "publishers"."id" LEFT JOIN "series" ON "books"."series_id" = "series"."id" LEFT JOIN "books_tags" ON "books"."id" = "books_tags"."book_id" LEFT JOIN "tags" ON "books_tags"."tag_id" = "tags"."id" WHERE "categories"."name" IN ('a') AND "publishers"."name" IN ('b') AND "series"."name" IN ('c') AND "tags"."name" IN ('d') AND "books"."id" < '{}' ORDER BY "books"."id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
-                filter_id, items
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description", "books"."created_at" FROM "books" WHERE ("books"."category_id" IN (SELECT "id" FROM "categories" WHERE "name" IN ('a')) AND "books"."publisher_id" IN (SELECT "id" FROM "publishers" WHERE "name" IN ('b')) AND "books"."series_id" IN (SELECT "id" FROM "series" WHERE "name" IN ('c')) AND "books"."id" IN (SELECT "books_tags"."book_id" FROM "books_tags" INNER JOIN "tags" ON "books_tags"."tag_id" = "tags"."id" WHERE "tags"."name" IN ('d'))) AND "books"."id" < '{}' ORDER BY "books"."id" DESC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    fn book_details() -> BookDetails {
+        BookDetails {
+            code_identifier: 1,
+            isbn: Some("9783161484100".into()),
+            issn: None,
+            release_year: 2021,
+            edition: Some(2),
+            pages: None,
+            title: "title".into(),
+            subtitle: None,
+            description: Some("description".into()),
+            category_id: Uuid::new_v4(),
+            language_id: Uuid::new_v4(),
+            publisher_id: Uuid::new_v4(),
+            series_id: None,
+            author_ids: vec![],
+            editor_ids: vec![],
+            subject_area_ids: vec![],
+            tag_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn ut_get_books_filter_any() {
+        let filter_id = Uuid::new_v4();
+        let author_id = Uuid::new_v4();
+        let editor_id = Uuid::new_v4();
+        let items = 10;
+        let page = filters::KeysetPage::new(
+            filters::Sort::default(),
+            Some(filters::Keyset::new(
+                filters::Position::After,
+                filters::Sort::default(),
+                vec![],
+                filter_id,
+            )),
+            filters::Items::new(items),
+        );
+        let book = filters::Book::new(None, None, None, None)
+            .with_matching(filters::Match::Any)
+            .with_subject_areas(vec!["e".into()])
+            .with_languages(vec!["eng".into()])
+            .with_authors(vec![author_id])
+            .with_editors(vec![editor_id])
+            .with_release_year(filters::Range::new(Some(2000), Some(2010)))
+            .with_pages(filters::Range::new(None, Some(300)))
+            .with_available(true);
+        let query = get_books(page, book);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM (SELECT "books"."id", "books"."code_identifier", "books"."isbn", "books"."issn", "books"."release_year", "books"."edition", "books"."pages", "books"."subtitle", "books"."title", "books"."description", "books"."created_at" FROM "books" WHERE ("books"."id" IN (SELECT "books_subject_areas"."book_id" FROM "books_subject_areas" INNER JOIN "subject_areas" ON "books_subject_areas"."subject_area_id" = "subject_areas"."id" WHERE "subject_areas"."name" IN ('e')) OR "books"."language_id" IN (SELECT "id" FROM "languages" WHERE "iso_code" IN ('eng')) OR "books"."id" IN (SELECT "book_id" FROM "books_authors" WHERE "person_id" IN ('{}')) OR "books"."id" IN (SELECT "book_id" FROM "books_editors" WHERE "person_id" IN ('{}')) OR ("books"."release_year" BETWEEN 2000 AND 2010) OR "books"."pages" <= 300 OR "books"."id" IN (SELECT "book_id" FROM "copies" WHERE "retired_at" IS NULL AND "id" NOT IN (SELECT "copy_id" FROM "loans" WHERE "returned_at" IS NULL) AND "id" NOT IN (SELECT "copy_id" FROM "holds" WHERE "status" = 'ready' AND "copy_id" IS NOT NULL))) AND "books"."id" > '{}' ORDER BY "books"."id" ASC LIMIT {}) AS "t" ORDER BY "id" ASC"#,
+                author_id,
+                editor_id,
+                filter_id,
+                items + 1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_authors_by_book_ids() {
+        let ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let query = get_authors_by_book_ids(&ids);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "books_authors"."book_id" AS "book_id", "persons"."id", "persons"."first_name", "persons"."last_name", "persons"."date_of_birth", "persons"."isni", "persons"."orcid", "persons"."oclc" FROM "books_authors" INNER JOIN "persons" ON "books_authors"."person_id" = "persons"."id" WHERE "books_authors"."book_id" IN ('{}', '{}') ORDER BY "persons"."last_name" ASC, "persons"."first_name" ASC, "persons"."id" ASC"#,
+                ids[0], ids[1]
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_persons_by_identifiers() {
+        let query = get_persons_by_identifiers(Some("0000000121032683"), None);
+
+        assert_eq!(
+            r#"SELECT "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc" FROM "persons" WHERE "isni" = '0000000121032683' ORDER BY "id" ASC"#,
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_catalogue() {
+        let id = Uuid::new_v4();
+        let query = get_catalogue(filters::Page::new(
+            filters::Cursor::After(id),
+            filters::Items::new(100),
+        ));
+
+        assert_eq!(
+            format!(
+                r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description", "category_id", "language_id", "publisher_id", "series_id", "created_at", "copies" FROM (SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description", "category_id", "language_id", "publisher_id", "series_id", "created_at", (SELECT COUNT("copies"."id") FROM "copies" WHERE "copies"."book_id" = "books"."id" AND "copies"."retired_at" IS NULL) AS "copies" FROM "books" WHERE "id" > '{}' ORDER BY "id" ASC LIMIT 101) AS "t" ORDER BY "id" ASC"#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_series_by_book_ids() {
+        let ids = vec![Uuid::new_v4()];
+        let query = get_series_by_book_ids(&ids);
+
+        assert_eq!(
+            format!(
+                r#"SELECT "books"."id" AS "book_id", "series"."id", "series"."publisher_id", "series"."name" FROM "books" INNER JOIN "series" ON "books"."series_id" = "series"."id" WHERE "books"."id" IN ('{}')"#,
+                ids[0]
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_create_book() {
+        let id = Uuid::new_v4();
+        let book = book_details();
+        let query = create_book(id, &book);
+
+        assert_eq!(
+            format!(
+                r#"INSERT INTO "books" ("id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "category_id", "language_id", "publisher_id", "series_id") VALUES ('{}', 1, '9783161484100', NULL, 2021, 2, NULL, NULL, 'title', 'description', '{}', '{}', '{}', NULL) RETURNING "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description", "created_at""#,
+                id, book.category_id, book.language_id, book.publisher_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_update_book() {
+        let id = Uuid::new_v4();
+        let book = book_details();
+        let query = update_book(id, &book);
+
+        assert_eq!(
+            format!(
+                r#"UPDATE "books" SET "code_identifier" = 1, "isbn" = '9783161484100', "issn" = NULL, "release_year" = 2021, "edition" = 2, "pages" = NULL, "subtitle" = NULL, "title" = 'title', "description" = 'description', "category_id" = '{}', "language_id" = '{}', "publisher_id" = '{}', "series_id" = NULL WHERE "id" = '{}' RETURNING "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "title", "subtitle", "description", "created_at""#,
+                book.category_id, book.language_id, book.publisher_id, id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_delete_book() {
+        let id = Uuid::new_v4();
+        let query = delete_book(id);
+
+        assert_eq!(
+            format!(r#"DELETE FROM "books" WHERE "id" = '{}'"#, id),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_create_books_authors() {
+        let book_id = Uuid::new_v4();
+        let links = vec![
+            (Uuid::new_v4(), Uuid::new_v4()),
+            (Uuid::new_v4(), Uuid::new_v4()),
+        ];
+        let query = create_books_authors(book_id, &links);
+
+        assert_eq!(
+            format!(
+                r#"INSERT INTO "books_authors" ("id", "book_id", "person_id") VALUES ('{}', '{}', '{}'), ('{}', '{}', '{}')"#,
+                links[0].0, book_id, links[0].1, links[1].0, book_id, links[1].1
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_delete_books_tags() {
+        let book_id = Uuid::new_v4();
+        let query = delete_books_tags(book_id);
+
+        assert_eq!(
+            format!(
+                r#"DELETE FROM "books_tags" WHERE "book_id" = '{}'"#,
+                book_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_create_tag() {
+        let id = Uuid::new_v4();
+        let query = create_tag(id, &NameDetails { name: "tag".into() });
+
+        assert_eq!(
+            format!(
+                r#"INSERT INTO "tags" ("id", "name") VALUES ('{}', 'tag') RETURNING "id", "name""#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_update_series() {
+        let id = Uuid::new_v4();
+        let patch = SeriesPatch {
+            publisher_id: None,
+            name: Some("series".into()),
+        };
+        let query = update_series(id, &patch);
+
+        assert_eq!(
+            format!(
+                r#"UPDATE "series" SET "name" = 'series' WHERE "id" = '{}' RETURNING "id", "publisher_id", "name""#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_count_books_by_publisher_id() {
+        let id = Uuid::new_v4();
+        let query = count_books_by_publisher_id(id);
+
+        assert_eq!(
+            format!(
+                r#"SELECT COUNT("id") FROM "books" WHERE "publisher_id" = '{}'"#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_create_person() {
+        let id = Uuid::new_v4();
+        let person = PersonDetails {
+            first_name: "first".into(),
+            last_name: "last".into(),
+            date_of_birth: Some(chrono::NaiveDate::from_ymd(1900, 1, 31)),
+            isni: None,
+            orcid: Some("0000000218250097".into()),
+            oclc: None,
+        };
+        let query = create_person(id, &person);
+
+        assert_eq!(
+            format!(
+                r#"INSERT INTO "persons" ("id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc") VALUES ('{}', 'first', 'last', '1900-01-31', NULL, '0000000218250097', NULL) RETURNING "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc""#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_update_person() {
+        let id = Uuid::new_v4();
+        let patch = PersonPatch {
+            last_name: Some("last".into()),
+            isni: Some(None),
+            ..PersonPatch::default()
+        };
+        let query = update_person(id, &patch);
+
+        assert_eq!(
+            format!(
+                r#"UPDATE "persons" SET "last_name" = 'last', "isni" = NULL WHERE "id" = '{}' RETURNING "id", "first_name", "last_name", "date_of_birth", "isni", "orcid", "oclc""#,
+                id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_delete_duplicate_books_authors() {
+        let person_id = Uuid::new_v4();
+        let duplicate_id = Uuid::new_v4();
+        let query = delete_duplicate_books_authors(person_id, duplicate_id);
+
+        assert_eq!(
+            format!(
+                r#"DELETE FROM "books_authors" WHERE "person_id" = '{}' AND "book_id" IN (SELECT "book_id" FROM "books_authors" WHERE "person_id" = '{}')"#,
+                duplicate_id, person_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_update_books_editors_person() {
+        let person_id = Uuid::new_v4();
+        let duplicate_id = Uuid::new_v4();
+        let query = update_books_editors_person(person_id, duplicate_id);
+
+        assert_eq!(
+            format!(
+                r#"UPDATE "books_editors" SET "person_id" = '{}' WHERE "person_id" = '{}'"#,
+                person_id, duplicate_id
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_create_copies() {
+        let book_id = Uuid::new_v4();
+        let ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let created_by = Uuid::new_v4();
+        let created_at = Utc.ymd(2021, 3, 1).and_hms(12, 0, 0);
+        let query = create_copies(book_id, &ids, created_at, created_by);
+
+        assert_eq!(
+            format!(
+                r#"INSERT INTO "copies" ("id", "book_id", "created_at", "created_by") VALUES ('{0}', '{2}', '2021-03-01 12:00:00 +00:00', '{3}'), ('{1}', '{2}', '2021-03-01 12:00:00 +00:00', '{3}') RETURNING "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason""#,
+                ids[0], ids[1], book_id, created_by
+            ),
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_get_copies_by_label() {
+        let query = get_copies_by_label(4711, 3);
+
+        assert_eq!(
+            r#"SELECT "copies"."id", "copies"."book_id", "copies"."copy_id", "copies"."created_at", "copies"."created_by", "copies"."retired_at", "copies"."retired_by", "copies"."retirement_reason" FROM "copies" INNER JOIN "books" ON "books"."id" = "copies"."book_id" WHERE "books"."code_identifier" = 4711 AND "copies"."copy_id" = 3 ORDER BY "copies"."id" ASC LIMIT 2"#,
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_retire_copy() {
+        let id = Uuid::new_v4();
+        let retired_by = Uuid::new_v4();
+        let retired_at = Utc.ymd(2021, 3, 1).and_hms(12, 0, 0);
+        let query = retire_copy(id, RetirementReason::Lost, retired_at, retired_by);
+
+        assert_eq!(
+            format!(
+                r#"UPDATE "copies" SET "retired_at" = '2021-03-01 12:00:00 +00:00', "retired_by" = '{}', "retirement_reason" = 'lost' WHERE "id" = '{}' AND "retired_at" IS NULL AND "id" NOT IN (SELECT "copy_id" FROM "loans" WHERE "returned_at" IS NULL) AND "id" NOT IN (SELECT "copy_id" FROM "holds" WHERE "status" = 'ready' AND "copy_id" IS NOT NULL) RETURNING "id", "book_id", "copy_id", "created_at", "created_by", "retired_at", "retired_by", "retirement_reason""#,
+                retired_by, id
             ),
             query.to_string(PostgresQueryBuilder)
         );
     }
+
+    #[test]
+    fn ut_lent_copies() {
+        let query = lent_copies();
+
+        assert_eq!(
+            r#"SELECT "copy_id" FROM "loans" WHERE "returned_at" IS NULL"#,
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_held_copies() {
+        let query = held_copies();
+
+        assert_eq!(
+            r#"SELECT "copy_id" FROM "holds" WHERE "status" = 'ready' AND "copy_id" IS NOT NULL"#,
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
+
+    #[test]
+    fn ut_search_books() {
+        let query = search_books("librar:*", 10);
+
+        assert_eq!(
+            r#"SELECT "id", "code_identifier", "isbn", "issn", "release_year", "edition", "pages", "subtitle", "title", "description", "created_at" FROM "books" WHERE "search_vector" @@ books_search_query('librar:*') AND "search_vector" @@ books_search_query('librar:*', "language_id") ORDER BY ts_rank_cd("search_vector", books_search_query('librar:*', "language_id")) DESC, "id" ASC LIMIT 10"#,
+            query.to_string(PostgresQueryBuilder)
+        );
+    }
 }
@@ -73,10 +73,10 @@ pub(crate) enum Books {
     LanguageId,
     PublisherId,
     SeriesId,
+    CreatedAt,
 }
 
 #[derive(Iden)]
-#[allow(dead_code)]
 pub(crate) enum BooksAuthors {
     Table,
     Id,
@@ -85,7 +85,6 @@ pub(crate) enum BooksAuthors {
 }
 
 #[derive(Iden)]
-#[allow(dead_code)]
 pub(crate) enum BooksEditors {
     Table,
     Id,
@@ -94,7 +93,6 @@ pub(crate) enum BooksEditors {
 }
 
 #[derive(Iden)]
-#[allow(dead_code)]
 pub(crate) enum BooksSubjectAreas {
     Table,
     Id,
@@ -103,7 +101,6 @@ pub(crate) enum BooksSubjectAreas {
 }
 
 #[derive(Iden)]
-#[allow(dead_code)]
 pub(crate) enum BooksTags {
     Table,
     Id,
@@ -116,7 +113,25 @@ pub(crate) enum Copies {
     Table,
     Id,
     BookId,
-    CodeIdentifierCopyId,
+    CopyId,
     CreatedAt,
     CreatedBy,
+    RetiredAt,
+    RetiredBy,
+    RetirementReason,
+}
+
+// Owned by the BORROW service, only read by this service
+#[derive(Iden)]
+pub(crate) enum Loans {
+    Table,
+    CopyId,
+    ReturnedAt,
+}
+
+#[derive(Iden)]
+pub(crate) enum Holds {
+    Table,
+    CopyId,
+    Status,
 }
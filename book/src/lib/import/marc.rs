@@ -0,0 +1,408 @@
+use helpers::rpc::{Error, RpcResult};
+
+use super::{
+    clean, inverted_name, isbn, issn, language, numbers, person_identifier, push_distinct, year,
+    Contributor, Record,
+};
+
+/// Separators of ISO 2709, in which MARC 21 records are exchanged
+const RECORD_TERMINATOR: u8 = 0x1d;
+const FIELD_TERMINATOR: u8 = 0x1e;
+const SUBFIELD_DELIMITER: char = '\u{1f}';
+
+const LEADER_LENGTH: usize = 24;
+const DIRECTORY_ENTRY_LENGTH: usize = 12;
+
+/// Control field with its data or data field with its subfields
+struct Field {
+    tag: String,
+    indicators: [char; 2],
+    data: String,
+    subfields: Vec<(char, String)>,
+}
+
+impl Field {
+    fn subfield(&self, code: char) -> Option<&str> {
+        self.subfields
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn subfields(&self, code: char) -> impl Iterator<Item = &str> {
+        self.subfields
+            .iter()
+            .filter(move |(c, _)| *c == code)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Reads MARC 21 records in ISO 2709, which are expected to be encoded in UTF-8
+pub(super) fn parse_marc21(data: &[u8]) -> RpcResult<Vec<Record>> {
+    let mut records = vec![];
+    let mut rest = data;
+    loop {
+        // records may be separated by line breaks
+        while let Some((first, tail)) = rest.split_first() {
+            if first.is_ascii_whitespace() {
+                rest = tail;
+            } else {
+                break;
+            }
+        }
+        if rest.is_empty() {
+            break;
+        }
+
+        let length = number(rest.get(0..5))?;
+        let record = rest.get(..length).ok_or(Error::InvalidInput)?;
+        records.push(self::record(&fields(record)?));
+        rest = &rest[length..];
+    }
+    Ok(records)
+}
+
+/// Splits an ISO 2709 record along its directory
+fn fields(record: &[u8]) -> RpcResult<Vec<Field>> {
+    if record.len() < LEADER_LENGTH || record.last() != Some(&RECORD_TERMINATOR) {
+        return Err(Error::InvalidInput);
+    }
+    let base = number(record.get(12..17))?;
+    let directory = record
+        .get(LEADER_LENGTH..base.saturating_sub(1))
+        .ok_or(Error::InvalidInput)?;
+    if directory.len() % DIRECTORY_ENTRY_LENGTH != 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    directory
+        .chunks(DIRECTORY_ENTRY_LENGTH)
+        .map(|entry| {
+            let tag = String::from_utf8_lossy(&entry[0..3]).into_owned();
+            let length = number(entry.get(3..7))?;
+            let start = base + number(entry.get(7..12))?;
+            let data = record
+                .get(start..start + length)
+                .ok_or(Error::InvalidInput)?;
+            let data = data.strip_suffix(&[FIELD_TERMINATOR]).unwrap_or(data);
+            Ok(field(tag, &String::from_utf8_lossy(data)))
+        })
+        .collect()
+}
+
+/// Number of the fixed length, which ISO 2709 uses for lengths and positions
+fn number(digits: Option<&[u8]>) -> RpcResult<usize> {
+    digits
+        .filter(|digits| digits.iter().all(u8::is_ascii_digit))
+        .and_then(|digits| std::str::from_utf8(digits).ok())
+        .and_then(|digits| digits.parse().ok())
+        .ok_or(Error::InvalidInput)
+}
+
+fn field(tag: String, data: &str) -> Field {
+    if tag.as_str() < "010" {
+        return Field {
+            tag,
+            indicators: [' ', ' '],
+            data: data.to_string(),
+            subfields: vec![],
+        };
+    }
+
+    let mut parts = data.split(SUBFIELD_DELIMITER);
+    let mut indicators = parts.next().unwrap_or_default().chars();
+    Field {
+        tag,
+        indicators: [
+            indicators.next().unwrap_or(' '),
+            indicators.next().unwrap_or(' '),
+        ],
+        data: String::new(),
+        subfields: parts
+            .filter_map(|subfield| {
+                let mut chars = subfield.chars();
+                Some((chars.next()?, chars.as_str().to_string()))
+            })
+            .collect(),
+    }
+}
+
+/// Reads the records of a MARCXML collection or a single MARCXML record
+pub(super) fn parse_marcxml(data: &[u8]) -> RpcResult<Vec<Record>> {
+    let text = std::str::from_utf8(data).map_err(|_| Error::InvalidInput)?;
+    let document = roxmltree::Document::parse(text).map_err(|_| Error::InvalidInput)?;
+
+    Ok(document
+        .descendants()
+        .filter(|node| node.tag_name().name() == "record")
+        .map(|node| {
+            let fields: Vec<Field> = node
+                .children()
+                .filter_map(|child| {
+                    let tag = child.attribute("tag")?.to_string();
+                    match child.tag_name().name() {
+                        "controlfield" => Some(Field {
+                            tag,
+                            indicators: [' ', ' '],
+                            data: child.text().unwrap_or_default().to_string(),
+                            subfields: vec![],
+                        }),
+                        "datafield" => Some(Field {
+                            tag,
+                            indicators: [
+                                indicator(child.attribute("ind1")),
+                                indicator(child.attribute("ind2")),
+                            ],
+                            data: String::new(),
+                            subfields: child
+                                .children()
+                                .filter(|subfield| subfield.tag_name().name() == "subfield")
+                                .filter_map(|subfield| {
+                                    Some((
+                                        subfield.attribute("code")?.chars().next()?,
+                                        subfield.text().unwrap_or_default().to_string(),
+                                    ))
+                                })
+                                .collect(),
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect();
+            record(&fields)
+        })
+        .collect())
+}
+
+fn indicator(value: Option<&str>) -> char {
+    value.and_then(|value| value.chars().next()).unwrap_or(' ')
+}
+
+/// Maps the fields of a MARC 21 bibliographic record onto a record
+fn record(fields: &[Field]) -> Record {
+    let tagged = |tag: &'static str| fields.iter().filter(move |field| field.tag == tag);
+    let first = |tag: &'static str, code: char| {
+        tagged(tag)
+            .find_map(|field| field.subfield(code))
+            .and_then(clean)
+    };
+    let fixed = tagged("008").next().map(|field| field.data.as_str());
+
+    // the publication statement of RDA, which replaced 260 in newer records
+    let publication = fields
+        .iter()
+        .find(|field| field.tag == "264" && field.indicators[1] == '1')
+        .or_else(|| tagged("260").next());
+
+    let mut record = Record {
+        isbn: tagged("020").find_map(|field| field.subfield('a').and_then(isbn)),
+        issn: tagged("022").find_map(|field| field.subfield('a').and_then(issn)),
+        title: first("245", 'a'),
+        subtitle: first("245", 'b'),
+        release_year: publication
+            .and_then(|field| field.subfield('c'))
+            .and_then(year)
+            .or_else(|| fixed.and_then(|data| data.get(7..11)).and_then(year)),
+        edition: tagged("250")
+            .find_map(|field| field.subfield('a'))
+            .and_then(|edition| numbers(edition).next())
+            .filter(|edition| *edition >= 1),
+        pages: tagged("300")
+            .find_map(|field| field.subfield('a'))
+            .and_then(|extent| numbers(extent).max())
+            .filter(|pages| *pages >= 1),
+        description: tagged("520")
+            .find_map(|field| field.subfield('a'))
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map(str::to_string),
+        language: fixed
+            .and_then(|data| data.get(35..38))
+            .and_then(language)
+            .or_else(|| first("041", 'a').as_deref().and_then(language)),
+        publisher: publication
+            .and_then(|field| field.subfield('b'))
+            .and_then(clean),
+        series: first("490", 'a').or_else(|| first("830", 'a')),
+        ..Record::default()
+    };
+
+    for field in fields
+        .iter()
+        .filter(|field| field.tag == "100" || field.tag == "700")
+    {
+        let (first_name, last_name) = match field.subfield('a').and_then(inverted_name) {
+            Some(name) => name,
+            None => continue,
+        };
+        let mut contributor = Contributor {
+            first_name,
+            last_name,
+            ..Contributor::default()
+        };
+        for identifier in field.subfields('0').chain(field.subfields('1')) {
+            let lowercase = identifier.to_lowercase();
+            if lowercase.contains("isni") {
+                contributor.isni = person_identifier(identifier);
+            } else if lowercase.contains("orcid") {
+                contributor.orcid = person_identifier(identifier);
+            }
+        }
+
+        // relators, of which only authors and editors are catalogued
+        let relators: Vec<String> = field
+            .subfields('4')
+            .map(|code| code.trim().to_lowercase())
+            .chain(field.subfields('e').filter_map(clean).map(|term| {
+                match term.to_lowercase().as_str() {
+                    "author" => "aut".to_string(),
+                    "editor" => "edt".to_string(),
+                    term => term.to_string(),
+                }
+            }))
+            .collect();
+        if relators.iter().any(|relator| relator == "edt") {
+            record.editors.push(contributor);
+        } else if relators.is_empty() || relators.iter().any(|relator| relator == "aut") {
+            record.authors.push(contributor);
+        }
+    }
+
+    for field in tagged("650").chain(tagged("653")) {
+        push_distinct(
+            &mut record.subject_areas,
+            field.subfield('a').and_then(clean),
+        );
+    }
+
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes fields into an ISO 2709 record
+    fn iso2709(fields: &[(&str, &str)]) -> Vec<u8> {
+        let mut directory = String::new();
+        let mut data = String::new();
+        for (tag, field) in fields {
+            let field = format!("{}\u{1e}", field);
+            directory.push_str(&format!("{}{:04}{:05}", tag, field.len(), data.len()));
+            data.push_str(&field);
+        }
+        let base = LEADER_LENGTH + directory.len() + 1;
+        let length = base + data.len() + 1;
+        format!(
+            "{:05}nam a22{:05} i 4500{}\u{1e}{}\u{1d}",
+            length, base, directory, data
+        )
+        .into_bytes()
+    }
+
+    fn expected() -> Record {
+        Record {
+            isbn: Some("9780306406157".into()),
+            issn: None,
+            title: Some("Data structures".into()),
+            subtitle: Some("an introduction".into()),
+            release_year: Some(2019),
+            edition: Some(2),
+            pages: Some(320),
+            description: Some("An introduction to data structures.".into()),
+            language: Some("eng".into()),
+            publisher: Some("Example Press".into()),
+            series: Some("Computing essentials".into()),
+            authors: vec![Contributor {
+                first_name: "Jane".into(),
+                last_name: "Doe".into(),
+                isni: Some("0000000121032683".into()),
+                orcid: None,
+            }],
+            editors: vec![Contributor {
+                first_name: "John R.".into(),
+                last_name: "Smith".into(),
+                isni: None,
+                orcid: Some("000000021825009X".into()),
+            }],
+            subject_areas: vec!["Data structures (Computer science)".into()],
+        }
+    }
+
+    #[test]
+    fn ut_parse_marc21() {
+        let record = iso2709(&[
+            ("001", "12345"),
+            ("008", "190101s2019    xxu           000 0 eng d"),
+            ("020", "  \u{1f}a0306406152 (pbk.)"),
+            (
+                "100",
+                "1 \u{1f}aDoe, Jane,\u{1f}eauthor.\u{1f}0(isni)0000 0001 2103 2683",
+            ),
+            (
+                "245",
+                "10\u{1f}aData structures :\u{1f}ban introduction /\u{1f}cJane Doe.",
+            ),
+            ("250", "  \u{1f}a2nd edition."),
+            (
+                "264",
+                " 1\u{1f}aNew York :\u{1f}bExample Press,\u{1f}c[2019]",
+            ),
+            ("300", "  \u{1f}axii, 320 pages ;\u{1f}c24 cm"),
+            ("490", "1 \u{1f}aComputing essentials ;\u{1f}v3"),
+            ("520", "  \u{1f}aAn introduction to data structures."),
+            ("650", " 0\u{1f}aData structures (Computer science)"),
+            (
+                "700",
+                "1 \u{1f}aSmith, John R.,\u{1f}4edt\u{1f}1https://orcid.org/0000-0002-1825-009X",
+            ),
+            ("700", "1 \u{1f}aRoe, Richard,\u{1f}4ill"),
+        ]);
+        let mut file = record.clone();
+        file.push(b'\n');
+        file.extend(&record);
+
+        assert_eq!(parse_marc21(&file), Ok(vec![expected(), expected()]));
+        assert_eq!(parse_marc21(&record[..100]), Err(Error::InvalidInput));
+    }
+
+    #[test]
+    fn ut_parse_marcxml() {
+        let file = r#"<?xml version="1.0" encoding="UTF-8"?>
+<collection xmlns="http://www.loc.gov/MARC21/slim">
+  <record>
+    <leader>00000nam a2200000 i 4500</leader>
+    <controlfield tag="008">190101s2019    xxu           000 0 eng d</controlfield>
+    <datafield tag="020" ind1=" " ind2=" "><subfield code="a">0306406152 (pbk.)</subfield></datafield>
+    <datafield tag="100" ind1="1" ind2=" ">
+      <subfield code="a">Doe, Jane,</subfield>
+      <subfield code="0">https://isni.org/isni/0000000121032683</subfield>
+    </datafield>
+    <datafield tag="245" ind1="1" ind2="0">
+      <subfield code="a">Data structures :</subfield>
+      <subfield code="b">an introduction /</subfield>
+    </datafield>
+    <datafield tag="250" ind1=" " ind2=" "><subfield code="a">2nd ed.</subfield></datafield>
+    <datafield tag="260" ind1=" " ind2=" ">
+      <subfield code="b">Example Press,</subfield>
+      <subfield code="c">c2019.</subfield>
+    </datafield>
+    <datafield tag="300" ind1=" " ind2=" "><subfield code="a">320 p. :</subfield></datafield>
+    <datafield tag="520" ind1=" " ind2=" "><subfield code="a">An introduction to data structures.</subfield></datafield>
+    <datafield tag="650" ind1=" " ind2="0"><subfield code="a">Data structures (Computer science)</subfield></datafield>
+    <datafield tag="700" ind1="1" ind2=" ">
+      <subfield code="a">Smith, John R.,</subfield>
+      <subfield code="e">editor.</subfield>
+      <subfield code="0">(orcid)0000-0002-1825-009X</subfield>
+    </datafield>
+    <datafield tag="830" ind1=" " ind2="0"><subfield code="a">Computing essentials.</subfield></datafield>
+  </record>
+</collection>"#;
+
+        assert_eq!(parse_marcxml(file.as_bytes()), Ok(vec![expected()]));
+        assert_eq!(
+            parse_marcxml(b"<collection><record></collection>"),
+            Err(Error::InvalidInput)
+        );
+    }
+}
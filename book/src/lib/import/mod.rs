@@ -0,0 +1,209 @@
+mod marc;
+mod onix;
+
+use helpers::rpc::RpcResult;
+
+use crate::rpc::models::ImportFormat;
+
+/// Bibliographic record of a book, as read from an import file
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Record {
+    /// ISBN-13 without hyphens, an ISBN-10 is converted
+    pub isbn: Option<String>,
+    pub issn: Option<String>,
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub release_year: Option<i16>,
+    pub edition: Option<i32>,
+    pub pages: Option<i32>,
+    pub description: Option<String>,
+    /// ISO 639-2/B code of the language
+    pub language: Option<String>,
+    pub publisher: Option<String>,
+    pub series: Option<String>,
+    pub authors: Vec<Contributor>,
+    pub editors: Vec<Contributor>,
+    pub subject_areas: Vec<String>,
+}
+
+/// Author or editor of a record
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Contributor {
+    pub first_name: String,
+    pub last_name: String,
+    /// ISNI without spaces
+    pub isni: Option<String>,
+    /// ORCID iD without hyphens
+    pub orcid: Option<String>,
+}
+
+/// Reads the records of a file in the given format
+pub(crate) fn parse(format: ImportFormat, data: &[u8]) -> RpcResult<Vec<Record>> {
+    match format {
+        ImportFormat::Marc21 => marc::parse_marc21(data),
+        ImportFormat::Marcxml => marc::parse_marcxml(data),
+        ImportFormat::Onix => onix::parse_onix(data),
+    }
+}
+
+/// Trims whitespace and the ISBD punctuation, which catalogues put between the elements
+fn clean(text: &str) -> Option<String> {
+    let text = text
+        .trim()
+        .trim_end_matches(|c: char| matches!(c, '/' | ':' | ';' | ',' | '=') || c.is_whitespace())
+        .trim_end();
+    // a final period is kept after an initial or an abbreviation like "Jr."
+    let text = match text.strip_suffix('.') {
+        Some(stripped) if stripped.rsplit(' ').next().map_or(0, str::len) > 3 => stripped,
+        _ => text,
+    };
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// ISBN-13 of a catalogued ISBN, which may be followed by a qualifier like "(pbk.)"
+fn isbn(text: &str) -> Option<String> {
+    let number: String = text
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .find(|part| !part.is_empty())?
+        .chars()
+        .filter(|c| *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    if !number.is_ascii() {
+        return None;
+    }
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+
+    match number.len() {
+        13 if is_digits(&number) => Some(number),
+        10 if is_digits(&number[..9]) && (is_digits(&number[9..]) || number.ends_with('X')) => {
+            // the check digit of an ISBN-13 is weighted differently
+            let isbn = format!("978{}", &number[..9]);
+            let sum: u32 = isbn
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .enumerate()
+                .map(|(i, digit)| if i % 2 == 0 { digit } else { digit * 3 })
+                .sum();
+            Some(format!("{}{}", isbn, (10 - sum % 10) % 10))
+        }
+        _ => None,
+    }
+}
+
+/// ISSN without hyphen
+fn issn(text: &str) -> Option<String> {
+    let number: String = text
+        .trim()
+        .chars()
+        .take_while(|c| !c.is_whitespace())
+        .filter(|c| *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    if number.len() == 8 {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+/// First number of four digits in a date like "c2021" or "2021-05-04"
+fn year(text: &str) -> Option<i16> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|part| part.len() == 4)
+        .and_then(|year| year.parse().ok())
+}
+
+/// Numbers in a text like "xii, 320 p." or "2nd ed."
+fn numbers(text: &str) -> impl Iterator<Item = i32> + '_ {
+    text.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+}
+
+/// ISO 639-2/B code of a language
+fn language(code: &str) -> Option<String> {
+    let code = code.trim().to_lowercase();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_lowercase()) {
+        Some(code)
+    } else {
+        None
+    }
+}
+
+/// Identifier of 16 characters at the end of a text like "(isni)0000 0001 2103 2683"
+/// or "https://orcid.org/0000-0002-1825-0097"
+fn person_identifier(text: &str) -> Option<String> {
+    let identifier: String = text
+        .rsplit(['/', ')', ':'])
+        .next()?
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    if identifier.len() == 16 {
+        Some(identifier)
+    } else {
+        None
+    }
+}
+
+/// Splits an inverted name like "Doe, Jane" into first and last name
+fn inverted_name(name: &str) -> Option<(String, String)> {
+    let name = clean(name)?;
+    Some(match name.split_once(',') {
+        Some((last_name, first_name)) => (
+            clean(first_name).unwrap_or_default(),
+            last_name.trim().to_string(),
+        ),
+        None => (String::new(), name),
+    })
+}
+
+/// Adds a name to a list, unless it is already listed
+fn push_distinct(names: &mut Vec<String>, name: Option<String>) {
+    if let Some(name) = name {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ut_isbn() {
+        assert_eq!(isbn("978-3-16-148410-0"), Some("9783161484100".into()));
+        assert_eq!(isbn("0306406152 (pbk.)"), Some("9780306406157".into()));
+        assert_eq!(isbn("080442957X"), Some("9780804429573".into()));
+        assert_eq!(isbn("(pbk.)"), None);
+        assert_eq!(isbn("12345"), None);
+    }
+
+    #[test]
+    fn ut_clean() {
+        assert_eq!(clean("The title /"), Some("The title".into()));
+        assert_eq!(clean("Penguin Books,"), Some("Penguin Books".into()));
+        assert_eq!(clean("Data structures."), Some("Data structures".into()));
+        assert_eq!(clean("Smith, John R."), Some("Smith, John R.".into()));
+        assert_eq!(clean(" : "), None);
+    }
+
+    #[test]
+    fn ut_person_identifier() {
+        assert_eq!(
+            person_identifier("(isni)0000 0001 2103 2683"),
+            Some("0000000121032683".into())
+        );
+        assert_eq!(
+            person_identifier("https://orcid.org/0000-0002-1825-009x"),
+            Some("000000021825009X".into())
+        );
+        assert_eq!(person_identifier("(DE-588)118540238"), None);
+    }
+}
@@ -0,0 +1,315 @@
+use roxmltree::Node;
+
+use helpers::rpc::{Error, RpcResult};
+
+use super::{
+    clean, inverted_name, isbn, language, numbers, person_identifier, push_distinct, year,
+    Contributor, Record,
+};
+
+/// Codes of the ONIX 3.0 code lists, which are mapped onto records
+const PRODUCT_ID_ISBN_13: &str = "15";
+const TITLE_DISTINCTIVE: &str = "01";
+const TITLE_ELEMENT_PRODUCT: &str = "01";
+const TITLE_ELEMENT_COLLECTION: &str = "02";
+const CONTRIBUTOR_AUTHOR: &str = "A01";
+const CONTRIBUTOR_EDITOR: &str = "B01";
+const NAME_ID_ISNI: &str = "16";
+const NAME_ID_ORCID: &str = "21";
+const LANGUAGE_OF_TEXT: &str = "01";
+const EXTENT_MAIN_CONTENT_PAGES: &str = "00";
+const EXTENT_TOTAL_PAGES: &str = "11";
+const TEXT_DESCRIPTION: &str = "03";
+const PUBLISHING_ROLE_PUBLISHER: &str = "01";
+const PUBLISHING_DATE_PUBLICATION: &str = "01";
+
+/// Reads the products of an ONIX 3.0 message with reference tags
+pub(super) fn parse_onix(data: &[u8]) -> RpcResult<Vec<Record>> {
+    let text = std::str::from_utf8(data).map_err(|_| Error::InvalidInput)?;
+    let document = roxmltree::Document::parse(text).map_err(|_| Error::InvalidInput)?;
+    if document.root_element().tag_name().name() != "ONIXMessage" {
+        return Err(Error::InvalidInput);
+    }
+
+    Ok(children(document.root_element(), "Product")
+        .map(record)
+        .collect())
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.tag_name().name() == name)
+}
+
+fn child<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'static str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// Trimmed text of a child element
+fn text<'a>(node: Node<'a, '_>, name: &'static str) -> Option<&'a str> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+}
+
+/// Text of an element, which may contain XHTML, like the text content of a product
+fn inner_text(node: Node) -> String {
+    node.descendants()
+        .filter(|descendant| descendant.is_text())
+        .filter_map(|descendant| descendant.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Title and subtitle of the distinctive title of a product or collection
+fn titles(node: Node, level: &str) -> (Option<String>, Option<String>) {
+    let element = children(node, "TitleDetail")
+        .filter(|detail| text(*detail, "TitleType") == Some(TITLE_DISTINCTIVE))
+        .flat_map(|detail| children(detail, "TitleElement"))
+        .find(|element| text(*element, "TitleElementLevel") == Some(level));
+    let element = match element {
+        Some(element) => element,
+        None => return (None, None),
+    };
+
+    let title = text(element, "TitleText").map(str::to_string).or_else(|| {
+        let without_prefix = text(element, "TitleWithoutPrefix")?;
+        Some(match text(element, "TitlePrefix") {
+            Some(prefix) => format!("{} {}", prefix, without_prefix),
+            None => without_prefix.to_string(),
+        })
+    });
+    (
+        title.as_deref().and_then(clean),
+        text(element, "Subtitle").and_then(clean),
+    )
+}
+
+fn contributor(node: Node) -> Option<Contributor> {
+    let (first_name, last_name) = match text(node, "KeyNames") {
+        Some(key_names) => (
+            text(node, "NamesBeforeKey").unwrap_or_default().to_string(),
+            key_names.to_string(),
+        ),
+        None => inverted_name(text(node, "PersonNameInverted")?)?,
+    };
+
+    let mut contributor = Contributor {
+        first_name,
+        last_name,
+        ..Contributor::default()
+    };
+    for identifier in children(node, "NameIdentifier") {
+        let value = text(identifier, "IDValue").and_then(person_identifier);
+        match text(identifier, "NameIDType") {
+            Some(NAME_ID_ISNI) => contributor.isni = value,
+            Some(NAME_ID_ORCID) => contributor.orcid = value,
+            _ => (),
+        }
+    }
+    Some(contributor)
+}
+
+/// Maps a product of an ONIX message onto a record
+fn record(product: Node) -> Record {
+    let descriptive = child(product, "DescriptiveDetail");
+    let publishing = child(product, "PublishingDetail");
+    let (title, subtitle) =
+        descriptive.map_or((None, None), |node| titles(node, TITLE_ELEMENT_PRODUCT));
+
+    let mut record = Record {
+        isbn: children(product, "ProductIdentifier")
+            .filter(|identifier| text(*identifier, "ProductIDType") == Some(PRODUCT_ID_ISBN_13))
+            .find_map(|identifier| text(identifier, "IDValue").and_then(isbn)),
+        title,
+        subtitle,
+        release_year: publishing
+            .into_iter()
+            .flat_map(|node| children(node, "PublishingDate"))
+            .find(|date| text(*date, "PublishingDateRole") == Some(PUBLISHING_DATE_PUBLICATION))
+            .and_then(|date| text(date, "Date"))
+            // dates like 20190315 start with the year
+            .and_then(|date| date.get(0..4))
+            .and_then(year),
+        description: child(product, "CollateralDetail")
+            .into_iter()
+            .flat_map(|node| children(node, "TextContent"))
+            .find(|content| text(*content, "TextType") == Some(TEXT_DESCRIPTION))
+            .and_then(|content| child(content, "Text"))
+            .map(inner_text)
+            .filter(|text| !text.is_empty()),
+        publisher: publishing
+            .into_iter()
+            .flat_map(|node| children(node, "Publisher"))
+            .find(|publisher| {
+                text(*publisher, "PublishingRole")
+                    .map_or(true, |role| role == PUBLISHING_ROLE_PUBLISHER)
+            })
+            .and_then(|publisher| text(publisher, "PublisherName"))
+            .and_then(clean),
+        ..Record::default()
+    };
+
+    if let Some(descriptive) = descriptive {
+        record.edition = text(descriptive, "EditionNumber")
+            .and_then(|edition| numbers(edition).next())
+            .filter(|edition| *edition >= 1);
+        record.pages = children(descriptive, "Extent")
+            .filter(|extent| {
+                matches!(
+                    text(*extent, "ExtentType"),
+                    Some(EXTENT_MAIN_CONTENT_PAGES) | Some(EXTENT_TOTAL_PAGES)
+                )
+            })
+            .find_map(|extent| text(extent, "ExtentValue")?.parse().ok())
+            .filter(|pages| *pages >= 1);
+        record.language = children(descriptive, "Language")
+            .find(|language| text(*language, "LanguageRole") == Some(LANGUAGE_OF_TEXT))
+            .and_then(|node| text(node, "LanguageCode"))
+            .and_then(language);
+        record.series = children(descriptive, "Collection")
+            .find_map(|collection| titles(collection, TITLE_ELEMENT_COLLECTION).0)
+            // a collection may be named among the title elements of the product
+            .or_else(|| titles(descriptive, TITLE_ELEMENT_COLLECTION).0);
+
+        for node in children(descriptive, "Contributor") {
+            let roles: Vec<&str> = children(node, "ContributorRole")
+                .filter_map(|role| role.text())
+                .map(str::trim)
+                .collect();
+            let contributor = match contributor(node) {
+                Some(contributor) => contributor,
+                None => continue,
+            };
+            if roles.contains(&CONTRIBUTOR_EDITOR) {
+                record.editors.push(contributor);
+            } else if roles.contains(&CONTRIBUTOR_AUTHOR) {
+                record.authors.push(contributor);
+            }
+        }
+
+        for subject in children(descriptive, "Subject") {
+            push_distinct(
+                &mut record.subject_areas,
+                text(subject, "SubjectHeadingText").and_then(clean),
+            );
+        }
+    }
+
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ut_parse_onix() {
+        let file = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ONIXMessage release="3.0" xmlns="http://ns.editeur.org/onix/3.0/reference">
+  <Header><Sender><SenderName>Example Press</SenderName></Sender></Header>
+  <Product>
+    <RecordReference>com.example.9780306406157</RecordReference>
+    <NotificationType>03</NotificationType>
+    <ProductIdentifier><ProductIDType>01</ProductIDType><IDValue>EX-1</IDValue></ProductIdentifier>
+    <ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>978-0-306-40615-7</IDValue></ProductIdentifier>
+    <DescriptiveDetail>
+      <ProductComposition>00</ProductComposition>
+      <ProductForm>BC</ProductForm>
+      <Collection>
+        <CollectionType>10</CollectionType>
+        <TitleDetail>
+          <TitleType>01</TitleType>
+          <TitleElement>
+            <TitleElementLevel>02</TitleElementLevel>
+            <TitleText>Computing essentials</TitleText>
+          </TitleElement>
+        </TitleDetail>
+      </Collection>
+      <TitleDetail>
+        <TitleType>01</TitleType>
+        <TitleElement>
+          <TitleElementLevel>01</TitleElementLevel>
+          <TitleText>Data structures</TitleText>
+          <Subtitle>an introduction</Subtitle>
+        </TitleElement>
+      </TitleDetail>
+      <Contributor>
+        <SequenceNumber>1</SequenceNumber>
+        <ContributorRole>A01</ContributorRole>
+        <NameIdentifier><NameIDType>16</NameIDType><IDValue>0000000121032683</IDValue></NameIdentifier>
+        <NamesBeforeKey>Jane</NamesBeforeKey>
+        <KeyNames>Doe</KeyNames>
+      </Contributor>
+      <Contributor>
+        <SequenceNumber>2</SequenceNumber>
+        <ContributorRole>B01</ContributorRole>
+        <NameIdentifier><NameIDType>21</NameIDType><IDValue>0000-0002-1825-009X</IDValue></NameIdentifier>
+        <PersonNameInverted>Smith, John R.</PersonNameInverted>
+      </Contributor>
+      <Contributor>
+        <SequenceNumber>3</SequenceNumber>
+        <ContributorRole>A12</ContributorRole>
+        <KeyNames>Roe</KeyNames>
+      </Contributor>
+      <EditionNumber>2</EditionNumber>
+      <Language><LanguageRole>01</LanguageRole><LanguageCode>eng</LanguageCode></Language>
+      <Extent><ExtentType>00</ExtentType><ExtentValue>320</ExtentValue><ExtentUnit>03</ExtentUnit></Extent>
+      <Subject>
+        <SubjectSchemeIdentifier>20</SubjectSchemeIdentifier>
+        <SubjectHeadingText>Data structures (Computer science)</SubjectHeadingText>
+      </Subject>
+    </DescriptiveDetail>
+    <CollateralDetail>
+      <TextContent>
+        <TextType>03</TextType>
+        <ContentAudience>00</ContentAudience>
+        <Text textformat="05"><p xmlns="http://www.w3.org/1999/xhtml">An introduction to <em>data structures</em>.</p></Text>
+      </TextContent>
+    </CollateralDetail>
+    <PublishingDetail>
+      <Publisher><PublishingRole>01</PublishingRole><PublisherName>Example Press</PublisherName></Publisher>
+      <PublishingDate><PublishingDateRole>01</PublishingDateRole><Date>20190315</Date></PublishingDate>
+    </PublishingDetail>
+  </Product>
+</ONIXMessage>"#;
+
+        assert_eq!(
+            parse_onix(file.as_bytes()),
+            Ok(vec![Record {
+                isbn: Some("9780306406157".into()),
+                issn: None,
+                title: Some("Data structures".into()),
+                subtitle: Some("an introduction".into()),
+                release_year: Some(2019),
+                edition: Some(2),
+                pages: Some(320),
+                description: Some("An introduction to data structures.".into()),
+                language: Some("eng".into()),
+                publisher: Some("Example Press".into()),
+                series: Some("Computing essentials".into()),
+                authors: vec![Contributor {
+                    first_name: "Jane".into(),
+                    last_name: "Doe".into(),
+                    isni: Some("0000000121032683".into()),
+                    orcid: None,
+                }],
+                editors: vec![Contributor {
+                    first_name: "John R.".into(),
+                    last_name: "Smith".into(),
+                    isni: None,
+                    orcid: Some("000000021825009X".into()),
+                }],
+                subject_areas: vec!["Data structures (Computer science)".into()],
+            }])
+        );
+        assert_eq!(
+            parse_onix(b"<collection></collection>"),
+            Err(Error::InvalidInput)
+        );
+    }
+}
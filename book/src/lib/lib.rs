@@ -1,5 +1,6 @@
 pub mod config;
 pub mod db;
+mod import;
 mod rpc;
 
 pub use crate::rpc::*;
@@ -1,6 +1,7 @@
 pub mod models;
 pub mod server;
 pub mod service;
+mod validation;
 
 use std::io;
 use std::net::SocketAddr;
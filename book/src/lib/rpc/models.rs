@@ -1,7 +1,11 @@
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
+use helpers::filters;
+
+pub use crate::db::models::RetirementReason;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Language {
     pub id: Uuid,
@@ -96,6 +100,31 @@ impl From<crate::db::models::Tag> for Tag {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Person {
+    pub id: Uuid,
+    first_name: String,
+    last_name: String,
+    date_of_birth: Option<NaiveDate>,
+    isni: Option<String>,
+    orcid: Option<String>,
+    oclc: Option<i32>,
+}
+
+impl From<crate::db::models::Person> for Person {
+    fn from(person: crate::db::models::Person) -> Self {
+        Person {
+            id: person.id,
+            first_name: person.first_name,
+            last_name: person.last_name,
+            date_of_birth: person.date_of_birth,
+            isni: person.isni,
+            orcid: person.orcid,
+            oclc: person.oclc,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Author {
     pub id: Uuid,
@@ -153,6 +182,9 @@ pub struct Copy {
     pub copy_id: i64,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub retired_at: Option<DateTime<Utc>>,
+    pub retired_by: Option<Uuid>,
+    pub retirement_reason: Option<RetirementReason>,
 }
 
 impl From<crate::db::models::Copy> for Copy {
@@ -160,9 +192,12 @@ impl From<crate::db::models::Copy> for Copy {
         Copy {
             id: copy.id,
             book_id: copy.book_id,
-            copy_id: copy.copy_id,
+            copy_id: copy.copy_id.into(),
             created_at: copy.created_at,
             created_by: copy.created_by,
+            retired_at: copy.retired_at,
+            retired_by: copy.retired_by,
+            retirement_reason: copy.retirement_reason,
         }
     }
 }
@@ -176,9 +211,10 @@ pub struct Book {
     release_year: i16,
     edition: Option<i32>,
     pages: Option<i32>,
-    title: String,
+    pub title: String,
     subtitle: Option<String>,
     description: Option<String>,
+    created_at: DateTime<Utc>,
 }
 
 impl From<crate::db::models::Book> for Book {
@@ -194,6 +230,364 @@ impl From<crate::db::models::Book> for Book {
             title: book.title,
             subtitle: book.subtitle,
             description: book.description,
+            created_at: book.created_at,
         }
     }
 }
+
+impl Book {
+    /// Cursor after or before this book in a listing of the given order
+    pub fn keyset(&self, position: filters::Position, sort: &filters::Sort) -> filters::Keyset {
+        let values = sort
+            .get_keys()
+            .iter()
+            .map(|(field, _)| match field {
+                filters::SortField::Title => filters::SortValue::Text(self.title.clone()),
+                filters::SortField::ReleaseYear => {
+                    filters::SortValue::Integer(self.release_year.into())
+                }
+                filters::SortField::CreatedAt => filters::SortValue::Timestamp(self.created_at),
+            })
+            .collect();
+        filters::Keyset::new(position, sort.clone(), values, self.id)
+    }
+}
+
+/// Relations of a book, of which only the requested ones are present
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct BookRelations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<Author>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editors: Option<Vec<Editor>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Publisher>,
+    /// `null` for a book without series
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub series: Option<Option<Series>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_areas: Option<Vec<SubjectArea>>,
+}
+
+/// Content of a book and its relations, as written by librarians
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BookDetails {
+    pub code_identifier: i32,
+    /// ISBN-13, hyphens and spaces are ignored
+    pub isbn: Option<String>,
+    /// ISSN, hyphens and spaces are ignored
+    pub issn: Option<String>,
+    pub release_year: i16,
+    pub edition: Option<i32>,
+    pub pages: Option<i32>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub category_id: Uuid,
+    pub language_id: Uuid,
+    pub publisher_id: Uuid,
+    pub series_id: Option<Uuid>,
+    pub author_ids: Vec<Uuid>,
+    pub editor_ids: Vec<Uuid>,
+    pub subject_area_ids: Vec<Uuid>,
+    pub tag_ids: Vec<Uuid>,
+}
+
+/// Name of a category, publisher, subject area or tag, as written by librarians
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NameDetails {
+    pub name: String,
+}
+
+/// Content of a series, as written by librarians
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SeriesDetails {
+    pub publisher_id: Uuid,
+    pub name: String,
+}
+
+/// Changes of a series, absent fields are left unchanged
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SeriesPatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Content of a person, who may be author or editor of books, as written by librarians
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PersonDetails {
+    pub first_name: String,
+    pub last_name: String,
+    pub date_of_birth: Option<NaiveDate>,
+    /// ISNI, hyphens and spaces are ignored
+    pub isni: Option<String>,
+    /// ORCID iD, hyphens and spaces are ignored
+    pub orcid: Option<String>,
+    pub oclc: Option<i32>,
+}
+
+/// Changes of a person, absent fields are left unchanged and `null` clears a field
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PersonPatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date_of_birth: Option<Option<NaiveDate>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub isni: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub orcid: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub oclc: Option<Option<i32>>,
+}
+
+/// Book with its relations flattened into ids, as exported and re-imported by its id.
+/// The names of authors and tags, the number of copies and the creation time are only exported.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CatalogueEntry {
+    pub id: Uuid,
+    pub code_identifier: i32,
+    pub isbn: Option<String>,
+    pub issn: Option<String>,
+    pub release_year: i16,
+    pub edition: Option<i32>,
+    pub pages: Option<i32>,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub description: Option<String>,
+    pub category_id: Uuid,
+    pub language_id: Uuid,
+    pub publisher_id: Uuid,
+    pub series_id: Option<Uuid>,
+    pub author_ids: Vec<Uuid>,
+    /// Names of the authors like "Doe, Jane"
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub editor_ids: Vec<Uuid>,
+    pub subject_area_ids: Vec<Uuid>,
+    pub tag_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Copies in the library, without the retired ones
+    #[serde(default)]
+    pub copies: i64,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<&CatalogueEntry> for BookDetails {
+    fn from(entry: &CatalogueEntry) -> Self {
+        BookDetails {
+            code_identifier: entry.code_identifier,
+            isbn: entry.isbn.clone(),
+            issn: entry.issn.clone(),
+            release_year: entry.release_year,
+            edition: entry.edition,
+            pages: entry.pages,
+            title: entry.title.clone(),
+            subtitle: entry.subtitle.clone(),
+            description: entry.description.clone(),
+            category_id: entry.category_id,
+            language_id: entry.language_id,
+            publisher_id: entry.publisher_id,
+            series_id: entry.series_id,
+            author_ids: entry.author_ids.clone(),
+            editor_ids: entry.editor_ids.clone(),
+            subject_area_ids: entry.subject_area_ids.clone(),
+            tag_ids: entry.tag_ids.clone(),
+        }
+    }
+}
+
+/// Format of a file of bibliographic records, which is imported
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// MARC 21 in ISO 2709, encoded in UTF-8
+    Marc21,
+    Marcxml,
+    /// ONIX 3.0 with reference tags
+    Onix,
+}
+
+/// Content of a file, which is imported. It is sent as base64, because the JSON transport
+/// would send every byte as a number, which the frame length of an RPC can't fit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportFile(pub Vec<u8>);
+
+impl Serialize for ImportFile {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&base64::encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for ImportFile {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        base64::decode(encoded)
+            .map(ImportFile)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Values of the books created by an import, which the records don't carry
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ImportOptions {
+    pub category_id: Uuid,
+    pub code_identifier: i32,
+}
+
+/// Outcome of an import, which is only written if it isn't a dry run
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub conflicts: usize,
+    pub records: Vec<ImportedRecord>,
+}
+
+impl ImportReport {
+    pub(crate) fn new(dry_run: bool) -> Self {
+        ImportReport {
+            dry_run,
+            created: 0,
+            updated: 0,
+            unchanged: 0,
+            conflicts: 0,
+            records: vec![],
+        }
+    }
+
+    /// Adds the outcome of a record, which is counted by its action
+    pub(crate) fn push(&mut self, record: ImportedRecord) {
+        match record.action {
+            ImportAction::Create => self.created += 1,
+            ImportAction::Update => self.updated += 1,
+            ImportAction::Unchanged => self.unchanged += 1,
+            ImportAction::Conflict => self.conflicts += 1,
+        }
+        self.records.push(record);
+    }
+
+    /// Adds the outcome of an import of the records following these, whose positions are
+    /// counted on from them
+    pub fn extend(&mut self, report: ImportReport) {
+        let offset = self.records.len();
+        for mut record in report.records {
+            record.index += offset;
+            self.push(record);
+        }
+    }
+}
+
+/// Outcome of a record of an import file
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportedRecord {
+    /// Position of the record in the file, counted from 0
+    pub index: usize,
+    pub isbn: Option<String>,
+    pub title: Option<String>,
+    pub action: ImportAction,
+    /// Book, which is created, updated or unchanged, the id of a created book is only kept
+    /// if it isn't a dry run
+    pub book_id: Option<Uuid>,
+    /// Persons, publishers, series and subject areas, which are created for the book
+    pub created: Vec<ImportedEntity>,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    /// The record is skipped
+    Conflict,
+}
+
+/// Entity, which is created by an import
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ImportedEntity {
+    pub entity: ImportedEntityKind,
+    pub name: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportedEntityKind {
+    Person,
+    Publisher,
+    Series,
+    SubjectArea,
+}
+
+/// Reason, why a record can't be imported
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflict {
+    MissingTitle,
+    MissingReleaseYear,
+    MissingPublisher,
+    /// The language is missing or not one of the catalogue
+    UnknownLanguage,
+    /// An earlier record of the file has the same ISBN
+    DuplicateIsbn,
+    /// An earlier record of the file has the same id
+    DuplicateId,
+    /// The ISNI and ORCID iD of a contributor identify different persons,
+    /// or the name of a contributor without identifiers matches several persons
+    AmbiguousPerson,
+    /// A value is out of the bounds of the catalogue, like a title of more than 100 characters,
+    /// or an id doesn't reference an entity of the catalogue
+    InvalidData,
+    /// The ISSN or an identifier of a contributor is taken by another book or person
+    AlreadyExists,
+}
+
+/// Distinguishes a present `null` from an absent field, which is left to `default`
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -1,18 +1,33 @@
-use sea_query::PostgresQueryBuilder;
-use sqlx::query_as;
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_query::{PostgresQueryBuilder, SelectStatement, Values};
+use sqlx::{postgres::PgRow, query, query_as, Acquire, FromRow, Postgres, Row, Transaction};
 use tarpc::context::Context;
 use uuid::Uuid;
 
-use helpers::{filters, rpc::RpcResult};
+use helpers::{
+    filters::{self, Paginated},
+    rpc::{Error, RpcResult, PG_FOREIGN_KEY_VIOLATION},
+};
 
 use super::models::{
-    Author, Book, Category, Copy, Editor, Language, Publisher, Series, SubjectArea, Tag,
+    Author, Book, BookDetails, BookRelations, CatalogueEntry, Category, Copy, Editor, ImportAction,
+    ImportConflict, ImportFile, ImportFormat, ImportOptions, ImportReport, ImportedEntity,
+    ImportedEntityKind, ImportedRecord, Language, NameDetails, Person, PersonDetails, PersonPatch,
+    Publisher, RetirementReason, Series, SeriesDetails, SeriesPatch, SubjectArea, Tag,
 };
 use super::service::BookService;
+use super::validation::{
+    parse_copy_label, search_query, validate_book, validate_keyset, validate_name, validate_person,
+    validate_person_patch, validate_series, validate_series_patch, COPIES_MAX_COUNT,
+    NAME_MAX_LENGTH, TAG_NAME_MAX_LENGTH,
+};
 use crate::db::{models as db_models, queries, DbConnection, DbPool};
+use crate::import::{self, Contributor, Record};
 
 sea_query::sea_query_driver_postgres!();
-use sea_query_driver_postgres::bind_query_as;
+use sea_query_driver_postgres::{bind_query, bind_query_as};
 
 #[derive(Clone)]
 pub struct BookServer {
@@ -30,6 +45,620 @@ impl BookServer {
             .await
             .expect("Failed to retrieve connection from the pool")
     }
+
+    /// Reads the items of a page and the total count of the listing, if it is requested
+    async fn get_page<M, T>(
+        &self,
+        (query, values): (String, Values),
+        total: Option<(String, Values)>,
+    ) -> RpcResult<(Vec<T>, Option<i64>)>
+    where
+        M: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+        T: From<M>,
+    {
+        let mut conn = self.get_db_connection().await;
+        let items = bind_query_as(query_as::<_, M>(&query), &values)
+            .fetch_all(&mut conn)
+            .await?
+            .into_iter()
+            .map(T::from)
+            .collect();
+        let total = match total {
+            Some((query, values)) => Some(
+                bind_query_as(query_as::<_, (i64,)>(&query), &values)
+                    .fetch_one(&mut conn)
+                    .await?
+                    .0,
+            ),
+            None => None,
+        };
+        Ok((items, total))
+    }
+}
+
+/// Reads the entities related to books, which are grouped by the id of their book
+async fn get_related<M, T>(
+    conn: &mut DbConnection,
+    query: Option<(String, Values)>,
+) -> RpcResult<Option<HashMap<Uuid, Vec<T>>>>
+where
+    M: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    T: From<M>,
+{
+    let (query, values) = match query {
+        Some(query) => query,
+        None => return Ok(None),
+    };
+
+    let mut related: HashMap<Uuid, Vec<T>> = HashMap::new();
+    for row in bind_query_as(query_as::<_, db_models::Related<M>>(&query), &values)
+        .fetch_all(&mut *conn)
+        .await?
+    {
+        related
+            .entry(row.book_id)
+            .or_default()
+            .push(row.entity.into());
+    }
+    Ok(Some(related))
+}
+
+/// Maps the violation of a restricting foreign key by a delete to `Error::InUse`
+fn restricted(e: sqlx::Error) -> Error {
+    match &e {
+        sqlx::Error::Database(db_error)
+            if db_error.code().as_deref() == Some(PG_FOREIGN_KEY_VIOLATION) =>
+        {
+            Error::InUse
+        }
+        _ => e.into(),
+    }
+}
+
+/// Executes a delete statement, failing if nothing was deleted
+async fn delete<'c, E>(query: &str, values: &Values, executor: E) -> RpcResult<()>
+where
+    E: sqlx::Executor<'c, Database = Postgres>,
+{
+    let deleted = bind_query(sqlx::query(query), values)
+        .execute(executor)
+        .await
+        .map_err(restricted)?
+        .rows_affected();
+    match deleted {
+        0 => Err(Error::NotFound),
+        _ => Ok(()),
+    }
+}
+
+/// Change of a field, which fills it with the given value if it is missing
+fn fill<T>(field: &Option<T>, value: Option<T>) -> Option<Option<T>> {
+    match (field, value) {
+        (None, Some(value)) => Some(Some(value)),
+        _ => None,
+    }
+}
+
+/// Pairs every linked id with a new id for its join row
+fn links(ids: &[Uuid]) -> Vec<(Uuid, Uuid)> {
+    ids.iter().map(|id| (Uuid::new_v4(), *id)).collect()
+}
+
+/// Writes the join rows of a book to its authors, editors, subject areas and tags
+async fn link_book(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    book: &BookDetails,
+) -> RpcResult<()> {
+    let mut statements = vec![];
+    if !book.author_ids.is_empty() {
+        statements.push(
+            queries::create_books_authors(id, &links(&book.author_ids)).build(PostgresQueryBuilder),
+        );
+    }
+    if !book.editor_ids.is_empty() {
+        statements.push(
+            queries::create_books_editors(id, &links(&book.editor_ids)).build(PostgresQueryBuilder),
+        );
+    }
+    if !book.subject_area_ids.is_empty() {
+        statements.push(
+            queries::create_books_subject_areas(id, &links(&book.subject_area_ids))
+                .build(PostgresQueryBuilder),
+        );
+    }
+    if !book.tag_ids.is_empty() {
+        statements.push(
+            queries::create_books_tags(id, &links(&book.tag_ids)).build(PostgresQueryBuilder),
+        );
+    }
+
+    for (statement, values) in statements {
+        bind_query(query(&statement), &values)
+            .execute(&mut *tx)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Removes the join rows of a book to its authors, editors, subject areas and tags
+async fn unlink_book(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> RpcResult<()> {
+    let statements = vec![
+        queries::delete_books_authors(id).build(PostgresQueryBuilder),
+        queries::delete_books_editors(id).build(PostgresQueryBuilder),
+        queries::delete_books_subject_areas(id).build(PostgresQueryBuilder),
+        queries::delete_books_tags(id).build(PostgresQueryBuilder),
+    ];
+
+    for (statement, values) in statements {
+        bind_query(query(&statement), &values)
+            .execute(&mut *tx)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Reads the ids of the entities, which a batched query relates to a book
+async fn related_ids(
+    tx: &mut Transaction<'_, Postgres>,
+    (query, values): (String, Values),
+) -> RpcResult<Vec<Uuid>> {
+    Ok(bind_query(sqlx::query(&query), &values)
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(|row| row.try_get("id"))
+        .collect::<Result<_, _>>()?)
+}
+
+/// Reads a book with all of its relations
+async fn book_details(
+    tx: &mut Transaction<'_, Postgres>,
+    book: db_models::BookEntry,
+) -> RpcResult<BookDetails> {
+    let ids = [book.id];
+    let authors = queries::get_authors_by_book_ids(&ids).build(PostgresQueryBuilder);
+    let editors = queries::get_editors_by_book_ids(&ids).build(PostgresQueryBuilder);
+    let subject_areas = queries::get_subject_areas_by_book_ids(&ids).build(PostgresQueryBuilder);
+    let tags = queries::get_tags_by_book_ids(&ids).build(PostgresQueryBuilder);
+
+    Ok(BookDetails {
+        author_ids: related_ids(tx, authors).await?,
+        editor_ids: related_ids(tx, editors).await?,
+        subject_area_ids: related_ids(tx, subject_areas).await?,
+        tag_ids: related_ids(tx, tags).await?,
+        code_identifier: book.code_identifier,
+        isbn: book.isbn,
+        issn: book.issn,
+        release_year: book.release_year,
+        edition: book.edition,
+        pages: book.pages,
+        title: book.title,
+        subtitle: book.subtitle,
+        description: book.description,
+        category_id: book.category_id,
+        language_id: book.language_id,
+        publisher_id: book.publisher_id,
+        series_id: book.series_id,
+    })
+}
+
+/// Whether two versions of a book are the same, regardless of the order of their relations
+fn is_same_book(a: &BookDetails, b: &BookDetails) -> bool {
+    let sorted = |book: &BookDetails| {
+        let mut book = book.clone();
+        book.author_ids.sort();
+        book.editor_ids.sort();
+        book.subject_area_ids.sort();
+        book.tag_ids.sort();
+        book
+    };
+    sorted(a) == sorted(b)
+}
+
+/// Outcome of a record of an import, the book it writes or the conflicts, for which it is skipped
+type ImportOutcome = Result<(ImportAction, Uuid), Vec<ImportConflict>>;
+
+/// Ends the savepoint of a record, which keeps its writes unless it has conflicts.
+/// Violations of the constraints of the database are reported like the other conflicts.
+async fn settle(
+    savepoint: Transaction<'_, Postgres>,
+    outcome: RpcResult<ImportOutcome>,
+) -> RpcResult<ImportOutcome> {
+    let outcome = match outcome {
+        Err(Error::AlreadyExists) => Err(vec![ImportConflict::AlreadyExists]),
+        Err(Error::InvalidInput) => Err(vec![ImportConflict::InvalidData]),
+        outcome => outcome?,
+    };
+    match outcome {
+        Ok(_) => savepoint.commit().await?,
+        Err(_) => savepoint.rollback().await?,
+    }
+    Ok(outcome)
+}
+
+/// Reports the outcome of a record, of which only a kept book is named
+fn imported_record(
+    index: usize,
+    isbn: Option<String>,
+    title: Option<String>,
+    outcome: ImportOutcome,
+    created: Vec<ImportedEntity>,
+    dry_run: bool,
+) -> ImportedRecord {
+    match outcome {
+        Ok((action, id)) => ImportedRecord {
+            index,
+            isbn,
+            title,
+            action,
+            book_id: Some(id).filter(|_| !(dry_run && action == ImportAction::Create)),
+            created,
+            conflicts: vec![],
+        },
+        Err(conflicts) => ImportedRecord {
+            index,
+            isbn,
+            title,
+            action: ImportAction::Conflict,
+            book_id: None,
+            created: vec![],
+            conflicts,
+        },
+    }
+}
+
+/// Creates a book, or updates it if it differs from its current version
+async fn write_book(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    book: &BookDetails,
+    current: Option<&BookDetails>,
+) -> RpcResult<ImportAction> {
+    let (action, (query, values)) = match current {
+        None => (
+            ImportAction::Create,
+            queries::create_book(id, book).build(PostgresQueryBuilder),
+        ),
+        Some(current) if is_same_book(book, current) => return Ok(ImportAction::Unchanged),
+        Some(_) => (
+            ImportAction::Update,
+            queries::update_book(id, book).build(PostgresQueryBuilder),
+        ),
+    };
+
+    bind_query(sqlx::query(&query), &values)
+        .execute(&mut *tx)
+        .await?;
+    if action == ImportAction::Update {
+        unlink_book(tx, id).await?;
+    }
+    link_book(tx, id, book).await?;
+    Ok(action)
+}
+
+/// Writes a book of a catalogue export by its id, so that a repeated import changes nothing
+async fn import_entry(
+    tx: &mut Transaction<'_, Postgres>,
+    entry: &CatalogueEntry,
+) -> RpcResult<ImportOutcome> {
+    let book = validate_book(entry.into())?;
+    let (query, values) = queries::get_book_entry_by_id(entry.id).build(PostgresQueryBuilder);
+
+    let current = match bind_query_as(query_as::<_, db_models::BookEntry>(&query), &values)
+        .fetch_optional(&mut *tx)
+        .await?
+    {
+        Some(existing) => Some(book_details(tx, existing).await?),
+        None => None,
+    };
+    let action = write_book(tx, entry.id, &book, current.as_ref()).await?;
+    Ok(Ok((action, entry.id)))
+}
+
+/// Finds the person of a contributor by its ISNI or ORCID iD and else by its name, or creates it.
+/// Nothing is found, if the contributor can't be told apart from other persons.
+async fn import_person(
+    tx: &mut Transaction<'_, Postgres>,
+    contributor: &Contributor,
+    created: &mut Vec<ImportedEntity>,
+) -> RpcResult<Option<Uuid>> {
+    if contributor.isni.is_some() || contributor.orcid.is_some() {
+        let (query, values) = queries::get_persons_by_identifiers(
+            contributor.isni.as_deref(),
+            contributor.orcid.as_deref(),
+        )
+        .build(PostgresQueryBuilder);
+        let persons = bind_query_as(query_as::<_, db_models::Person>(&query), &values)
+            .fetch_all(&mut *tx)
+            .await?;
+        match persons.as_slice() {
+            [person] => return Ok(Some(person.id)),
+            [] => (),
+            _ => return Ok(None),
+        }
+    }
+
+    let (query, values) =
+        queries::get_persons_by_name(&contributor.first_name, &contributor.last_name)
+            .build(PostgresQueryBuilder);
+    let persons = bind_query_as(query_as::<_, db_models::Person>(&query), &values)
+        .fetch_all(&mut *tx)
+        .await?;
+    // a person of the same name, who is known by other identifiers, is someone else
+    let compatible = |known: &Option<String>, imported: &Option<String>| {
+        known.is_none() || imported.is_none() || known == imported
+    };
+    let persons: Vec<_> = persons
+        .iter()
+        .filter(|person| {
+            compatible(&person.isni, &contributor.isni)
+                && compatible(&person.orcid, &contributor.orcid)
+        })
+        .collect();
+    match persons.as_slice() {
+        [person] => Ok(Some(person.id)),
+        [] => {
+            let person = validate_person(PersonDetails {
+                first_name: contributor.first_name.clone(),
+                last_name: contributor.last_name.clone(),
+                date_of_birth: None,
+                isni: contributor.isni.clone(),
+                orcid: contributor.orcid.clone(),
+                oclc: None,
+            })?;
+            let id = Uuid::new_v4();
+            let (query, values) = queries::create_person(id, &person).build(PostgresQueryBuilder);
+            bind_query(sqlx::query(&query), &values)
+                .execute(&mut *tx)
+                .await?;
+            created.push(ImportedEntity {
+                entity: ImportedEntityKind::Person,
+                name: format!("{} {}", person.first_name, person.last_name),
+            });
+            Ok(Some(id))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Finds a publisher by its name, or creates it
+async fn import_publisher(
+    tx: &mut Transaction<'_, Postgres>,
+    name: &str,
+    created: &mut Vec<ImportedEntity>,
+) -> RpcResult<Uuid> {
+    let (query, values) = queries::get_publisher_by_name(name).build(PostgresQueryBuilder);
+    if let Some(publisher) = bind_query_as(query_as::<_, db_models::Publisher>(&query), &values)
+        .fetch_optional(&mut *tx)
+        .await?
+    {
+        return Ok(publisher.id);
+    }
+
+    let publisher = validate_name(NameDetails { name: name.into() }, NAME_MAX_LENGTH)?;
+    let id = Uuid::new_v4();
+    let (query, values) = queries::create_publisher(id, &publisher).build(PostgresQueryBuilder);
+    bind_query(sqlx::query(&query), &values)
+        .execute(&mut *tx)
+        .await?;
+    created.push(ImportedEntity {
+        entity: ImportedEntityKind::Publisher,
+        name: publisher.name,
+    });
+    Ok(id)
+}
+
+/// Finds a series of a publisher by its name, or creates it
+async fn import_series(
+    tx: &mut Transaction<'_, Postgres>,
+    publisher_id: Uuid,
+    name: &str,
+    created: &mut Vec<ImportedEntity>,
+) -> RpcResult<Uuid> {
+    let (query, values) =
+        queries::get_series_by_name(publisher_id, name).build(PostgresQueryBuilder);
+    if let Some(series) = bind_query_as(query_as::<_, db_models::Series>(&query), &values)
+        .fetch_optional(&mut *tx)
+        .await?
+    {
+        return Ok(series.id);
+    }
+
+    let series = validate_series(SeriesDetails {
+        publisher_id,
+        name: name.into(),
+    })?;
+    let id = Uuid::new_v4();
+    let (query, values) = queries::create_series(id, &series).build(PostgresQueryBuilder);
+    bind_query(sqlx::query(&query), &values)
+        .execute(&mut *tx)
+        .await?;
+    created.push(ImportedEntity {
+        entity: ImportedEntityKind::Series,
+        name: series.name,
+    });
+    Ok(id)
+}
+
+/// Finds a subject area by its name, or creates it
+async fn import_subject_area(
+    tx: &mut Transaction<'_, Postgres>,
+    name: &str,
+    created: &mut Vec<ImportedEntity>,
+) -> RpcResult<Uuid> {
+    let (query, values) = queries::get_subject_area_by_name(name).build(PostgresQueryBuilder);
+    if let Some(subject_area) =
+        bind_query_as(query_as::<_, db_models::SubjectArea>(&query), &values)
+            .fetch_optional(&mut *tx)
+            .await?
+    {
+        return Ok(subject_area.id);
+    }
+
+    let subject_area = validate_name(NameDetails { name: name.into() }, NAME_MAX_LENGTH)?;
+    let id = Uuid::new_v4();
+    let (query, values) =
+        queries::create_subject_area(id, &subject_area).build(PostgresQueryBuilder);
+    bind_query(sqlx::query(&query), &values)
+        .execute(&mut *tx)
+        .await?;
+    created.push(ImportedEntity {
+        entity: ImportedEntityKind::SubjectArea,
+        name: subject_area.name,
+    });
+    Ok(id)
+}
+
+/// Writes a record into the catalogue, which creates or updates the book of its ISBN.
+/// The conflicts of a record are returned instead, whatever was written for it before
+/// is expected to be rolled back.
+async fn import_record(
+    tx: &mut Transaction<'_, Postgres>,
+    record: &Record,
+    options: &ImportOptions,
+    created: &mut Vec<ImportedEntity>,
+) -> RpcResult<ImportOutcome> {
+    let language = match &record.language {
+        Some(iso_code) => {
+            let (query, values) =
+                queries::get_language_by_iso_code(iso_code).build(PostgresQueryBuilder);
+            bind_query_as(query_as::<_, db_models::Language>(&query), &values)
+                .fetch_optional(&mut *tx)
+                .await?
+        }
+        None => None,
+    };
+
+    let mut conflicts = vec![];
+    let mut author_ids = vec![];
+    for author in &record.authors {
+        match import_person(tx, author, created).await? {
+            Some(id) if !author_ids.contains(&id) => author_ids.push(id),
+            Some(_) => (),
+            None => conflicts.push(ImportConflict::AmbiguousPerson),
+        }
+    }
+    let mut editor_ids = vec![];
+    for editor in &record.editors {
+        match import_person(tx, editor, created).await? {
+            Some(id) if !editor_ids.contains(&id) => editor_ids.push(id),
+            Some(_) => (),
+            None => conflicts.push(ImportConflict::AmbiguousPerson),
+        }
+    }
+    conflicts.dedup();
+
+    let (title, release_year, publisher, language) = match (
+        &record.title,
+        record.release_year,
+        &record.publisher,
+        language,
+    ) {
+        (Some(title), Some(release_year), Some(publisher), Some(language))
+            if conflicts.is_empty() =>
+        {
+            (title, release_year, publisher, language)
+        }
+        (title, release_year, publisher, language) => {
+            let missing = [
+                (title.is_none(), ImportConflict::MissingTitle),
+                (release_year.is_none(), ImportConflict::MissingReleaseYear),
+                (publisher.is_none(), ImportConflict::MissingPublisher),
+                (language.is_none(), ImportConflict::UnknownLanguage),
+            ];
+            conflicts.extend(
+                missing
+                    .iter()
+                    .filter(|(is_missing, _)| *is_missing)
+                    .map(|(_, conflict)| *conflict),
+            );
+            return Ok(Err(conflicts));
+        }
+    };
+
+    let publisher_id = import_publisher(tx, publisher, created).await?;
+    let series_id = match &record.series {
+        Some(series) => Some(import_series(tx, publisher_id, series, created).await?),
+        None => None,
+    };
+    let mut subject_area_ids = vec![];
+    for subject_area in &record.subject_areas {
+        subject_area_ids.push(import_subject_area(tx, subject_area, created).await?);
+    }
+
+    let existing = match &record.isbn {
+        Some(isbn) => {
+            let (query, values) = queries::get_book_entry_by_isbn(isbn).build(PostgresQueryBuilder);
+            bind_query_as(query_as::<_, db_models::BookEntry>(&query), &values)
+                .fetch_optional(&mut *tx)
+                .await?
+        }
+        None => None,
+    };
+
+    let existing = match existing {
+        Some(existing) => existing,
+        None => {
+            let book = validate_book(BookDetails {
+                code_identifier: options.code_identifier,
+                isbn: record.isbn.clone(),
+                issn: record.issn.clone(),
+                release_year,
+                edition: record.edition,
+                pages: record.pages,
+                title: title.clone(),
+                subtitle: record.subtitle.clone(),
+                description: record.description.clone(),
+                category_id: options.category_id,
+                language_id: language.id,
+                publisher_id,
+                series_id,
+                author_ids,
+                editor_ids,
+                subject_area_ids,
+                tag_ids: vec![],
+            })?;
+            let id = Uuid::new_v4();
+            let action = write_book(tx, id, &book, None).await?;
+            return Ok(Ok((action, id)));
+        }
+    };
+
+    // values missing from the record are kept, as are the category, code and tags of the book
+    let id = existing.id;
+    let current = book_details(tx, existing).await?;
+    let or_current = |ids: Vec<Uuid>, current: &[Uuid]| {
+        if ids.is_empty() {
+            current.to_vec()
+        } else {
+            ids
+        }
+    };
+    let book = validate_book(BookDetails {
+        code_identifier: current.code_identifier,
+        isbn: current.isbn.clone(),
+        issn: record.issn.clone().or_else(|| current.issn.clone()),
+        release_year,
+        edition: record.edition.or(current.edition),
+        pages: record.pages.or(current.pages),
+        title: title.clone(),
+        subtitle: record.subtitle.clone().or_else(|| current.subtitle.clone()),
+        description: record
+            .description
+            .clone()
+            .or_else(|| current.description.clone()),
+        category_id: current.category_id,
+        language_id: language.id,
+        publisher_id,
+        series_id: series_id.or(current.series_id),
+        author_ids: or_current(author_ids, &current.author_ids),
+        editor_ids: or_current(editor_ids, &current.editor_ids),
+        subject_area_ids: or_current(subject_area_ids, &current.subject_area_ids),
+        tag_ids: current.tag_ids.clone(),
+    })?;
+    let action = write_book(tx, id, &book, Some(&current)).await?;
+    Ok(Ok((action, id)))
 }
 
 #[tarpc::server]
@@ -56,17 +685,22 @@ impl BookService for BookServer {
         )
     }
 
-    async fn get_languages(self, _: Context, page: filters::Page) -> RpcResult<Vec<Language>> {
-        let (query, values) = queries::get_languages(page).build(PostgresQueryBuilder);
-
-        Ok(
-            bind_query_as(query_as::<_, db_models::Language>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
-                .await?
-                .into_iter()
-                .map(|language| language.into())
-                .collect(),
-        )
+    async fn get_languages(
+        self,
+        _: Context,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Language>> {
+        let (query, values) = queries::get_languages(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_languages(filters::Page::all())).build(PostgresQueryBuilder)
+        });
+
+        let (languages, total) = self
+            .get_page::<db_models::Language, Language>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(languages, |language| language.id)
+            .with_total(total))
     }
 
     async fn get_category_by_id(self, _: Context, id: Uuid) -> RpcResult<Category> {
@@ -91,17 +725,23 @@ impl BookService for BookServer {
         )
     }
 
-    async fn get_categories(self, _: Context, page: filters::Page) -> RpcResult<Vec<Category>> {
-        let (query, values) = queries::get_categories(page).build(PostgresQueryBuilder);
-
-        Ok(
-            bind_query_as(query_as::<_, db_models::Category>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
-                .await?
-                .into_iter()
-                .map(|category| category.into())
-                .collect(),
-        )
+    async fn get_categories(
+        self,
+        _: Context,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Category>> {
+        let (query, values) = queries::get_categories(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_categories(filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (categories, total) = self
+            .get_page::<db_models::Category, Category>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(categories, |category| category.id)
+            .with_total(total))
     }
 
     async fn get_publisher_by_id(self, _: Context, id: Uuid) -> RpcResult<Publisher> {
@@ -126,17 +766,23 @@ impl BookService for BookServer {
         )
     }
 
-    async fn get_publishers(self, _: Context, page: filters::Page) -> RpcResult<Vec<Publisher>> {
-        let (query, values) = queries::get_publishers(page).build(PostgresQueryBuilder);
-
-        Ok(
-            bind_query_as(query_as::<_, db_models::Publisher>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
-                .await?
-                .into_iter()
-                .map(|publisher| publisher.into())
-                .collect(),
-        )
+    async fn get_publishers(
+        self,
+        _: Context,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Publisher>> {
+        let (query, values) = queries::get_publishers(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_publishers(filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (publishers, total) = self
+            .get_page::<db_models::Publisher, Publisher>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(publishers, |publisher| publisher.id)
+            .with_total(total))
     }
 
     async fn get_series_by_id(self, _: Context, id: Uuid) -> RpcResult<Series> {
@@ -161,17 +807,16 @@ impl BookService for BookServer {
         )
     }
 
-    async fn get_series(self, _: Context, page: filters::Page) -> RpcResult<Vec<Series>> {
-        let (query, values) = queries::get_series(page).build(PostgresQueryBuilder);
+    async fn get_series(self, _: Context, page: filters::Page) -> RpcResult<Paginated<Series>> {
+        let (query, values) = queries::get_series(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_series(filters::Page::all())).build(PostgresQueryBuilder)
+        });
 
-        Ok(
-            bind_query_as(query_as::<_, db_models::Series>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
-                .await?
-                .into_iter()
-                .map(|series| series.into())
-                .collect(),
-        )
+        let (series, total) = self
+            .get_page::<db_models::Series, Series>((query, values), total)
+            .await?;
+        Ok(page.paginate(series, |series| series.id).with_total(total))
     }
 
     async fn get_subject_area_by_id(self, _: Context, id: Uuid) -> RpcResult<SubjectArea> {
@@ -189,17 +834,19 @@ impl BookService for BookServer {
         self,
         _: Context,
         page: filters::Page,
-    ) -> RpcResult<Vec<SubjectArea>> {
-        let (query, values) = queries::get_subject_areas(page).build(PostgresQueryBuilder);
-
-        Ok(
-            bind_query_as(query_as::<_, db_models::SubjectArea>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
-                .await?
-                .into_iter()
-                .map(|subject_area| subject_area.into())
-                .collect(),
-        )
+    ) -> RpcResult<Paginated<SubjectArea>> {
+        let (query, values) = queries::get_subject_areas(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_subject_areas(filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (subject_areas, total) = self
+            .get_page::<db_models::SubjectArea, SubjectArea>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(subject_areas, |subject_area| subject_area.id)
+            .with_total(total))
     }
 
     async fn get_subject_areas_by_book_id(
@@ -207,214 +854,899 @@ impl BookService for BookServer {
         _: Context,
         id: Uuid,
         page: filters::Page,
-    ) -> RpcResult<Vec<SubjectArea>> {
+    ) -> RpcResult<Paginated<SubjectArea>> {
         let (query, values) =
-            queries::get_subject_areas_by_book_id(id, page).build(PostgresQueryBuilder);
+            queries::get_subject_areas_by_book_id(id, page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_subject_areas_by_book_id(
+                id,
+                filters::Page::all(),
+            ))
+            .build(PostgresQueryBuilder)
+        });
+
+        let (subject_areas, total) = self
+            .get_page::<db_models::SubjectArea, SubjectArea>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(subject_areas, |subject_area| subject_area.id)
+            .with_total(total))
+    }
+
+    async fn get_tag_by_id(self, _: Context, id: Uuid) -> RpcResult<Tag> {
+        let (query, values) = queries::get_tag_by_id(id).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::SubjectArea>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|subject_area| subject_area.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_tag_by_id(self, _: Context, id: Uuid) -> RpcResult<Tag> {
-        let (query, values) = queries::get_tag_by_id(id).build(PostgresQueryBuilder);
+    async fn get_tags(self, _: Context, page: filters::Page) -> RpcResult<Paginated<Tag>> {
+        let (query, values) = queries::get_tags(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_tags(filters::Page::all())).build(PostgresQueryBuilder)
+        });
+
+        let (tags, total) = self
+            .get_page::<db_models::Tag, Tag>((query, values), total)
+            .await?;
+        Ok(page.paginate(tags, |tag| tag.id).with_total(total))
+    }
+
+    async fn get_tags_by_book_id(
+        self,
+        _: Context,
+        id: Uuid,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Tag>> {
+        let (query, values) =
+            queries::get_tags_by_book_id(id, page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_tags_by_book_id(id, filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (tags, total) = self
+            .get_page::<db_models::Tag, Tag>((query, values), total)
+            .await?;
+        Ok(page.paginate(tags, |tag| tag.id).with_total(total))
+    }
+
+    async fn get_author_by_id(self, _: Context, id: Uuid) -> RpcResult<Author> {
+        let (query, values) = queries::get_author_by_id(id).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Author>(&query), &values)
                 .fetch_one(&mut self.get_db_connection().await)
                 .await?
                 .into(),
         )
     }
 
-    async fn get_tags(self, _: Context, page: filters::Page) -> RpcResult<Vec<Tag>> {
-        let (query, values) = queries::get_tags(page).build(PostgresQueryBuilder);
+    async fn get_authors(self, _: Context, page: filters::Page) -> RpcResult<Paginated<Author>> {
+        let (query, values) = queries::get_authors(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_authors(filters::Page::all())).build(PostgresQueryBuilder)
+        });
+
+        let (authors, total) = self
+            .get_page::<db_models::Author, Author>((query, values), total)
+            .await?;
+        Ok(page.paginate(authors, |author| author.id).with_total(total))
+    }
+
+    async fn get_authors_by_book_id(
+        self,
+        _: Context,
+        id: Uuid,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Author>> {
+        let (query, values) =
+            queries::get_authors_by_book_id(id, page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_authors_by_book_id(id, filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (authors, total) = self
+            .get_page::<db_models::Author, Author>((query, values), total)
+            .await?;
+        Ok(page.paginate(authors, |author| author.id).with_total(total))
+    }
+
+    async fn get_editor_by_id(self, _: Context, id: Uuid) -> RpcResult<Editor> {
+        let (query, values) = queries::get_editor_by_id(id).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Editor>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|tag| tag.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_tags_by_book_id(
+    async fn get_editors(self, _: Context, page: filters::Page) -> RpcResult<Paginated<Editor>> {
+        let (query, values) = queries::get_editors(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_editors(filters::Page::all())).build(PostgresQueryBuilder)
+        });
+
+        let (editors, total) = self
+            .get_page::<db_models::Editor, Editor>((query, values), total)
+            .await?;
+        Ok(page.paginate(editors, |editor| editor.id).with_total(total))
+    }
+
+    async fn get_editors_by_book_id(
         self,
         _: Context,
         id: Uuid,
         page: filters::Page,
-    ) -> RpcResult<Vec<Tag>> {
-        let (query, values) = queries::get_tags_by_book_id(id, page).build(PostgresQueryBuilder);
+    ) -> RpcResult<Paginated<Editor>> {
+        let (query, values) =
+            queries::get_editors_by_book_id(id, page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_editors_by_book_id(id, filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (editors, total) = self
+            .get_page::<db_models::Editor, Editor>((query, values), total)
+            .await?;
+        Ok(page.paginate(editors, |editor| editor.id).with_total(total))
+    }
+
+    async fn get_copy_by_id(self, _: Context, id: Uuid) -> RpcResult<Copy> {
+        let (query, values) = queries::get_copy_by_id(id).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|tag| tag.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_author_by_id(self, _: Context, id: Uuid) -> RpcResult<Author> {
-        let (query, values) = queries::get_author_by_id(id).build(PostgresQueryBuilder);
+    async fn get_copies(self, _: Context, page: filters::Page) -> RpcResult<Paginated<Copy>> {
+        let (query, values) = queries::get_copies(page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_copies(filters::Page::all())).build(PostgresQueryBuilder)
+        });
+
+        let (copies, total) = self
+            .get_page::<db_models::Copy, Copy>((query, values), total)
+            .await?;
+        Ok(page.paginate(copies, |copy| copy.id).with_total(total))
+    }
+
+    async fn get_copies_by_book_id(
+        self,
+        _: Context,
+        id: Uuid,
+        page: filters::Page,
+    ) -> RpcResult<Paginated<Copy>> {
+        let (query, values) =
+            queries::get_copies_by_book_id(id, page.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_copies_by_book_id(id, filters::Page::all()))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (copies, total) = self
+            .get_page::<db_models::Copy, Copy>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(copies, |language| language.id)
+            .with_total(total))
+    }
+
+    async fn get_copy_by_label(self, _: Context, label: String) -> RpcResult<Copy> {
+        let (code_identifier, copy_id) = parse_copy_label(&label)?;
+        let (query, values) =
+            queries::get_copies_by_label(code_identifier, copy_id).build(PostgresQueryBuilder);
+
+        let mut copies = bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
+            .fetch_all(&mut self.get_db_connection().await)
+            .await?;
+        match copies.len() {
+            0 => Err(Error::NotFound),
+            1 => Ok(copies.remove(0).into()),
+            _ => {
+                log::warn!("Copy label '{}' is ambiguous", label);
+                Err(Error::InvalidData)
+            }
+        }
+    }
+
+    async fn create_copies(
+        self,
+        _: Context,
+        book_id: Uuid,
+        count: u32,
+        created_by: Uuid,
+    ) -> RpcResult<Vec<Copy>> {
+        if count == 0 || count > COPIES_MAX_COUNT {
+            return Err(Error::InvalidInput);
+        }
+        let ids: Vec<Uuid> = (0..count).map(|_| Uuid::new_v4()).collect();
+        let (book_query, book_values) =
+            queries::get_book_by_id(book_id).build(PostgresQueryBuilder);
+        let (query, values) = queries::create_copies(book_id, &ids, Utc::now(), created_by)
+            .build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        bind_query(sqlx::query(&book_query), &book_values)
+            .fetch_one(&mut tx)
+            .await?;
+        let copies = bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
+            .fetch_all(&mut tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(copies.into_iter().map(|copy| copy.into()).collect())
+    }
+
+    async fn retire_copy(
+        self,
+        _: Context,
+        id: Uuid,
+        reason: RetirementReason,
+        retired_by: Uuid,
+    ) -> RpcResult<Copy> {
+        let (query, values) =
+            queries::retire_copy(id, reason, Utc::now(), retired_by).build(PostgresQueryBuilder);
+        let (copy_query, copy_values) = queries::get_copy_by_id(id).build(PostgresQueryBuilder);
+
+        let mut db = self.get_db_connection().await;
+        let retired = bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
+            .fetch_optional(&mut db)
+            .await?;
+        match retired {
+            Some(copy) => Ok(copy.into()),
+            // the copy is missing, was retired before, or is lent or held
+            None => {
+                let copy = bind_query_as(query_as::<_, db_models::Copy>(&copy_query), &copy_values)
+                    .fetch_one(&mut db)
+                    .await?;
+                match copy.retired_at {
+                    Some(_) => Err(Error::AlreadyExists),
+                    None => Err(Error::InUse),
+                }
+            }
+        }
+    }
+
+    async fn get_book_by_id(self, _: Context, id: Uuid) -> RpcResult<Book> {
+        let (query, values) = queries::get_book_by_id(id).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Author>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Book>(&query), &values)
                 .fetch_one(&mut self.get_db_connection().await)
                 .await?
                 .into(),
         )
     }
 
-    async fn get_authors(self, _: Context, page: filters::Page) -> RpcResult<Vec<Author>> {
-        let (query, values) = queries::get_authors(page).build(PostgresQueryBuilder);
+    async fn get_books(
+        self,
+        _: Context,
+        page: filters::KeysetPage,
+        book: filters::Book,
+    ) -> RpcResult<Paginated<Book, filters::Keyset>> {
+        validate_keyset(&page)?;
+        let (query, values) =
+            queries::get_books(page.clone(), book.clone()).build(PostgresQueryBuilder);
+        let total = page.get_total().then(|| {
+            queries::count(queries::get_books(filters::KeysetPage::all(), book))
+                .build(PostgresQueryBuilder)
+        });
+
+        let (books, total) = self
+            .get_page::<db_models::Book, Book>((query, values), total)
+            .await?;
+        Ok(page
+            .paginate(books, |book, position, sort| book.keyset(position, sort))
+            .with_total(total))
+    }
+
+    async fn get_book_relations(
+        self,
+        _: Context,
+        ids: Vec<Uuid>,
+        include: filters::Include,
+    ) -> RpcResult<Vec<BookRelations>> {
+        let query = |relation, query: fn(&[Uuid]) -> SelectStatement| {
+            include
+                .contains(relation)
+                .then(|| query(&ids).build(PostgresQueryBuilder))
+        };
+        let authors = query(filters::Relation::Authors, queries::get_authors_by_book_ids);
+        let editors = query(filters::Relation::Editors, queries::get_editors_by_book_ids);
+        let categories = query(
+            filters::Relation::Category,
+            queries::get_categories_by_book_ids,
+        );
+        let languages = query(
+            filters::Relation::Language,
+            queries::get_languages_by_book_ids,
+        );
+        let publishers = query(
+            filters::Relation::Publisher,
+            queries::get_publishers_by_book_ids,
+        );
+        let series = query(filters::Relation::Series, queries::get_series_by_book_ids);
+        let tags = query(filters::Relation::Tags, queries::get_tags_by_book_ids);
+        let subject_areas = query(
+            filters::Relation::SubjectAreas,
+            queries::get_subject_areas_by_book_ids,
+        );
+
+        let mut conn = self.get_db_connection().await;
+        let mut authors = get_related::<db_models::Author, Author>(&mut conn, authors).await?;
+        let mut editors = get_related::<db_models::Editor, Editor>(&mut conn, editors).await?;
+        let mut categories =
+            get_related::<db_models::Category, Category>(&mut conn, categories).await?;
+        let mut languages =
+            get_related::<db_models::Language, Language>(&mut conn, languages).await?;
+        let mut publishers =
+            get_related::<db_models::Publisher, Publisher>(&mut conn, publishers).await?;
+        let mut series = get_related::<db_models::Series, Series>(&mut conn, series).await?;
+        let mut tags = get_related::<db_models::Tag, Tag>(&mut conn, tags).await?;
+        let mut subject_areas =
+            get_related::<db_models::SubjectArea, SubjectArea>(&mut conn, subject_areas).await?;
+
+        // a book references one category, language, publisher and series at most
+        Ok(ids
+            .iter()
+            .map(|id| BookRelations {
+                authors: authors
+                    .as_mut()
+                    .map(|authors| authors.remove(id).unwrap_or_default()),
+                editors: editors
+                    .as_mut()
+                    .map(|editors| editors.remove(id).unwrap_or_default()),
+                category: categories
+                    .as_mut()
+                    .and_then(|categories| categories.remove(id)?.pop()),
+                language: languages
+                    .as_mut()
+                    .and_then(|languages| languages.remove(id)?.pop()),
+                publisher: publishers
+                    .as_mut()
+                    .and_then(|publishers| publishers.remove(id)?.pop()),
+                series: series
+                    .as_mut()
+                    .map(|series| series.remove(id).and_then(|mut series| series.pop())),
+                tags: tags
+                    .as_mut()
+                    .map(|tags| tags.remove(id).unwrap_or_default()),
+                subject_areas: subject_areas
+                    .as_mut()
+                    .map(|subject_areas| subject_areas.remove(id).unwrap_or_default()),
+            })
+            .collect())
+    }
+
+    async fn search_books(self, _: Context, search: filters::Search) -> RpcResult<Vec<Book>> {
+        if search.get_items() < 1 {
+            return Err(Error::InvalidInput);
+        }
+        let (query, values) =
+            queries::search_books(&search_query(search.get_q())?, search.get_items())
+                .build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Author>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Book>(&query), &values)
                 .fetch_all(&mut self.get_db_connection().await)
                 .await?
                 .into_iter()
-                .map(|author| author.into())
+                .map(|book| book.into())
                 .collect(),
         )
     }
 
-    async fn get_authors_by_book_id(
+    async fn create_book(self, _: Context, book: BookDetails) -> RpcResult<Book> {
+        let book = validate_book(book)?;
+        let id = Uuid::new_v4();
+        let (query, values) = queries::create_book(id, &book).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        let created = bind_query_as(query_as::<_, db_models::Book>(&query), &values)
+            .fetch_one(&mut tx)
+            .await?;
+        link_book(&mut tx, id, &book).await?;
+        tx.commit().await?;
+
+        Ok(created.into())
+    }
+
+    async fn update_book(self, _: Context, id: Uuid, book: BookDetails) -> RpcResult<Book> {
+        let book = validate_book(book)?;
+        let (query, values) = queries::update_book(id, &book).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        let updated = bind_query_as(query_as::<_, db_models::Book>(&query), &values)
+            .fetch_optional(&mut tx)
+            .await?
+            .ok_or(Error::NotFound)?;
+        unlink_book(&mut tx, id).await?;
+        link_book(&mut tx, id, &book).await?;
+        tx.commit().await?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_book(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_book(id).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        unlink_book(&mut tx, id).await?;
+        delete(&query, &values, &mut tx).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn import_books(
+        self,
+        _: Context,
+        format: ImportFormat,
+        file: ImportFile,
+        options: ImportOptions,
+        dry_run: bool,
+    ) -> RpcResult<ImportReport> {
+        let records = import::parse(format, &file.0)?;
+        if options.code_identifier < 0 {
+            return Err(Error::InvalidInput);
+        }
+        let (query, values) =
+            queries::get_category_by_id(options.category_id).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        bind_query_as(query_as::<_, db_models::Category>(&query), &values)
+            .fetch_optional(&mut tx)
+            .await?
+            .ok_or(Error::InvalidInput)?;
+
+        let mut report = ImportReport::new(dry_run);
+        let mut isbns = HashSet::new();
+        for (index, record) in records.iter().enumerate() {
+            let mut created = vec![];
+            let outcome = if record
+                .isbn
+                .as_ref()
+                .is_some_and(|isbn| !isbns.insert(isbn.clone()))
+            {
+                Err(vec![ImportConflict::DuplicateIsbn])
+            } else {
+                // a record is rolled back on its own, so that a conflict doesn't stop the others
+                let mut savepoint = tx.begin().await?;
+                let outcome = import_record(&mut savepoint, record, &options, &mut created).await;
+                settle(savepoint, outcome).await?
+            };
+            report.push(imported_record(
+                index,
+                record.isbn.clone(),
+                record.title.clone(),
+                outcome,
+                created,
+                dry_run,
+            ));
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+        Ok(report)
+    }
+
+    async fn export_books(
         self,
         _: Context,
-        id: Uuid,
         page: filters::Page,
-    ) -> RpcResult<Vec<Author>> {
-        let (query, values) = queries::get_authors_by_book_id(id, page).build(PostgresQueryBuilder);
+    ) -> RpcResult<Paginated<CatalogueEntry>> {
+        let (query, values) = queries::get_catalogue(page.clone()).build(PostgresQueryBuilder);
+
+        let mut conn = self.get_db_connection().await;
+        let books = bind_query_as(query_as::<_, db_models::CatalogueEntry>(&query), &values)
+            .fetch_all(&mut conn)
+            .await?;
+        let page = page.paginate(books, |book| book.id);
+
+        let ids: Vec<Uuid> = page.get_items().iter().map(|book| book.id).collect();
+        let query = |query: fn(&[Uuid]) -> SelectStatement| {
+            (!ids.is_empty()).then(|| query(&ids).build(PostgresQueryBuilder))
+        };
+        let authors = query(queries::get_authors_by_book_ids);
+        let editors = query(queries::get_editors_by_book_ids);
+        let subject_areas = query(queries::get_subject_areas_by_book_ids);
+        let tags = query(queries::get_tags_by_book_ids);
+
+        let mut authors = get_related::<db_models::Author, db_models::Author>(&mut conn, authors)
+            .await?
+            .unwrap_or_default();
+        let mut editors = get_related::<db_models::Editor, db_models::Editor>(&mut conn, editors)
+            .await?
+            .unwrap_or_default();
+        let mut subject_areas =
+            get_related::<db_models::SubjectArea, db_models::SubjectArea>(&mut conn, subject_areas)
+                .await?
+                .unwrap_or_default();
+        let mut tags = get_related::<db_models::Tag, db_models::Tag>(&mut conn, tags)
+            .await?
+            .unwrap_or_default();
+
+        Ok(page.map(|books| {
+            books
+                .into_iter()
+                .map(|book| {
+                    let authors = authors.remove(&book.id).unwrap_or_default();
+                    let tags = tags.remove(&book.id).unwrap_or_default();
+                    CatalogueEntry {
+                        id: book.id,
+                        code_identifier: book.code_identifier,
+                        isbn: book.isbn,
+                        issn: book.issn,
+                        release_year: book.release_year,
+                        edition: book.edition,
+                        pages: book.pages,
+                        title: book.title,
+                        subtitle: book.subtitle,
+                        description: book.description,
+                        category_id: book.category_id,
+                        language_id: book.language_id,
+                        publisher_id: book.publisher_id,
+                        series_id: book.series_id,
+                        author_ids: authors.iter().map(|author| author.id).collect(),
+                        authors: authors
+                            .iter()
+                            .map(|author| format!("{}, {}", author.last_name, author.first_name))
+                            .collect(),
+                        editor_ids: editors
+                            .remove(&book.id)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|editor| editor.id)
+                            .collect(),
+                        subject_area_ids: subject_areas
+                            .remove(&book.id)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|subject_area| subject_area.id)
+                            .collect(),
+                        tag_ids: tags.iter().map(|tag| tag.id).collect(),
+                        tags: tags.into_iter().map(|tag| tag.name).collect(),
+                        copies: book.copies,
+                        created_at: Some(book.created_at),
+                    }
+                })
+                .collect()
+        }))
+    }
+
+    async fn import_catalogue(
+        self,
+        _: Context,
+        entries: Vec<CatalogueEntry>,
+        dry_run: bool,
+    ) -> RpcResult<ImportReport> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut report = ImportReport::new(dry_run);
+        let mut ids = HashSet::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let outcome = if !ids.insert(entry.id) {
+                Err(vec![ImportConflict::DuplicateId])
+            } else {
+                let mut savepoint = tx.begin().await?;
+                let outcome = import_entry(&mut savepoint, entry).await;
+                settle(savepoint, outcome).await?
+            };
+            report.push(imported_record(
+                index,
+                entry.isbn.clone(),
+                Some(entry.title.clone()),
+                outcome,
+                vec![],
+                dry_run,
+            ));
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+        Ok(report)
+    }
+
+    async fn create_category(self, _: Context, category: NameDetails) -> RpcResult<Category> {
+        let category = validate_name(category, NAME_MAX_LENGTH)?;
+        let (query, values) =
+            queries::create_category(Uuid::new_v4(), &category).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Author>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Category>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|author| author.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_editor_by_id(self, _: Context, id: Uuid) -> RpcResult<Editor> {
-        let (query, values) = queries::get_editor_by_id(id).build(PostgresQueryBuilder);
+    async fn update_category(
+        self,
+        _: Context,
+        id: Uuid,
+        category: NameDetails,
+    ) -> RpcResult<Category> {
+        let category = validate_name(category, NAME_MAX_LENGTH)?;
+        let (query, values) = queries::update_category(id, &category).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Editor>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Category>(&query), &values)
                 .fetch_one(&mut self.get_db_connection().await)
                 .await?
                 .into(),
         )
     }
 
-    async fn get_editors(self, _: Context, page: filters::Page) -> RpcResult<Vec<Editor>> {
-        let (query, values) = queries::get_editors(page).build(PostgresQueryBuilder);
+    async fn delete_category(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_category(id).build(PostgresQueryBuilder);
+
+        delete(&query, &values, &mut self.get_db_connection().await).await
+    }
+
+    async fn create_publisher(self, _: Context, publisher: NameDetails) -> RpcResult<Publisher> {
+        let publisher = validate_name(publisher, NAME_MAX_LENGTH)?;
+        let (query, values) =
+            queries::create_publisher(Uuid::new_v4(), &publisher).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Editor>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Publisher>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|editor| editor.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_editors_by_book_id(
+    async fn update_publisher(
         self,
         _: Context,
         id: Uuid,
-        page: filters::Page,
-    ) -> RpcResult<Vec<Editor>> {
-        let (query, values) = queries::get_editors_by_book_id(id, page).build(PostgresQueryBuilder);
+        publisher: NameDetails,
+    ) -> RpcResult<Publisher> {
+        let publisher = validate_name(publisher, NAME_MAX_LENGTH)?;
+        let (query, values) = queries::update_publisher(id, &publisher).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Editor>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Publisher>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|editor| editor.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_copy_by_id(self, _: Context, id: Uuid) -> RpcResult<Copy> {
-        let (query, values) = queries::get_copy_by_id(id).build(PostgresQueryBuilder);
+    async fn delete_publisher(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::count_books_by_publisher_id(id).build(PostgresQueryBuilder);
+        let (delete_query, delete_values) =
+            queries::delete_publisher(id).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        // books would be deleted along with their publisher
+        let (books,) = bind_query_as(query_as::<_, (i64,)>(&query), &values)
+            .fetch_one(&mut tx)
+            .await?;
+        if books > 0 {
+            return Err(Error::InUse);
+        }
+        delete(&delete_query, &delete_values, &mut tx).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn create_series(self, _: Context, series: SeriesDetails) -> RpcResult<Series> {
+        let series = validate_series(series)?;
+        let (query, values) =
+            queries::create_series(Uuid::new_v4(), &series).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Series>(&query), &values)
                 .fetch_one(&mut self.get_db_connection().await)
                 .await?
                 .into(),
         )
     }
 
-    async fn get_copies(self, _: Context, page: filters::Page) -> RpcResult<Vec<Copy>> {
-        let (query, values) = queries::get_copies(page).build(PostgresQueryBuilder);
+    async fn update_series(self, _: Context, id: Uuid, series: SeriesPatch) -> RpcResult<Series> {
+        let series = validate_series_patch(series)?;
+        let (query, values) = if series == SeriesPatch::default() {
+            queries::get_series_by_id(id).build(PostgresQueryBuilder)
+        } else {
+            queries::update_series(id, &series).build(PostgresQueryBuilder)
+        };
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Series>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|copy| copy.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_copies_by_book_id(
+    async fn delete_series(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_series(id).build(PostgresQueryBuilder);
+
+        delete(&query, &values, &mut self.get_db_connection().await).await
+    }
+
+    async fn create_subject_area(
+        self,
+        _: Context,
+        subject_area: NameDetails,
+    ) -> RpcResult<SubjectArea> {
+        let subject_area = validate_name(subject_area, NAME_MAX_LENGTH)?;
+        let (query, values) =
+            queries::create_subject_area(Uuid::new_v4(), &subject_area).build(PostgresQueryBuilder);
+
+        Ok(
+            bind_query_as(query_as::<_, db_models::SubjectArea>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
+                .await?
+                .into(),
+        )
+    }
+
+    async fn update_subject_area(
         self,
         _: Context,
         id: Uuid,
-        page: filters::Page,
-    ) -> RpcResult<Vec<Copy>> {
-        let (query, values) = queries::get_copies_by_book_id(id, page).build(PostgresQueryBuilder);
+        subject_area: NameDetails,
+    ) -> RpcResult<SubjectArea> {
+        let subject_area = validate_name(subject_area, NAME_MAX_LENGTH)?;
+        let (query, values) =
+            queries::update_subject_area(id, &subject_area).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Copy>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::SubjectArea>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|language| language.into())
-                .collect(),
+                .into(),
         )
     }
 
-    async fn get_book_by_id(self, _: Context, id: Uuid) -> RpcResult<Book> {
-        let (query, values) = queries::get_book_by_id(id).build(PostgresQueryBuilder);
+    async fn delete_subject_area(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_subject_area(id).build(PostgresQueryBuilder);
+
+        delete(&query, &values, &mut self.get_db_connection().await).await
+    }
+
+    async fn create_tag(self, _: Context, tag: NameDetails) -> RpcResult<Tag> {
+        let tag = validate_name(tag, TAG_NAME_MAX_LENGTH)?;
+        let (query, values) = queries::create_tag(Uuid::new_v4(), &tag).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Book>(&query), &values)
+            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
                 .fetch_one(&mut self.get_db_connection().await)
                 .await?
                 .into(),
         )
     }
 
-    async fn get_books(
-        self,
-        _: Context,
-        page: filters::Page,
-        book: filters::Book,
-    ) -> RpcResult<Vec<Book>> {
-        let (query, values) = queries::get_books(page, book).build(PostgresQueryBuilder);
+    async fn update_tag(self, _: Context, id: Uuid, tag: NameDetails) -> RpcResult<Tag> {
+        let tag = validate_name(tag, TAG_NAME_MAX_LENGTH)?;
+        let (query, values) = queries::update_tag(id, &tag).build(PostgresQueryBuilder);
 
         Ok(
-            bind_query_as(query_as::<_, db_models::Book>(&query), &values)
-                .fetch_all(&mut self.get_db_connection().await)
+            bind_query_as(query_as::<_, db_models::Tag>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
                 .await?
-                .into_iter()
-                .map(|book| book.into())
-                .collect(),
+                .into(),
+        )
+    }
+
+    async fn delete_tag(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_tag(id).build(PostgresQueryBuilder);
+
+        delete(&query, &values, &mut self.get_db_connection().await).await
+    }
+
+    async fn create_person(self, _: Context, person: PersonDetails) -> RpcResult<Person> {
+        let person = validate_person(person)?;
+        let (query, values) =
+            queries::create_person(Uuid::new_v4(), &person).build(PostgresQueryBuilder);
+
+        Ok(
+            bind_query_as(query_as::<_, db_models::Person>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
+                .await?
+                .into(),
+        )
+    }
+
+    async fn update_person(self, _: Context, id: Uuid, person: PersonPatch) -> RpcResult<Person> {
+        let person = validate_person_patch(person)?;
+        let (query, values) = if person == PersonPatch::default() {
+            queries::get_person_by_id(id).build(PostgresQueryBuilder)
+        } else {
+            queries::update_person(id, &person).build(PostgresQueryBuilder)
+        };
+
+        Ok(
+            bind_query_as(query_as::<_, db_models::Person>(&query), &values)
+                .fetch_one(&mut self.get_db_connection().await)
+                .await?
+                .into(),
+        )
+    }
+
+    async fn delete_person(self, _: Context, id: Uuid) -> RpcResult<()> {
+        let (query, values) = queries::delete_person(id).build(PostgresQueryBuilder);
+
+        delete(&query, &values, &mut self.get_db_connection().await).await
+    }
+
+    async fn merge_persons(self, _: Context, id: Uuid, duplicate_id: Uuid) -> RpcResult<Person> {
+        if id == duplicate_id {
+            return Err(Error::InvalidInput);
+        }
+        let (person_query, person_values) =
+            queries::get_person_by_id(id).build(PostgresQueryBuilder);
+        let (duplicate_query, duplicate_values) =
+            queries::get_person_by_id(duplicate_id).build(PostgresQueryBuilder);
+        let (delete_query, delete_values) =
+            queries::delete_person(duplicate_id).build(PostgresQueryBuilder);
+
+        let mut tx = self.db_pool.begin().await?;
+        let person = bind_query_as(
+            query_as::<_, db_models::Person>(&person_query),
+            &person_values,
         )
+        .fetch_one(&mut tx)
+        .await?;
+        let duplicate = bind_query_as(
+            query_as::<_, db_models::Person>(&duplicate_query),
+            &duplicate_values,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        // a book may list both, so that their links are unique only without those of the duplicate
+        let statements = vec![
+            queries::delete_duplicate_books_authors(id, duplicate_id).build(PostgresQueryBuilder),
+            queries::update_books_authors_person(id, duplicate_id).build(PostgresQueryBuilder),
+            queries::delete_duplicate_books_editors(id, duplicate_id).build(PostgresQueryBuilder),
+            queries::update_books_editors_person(id, duplicate_id).build(PostgresQueryBuilder),
+        ];
+        for (statement, values) in statements {
+            bind_query(sqlx::query(&statement), &values)
+                .execute(&mut tx)
+                .await?;
+        }
+        // the identifiers are unique, so that the duplicate is deleted before they are taken over
+        delete(&delete_query, &delete_values, &mut tx).await?;
+
+        let patch = PersonPatch {
+            date_of_birth: fill(&person.date_of_birth, duplicate.date_of_birth),
+            isni: fill(&person.isni, duplicate.isni),
+            orcid: fill(&person.orcid, duplicate.orcid),
+            oclc: fill(&person.oclc, duplicate.oclc),
+            ..PersonPatch::default()
+        };
+        let merged = if patch == PersonPatch::default() {
+            person
+        } else {
+            let (query, values) = queries::update_person(id, &patch).build(PostgresQueryBuilder);
+            bind_query_as(query_as::<_, db_models::Person>(&query), &values)
+                .fetch_one(&mut tx)
+                .await?
+        };
+        tx.commit().await?;
+
+        Ok(merged.into())
     }
 }
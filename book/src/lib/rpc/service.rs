@@ -1,42 +1,105 @@
 use uuid::Uuid;
 
 use super::models::{
-    Author, Book, Category, Copy, Editor, Language, Publisher, Series, SubjectArea, Tag,
+    Author, Book, BookDetails, BookRelations, CatalogueEntry, Category, Copy, Editor, ImportFile,
+    ImportFormat, ImportOptions, ImportReport, Language, NameDetails, Person, PersonDetails,
+    PersonPatch, Publisher, RetirementReason, Series, SeriesDetails, SeriesPatch, SubjectArea, Tag,
+};
+use helpers::{
+    filters::{self, Paginated},
+    rpc::RpcResult,
 };
-use helpers::{filters, rpc::RpcResult};
 
 #[tarpc::service]
 pub trait BookService {
     async fn get_language_by_id(id: Uuid) -> RpcResult<Language>;
     async fn get_language_by_book_id(id: Uuid) -> RpcResult<Language>;
-    async fn get_languages(page: filters::Page) -> RpcResult<Vec<Language>>;
+    async fn get_languages(page: filters::Page) -> RpcResult<Paginated<Language>>;
     async fn get_category_by_id(id: Uuid) -> RpcResult<Category>;
     async fn get_category_by_book_id(id: Uuid) -> RpcResult<Category>;
-    async fn get_categories(page: filters::Page) -> RpcResult<Vec<Category>>;
+    async fn get_categories(page: filters::Page) -> RpcResult<Paginated<Category>>;
     async fn get_publisher_by_id(id: Uuid) -> RpcResult<Publisher>;
     async fn get_publisher_by_book_id(id: Uuid) -> RpcResult<Publisher>;
-    async fn get_publishers(page: filters::Page) -> RpcResult<Vec<Publisher>>;
+    async fn get_publishers(page: filters::Page) -> RpcResult<Paginated<Publisher>>;
     async fn get_series_by_id(id: Uuid) -> RpcResult<Series>;
     async fn get_series_by_book_id(id: Uuid) -> RpcResult<Series>;
-    async fn get_series(page: filters::Page) -> RpcResult<Vec<Series>>;
+    async fn get_series(page: filters::Page) -> RpcResult<Paginated<Series>>;
     async fn get_subject_area_by_id(id: Uuid) -> RpcResult<SubjectArea>;
-    async fn get_subject_areas(page: filters::Page) -> RpcResult<Vec<SubjectArea>>;
+    async fn get_subject_areas(page: filters::Page) -> RpcResult<Paginated<SubjectArea>>;
     async fn get_subject_areas_by_book_id(
         id: Uuid,
         page: filters::Page,
-    ) -> RpcResult<Vec<SubjectArea>>;
+    ) -> RpcResult<Paginated<SubjectArea>>;
     async fn get_tag_by_id(id: Uuid) -> RpcResult<Tag>;
-    async fn get_tags(page: filters::Page) -> RpcResult<Vec<Tag>>;
-    async fn get_tags_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Vec<Tag>>;
+    async fn get_tags(page: filters::Page) -> RpcResult<Paginated<Tag>>;
+    async fn get_tags_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Paginated<Tag>>;
     async fn get_author_by_id(id: Uuid) -> RpcResult<Author>;
-    async fn get_authors(page: filters::Page) -> RpcResult<Vec<Author>>;
-    async fn get_authors_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Vec<Author>>;
+    async fn get_authors(page: filters::Page) -> RpcResult<Paginated<Author>>;
+    async fn get_authors_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Paginated<Author>>;
     async fn get_editor_by_id(id: Uuid) -> RpcResult<Editor>;
-    async fn get_editors(page: filters::Page) -> RpcResult<Vec<Editor>>;
-    async fn get_editors_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Vec<Editor>>;
+    async fn get_editors(page: filters::Page) -> RpcResult<Paginated<Editor>>;
+    async fn get_editors_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Paginated<Editor>>;
     async fn get_copy_by_id(id: Uuid) -> RpcResult<Copy>;
-    async fn get_copies(page: filters::Page) -> RpcResult<Vec<Copy>>;
-    async fn get_copies_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Vec<Copy>>;
+    async fn get_copies(page: filters::Page) -> RpcResult<Paginated<Copy>>;
+    async fn get_copies_by_book_id(id: Uuid, page: filters::Page) -> RpcResult<Paginated<Copy>>;
+    /// Looks up a copy by its label `code_identifier-copy_id`
+    async fn get_copy_by_label(label: String) -> RpcResult<Copy>;
+    /// Adds copies of a book, which were acquired by the given user
+    async fn create_copies(book_id: Uuid, count: u32, created_by: Uuid) -> RpcResult<Vec<Copy>>;
+    /// Retires a copy, which is kept for the history of its loans, unless it is lent or held
+    async fn retire_copy(id: Uuid, reason: RetirementReason, retired_by: Uuid) -> RpcResult<Copy>;
     async fn get_book_by_id(id: Uuid) -> RpcResult<Book>;
-    async fn get_books(page: filters::Page, book: filters::Book) -> RpcResult<Vec<Book>>;
+    /// Lists the books in the order of the page, after or before its cursor
+    async fn get_books(
+        page: filters::KeysetPage,
+        book: filters::Book,
+    ) -> RpcResult<Paginated<Book, filters::Keyset>>;
+    /// Reads the requested relations of many books at once, in the order of the given ids
+    async fn get_book_relations(
+        ids: Vec<Uuid>,
+        include: filters::Include,
+    ) -> RpcResult<Vec<BookRelations>>;
+    /// Searches title, subtitle, description, authors, editors, ISBN, ISSN and tags of the books
+    async fn search_books(search: filters::Search) -> RpcResult<Vec<Book>>;
+    async fn create_book(book: BookDetails) -> RpcResult<Book>;
+    async fn update_book(id: Uuid, book: BookDetails) -> RpcResult<Book>;
+    async fn delete_book(id: Uuid) -> RpcResult<()>;
+    /// Imports the records of a MARC 21, MARCXML or ONIX file into the catalogue, which creates
+    /// or updates the books by their ISBN along with the persons, publishers, series and
+    /// subject areas they reference. A dry run reports the same outcome without writing it.
+    async fn import_books(
+        format: ImportFormat,
+        file: ImportFile,
+        options: ImportOptions,
+        dry_run: bool,
+    ) -> RpcResult<ImportReport>;
+    /// Lists the books with their relations flattened into ids, in the order of their ids
+    async fn export_books(page: filters::Page) -> RpcResult<Paginated<CatalogueEntry>>;
+    /// Imports the books of a catalogue export by their ids, which creates the missing ones
+    /// and updates the changed ones. A dry run reports the same outcome without writing it.
+    async fn import_catalogue(
+        entries: Vec<CatalogueEntry>,
+        dry_run: bool,
+    ) -> RpcResult<ImportReport>;
+    async fn create_category(category: NameDetails) -> RpcResult<Category>;
+    async fn update_category(id: Uuid, category: NameDetails) -> RpcResult<Category>;
+    async fn delete_category(id: Uuid) -> RpcResult<()>;
+    async fn create_publisher(publisher: NameDetails) -> RpcResult<Publisher>;
+    async fn update_publisher(id: Uuid, publisher: NameDetails) -> RpcResult<Publisher>;
+    async fn delete_publisher(id: Uuid) -> RpcResult<()>;
+    async fn create_series(series: SeriesDetails) -> RpcResult<Series>;
+    async fn update_series(id: Uuid, series: SeriesPatch) -> RpcResult<Series>;
+    async fn delete_series(id: Uuid) -> RpcResult<()>;
+    async fn create_subject_area(subject_area: NameDetails) -> RpcResult<SubjectArea>;
+    async fn update_subject_area(id: Uuid, subject_area: NameDetails) -> RpcResult<SubjectArea>;
+    async fn delete_subject_area(id: Uuid) -> RpcResult<()>;
+    async fn create_tag(tag: NameDetails) -> RpcResult<Tag>;
+    async fn update_tag(id: Uuid, tag: NameDetails) -> RpcResult<Tag>;
+    async fn delete_tag(id: Uuid) -> RpcResult<()>;
+    async fn create_person(person: PersonDetails) -> RpcResult<Person>;
+    async fn update_person(id: Uuid, person: PersonPatch) -> RpcResult<Person>;
+    async fn delete_person(id: Uuid) -> RpcResult<()>;
+    /// Merges a duplicate into a person, moving its authorships and editorships
+    /// and filling the missing fields of the person from it, before it is deleted
+    async fn merge_persons(id: Uuid, duplicate_id: Uuid) -> RpcResult<Person>;
 }
@@ -0,0 +1,532 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use helpers::{
+    filters,
+    rpc::{Error, RpcResult},
+};
+
+use super::models::{
+    BookDetails, NameDetails, PersonDetails, PersonPatch, SeriesDetails, SeriesPatch,
+};
+
+/// Maximum lengths of the text columns of `books`
+const TITLE_MAX_LENGTH: usize = 100;
+const SUBTITLE_MAX_LENGTH: usize = 500;
+
+/// Maximum lengths of the names of the catalogue reference data
+pub(crate) const NAME_MAX_LENGTH: usize = 100;
+pub(crate) const TAG_NAME_MAX_LENGTH: usize = 50;
+
+/// Maximum number of copies added at once
+pub(crate) const COPIES_MAX_COUNT: u32 = 100;
+
+/// Maximum length and number of terms of a full-text search
+const SEARCH_MAX_LENGTH: usize = 200;
+const SEARCH_MAX_TERMS: usize = 16;
+
+/// Checks the details of a book against the constraints of the database,
+/// so that invalid input is rejected before anything is written.
+/// ISBN and ISSN are returned without hyphens and spaces.
+pub(crate) fn validate_book(mut book: BookDetails) -> RpcResult<BookDetails> {
+    book.isbn = book.isbn.as_deref().map(normalize);
+    book.issn = book.issn.as_deref().map(normalize);
+
+    let valid = book.code_identifier >= 0
+        && book.isbn.as_deref().map_or(true, is_valid_isbn)
+        && book.issn.as_deref().map_or(true, is_valid_issn)
+        && book.release_year >= 0
+        && book.pages.map_or(true, |pages| pages >= 1)
+        && book.edition.map_or(true, |edition| edition >= 1)
+        && is_valid_name(&book.title, TITLE_MAX_LENGTH)
+        && book.subtitle.as_ref().map_or(true, |subtitle| {
+            subtitle.chars().count() <= SUBTITLE_MAX_LENGTH
+        })
+        && is_distinct(&book.author_ids)
+        && is_distinct(&book.editor_ids)
+        && is_distinct(&book.subject_area_ids)
+        && is_distinct(&book.tag_ids);
+
+    if valid {
+        Ok(book)
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+/// Checks a category, publisher, subject area or tag name against the given maximum length
+pub(crate) fn validate_name(details: NameDetails, max_length: usize) -> RpcResult<NameDetails> {
+    if is_valid_name(&details.name, max_length) {
+        Ok(details)
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+pub(crate) fn validate_series(series: SeriesDetails) -> RpcResult<SeriesDetails> {
+    if is_valid_name(&series.name, NAME_MAX_LENGTH) {
+        Ok(series)
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+pub(crate) fn validate_series_patch(patch: SeriesPatch) -> RpcResult<SeriesPatch> {
+    if patch
+        .name
+        .as_deref()
+        .map_or(true, |name| is_valid_name(name, NAME_MAX_LENGTH))
+    {
+        Ok(patch)
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+/// Checks the details of a person, ISNI and ORCID iD are returned without hyphens and spaces
+pub(crate) fn validate_person(person: PersonDetails) -> RpcResult<PersonDetails> {
+    let patch = validate_person_patch(PersonPatch {
+        first_name: Some(person.first_name),
+        last_name: Some(person.last_name),
+        date_of_birth: Some(person.date_of_birth),
+        isni: Some(person.isni),
+        orcid: Some(person.orcid),
+        oclc: Some(person.oclc),
+    })?;
+
+    Ok(PersonDetails {
+        first_name: patch.first_name.unwrap_or_default(),
+        last_name: patch.last_name.unwrap_or_default(),
+        date_of_birth: patch.date_of_birth.flatten(),
+        isni: patch.isni.flatten(),
+        orcid: patch.orcid.flatten(),
+        oclc: patch.oclc.flatten(),
+    })
+}
+
+/// Checks the changes of a person, ISNI and ORCID iD are returned without hyphens and spaces
+pub(crate) fn validate_person_patch(mut patch: PersonPatch) -> RpcResult<PersonPatch> {
+    patch.isni = patch.isni.map(|isni| isni.as_deref().map(normalize));
+    patch.orcid = patch.orcid.map(|orcid| orcid.as_deref().map(normalize));
+
+    let valid = patch
+        .first_name
+        .as_deref()
+        .map_or(true, |name| is_valid_name(name, NAME_MAX_LENGTH))
+        && patch
+            .last_name
+            .as_deref()
+            .map_or(true, |name| is_valid_name(name, NAME_MAX_LENGTH))
+        && patch
+            .isni
+            .iter()
+            .flatten()
+            .all(|isni| is_valid_person_identifier(isni))
+        && patch
+            .orcid
+            .iter()
+            .flatten()
+            .all(|orcid| is_valid_person_identifier(orcid))
+        && patch.oclc.iter().flatten().all(|oclc| *oclc >= 1);
+
+    if valid {
+        Ok(patch)
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+/// Checks that the cursor of a page was issued for its order and carries a value of the
+/// right type for every sort field
+pub(crate) fn validate_keyset(page: &filters::KeysetPage) -> RpcResult<()> {
+    let valid = page.get_items() >= 1
+        && page.get_cursor().map_or(true, |cursor| {
+            cursor.get_sort() == page.get_sort()
+                && cursor.get_values().len() == page.get_sort().get_keys().len()
+                && page
+                    .get_sort()
+                    .get_keys()
+                    .iter()
+                    .zip(cursor.get_values())
+                    .all(|((field, _), value)| {
+                        matches!(
+                            (field, value),
+                            (filters::SortField::Title, filters::SortValue::Text(_))
+                                | (
+                                    filters::SortField::ReleaseYear,
+                                    filters::SortValue::Integer(_)
+                                )
+                                | (
+                                    filters::SortField::CreatedAt,
+                                    filters::SortValue::Timestamp(_)
+                                )
+                        )
+                    })
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput)
+    }
+}
+
+/// Parses the label `code_identifier-copy_id` of a copy
+pub(crate) fn parse_copy_label(label: &str) -> RpcResult<(i32, i32)> {
+    let number = |part: &str| {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        part.parse::<i32>().ok()
+    };
+
+    label
+        .split_once('-')
+        .and_then(|(code_identifier, copy_id)| Some((number(code_identifier)?, number(copy_id)?)))
+        .ok_or(Error::InvalidInput)
+}
+
+/// Turns free text into a text search query, which matches books containing every term,
+/// the last term as a prefix for type-ahead. Hyphens are dropped from numbers, like an ISBN,
+/// and other characters, which are not alphanumeric, separate terms.
+pub(crate) fn search_query(text: &str) -> RpcResult<String> {
+    if text.chars().count() > SEARCH_MAX_LENGTH {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut terms = vec![];
+    for word in text.split_whitespace() {
+        let is_number = word.chars().any(|c| c.is_ascii_digit())
+            && word
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '-' || c == 'X' || c == 'x');
+        if is_number {
+            terms.push(word.replace('-', "").to_lowercase());
+        } else {
+            terms.extend(
+                word.split(|c: char| !c.is_alphanumeric())
+                    .filter(|term| !term.is_empty())
+                    .map(|term| term.to_lowercase()),
+            );
+        }
+    }
+    if terms.is_empty() || terms.len() > SEARCH_MAX_TERMS {
+        return Err(Error::InvalidInput);
+    }
+
+    let last = terms.len() - 1;
+    Ok(terms
+        .iter()
+        .enumerate()
+        .map(|(i, term)| {
+            if i == last {
+                format!("{}:*", term)
+            } else {
+                term.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" & "))
+}
+
+fn is_valid_name(name: &str, max_length: usize) -> bool {
+    !name.trim().is_empty() && name.chars().count() <= max_length
+}
+
+fn normalize(number: &str) -> String {
+    number
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// ISBN-13 with the prefix 978 or 979 and a valid check digit
+fn is_valid_isbn(isbn: &str) -> bool {
+    let digits: Vec<u32> = isbn.chars().filter_map(|c| c.to_digit(10)).collect();
+    if isbn.len() != 13 || digits.len() != 13 {
+        return false;
+    }
+    if !(isbn.starts_with("978") || isbn.starts_with("979")) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| if i % 2 == 0 { *digit } else { digit * 3 })
+        .sum();
+    sum % 10 == 0
+}
+
+/// ISSN with a valid check digit, which may be `X` for 10
+fn is_valid_issn(issn: &str) -> bool {
+    let chars: Vec<char> = issn.chars().collect();
+    if chars.len() != 8 {
+        return false;
+    }
+
+    let mut sum = 0;
+    for (i, c) in chars.iter().enumerate() {
+        let value = match (i, c) {
+            (7, 'X') => 10,
+            (_, c) => match c.to_digit(10) {
+                Some(digit) => digit,
+                None => return false,
+            },
+        };
+        sum += value * (8 - i as u32);
+    }
+    sum % 11 == 0
+}
+
+/// ISNI or ORCID iD, which share the format of 16 characters with an ISO 7064 MOD 11-2
+/// check digit, which may be `X` for 10
+fn is_valid_person_identifier(identifier: &str) -> bool {
+    let chars: Vec<char> = identifier.chars().collect();
+    if chars.len() != 16 {
+        return false;
+    }
+
+    let mut total = 0;
+    for c in &chars[..15] {
+        match c.to_digit(10) {
+            Some(digit) => total = (total + digit) * 2,
+            None => return false,
+        }
+    }
+    match (12 - total % 11) % 11 {
+        10 => chars[15] == 'X',
+        check => chars[15].to_digit(10) == Some(check),
+    }
+}
+
+fn is_distinct(ids: &[Uuid]) -> bool {
+    ids.iter().collect::<HashSet<_>>().len() == ids.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> BookDetails {
+        BookDetails {
+            code_identifier: 1,
+            isbn: Some("978-3-16-148410-0".into()),
+            issn: Some("0317-8471".into()),
+            release_year: 2021,
+            edition: Some(1),
+            pages: Some(320),
+            title: "Title".into(),
+            subtitle: None,
+            description: None,
+            category_id: Uuid::new_v4(),
+            language_id: Uuid::new_v4(),
+            publisher_id: Uuid::new_v4(),
+            series_id: None,
+            author_ids: vec![Uuid::new_v4(), Uuid::new_v4()],
+            editor_ids: vec![],
+            subject_area_ids: vec![],
+            tag_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn ut_validate_book_valid() {
+        let result = validate_book(book()).unwrap();
+
+        assert_eq!(Some("9783161484100".into()), result.isbn);
+        assert_eq!(Some("03178471".into()), result.issn);
+    }
+
+    #[test]
+    fn ut_validate_book_invalid() {
+        let author_id = Uuid::new_v4();
+        let invalid_books = vec![
+            BookDetails {
+                isbn: Some("978-3-16-148410-1".into()),
+                ..book()
+            },
+            BookDetails {
+                isbn: Some("3-16-148410-X".into()),
+                ..book()
+            },
+            BookDetails {
+                issn: Some("0317-8472".into()),
+                ..book()
+            },
+            BookDetails {
+                release_year: -1,
+                ..book()
+            },
+            BookDetails {
+                pages: Some(0),
+                ..book()
+            },
+            BookDetails {
+                title: " ".into(),
+                ..book()
+            },
+            BookDetails {
+                author_ids: vec![author_id, author_id],
+                ..book()
+            },
+        ];
+
+        for invalid_book in invalid_books {
+            assert_eq!(Err(Error::InvalidInput), validate_book(invalid_book));
+        }
+    }
+
+    #[test]
+    fn ut_is_valid_isbn() {
+        assert!(is_valid_isbn("9780306406157"));
+        assert!(is_valid_isbn("9791032305690"));
+        assert!(!is_valid_isbn("9780306406158"));
+        assert!(!is_valid_isbn("0306406152"));
+        assert!(!is_valid_isbn("97803064061A7"));
+    }
+
+    #[test]
+    fn ut_is_valid_issn() {
+        assert!(is_valid_issn("03178471"));
+        assert!(is_valid_issn("2434561X"));
+        assert!(!is_valid_issn("03178472"));
+        assert!(!is_valid_issn("0317847"));
+    }
+
+    #[test]
+    fn ut_validate_name() {
+        let name = |name: &str| NameDetails { name: name.into() };
+
+        assert!(validate_name(name("Fantasy"), TAG_NAME_MAX_LENGTH).is_ok());
+        assert_eq!(
+            Err(Error::InvalidInput),
+            validate_name(name(""), NAME_MAX_LENGTH)
+        );
+        assert_eq!(
+            Err(Error::InvalidInput),
+            validate_name(name(&"a".repeat(51)), TAG_NAME_MAX_LENGTH)
+        );
+    }
+
+    #[test]
+    fn ut_validate_person() {
+        let person = PersonDetails {
+            first_name: "Josiah".into(),
+            last_name: "Carberry".into(),
+            date_of_birth: None,
+            isni: None,
+            orcid: Some("0000-0002-1825-0097".into()),
+            oclc: None,
+        };
+
+        let result = validate_person(person.clone()).unwrap();
+
+        assert_eq!(Some("0000000218250097".into()), result.orcid);
+        assert_eq!(
+            Err(Error::InvalidInput),
+            validate_person(PersonDetails {
+                last_name: "".into(),
+                ..person.clone()
+            })
+        );
+        assert_eq!(
+            Err(Error::InvalidInput),
+            validate_person(PersonDetails {
+                isni: Some("0000000218250098".into()),
+                ..person
+            })
+        );
+    }
+
+    #[test]
+    fn ut_validate_person_patch() {
+        let clear = PersonPatch {
+            isni: Some(None),
+            ..PersonPatch::default()
+        };
+        let invalid = PersonPatch {
+            first_name: Some(" ".into()),
+            ..PersonPatch::default()
+        };
+
+        assert_eq!(Ok(clear.clone()), validate_person_patch(clear));
+        assert_eq!(Err(Error::InvalidInput), validate_person_patch(invalid));
+    }
+
+    #[test]
+    fn ut_is_valid_person_identifier() {
+        assert!(is_valid_person_identifier("0000000218250097"));
+        assert!(is_valid_person_identifier("000000021694233X"));
+        assert!(!is_valid_person_identifier("0000000218250098"));
+        assert!(!is_valid_person_identifier("000000021825009"));
+    }
+
+    #[test]
+    fn ut_parse_copy_label() {
+        assert_eq!(Ok((4711, 3)), parse_copy_label("4711-3"));
+        assert_eq!(Ok((0, 1)), parse_copy_label("0-1"));
+
+        assert!(parse_copy_label("4711").is_err());
+        assert!(parse_copy_label("4711-").is_err());
+        assert!(parse_copy_label("-3").is_err());
+        assert!(parse_copy_label("4711-3-1").is_err());
+        assert!(parse_copy_label("+4711-3").is_err());
+        assert!(parse_copy_label("4711-99999999999").is_err());
+    }
+
+    #[test]
+    fn ut_validate_keyset() {
+        let sort = filters::Sort::new(vec![(
+            filters::SortField::ReleaseYear,
+            filters::Direction::Desc,
+        )]);
+        let page = |sort: filters::Sort, values: Vec<filters::SortValue>| {
+            filters::KeysetPage::new(
+                sort.clone(),
+                Some(filters::Keyset::new(
+                    filters::Position::After,
+                    sort,
+                    values,
+                    Uuid::new_v4(),
+                )),
+                filters::Items::default(),
+            )
+        };
+
+        assert!(validate_keyset(&filters::KeysetPage::default()).is_ok());
+        assert!(
+            validate_keyset(&page(sort.clone(), vec![filters::SortValue::Integer(2000)])).is_ok()
+        );
+        assert!(validate_keyset(&page(sort.clone(), vec![])).is_err());
+        assert!(
+            validate_keyset(&page(sort, vec![filters::SortValue::Text("2000".into())])).is_err()
+        );
+        assert!(validate_keyset(&filters::KeysetPage::new(
+            filters::Sort::default(),
+            page(filters::Sort::default(), vec![]).get_cursor().cloned(),
+            filters::Items::new(0),
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn ut_search_query() {
+        assert_eq!(Ok("librar:*".to_string()), search_query("Librar"));
+        assert_eq!(
+            Ok("o & brien & running & lib:*".to_string()),
+            search_query(" O'Brien  running lib")
+        );
+        assert_eq!(
+            Ok("978316148410x:*".to_string()),
+            search_query("978-3-16-148410-X")
+        );
+        assert_eq!(Ok("müller:*".to_string()), search_query("Müller"));
+
+        assert!(search_query("").is_err());
+        assert!(search_query(" - & | ! ").is_err());
+        assert!(search_query(&"a ".repeat(SEARCH_MAX_TERMS + 1)).is_err());
+        assert!(search_query(&"a".repeat(SEARCH_MAX_LENGTH + 1)).is_err());
+    }
+}
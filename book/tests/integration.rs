@@ -1 +1,436 @@
+use std::env::set_var;
+use std::time::{Duration, SystemTime};
 
+use sqlx::{Connection, Executor, PgConnection};
+use tarpc::context;
+use uuid::Uuid;
+
+use book::config::Configuration;
+use book::db::{init_db_pool, DbPool};
+use book::models::{BookDetails, CatalogueEntry, ImportFile, ImportFormat, ImportOptions};
+use book::service::BookServiceClient;
+use book::{init_rpc_client, init_rpc_server};
+use helpers::filters::{Items, Search};
+use helpers::rpc::Error;
+
+mod sample_data;
+
+/// Size limit of the files, which the API accepts for an import
+const IMPORT_MAX_SIZE: usize = 5 * 1024 * 1024;
+/// Size limit of the entries, which the API imports from a catalogue by one call
+const IMPORT_BATCH_SIZE: usize = 4 * 1024 * 1024;
+
+/// Database of a test, which is created with the migrations applied and dropped afterwards
+struct DbTestContext {
+    connection_url: String,
+    db_name: String,
+}
+
+impl DbTestContext {
+    async fn new(connection_url: String, db_name: String) -> Self {
+        // connect to database service and create new database
+        let mut conn = PgConnection::connect(&format!("{}/postgres", connection_url))
+            .await
+            .expect("Could not connect to database service");
+        conn.execute(format!("CREATE DATABASE \"{}\"", db_name).as_str())
+            .await
+            .unwrap_or_else(|_| panic!("Could not create database: {}", db_name));
+
+        // reconnect to the new database and run migration
+        let mut conn = PgConnection::connect(&format!("{}/{}", connection_url, db_name))
+            .await
+            .expect("Could not connect to database service");
+        let mut migrator = sqlx::migrate!();
+        migrator
+            .migrations
+            .to_mut()
+            .retain(|migration| !migration.migration_type.is_down_migration());
+        migrator
+            .run(&mut conn)
+            .await
+            .expect("Failed to apply database migration");
+
+        // insert sample data for tests
+        conn.execute(sample_data::BOOKS)
+            .await
+            .expect("Error inserting sample data");
+
+        Self {
+            connection_url,
+            db_name,
+        }
+    }
+
+    fn get_connection_url(&self) -> String {
+        format!("{}/{}", self.connection_url, self.db_name)
+    }
+}
+
+impl Drop for DbTestContext {
+    fn drop(&mut self) {
+        let connection_url = format!("{}/postgres", self.connection_url);
+        let db_name = self.db_name.clone();
+
+        // the database is dropped outside of the runtime of the test, which may be shutting down
+        std::thread::spawn(move || {
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(async move {
+                    let mut conn = PgConnection::connect(&connection_url)
+                        .await
+                        .expect("Could not connect to database service");
+                    conn.execute(
+                        format!(
+                            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}'",
+                            db_name
+                        )
+                        .as_str(),
+                    )
+                    .await
+                    .unwrap();
+                    conn.execute(format!("DROP DATABASE \"{}\"", db_name).as_str())
+                        .await
+                        .unwrap_or_else(|_| panic!("Could not drop database: {}", db_name));
+                })
+        })
+        .join()
+        .unwrap();
+    }
+}
+
+fn get_test_configuration() -> Configuration {
+    set_var("SERVICE_SOCKET", "127.0.0.1:0");
+
+    Configuration::init().unwrap()
+}
+
+async fn setup(test_context_name: &str) -> (BookServiceClient, DbPool, DbTestContext) {
+    let configuration = get_test_configuration();
+    let db_test_context = DbTestContext::new(
+        configuration.get_db_connection_base_url(),
+        format!("book_{}", test_context_name),
+    )
+    .await;
+    let db_pool = init_db_pool(&db_test_context.get_connection_url()).await;
+    let (server, addr) = init_rpc_server(&configuration.get_service_socket(), db_pool.clone())
+        .await
+        .unwrap();
+    tokio::spawn(server);
+    let client = init_rpc_client(&addr).await.unwrap();
+
+    (client, db_pool, db_test_context)
+}
+
+/// Context of a call, which sends a large request and may take longer than the default deadline
+fn large_request_context() -> context::Context {
+    let mut context = context::current();
+    context.deadline = SystemTime::now() + Duration::from_secs(120);
+    context
+}
+
+fn uuid(id: &str) -> Uuid {
+    Uuid::parse_str(id).unwrap()
+}
+
+fn book_details(isbn: &str) -> BookDetails {
+    BookDetails {
+        code_identifier: 2,
+        isbn: Some(isbn.into()),
+        issn: None,
+        release_year: 1984,
+        edition: None,
+        pages: None,
+        title: "Dune Messiah".into(),
+        subtitle: None,
+        description: None,
+        category_id: uuid(sample_data::CATEGORY),
+        language_id: uuid(sample_data::LANGUAGE_ENG),
+        publisher_id: uuid(sample_data::PUBLISHER),
+        series_id: None,
+        author_ids: vec![uuid(sample_data::PERSON_1)],
+        editor_ids: vec![uuid(sample_data::PERSON_2)],
+        subject_area_ids: vec![uuid(sample_data::SUBJECT_AREA)],
+        tag_ids: vec![uuid(sample_data::TAG)],
+    }
+}
+
+/// Number of rows of every join table, in the order authors, editors, subject areas and tags
+async fn count_links(db_pool: &DbPool, book_id: Uuid) -> Vec<i64> {
+    let mut counts = vec![];
+    for table in [
+        "books_authors",
+        "books_editors",
+        "books_subject_areas",
+        "books_tags",
+    ] {
+        let count: i64 = sqlx::query_scalar(&format!(
+            "SELECT count(*) FROM {} WHERE book_id = $1",
+            table
+        ))
+        .bind(book_id)
+        .fetch_one(db_pool)
+        .await
+        .unwrap();
+        counts.push(count);
+    }
+    counts
+}
+
+async fn search(client: &BookServiceClient, q: &str) -> Vec<String> {
+    client
+        .search_books(context::current(), Search::new(q.into(), Items::new(10)))
+        .await
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|book| book.title)
+        .collect()
+}
+
+async fn count_books(db_pool: &DbPool) -> i64 {
+    sqlx::query_scalar("SELECT count(*) FROM books")
+        .fetch_one(db_pool)
+        .await
+        .unwrap()
+}
+
+// create a book with its authors, editors, subject areas and tags
+#[tokio::test]
+async fn create_book_linked() {
+    // Arrange
+    let (client, db_pool, _db_test_context) = setup("create_book_linked").await;
+
+    // Act
+    let result = client
+        .create_book(context::current(), book_details("9780140449136"))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(vec![1, 1, 1, 1], count_links(&db_pool, result.id).await);
+}
+
+// create a book with the ISBN of another book
+#[tokio::test]
+async fn create_book_isbn_exists() {
+    // Arrange
+    let (client, db_pool, _db_test_context) = setup("create_book_isbn_exists").await;
+
+    // Act
+    let result = client
+        .create_book(context::current(), book_details(sample_data::BOOK_1_ISBN))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Some(Error::AlreadyExists), result.err());
+    assert_eq!(1, count_books(&db_pool).await);
+}
+
+// create a book with an author, which does not exist, which rolls back the book
+#[tokio::test]
+async fn create_book_author_not_exists() {
+    // Arrange
+    let (client, db_pool, _db_test_context) = setup("create_book_author_not_exists").await;
+    let mut book = book_details("9780140449136");
+    book.author_ids = vec![Uuid::new_v4()];
+
+    // Act
+    let result = client.create_book(context::current(), book).await.unwrap();
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(1, count_books(&db_pool).await);
+}
+
+// update a book, which replaces its authors, editors, subject areas and tags
+#[tokio::test]
+async fn update_book_linked() {
+    // Arrange
+    let (client, db_pool, _db_test_context) = setup("update_book_linked").await;
+    let mut book = book_details(sample_data::BOOK_1_ISBN);
+    book.author_ids = vec![uuid(sample_data::PERSON_2)];
+    book.tag_ids = vec![];
+
+    // Act
+    let result = client
+        .update_book(context::current(), uuid(sample_data::BOOK_1), book)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(uuid(sample_data::BOOK_1), result.id);
+    assert_eq!(vec![1, 1, 1, 0], count_links(&db_pool, result.id).await);
+    let author_id: Uuid =
+        sqlx::query_scalar("SELECT person_id FROM books_authors WHERE book_id = $1")
+            .bind(result.id)
+            .fetch_one(&db_pool)
+            .await
+            .unwrap();
+    assert_eq!(uuid(sample_data::PERSON_2), author_id);
+}
+
+// update a book, which does not exist
+#[tokio::test]
+async fn update_book_not_exists() {
+    // Arrange
+    let (client, _db_pool, _db_test_context) = setup("update_book_not_exists").await;
+
+    // Act
+    let result = client
+        .update_book(
+            context::current(),
+            Uuid::new_v4(),
+            book_details("9780140449136"),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Some(Error::NotFound), result.err());
+}
+
+// delete a book together with its join rows
+#[tokio::test]
+async fn delete_book_linked() {
+    // Arrange
+    let (client, db_pool, _db_test_context) = setup("delete_book_linked").await;
+
+    // Act
+    let result = client
+        .delete_book(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(0, count_books(&db_pool).await);
+    assert_eq!(
+        vec![0, 0, 0, 0],
+        count_links(&db_pool, uuid(sample_data::BOOK_1)).await
+    );
+}
+
+// delete a book, which does not exist
+#[tokio::test]
+async fn delete_book_not_exists() {
+    // Arrange
+    let (client, _db_pool, _db_test_context) = setup("delete_book_not_exists").await;
+
+    // Act
+    let result = client
+        .delete_book(context::current(), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// search books by a term, which is a stop word in another language than theirs
+#[tokio::test]
+async fn search_books_stop_word_of_other_language() {
+    // Arrange
+    let (client, db_pool, _db_test_context) =
+        setup("search_books_stop_word_of_other_language").await;
+    db_pool
+        .execute(sample_data::SEARCH_BOOKS)
+        .await
+        .expect("Error inserting sample data");
+
+    // Act
+    let die_hard = search(&client, "die hard").await;
+    let und_zeiten = search(&client, "und zeiten").await;
+
+    // Assert
+    assert_eq!(vec!["Die Hard"], die_hard);
+    assert_eq!(vec!["Harte Zeiten"], und_zeiten);
+}
+
+// import a file close to the size limit of the API
+#[tokio::test]
+async fn import_books_large_file() {
+    // Arrange
+    let (client, _db_pool, _db_test_context) = setup("import_books_large_file").await;
+
+    let record = r#"<?xml version="1.0" encoding="UTF-8"?>
+<collection xmlns="http://www.loc.gov/MARC21/slim">
+  <record>
+    <leader>00000nam a2200000 i 4500</leader>
+    <controlfield tag="008">190101s2019    xxu           000 0 eng d</controlfield>
+    <datafield tag="245" ind1="1" ind2="0"><subfield code="a">Data structures</subfield></datafield>
+    <datafield tag="260" ind1=" " ind2=" "><subfield code="b">Example Press,</subfield></datafield>
+  </record>
+"#;
+    let mut file = record.to_string();
+    file.push_str(&" ".repeat(IMPORT_MAX_SIZE - record.len() - 100));
+    file.push_str("</collection>");
+
+    // Act
+    let report = client
+        .import_books(
+            large_request_context(),
+            ImportFormat::Marcxml,
+            ImportFile(file.into_bytes()),
+            ImportOptions {
+                category_id: uuid(sample_data::CATEGORY),
+                code_identifier: 100,
+            },
+            true,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert!(report.dry_run);
+    assert_eq!(1, report.created);
+}
+
+// import a batch of a catalogue of the size, which the API sends by one call
+#[tokio::test]
+async fn import_catalogue_batch() {
+    // Arrange
+    let (client, _db_pool, _db_test_context) = setup("import_catalogue_batch").await;
+
+    let entries: Vec<CatalogueEntry> = (0..40)
+        .map(|index| CatalogueEntry {
+            id: Uuid::new_v4(),
+            code_identifier: 100 + index,
+            isbn: None,
+            issn: None,
+            release_year: 2021,
+            edition: None,
+            pages: None,
+            title: format!("Volume {}", index),
+            subtitle: None,
+            description: Some("A".repeat(IMPORT_BATCH_SIZE / 40 - 1024)),
+            category_id: uuid(sample_data::CATEGORY),
+            language_id: uuid(sample_data::LANGUAGE_ENG),
+            publisher_id: uuid(sample_data::PUBLISHER),
+            series_id: None,
+            author_ids: vec![],
+            authors: vec![],
+            editor_ids: vec![],
+            subject_area_ids: vec![],
+            tag_ids: vec![],
+            tags: vec![],
+            copies: 0,
+            created_at: None,
+        })
+        .collect();
+
+    // Act
+    let report = client
+        .import_catalogue(large_request_context(), entries, true)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert!(report.dry_run);
+    assert_eq!(40, report.created);
+    assert_eq!(0, report.conflicts);
+}
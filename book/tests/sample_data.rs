@@ -0,0 +1,43 @@
+//! Reference data and books for the tests, the languages are inserted by the migrations.
+
+pub const CATEGORY: &str = "3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01";
+pub const PUBLISHER: &str = "7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02";
+pub const LANGUAGE_ENG: &str = "01798a0f-d0d2-0995-1cf2-9dba653a91f1";
+pub const PERSON_1: &str = "5b8d0f2a-4c6e-4a1b-9d3f-5a7c9e1b3d03";
+pub const PERSON_2: &str = "5b8d0f2a-4c6e-4a1b-9d3f-5a7c9e1b3d04";
+pub const SUBJECT_AREA: &str = "9e1a3c5b-7d9f-4b2c-8e4a-6c8e0a2c4e05";
+pub const TAG: &str = "2d4f6a8c-0e2a-4c3d-9f5b-7d9f1b3d5f06";
+pub const BOOK_1: &str = "a1b2c3d4-0000-4000-8000-000000000001";
+pub const BOOK_1_ISBN: &str = "9780306406157";
+
+pub const BOOKS: &str = r#"
+INSERT INTO categories (id, name) VALUES
+    ('3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01', 'Fiction');
+
+INSERT INTO publishers (id, name) VALUES
+    ('7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02', 'Penguin');
+
+INSERT INTO persons (id, first_name, last_name) VALUES
+    ('5b8d0f2a-4c6e-4a1b-9d3f-5a7c9e1b3d03', 'Frank', 'Herbert'),
+    ('5b8d0f2a-4c6e-4a1b-9d3f-5a7c9e1b3d04', 'Brian', 'Herbert');
+
+INSERT INTO subject_areas (id, name) VALUES
+    ('9e1a3c5b-7d9f-4b2c-8e4a-6c8e0a2c4e05', 'Literature');
+
+INSERT INTO tags (id, name) VALUES
+    ('2d4f6a8c-0e2a-4c3d-9f5b-7d9f1b3d5f06', 'Classic');
+
+INSERT INTO books (id, code_identifier, isbn, release_year, title, category_id, language_id, publisher_id) VALUES
+    ('a1b2c3d4-0000-4000-8000-000000000001', 1, '9780306406157', 1965, 'Dune', '3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01', '01798a0f-d0d2-0995-1cf2-9dba653a91f1', '7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02');
+
+INSERT INTO books_authors (id, book_id, person_id) VALUES
+    ('c3e5a7b9-1d3f-4e6a-8b0c-2e4a6c8e0a07', 'a1b2c3d4-0000-4000-8000-000000000001', '5b8d0f2a-4c6e-4a1b-9d3f-5a7c9e1b3d03');
+"#;
+
+/// Books with titles of stop words in another language than their own, for the search tests
+pub const SEARCH_BOOKS: &str = r#"
+INSERT INTO books (id, code_identifier, release_year, title, category_id, language_id, publisher_id) VALUES
+    ('a1b2c3d4-0000-4000-8000-000000000002', 2, 1988, 'Die Hard', '3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01', '01798a0f-d0d2-0995-1cf2-9dba653a91f1', '7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02'),
+    ('a1b2c3d4-0000-4000-8000-000000000003', 3, 1854, 'Hard Times', '3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01', '01798a0f-d0d2-0995-1cf2-9dba653a91f1', '7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02'),
+    ('a1b2c3d4-0000-4000-8000-000000000004', 4, 1854, 'Harte Zeiten', '3f0a6c1e-8b2d-4e5f-9a7c-1d3e5f7a9b01', '01798a0f-d0d4-184a-e329-df304b958b71', '7c2e4a6b-1d3f-4b5a-8c9e-2f4a6c8e0d02');
+"#;
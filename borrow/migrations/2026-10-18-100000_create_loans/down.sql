@@ -0,0 +1,3 @@
+DROP INDEX loans_user_id_idx;
+DROP INDEX loans_copy_id_open_key;
+DROP TABLE loans;
@@ -0,0 +1,19 @@
+-- The referenced copies and users are owned by the BOOK and IDENTITY services,
+-- which migrate the shared database on their own schedule. Their existence is
+-- therefore checked by the service instead of with foreign key constraints.
+CREATE TABLE loans (
+    id UUID,
+    copy_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    borrowed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    due_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    returned_at TIMESTAMP WITH TIME ZONE,
+    renewals INTEGER NOT NULL DEFAULT 0 CHECK (renewals >= 0),
+    PRIMARY KEY (id),
+    CHECK (due_at > borrowed_at),
+    CHECK (returned_at IS NULL OR returned_at >= borrowed_at)
+);
+
+-- A copy can only be lent once at a time
+CREATE UNIQUE INDEX loans_copy_id_open_key ON loans (copy_id) WHERE returned_at IS NULL;
+CREATE INDEX loans_user_id_idx ON loans (user_id);
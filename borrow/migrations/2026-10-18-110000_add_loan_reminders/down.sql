@@ -0,0 +1,4 @@
+DROP INDEX loans_due_at_open_idx;
+
+ALTER TABLE loans DROP COLUMN reminded_at;
+ALTER TABLE loans DROP COLUMN overdue_at;
@@ -0,0 +1,6 @@
+-- overdue_at: when the loan was first detected as overdue
+-- reminded_at: when the last reminder for the current due date was sent
+ALTER TABLE loans ADD COLUMN overdue_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE loans ADD COLUMN reminded_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX loans_due_at_open_idx ON loans (due_at) WHERE returned_at IS NULL;
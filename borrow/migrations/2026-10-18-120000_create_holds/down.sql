@@ -0,0 +1,6 @@
+DROP INDEX holds_user_id_idx;
+DROP INDEX holds_book_id_placed_at_idx;
+DROP INDEX holds_copy_id_ready_key;
+DROP INDEX holds_book_id_user_id_active_key;
+
+DROP TABLE holds;
@@ -0,0 +1,23 @@
+-- A hold is WAITING in the queue of its book until a returned copy is assigned.
+-- It is then READY for pickup until pickup_until and ends FULFILLED, CANCELLED or EXPIRED.
+CREATE TABLE holds (
+    id UUID,
+    book_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'waiting'
+        CHECK (status IN ('waiting', 'ready', 'fulfilled', 'cancelled', 'expired')),
+    placed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    copy_id UUID,
+    pickup_until TIMESTAMP WITH TIME ZONE,
+    closed_at TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (id),
+    CHECK (status <> 'ready' OR (copy_id IS NOT NULL AND pickup_until IS NOT NULL)),
+    CHECK ((status IN ('waiting', 'ready')) = (closed_at IS NULL))
+);
+
+-- An user can only queue once per book and a copy can only be held for one user
+CREATE UNIQUE INDEX holds_book_id_user_id_active_key ON holds (book_id, user_id)
+    WHERE status IN ('waiting', 'ready');
+CREATE UNIQUE INDEX holds_copy_id_ready_key ON holds (copy_id) WHERE status = 'ready';
+CREATE INDEX holds_book_id_placed_at_idx ON holds (book_id, placed_at);
+CREATE INDEX holds_user_id_idx ON holds (user_id);
@@ -0,0 +1,16 @@
+-- Borrowing rules for the roles of the IDENTITY service, matched by role name.
+-- A NULL limit does not restrict, a NULL loan_duration uses the configured LOAN_DURATION.
+-- Roles without a policy are not restricted either.
+CREATE TABLE policies (
+    role_name VARCHAR(50),
+    loan_duration INTEGER CHECK (loan_duration > 0),
+    max_open_loans INTEGER CHECK (max_open_loans >= 0),
+    max_renewals INTEGER CHECK (max_renewals >= 0),
+    holds_allowed BOOLEAN NOT NULL DEFAULT TRUE,
+    PRIMARY KEY (role_name)
+);
+
+INSERT INTO policies (role_name, loan_duration, max_open_loans, max_renewals, holds_allowed) VALUES
+    ('User', NULL, 5, 2, TRUE),
+    ('Manager', NULL, 10, 5, TRUE),
+    ('Administrator', NULL, 10, 5, TRUE);
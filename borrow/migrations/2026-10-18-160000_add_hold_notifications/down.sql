@@ -0,0 +1 @@
+ALTER TABLE holds DROP COLUMN notified_at;
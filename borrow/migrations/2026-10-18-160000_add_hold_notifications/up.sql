@@ -0,0 +1,2 @@
+-- Set once the holder was told that the hold is ready for pickup
+ALTER TABLE holds ADD COLUMN notified_at TIMESTAMP WITH TIME ZONE;
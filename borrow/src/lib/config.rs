@@ -1,3 +1,4 @@
+use chrono::Duration;
 use dotenv::dotenv;
 
 use std::{
@@ -5,6 +6,7 @@ use std::{
     fmt,
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
     str::FromStr,
+    time::Duration as StdDuration,
 };
 
 #[derive(Debug, PartialEq)]
@@ -14,6 +16,12 @@ pub struct Configuration {
     db_name: String,
     db_username: String,
     db_password: String,
+    loan_duration: Duration,
+    notification_socket: SocketAddr,
+    scheduler_interval: StdDuration,
+    due_reminder_lead_time: Duration,
+    overdue_reminder_interval: Duration,
+    hold_pickup_duration: Duration,
 }
 
 #[derive(Debug, PartialEq)]
@@ -38,6 +46,7 @@ impl fmt::Display for ConfigurationError {
 pub enum ErrorKind {
     EnvVarNotSet,
     EnvVarValueInvalid,
+    EnvVarValueRequired,
     SocketAddrInvalid,
 }
 
@@ -46,6 +55,7 @@ impl fmt::Display for ErrorKind {
         match self {
             ErrorKind::EnvVarNotSet => write!(f, "EnvVarNotSet"),
             ErrorKind::EnvVarValueInvalid => write!(f, "EnvVarValueInvalid"),
+            ErrorKind::EnvVarValueRequired => write!(f, "EnvVarValueRequired"),
             ErrorKind::SocketAddrInvalid => write!(f, "SocketAddrInvalid"),
         }
     }
@@ -68,6 +78,12 @@ impl Configuration {
             db_name: Configuration::init_db_name()?,
             db_username: Configuration::init_db_username()?,
             db_password: Configuration::init_db_password()?,
+            loan_duration: Configuration::init_loan_duration()?,
+            notification_socket: Configuration::init_notification_socket()?,
+            scheduler_interval: Configuration::init_scheduler_interval()?,
+            due_reminder_lead_time: Configuration::init_due_reminder_lead_time()?,
+            overdue_reminder_interval: Configuration::init_overdue_reminder_interval()?,
+            hold_pickup_duration: Configuration::init_hold_pickup_duration()?,
         })
     }
 
@@ -153,6 +169,135 @@ impl Configuration {
         }
     }
 
+    fn init_loan_duration() -> Result<Duration, ConfigurationError> {
+        let key = "LOAN_DURATION";
+        match var(key) {
+            Ok(days) => match days.parse::<u16>() {
+                Ok(days) if days > 0 => Ok(Duration::days(days.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::days(28))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_notification_socket() -> Result<SocketAddr, ConfigurationError> {
+        let key = "NOTIFICATION_SOCKET";
+        match var(key) {
+            Ok(socket) => {
+                let sockets = socket
+                    .to_socket_addrs()
+                    .map_err(|_| ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid))?
+                    .filter(|socket| socket.is_ipv4())
+                    .collect::<Vec<SocketAddr>>();
+                Ok(*sockets.get(0).ok_or_else(|| {
+                    ConfigurationError::new(key.into(), ErrorKind::SocketAddrInvalid)
+                })?)
+            }
+            Err(VarError::NotPresent) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueRequired,
+            )),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_scheduler_interval() -> Result<StdDuration, ConfigurationError> {
+        let key = "SCHEDULER_INTERVAL";
+        match var(key) {
+            Ok(seconds) => match seconds.parse::<u32>() {
+                Ok(seconds) if seconds > 0 => Ok(StdDuration::from_secs(seconds.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(StdDuration::from_secs(3600))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_due_reminder_lead_time() -> Result<Duration, ConfigurationError> {
+        let key = "DUE_REMINDER_LEAD_TIME";
+        match var(key) {
+            Ok(days) => match days.parse::<u16>() {
+                Ok(days) => Ok(Duration::days(days.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::days(3))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_overdue_reminder_interval() -> Result<Duration, ConfigurationError> {
+        let key = "OVERDUE_REMINDER_INTERVAL";
+        match var(key) {
+            Ok(days) => match days.parse::<u16>() {
+                Ok(days) if days > 0 => Ok(Duration::days(days.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::days(7))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_hold_pickup_duration() -> Result<Duration, ConfigurationError> {
+        let key = "HOLD_PICKUP_DURATION";
+        match var(key) {
+            Ok(days) => match days.parse::<u16>() {
+                Ok(days) if days > 0 => Ok(Duration::days(days.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::days(7))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
     pub fn get_service_socket(&self) -> SocketAddr {
         self.service_socket
     }
@@ -167,6 +312,40 @@ impl Configuration {
             self.db_name
         )
     }
+
+    pub fn get_db_connection_base_url(&self) -> String {
+        format!(
+            "postgres://{}:{}@{}:{}",
+            self.db_username,
+            self.db_password,
+            self.db_socket.ip(),
+            self.db_socket.port()
+        )
+    }
+
+    pub fn get_loan_duration(&self) -> Duration {
+        self.loan_duration
+    }
+
+    pub fn get_notification_socket(&self) -> SocketAddr {
+        self.notification_socket
+    }
+
+    pub fn get_scheduler_interval(&self) -> StdDuration {
+        self.scheduler_interval
+    }
+
+    pub fn get_due_reminder_lead_time(&self) -> Duration {
+        self.due_reminder_lead_time
+    }
+
+    pub fn get_overdue_reminder_interval(&self) -> Duration {
+        self.overdue_reminder_interval
+    }
+
+    pub fn get_hold_pickup_duration(&self) -> Duration {
+        self.hold_pickup_duration
+    }
 }
 
 pub fn get_configuration() -> Configuration {
@@ -187,6 +366,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -194,6 +379,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -208,6 +399,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000),
@@ -215,6 +412,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -229,6 +432,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result =
             ConfigurationError::new("SERVICE_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -245,6 +454,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -252,6 +467,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -266,6 +487,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -273,6 +500,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -287,6 +520,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result =
             ConfigurationError::new("DB_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -303,6 +542,12 @@ mod tests {
         remove_var("DB_NAME");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -310,6 +555,12 @@ mod tests {
             db_name: "postgres".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -324,6 +575,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -331,6 +588,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -345,6 +608,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         remove_var("DB_USERNAME");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -352,6 +621,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "postgres".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -366,6 +641,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -373,6 +654,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -387,6 +674,12 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         remove_var("DB_PASSWORD");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -394,6 +687,12 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
@@ -408,6 +707,177 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_loan_duration_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("LOAN_DURATION", "14");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(14),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_loan_duration_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("LOAN_DURATION", "0");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result =
+            ConfigurationError::new("LOAN_DURATION".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_missing() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        remove_var("NOTIFICATION_SOCKET");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result =
+            ConfigurationError::new("NOTIFICATION_SOCKET".into(), ErrorKind::EnvVarValueRequired);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_input_valid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:9000");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 9000),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_notification_socket_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result =
+            ConfigurationError::new("NOTIFICATION_SOCKET".into(), ErrorKind::SocketAddrInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_scheduler_interval_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        set_var("SCHEDULER_INTERVAL", "60");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -415,9 +885,206 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(60),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
         };
         let result = Configuration::init();
 
         assert_eq!(Ok(expected_result), result)
     }
+
+    #[test]
+    #[ignore]
+    fn uts_scheduler_interval_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        set_var("SCHEDULER_INTERVAL", "0");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result =
+            ConfigurationError::new("SCHEDULER_INTERVAL".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_due_reminder_lead_time_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        set_var("DUE_REMINDER_LEAD_TIME", "0");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(0),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(7),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_due_reminder_lead_time_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        set_var("DUE_REMINDER_LEAD_TIME", "-1");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = ConfigurationError::new(
+            "DUE_REMINDER_LEAD_TIME".into(),
+            ErrorKind::EnvVarValueInvalid,
+        );
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_overdue_reminder_interval_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        set_var("OVERDUE_REMINDER_INTERVAL", "14");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(14),
+            hold_pickup_duration: Duration::days(7),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_overdue_reminder_interval_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        set_var("OVERDUE_REMINDER_INTERVAL", "0");
+        remove_var("HOLD_PICKUP_DURATION");
+
+        let expected_result = ConfigurationError::new(
+            "OVERDUE_REMINDER_INTERVAL".into(),
+            ErrorKind::EnvVarValueInvalid,
+        );
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_hold_pickup_duration_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        set_var("HOLD_PICKUP_DURATION", "3");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            loan_duration: Duration::days(28),
+            notification_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            scheduler_interval: StdDuration::from_secs(3600),
+            due_reminder_lead_time: Duration::days(3),
+            overdue_reminder_interval: Duration::days(7),
+            hold_pickup_duration: Duration::days(3),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_hold_pickup_duration_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("LOAN_DURATION");
+        set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+        remove_var("SCHEDULER_INTERVAL");
+        remove_var("DUE_REMINDER_LEAD_TIME");
+        remove_var("OVERDUE_REMINDER_INTERVAL");
+        set_var("HOLD_PICKUP_DURATION", "0");
+
+        let expected_result =
+            ConfigurationError::new("HOLD_PICKUP_DURATION".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
 }
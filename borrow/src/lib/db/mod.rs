@@ -1 +1,5 @@
+pub mod models;
+pub mod queries;
+pub mod schema;
+
 pub use helpers::db::*;
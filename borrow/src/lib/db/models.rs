@@ -0,0 +1,128 @@
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::schema::*;
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Loan {
+    pub id: Uuid,
+    pub copy_id: Uuid,
+    pub user_id: Uuid,
+    pub borrowed_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub renewals: i32,
+    pub overdue_at: Option<DateTime<Utc>>,
+    pub reminded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "loans"]
+pub struct LoanAdd {
+    pub id: Uuid,
+    pub copy_id: Uuid,
+    pub user_id: Uuid,
+    pub borrowed_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct CopyStatus {
+    pub id: Uuid,
+    pub book_id: Uuid,
+    pub copy_id: i32,
+    pub due_at: Option<DateTime<Utc>>,
+    pub pickup_until: Option<DateTime<Utc>>,
+    pub retired_at: Option<DateTime<Utc>>,
+    pub retirement_reason: Option<String>,
+}
+
+impl CopyStatus {
+    /// Whether the copy is neither retired, lent nor held for pickup
+    pub fn is_available(&self) -> bool {
+        self.retired_at.is_none() && self.due_at.is_none() && self.pickup_until.is_none()
+    }
+}
+
+#[derive(AsExpression, Clone, Copy, Debug, Deserialize, FromSqlRow, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[sql_type = "Text"]
+pub enum HoldStatus {
+    Waiting,
+    Ready,
+    Fulfilled,
+    Cancelled,
+    Expired,
+}
+
+impl ToSql<Text, Pg> for HoldStatus {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let status: &[u8] = match self {
+            HoldStatus::Waiting => b"waiting",
+            HoldStatus::Ready => b"ready",
+            HoldStatus::Fulfilled => b"fulfilled",
+            HoldStatus::Cancelled => b"cancelled",
+            HoldStatus::Expired => b"expired",
+        };
+        out.write_all(status)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for HoldStatus {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"waiting" => Ok(HoldStatus::Waiting),
+            b"ready" => Ok(HoldStatus::Ready),
+            b"fulfilled" => Ok(HoldStatus::Fulfilled),
+            b"cancelled" => Ok(HoldStatus::Cancelled),
+            b"expired" => Ok(HoldStatus::Expired),
+            _ => Err("Unrecognized hold status".into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Hold {
+    pub id: Uuid,
+    pub book_id: Uuid,
+    pub user_id: Uuid,
+    pub status: HoldStatus,
+    pub placed_at: DateTime<Utc>,
+    pub copy_id: Option<Uuid>,
+    pub pickup_until: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "holds"]
+pub struct HoldAdd {
+    pub id: Uuid,
+    pub book_id: Uuid,
+    pub user_id: Uuid,
+    pub placed_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Policy {
+    pub role_name: String,
+    pub loan_duration: Option<i32>,
+    pub max_open_loans: Option<i32>,
+    pub max_renewals: Option<i32>,
+    pub holds_allowed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Borrower {
+    pub id: Uuid,
+    pub active: bool,
+    pub policy: Option<Policy>,
+}
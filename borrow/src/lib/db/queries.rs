@@ -0,0 +1,331 @@
+use chrono::{DateTime, Utc};
+use diesel::dsl::exists;
+use diesel::prelude::*;
+use diesel::result::QueryResult;
+use uuid::Uuid;
+
+use super::models::*;
+use super::schema;
+use super::DbConn;
+
+pub fn create_loan(loan: LoanAdd, db: &DbConn) -> QueryResult<Loan> {
+    use schema::loans::dsl::loans;
+
+    diesel::insert_into(loans).values(&loan).get_result(db)
+}
+
+pub fn get_loan(loan_id: Uuid, db: &DbConn) -> QueryResult<Loan> {
+    use schema::loans::dsl::loans;
+
+    loans.find(loan_id).first(db)
+}
+
+pub fn list_open_loans_by_user(user_id: Uuid, db: &DbConn) -> QueryResult<Vec<Loan>> {
+    use schema::loans::dsl;
+
+    dsl::loans
+        .filter(dsl::user_id.eq(user_id))
+        .filter(dsl::returned_at.is_null())
+        .order(dsl::due_at.asc())
+        .load(db)
+}
+
+pub fn close_loan_by_copy(
+    copy_id: Uuid,
+    returned_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Loan> {
+    use schema::loans::dsl;
+
+    diesel::update(
+        dsl::loans
+            .filter(dsl::copy_id.eq(copy_id))
+            .filter(dsl::returned_at.is_null()),
+    )
+    .set(dsl::returned_at.eq(returned_at))
+    .get_result(db)
+}
+
+pub fn extend_loan(loan_id: Uuid, due_at: DateTime<Utc>, db: &DbConn) -> QueryResult<Loan> {
+    use schema::loans::dsl;
+
+    diesel::update(dsl::loans.find(loan_id).filter(dsl::returned_at.is_null()))
+        .set((
+            dsl::due_at.eq(due_at),
+            dsl::renewals.eq(dsl::renewals + 1),
+            dsl::overdue_at.eq(None::<DateTime<Utc>>),
+            dsl::reminded_at.eq(None::<DateTime<Utc>>),
+        ))
+        .get_result(db)
+}
+
+pub fn list_open_loans_due_before(
+    due_before: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Vec<Loan>> {
+    use schema::loans::dsl;
+
+    dsl::loans
+        .filter(dsl::due_at.le(due_before))
+        .filter(dsl::returned_at.is_null())
+        .order(dsl::due_at.asc())
+        .load(db)
+}
+
+pub fn mark_loan_reminded(
+    loan_id: Uuid,
+    reminded_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Loan> {
+    use schema::loans::dsl;
+
+    diesel::update(dsl::loans.find(loan_id).filter(dsl::returned_at.is_null()))
+        .set(dsl::reminded_at.eq(reminded_at))
+        .get_result(db)
+}
+
+pub fn mark_loan_overdue(
+    loan_id: Uuid,
+    overdue_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Loan> {
+    use schema::loans::dsl;
+
+    diesel::update(
+        dsl::loans
+            .find(loan_id)
+            .filter(dsl::returned_at.is_null())
+            .filter(dsl::overdue_at.is_null()),
+    )
+    .set(dsl::overdue_at.eq(overdue_at))
+    .get_result(db)
+}
+
+pub fn list_copy_status_by_book(book_id: Uuid, db: &DbConn) -> QueryResult<Vec<CopyStatus>> {
+    use schema::{copies, holds, loans};
+
+    copies::table
+        .left_join(
+            loans::table.on(loans::copy_id
+                .eq(copies::id)
+                .and(loans::returned_at.is_null())),
+        )
+        .left_join(
+            holds::table.on(holds::copy_id
+                .eq(copies::id.nullable())
+                .and(holds::status.eq(HoldStatus::Ready))),
+        )
+        .filter(copies::book_id.eq(book_id))
+        .select((
+            copies::id,
+            copies::book_id,
+            copies::copy_id,
+            loans::due_at.nullable(),
+            holds::pickup_until.nullable(),
+            copies::retired_at,
+            copies::retirement_reason,
+        ))
+        .order(copies::copy_id.asc())
+        .load(db)
+}
+
+/// Whether a copy exists and is not retired, so that it can be lent
+pub fn copy_exists(copy_id: Uuid, db: &DbConn) -> QueryResult<bool> {
+    use schema::copies::dsl;
+
+    diesel::select(exists(
+        dsl::copies.find(copy_id).filter(dsl::retired_at.is_null()),
+    ))
+    .get_result(db)
+}
+
+pub fn get_copy_book_id(copy_id: Uuid, db: &DbConn) -> QueryResult<Uuid> {
+    use schema::copies::dsl;
+
+    dsl::copies.find(copy_id).select(dsl::book_id).first(db)
+}
+
+pub fn get_borrower(user_id: Uuid, db: &DbConn) -> QueryResult<Borrower> {
+    use schema::{policies, roles, users};
+
+    users::table
+        .inner_join(roles::table)
+        .left_join(policies::table.on(policies::role_name.eq(roles::name)))
+        .filter(users::id.eq(user_id))
+        .select((users::id, users::active, policies::all_columns.nullable()))
+        .first(db)
+}
+
+/// Locks the row of an user until the end of the transaction
+pub fn lock_user(user_id: Uuid, db: &DbConn) -> QueryResult<Uuid> {
+    use schema::users::dsl;
+
+    dsl::users
+        .find(user_id)
+        .select(dsl::id)
+        .for_update()
+        .get_result(db)
+}
+
+pub fn count_open_loans_by_user(user_id: Uuid, db: &DbConn) -> QueryResult<i64> {
+    use schema::loans::dsl;
+
+    dsl::loans
+        .filter(dsl::user_id.eq(user_id))
+        .filter(dsl::returned_at.is_null())
+        .count()
+        .get_result(db)
+}
+
+pub fn create_hold(hold: HoldAdd, db: &DbConn) -> QueryResult<Hold> {
+    use schema::holds::dsl::holds;
+
+    diesel::insert_into(holds).values(&hold).get_result(db)
+}
+
+pub fn get_hold(hold_id: Uuid, db: &DbConn) -> QueryResult<Hold> {
+    use schema::holds::dsl::holds;
+
+    holds.find(hold_id).first(db)
+}
+
+pub fn list_active_holds_by_book(book_id: Uuid, db: &DbConn) -> QueryResult<Vec<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::book_id.eq(book_id))
+        .filter(dsl::status.eq_any(vec![HoldStatus::Waiting, HoldStatus::Ready]))
+        .order(dsl::placed_at.asc())
+        .load(db)
+}
+
+pub fn list_active_holds_by_user(user_id: Uuid, db: &DbConn) -> QueryResult<Vec<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::user_id.eq(user_id))
+        .filter(dsl::status.eq_any(vec![HoldStatus::Waiting, HoldStatus::Ready]))
+        .order(dsl::placed_at.asc())
+        .load(db)
+}
+
+pub fn get_active_hold_by_user(
+    book_id: Uuid,
+    user_id: Uuid,
+    db: &DbConn,
+) -> QueryResult<Option<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::book_id.eq(book_id))
+        .filter(dsl::user_id.eq(user_id))
+        .filter(dsl::status.eq_any(vec![HoldStatus::Waiting, HoldStatus::Ready]))
+        .first(db)
+        .optional()
+}
+
+/// Returns the books, for which users are waiting in the queue
+pub fn list_books_with_waiting_holds(db: &DbConn) -> QueryResult<Vec<Uuid>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::status.eq(HoldStatus::Waiting))
+        .select(dsl::book_id)
+        .distinct()
+        .load(db)
+}
+
+pub fn get_ready_hold_by_copy(copy_id: Uuid, db: &DbConn) -> QueryResult<Option<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::copy_id.eq(copy_id))
+        .filter(dsl::status.eq(HoldStatus::Ready))
+        .first(db)
+        .optional()
+}
+
+pub fn list_ready_holds_picked_up_before(
+    pickup_until: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Vec<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::status.eq(HoldStatus::Ready))
+        .filter(dsl::pickup_until.le(pickup_until))
+        .order(dsl::pickup_until.asc())
+        .load(db)
+}
+
+pub fn list_ready_holds_not_notified(db: &DbConn) -> QueryResult<Vec<Hold>> {
+    use schema::holds::dsl;
+
+    dsl::holds
+        .filter(dsl::status.eq(HoldStatus::Ready))
+        .filter(dsl::notified_at.is_null())
+        .order(dsl::pickup_until.asc())
+        .load(db)
+}
+
+pub fn mark_hold_notified(
+    hold_id: Uuid,
+    notified_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Hold> {
+    use schema::holds::dsl;
+
+    diesel::update(dsl::holds.find(hold_id))
+        .set(dsl::notified_at.eq(notified_at))
+        .get_result(db)
+}
+
+/// Closes a waiting or ready hold with the given final status
+pub fn close_hold(
+    hold_id: Uuid,
+    status: HoldStatus,
+    closed_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Hold> {
+    use schema::holds::dsl;
+
+    diesel::update(
+        dsl::holds
+            .find(hold_id)
+            .filter(dsl::status.eq_any(vec![HoldStatus::Waiting, HoldStatus::Ready])),
+    )
+    .set((dsl::status.eq(status), dsl::closed_at.eq(closed_at)))
+    .get_result(db)
+}
+
+/// Assigns a copy to the longest waiting hold of a book, if there is any
+pub fn assign_copy_to_first_hold(
+    book_id: Uuid,
+    copy_id: Uuid,
+    pickup_until: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Option<Hold>> {
+    use schema::holds::dsl;
+
+    let hold_id = dsl::holds
+        .filter(dsl::book_id.eq(book_id))
+        .filter(dsl::status.eq(HoldStatus::Waiting))
+        .order(dsl::placed_at.asc())
+        .select(dsl::id)
+        .for_update()
+        .skip_locked()
+        .first::<Uuid>(db)
+        .optional()?;
+
+    match hold_id {
+        Some(hold_id) => diesel::update(dsl::holds.find(hold_id))
+            .set((
+                dsl::status.eq(HoldStatus::Ready),
+                dsl::copy_id.eq(copy_id),
+                dsl::pickup_until.eq(pickup_until),
+            ))
+            .get_result(db)
+            .map(Some),
+        None => Ok(None),
+    }
+}
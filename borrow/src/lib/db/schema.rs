@@ -0,0 +1,71 @@
+table! {
+    loans (id) {
+        id -> Uuid,
+        copy_id -> Uuid,
+        user_id -> Uuid,
+        borrowed_at -> Timestamptz,
+        due_at -> Timestamptz,
+        returned_at -> Nullable<Timestamptz>,
+        renewals -> Int4,
+        overdue_at -> Nullable<Timestamptz>,
+        reminded_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    holds (id) {
+        id -> Uuid,
+        book_id -> Uuid,
+        user_id -> Uuid,
+        status -> Varchar,
+        placed_at -> Timestamptz,
+        copy_id -> Nullable<Uuid>,
+        pickup_until -> Nullable<Timestamptz>,
+        closed_at -> Nullable<Timestamptz>,
+        notified_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    policies (role_name) {
+        role_name -> Varchar,
+        loan_duration -> Nullable<Int4>,
+        max_open_loans -> Nullable<Int4>,
+        max_renewals -> Nullable<Int4>,
+        holds_allowed -> Bool,
+    }
+}
+
+// Owned by the BOOK service, only read by this service
+table! {
+    copies (id) {
+        id -> Uuid,
+        book_id -> Uuid,
+        copy_id -> Int4,
+        created_at -> Timestamptz,
+        created_by -> Uuid,
+        retired_at -> Nullable<Timestamptz>,
+        retirement_reason -> Nullable<Varchar>,
+    }
+}
+
+// Owned by the IDENTITY service, only read by this service
+table! {
+    roles (id) {
+        id -> Uuid,
+        name -> Varchar,
+    }
+}
+
+// Owned by the IDENTITY service, only read by this service
+table! {
+    users (id) {
+        id -> Uuid,
+        active -> Bool,
+        role_id -> Uuid,
+    }
+}
+
+joinable!(users -> roles (role_id));
+
+allow_tables_to_appear_in_same_query!(copies, holds, loans, policies, roles, users,);
@@ -1,5 +1,8 @@
 pub mod config;
 pub mod db;
+pub mod policy;
 pub mod rpc;
+pub mod scheduler;
 
+#[macro_use]
 extern crate diesel;
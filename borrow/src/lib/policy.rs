@@ -0,0 +1,120 @@
+use chrono::Duration;
+
+use helpers::rpc::{Error, RpcResult};
+
+use crate::config::Configuration;
+use crate::db::models::{Borrower, Policy as PolicyRow};
+
+/// Borrowing rules applying to an user, as defined for the role of the user
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    pub loan_duration: Duration,
+    pub max_open_loans: Option<i64>,
+    pub max_renewals: Option<i32>,
+    pub holds_allowed: bool,
+}
+
+impl Policy {
+    /// Roles without a policy, or without a loan duration, fall back to the configured loan duration
+    pub fn new(policy: Option<PolicyRow>, conf: &Configuration) -> Self {
+        match policy {
+            Some(policy) => Self {
+                loan_duration: policy
+                    .loan_duration
+                    .map(|days| Duration::days(days.into()))
+                    .unwrap_or_else(|| conf.get_loan_duration()),
+                max_open_loans: policy.max_open_loans.map(i64::from),
+                max_renewals: policy.max_renewals,
+                holds_allowed: policy.holds_allowed,
+            },
+            None => Self {
+                loan_duration: conf.get_loan_duration(),
+                max_open_loans: None,
+                max_renewals: None,
+                holds_allowed: true,
+            },
+        }
+    }
+
+    /// Returns the policy of an active user, inactive users are not allowed to borrow
+    pub fn of_borrower(borrower: Borrower, conf: &Configuration) -> RpcResult<Self> {
+        if !borrower.active {
+            log::info!("Rejected request of inactive user '{}'", borrower.id);
+            return Err(Error::InvalidInput);
+        }
+
+        Ok(Self::new(borrower.policy, conf))
+    }
+
+    pub fn check_open_loans(&self, open_loans: i64) -> RpcResult<()> {
+        match self.max_open_loans {
+            Some(max_open_loans) if open_loans >= max_open_loans => Err(Error::PolicyViolation),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_renewals(&self, renewals: i32) -> RpcResult<()> {
+        match self.max_renewals {
+            Some(max_renewals) if renewals >= max_renewals => Err(Error::PolicyViolation),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_holds(&self) -> RpcResult<()> {
+        match self.holds_allowed {
+            true => Ok(()),
+            false => Err(Error::PolicyViolation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(
+        max_open_loans: Option<i64>,
+        max_renewals: Option<i32>,
+        holds_allowed: bool,
+    ) -> Policy {
+        Policy {
+            loan_duration: Duration::days(28),
+            max_open_loans,
+            max_renewals,
+            holds_allowed,
+        }
+    }
+
+    #[test]
+    fn uts_check_open_loans() {
+        assert_eq!(Ok(()), policy(Some(2), None, true).check_open_loans(1));
+        assert_eq!(
+            Err(Error::PolicyViolation),
+            policy(Some(2), None, true).check_open_loans(2)
+        );
+        assert_eq!(Ok(()), policy(None, None, true).check_open_loans(100));
+    }
+
+    #[test]
+    fn uts_check_renewals() {
+        assert_eq!(Ok(()), policy(None, Some(1), true).check_renewals(0));
+        assert_eq!(
+            Err(Error::PolicyViolation),
+            policy(None, Some(1), true).check_renewals(1)
+        );
+        assert_eq!(
+            Err(Error::PolicyViolation),
+            policy(None, Some(0), true).check_renewals(0)
+        );
+        assert_eq!(Ok(()), policy(None, None, true).check_renewals(100));
+    }
+
+    #[test]
+    fn uts_check_holds() {
+        assert_eq!(Ok(()), policy(None, None, true).check_holds());
+        assert_eq!(
+            Err(Error::PolicyViolation),
+            policy(None, None, false).check_holds()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod models;
 pub mod server;
 pub mod service;
 
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use crate::db::models::HoldStatus;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Loan {
+    pub id: Uuid,
+    pub copy_id: Uuid,
+    pub user_id: Uuid,
+    pub borrowed_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub renewals: i32,
+    pub overdue_at: Option<DateTime<Utc>>,
+}
+
+impl From<crate::db::models::Loan> for Loan {
+    fn from(loan: crate::db::models::Loan) -> Self {
+        Loan {
+            id: loan.id,
+            copy_id: loan.copy_id,
+            user_id: loan.user_id,
+            borrowed_at: loan.borrowed_at,
+            due_at: loan.due_at,
+            returned_at: loan.returned_at,
+            renewals: loan.renewals,
+            overdue_at: loan.overdue_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "state")]
+pub enum CopyState {
+    Available,
+    OnLoan {
+        due_at: DateTime<Utc>,
+    },
+    Reserved {
+        pickup_until: DateTime<Utc>,
+    },
+    Lost {
+        retired_at: DateTime<Utc>,
+    },
+    /// Damaged or withdrawn from the collection
+    Retired {
+        retired_at: DateTime<Utc>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CopyAvailability {
+    pub id: Uuid,
+    pub book_id: Uuid,
+    pub copy_id: i32,
+    #[serde(flatten)]
+    pub state: CopyState,
+}
+
+impl From<crate::db::models::CopyStatus> for CopyAvailability {
+    fn from(copy: crate::db::models::CopyStatus) -> Self {
+        CopyAvailability {
+            id: copy.id,
+            book_id: copy.book_id,
+            copy_id: copy.copy_id,
+            state: match (copy.retired_at, copy.due_at, copy.pickup_until) {
+                (Some(retired_at), _, _) if copy.retirement_reason.as_deref() == Some("lost") => {
+                    CopyState::Lost { retired_at }
+                }
+                (Some(retired_at), _, _) => CopyState::Retired { retired_at },
+                (None, Some(due_at), _) => CopyState::OnLoan { due_at },
+                (None, None, Some(pickup_until)) => CopyState::Reserved { pickup_until },
+                (None, None, None) => CopyState::Available,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Hold {
+    pub id: Uuid,
+    pub book_id: Uuid,
+    pub user_id: Uuid,
+    pub status: HoldStatus,
+    pub placed_at: DateTime<Utc>,
+    pub copy_id: Option<Uuid>,
+    pub pickup_until: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl From<crate::db::models::Hold> for Hold {
+    fn from(hold: crate::db::models::Hold) -> Self {
+        Hold {
+            id: hold.id,
+            book_id: hold.book_id,
+            user_id: hold.user_id,
+            status: hold.status,
+            placed_at: hold.placed_at,
+            copy_id: hold.copy_id,
+            pickup_until: hold.pickup_until,
+            closed_at: hold.closed_at,
+        }
+    }
+}
@@ -1,10 +1,19 @@
-use std::sync::Arc;
+use std::{cmp, sync::Arc};
 
+use chrono::{DateTime, Utc};
+use diesel::Connection;
 use tarpc::context;
+use uuid::Uuid;
 
-use super::service::BorrowService;
+use helpers::rpc::{Error, RpcResult};
+
+use super::{models::*, service::BorrowService};
 use crate::config::Configuration;
-use crate::db::DbPool;
+use crate::db::{
+    models::{CopyStatus, Hold as DbHold, HoldAdd, LoanAdd},
+    queries, DbConn, DbPool,
+};
+use crate::policy::Policy;
 
 #[derive(Clone)]
 pub struct BorrowServer {
@@ -16,11 +25,296 @@ impl BorrowServer {
     pub fn new(conf: Arc<Configuration>, db_pool: Arc<DbPool>) -> Self {
         Self { conf, db_pool }
     }
+
+    fn get_db(&self) -> DbConn {
+        self.db_pool
+            .get()
+            .expect("Can't retrieve connection from pool")
+    }
 }
 
 #[tarpc::server]
 impl BorrowService for BorrowServer {
-    async fn borrow(self, _: context::Context) {
-        unimplemented!();
+    /// Lends a copy to an user, as long as the copy is not lent already
+    /// and the open loan limit of the user is not reached
+    async fn checkout_copy(
+        self,
+        _: context::Context,
+        copy_id: Uuid,
+        user_id: Uuid,
+    ) -> RpcResult<Loan> {
+        let db = self.get_db();
+
+        if !queries::copy_exists(copy_id, &db)? {
+            return Err(Error::NotFound);
+        }
+
+        let policy = Policy::of_borrower(queries::get_borrower(user_id, &db)?, &self.conf)?;
+
+        let loan = db.transaction::<_, Error, _>(|| {
+            // Concurrent checkouts of the user wait for the lock,
+            // so they can't pass the open loan limit together
+            queries::lock_user(user_id, &db)?;
+            if let Err(e) =
+                policy.check_open_loans(queries::count_open_loans_by_user(user_id, &db)?)
+            {
+                log::info!(
+                    "Rejected checkout for user '{}' at the open loan limit",
+                    user_id
+                );
+                return Err(e);
+            }
+
+            // A copy held for pickup can only be lent to the holder, which fulfills the hold
+            if let Some(hold) = queries::get_ready_hold_by_copy(copy_id, &db)? {
+                if hold.user_id != user_id {
+                    log::info!("Rejected checkout of copy '{}' held for pickup", copy_id);
+                    return Err(Error::InvalidInput);
+                }
+                queries::close_hold(hold.id, HoldStatus::Fulfilled, Utc::now(), &db)?;
+            }
+
+            // Lending another copy of the book also fulfills the hold of the user,
+            // a copy held for the user is passed on to the next user in the queue
+            let book_id = queries::get_copy_book_id(copy_id, &db)?;
+            if let Some(hold) = queries::get_active_hold_by_user(book_id, user_id, &db)? {
+                let closed_at = Utc::now();
+                let hold = queries::close_hold(hold.id, HoldStatus::Fulfilled, closed_at, &db)?;
+                if let Some(held_copy_id) = hold.copy_id {
+                    assign_copy_to_first_hold(held_copy_id, closed_at, &self.conf, &db)?;
+                }
+            }
+
+            // The partial unique index on open loans rejects a second checkout of the copy
+            let borrowed_at = Utc::now();
+            Ok(queries::create_loan(
+                LoanAdd {
+                    id: Uuid::new_v4(),
+                    copy_id,
+                    user_id,
+                    borrowed_at,
+                    due_at: borrowed_at + policy.loan_duration,
+                },
+                &db,
+            )?)
+        })?;
+        log::info!("Copy '{}' lent to user '{}'", copy_id, user_id);
+
+        Ok(loan.into())
+    }
+
+    /// Ends the open loan of a copy and holds the copy for the first user in the queue of its book
+    async fn return_copy(self, _: context::Context, copy_id: Uuid) -> RpcResult<Loan> {
+        let db = self.get_db();
+
+        let loan = db.transaction::<_, Error, _>(|| {
+            let returned_at = Utc::now();
+            let loan = queries::close_loan_by_copy(copy_id, returned_at, &db)?;
+            assign_copy_to_first_hold(copy_id, returned_at, &self.conf, &db)?;
+            Ok(loan)
+        })?;
+        log::info!("Copy '{}' returned by user '{}'", copy_id, loan.user_id);
+
+        Ok(loan.into())
     }
+
+    /// Extends the due date of an open loan by another loan duration,
+    /// as long as the renewal limit of the user is not reached
+    async fn renew_loan(self, _: context::Context, loan_id: Uuid) -> RpcResult<Loan> {
+        let db = self.get_db();
+
+        let loan = db.transaction::<_, Error, _>(|| {
+            let loan = queries::get_loan(loan_id, &db)?;
+            if loan.returned_at.is_some() {
+                return Err(Error::InvalidInput);
+            }
+
+            let policy =
+                Policy::of_borrower(queries::get_borrower(loan.user_id, &db)?, &self.conf)?;
+            if let Err(e) = policy.check_renewals(loan.renewals) {
+                log::info!(
+                    "Rejected renewal of loan '{}' at the renewal limit",
+                    loan_id
+                );
+                return Err(e);
+            }
+
+            // Renewing ahead of time must not shorten the loan
+            let due_at = cmp::max(loan.due_at, Utc::now()) + policy.loan_duration;
+            Ok(queries::extend_loan(loan_id, due_at, &db)?)
+        })?;
+
+        Ok(loan.into())
+    }
+
+    /// Returns the open loans of an user, ordered by due date
+    async fn list_active_loans(self, _: context::Context, user_id: Uuid) -> RpcResult<Vec<Loan>> {
+        Ok(queries::list_open_loans_by_user(user_id, &self.get_db())?
+            .into_iter()
+            .map(|loan| loan.into())
+            .collect())
+    }
+
+    /// Returns the state of every copy of a book, ordered by copy number
+    async fn list_copy_availability(
+        self,
+        _: context::Context,
+        book_id: Uuid,
+    ) -> RpcResult<Vec<CopyAvailability>> {
+        Ok(queries::list_copy_status_by_book(book_id, &self.get_db())?
+            .into_iter()
+            .map(|copy| copy.into())
+            .collect())
+    }
+
+    /// Queues an user for a book of which no copy is available
+    async fn place_hold(
+        self,
+        _: context::Context,
+        book_id: Uuid,
+        user_id: Uuid,
+    ) -> RpcResult<Hold> {
+        let db = self.get_db();
+
+        let copies: Vec<_> = queries::list_copy_status_by_book(book_id, &db)?
+            .into_iter()
+            .filter(|copy| copy.retired_at.is_none())
+            .collect();
+        if copies.is_empty() {
+            return Err(Error::NotFound);
+        }
+        if copies.iter().any(CopyStatus::is_available) {
+            log::info!("Rejected hold on book '{}' with available copies", book_id);
+            return Err(Error::InvalidInput);
+        }
+
+        let policy = Policy::of_borrower(queries::get_borrower(user_id, &db)?, &self.conf)?;
+        if let Err(e) = policy.check_holds() {
+            log::info!(
+                "Rejected hold for user '{}' without hold permission",
+                user_id
+            );
+            return Err(e);
+        }
+
+        // The partial unique index on active holds rejects queueing twice for a book
+        let hold = queries::create_hold(
+            HoldAdd {
+                id: Uuid::new_v4(),
+                book_id,
+                user_id,
+                placed_at: Utc::now(),
+            },
+            &db,
+        )?;
+        log::info!("Hold on book '{}' placed by user '{}'", book_id, user_id);
+
+        Ok(hold.into())
+    }
+
+    /// Cancels a waiting or ready hold, a held copy is passed on to the next user in the queue
+    async fn cancel_hold(self, _: context::Context, hold_id: Uuid) -> RpcResult<Hold> {
+        let db = self.get_db();
+
+        let hold = db.transaction::<_, Error, _>(|| {
+            let hold = queries::get_hold(hold_id, &db)?;
+            if hold.closed_at.is_some() {
+                return Err(Error::InvalidInput);
+            }
+
+            let closed_at = Utc::now();
+            let hold = queries::close_hold(hold_id, HoldStatus::Cancelled, closed_at, &db)?;
+            if let Some(copy_id) = hold.copy_id {
+                assign_copy_to_first_hold(copy_id, closed_at, &self.conf, &db)?;
+            }
+            Ok(hold)
+        })?;
+
+        Ok(hold.into())
+    }
+
+    /// Returns the queue of waiting and ready holds of a book, ordered by placement
+    async fn list_holds_by_book(self, _: context::Context, book_id: Uuid) -> RpcResult<Vec<Hold>> {
+        Ok(queries::list_active_holds_by_book(book_id, &self.get_db())?
+            .into_iter()
+            .map(|hold| hold.into())
+            .collect())
+    }
+
+    /// Holds the available copies of a book for the users waiting in its queue,
+    /// e.g. once new copies of the book were acquired
+    async fn assign_available_copies(
+        self,
+        _: context::Context,
+        book_id: Uuid,
+    ) -> RpcResult<Vec<Hold>> {
+        let db = self.get_db();
+
+        let holds = db.transaction::<_, Error, _>(|| {
+            assign_available_copies(book_id, Utc::now(), &self.conf, &db)
+        })?;
+
+        Ok(holds.into_iter().map(|hold| hold.into()).collect())
+    }
+
+    /// Returns the waiting and ready holds of an user, ordered by placement
+    async fn list_holds_by_user(self, _: context::Context, user_id: Uuid) -> RpcResult<Vec<Hold>> {
+        Ok(queries::list_active_holds_by_user(user_id, &self.get_db())?
+            .into_iter()
+            .map(|hold| hold.into())
+            .collect())
+    }
+}
+
+/// Holds a copy, which became available, for the first user in the queue of its book
+pub(crate) fn assign_copy_to_first_hold(
+    copy_id: Uuid,
+    now: DateTime<Utc>,
+    conf: &Configuration,
+    db: &DbConn,
+) -> RpcResult<()> {
+    let book_id = queries::get_copy_book_id(copy_id, db)?;
+    let pickup_until = now + conf.get_hold_pickup_duration();
+
+    if let Some(hold) = queries::assign_copy_to_first_hold(book_id, copy_id, pickup_until, db)? {
+        log::info!(
+            "Copy '{}' held for user '{}' until {}",
+            copy_id,
+            hold.user_id,
+            pickup_until
+        );
+    }
+
+    Ok(())
+}
+
+/// Holds the available copies of a book for the first users in its queue, one copy each
+pub(crate) fn assign_available_copies(
+    book_id: Uuid,
+    now: DateTime<Utc>,
+    conf: &Configuration,
+    db: &DbConn,
+) -> RpcResult<Vec<DbHold>> {
+    let pickup_until = now + conf.get_hold_pickup_duration();
+
+    let mut holds = vec![];
+    for copy in queries::list_copy_status_by_book(book_id, db)?
+        .into_iter()
+        .filter(CopyStatus::is_available)
+    {
+        match queries::assign_copy_to_first_hold(book_id, copy.id, pickup_until, db)? {
+            Some(hold) => {
+                log::info!(
+                    "Copy '{}' held for user '{}' until {}",
+                    copy.id,
+                    hold.user_id,
+                    pickup_until
+                );
+                holds.push(hold);
+            }
+            None => break,
+        }
+    }
+
+    Ok(holds)
 }
@@ -1,4 +1,19 @@
+use uuid::Uuid;
+
+pub use helpers::rpc::{Error, RpcResult};
+
+use super::models::*;
+
 #[tarpc::service]
 pub trait BorrowService {
-    async fn borrow();
+    async fn checkout_copy(copy_id: Uuid, user_id: Uuid) -> RpcResult<Loan>;
+    async fn return_copy(copy_id: Uuid) -> RpcResult<Loan>;
+    async fn renew_loan(loan_id: Uuid) -> RpcResult<Loan>;
+    async fn list_active_loans(user_id: Uuid) -> RpcResult<Vec<Loan>>;
+    async fn list_copy_availability(book_id: Uuid) -> RpcResult<Vec<CopyAvailability>>;
+    async fn place_hold(book_id: Uuid, user_id: Uuid) -> RpcResult<Hold>;
+    async fn cancel_hold(hold_id: Uuid) -> RpcResult<Hold>;
+    async fn list_holds_by_book(book_id: Uuid) -> RpcResult<Vec<Hold>>;
+    async fn list_holds_by_user(user_id: Uuid) -> RpcResult<Vec<Hold>>;
+    async fn assign_available_copies(book_id: Uuid) -> RpcResult<Vec<Hold>>;
 }
@@ -0,0 +1,356 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use diesel::Connection;
+use tarpc::context;
+
+use helpers::rpc::{Error, RpcResult};
+use notification::rpc::{
+    get_rpc_client,
+    models::{Announcement, Notification, OverdueLoan},
+    service::NotificationServiceClient,
+};
+
+use crate::config::Configuration;
+use crate::db::{
+    models::{HoldStatus, Loan},
+    queries, DbPool,
+};
+use crate::rpc::server::{assign_available_copies, assign_copy_to_first_hold};
+
+/// Periodically marks overdue loans and expires holds, the resulting reminders
+/// and announcements are sent to the notification service
+pub async fn run(conf: Arc<Configuration>, db_pool: Arc<DbPool>) {
+    let mut interval = tokio::time::interval(conf.get_scheduler_interval());
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+
+        let overdue_loans = mark_overdue_loans(&db_pool, now).unwrap_or_else(|e| {
+            log::error!("Failed to mark overdue loans: {:?}", e);
+            vec![]
+        });
+        if let Err(e) = expire_holds(&conf, &db_pool, now) {
+            log::error!("Failed to expire holds: {:?}", e);
+        }
+        if let Err(e) = assign_waiting_holds(&conf, &db_pool, now) {
+            log::error!("Failed to assign copies to waiting holds: {:?}", e);
+        }
+
+        let client = match get_rpc_client(conf.get_notification_socket()).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::error!("Failed to connect to notification service: {}", e);
+                continue;
+            }
+        };
+        if !overdue_loans.is_empty() {
+            announce_overdue_loans(&client, &overdue_loans).await;
+        }
+        if let Err(e) = send_reminders(&conf, &db_pool, &client, now).await {
+            log::error!("Failed to send reminders: {:?}", e);
+        }
+        if let Err(e) = notify_ready_holds(&db_pool, &client, now).await {
+            log::error!("Failed to notify ready holds: {:?}", e);
+        }
+    }
+}
+
+/// Marks all open loans which are past their due date as overdue and returns them
+pub fn mark_overdue_loans(db_pool: &DbPool, now: DateTime<Utc>) -> RpcResult<Vec<Loan>> {
+    let db = db_pool.get().map_err(|_| Error::InternalError)?;
+
+    let mut marked = vec![];
+    for loan in queries::list_open_loans_due_before(now, &db)? {
+        if loan.overdue_at.is_none() {
+            let loan = queries::mark_loan_overdue(loan.id, now, &db)?;
+            log::info!("Loan '{}' is overdue since {}", loan.id, loan.due_at);
+            marked.push(loan);
+        }
+    }
+
+    Ok(marked)
+}
+
+/// Announces the loans which became overdue to the shared channels of the library
+pub async fn announce_overdue_loans(client: &NotificationServiceClient, loans: &[Loan]) {
+    let announcement = Announcement::OverdueLoans {
+        loans: loans
+            .iter()
+            .map(|loan| OverdueLoan {
+                loan_id: loan.id,
+                user_id: loan.user_id,
+                copy_id: loan.copy_id,
+                due_at: loan.due_at,
+            })
+            .collect(),
+    };
+
+    match client.announce(context::current(), announcement).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => log::error!("Announcement of overdue loans was rejected: {:?}", e),
+        Err(e) => log::error!("Failed to announce overdue loans: {}", e),
+    }
+}
+
+/// Expires the holds which were not picked up in time and passes their copies on
+pub fn expire_holds(
+    conf: &Configuration,
+    db_pool: &DbPool,
+    now: DateTime<Utc>,
+) -> RpcResult<usize> {
+    let db = db_pool.get().map_err(|_| Error::InternalError)?;
+
+    let mut expired = 0;
+    for hold in queries::list_ready_holds_picked_up_before(now, &db)? {
+        db.transaction::<_, Error, _>(|| {
+            let hold = queries::close_hold(hold.id, HoldStatus::Expired, now, &db)?;
+            log::info!("Hold '{}' of user '{}' expired", hold.id, hold.user_id);
+            if let Some(copy_id) = hold.copy_id {
+                assign_copy_to_first_hold(copy_id, now, conf, &db)?;
+            }
+            Ok(())
+        })?;
+        expired += 1;
+    }
+
+    Ok(expired)
+}
+
+/// Holds available copies for waiting users, which catches copies that became available
+/// without being returned, e.g. newly acquired ones that weren't assigned right away
+pub fn assign_waiting_holds(
+    conf: &Configuration,
+    db_pool: &DbPool,
+    now: DateTime<Utc>,
+) -> RpcResult<usize> {
+    let db = db_pool.get().map_err(|_| Error::InternalError)?;
+
+    let mut assigned = 0;
+    for book_id in queries::list_books_with_waiting_holds(&db)? {
+        assigned += db
+            .transaction::<_, Error, _>(|| assign_available_copies(book_id, now, conf, &db))?
+            .len();
+    }
+
+    Ok(assigned)
+}
+
+/// Hands a reminder to the notification service for every open loan that needs one.
+/// A loan is only marked as reminded once the notification service accepted the reminder,
+/// so failed reminders are retried on the next run.
+pub async fn send_reminders(
+    conf: &Configuration,
+    db_pool: &DbPool,
+    client: &NotificationServiceClient,
+    now: DateTime<Utc>,
+) -> RpcResult<usize> {
+    let db = db_pool.get().map_err(|_| Error::InternalError)?;
+
+    let mut sent = 0;
+    for loan in queries::list_open_loans_due_before(now + conf.get_due_reminder_lead_time(), &db)? {
+        let notification = match reminder_for(&loan, now, conf.get_overdue_reminder_interval()) {
+            Some(notification) => notification,
+            None => continue,
+        };
+
+        match client
+            .notify(context::current(), loan.user_id, notification)
+            .await
+        {
+            Ok(Ok(())) => {
+                queries::mark_loan_reminded(loan.id, now, &db)?;
+                sent += 1;
+            }
+            Ok(Err(e)) => log::error!("Reminder for loan '{}' was rejected: {:?}", loan.id, e),
+            Err(e) => {
+                log::error!("Failed to send reminder for loan '{}': {}", loan.id, e);
+                return Err(Error::InternalError);
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Tells the holders of ready holds that their copy can be picked up and announces it.
+/// A hold is only marked as notified once the notification service accepted the
+/// notification for the holder, so failed notifications are retried on the next run.
+pub async fn notify_ready_holds(
+    db_pool: &DbPool,
+    client: &NotificationServiceClient,
+    now: DateTime<Utc>,
+) -> RpcResult<usize> {
+    let db = db_pool.get().map_err(|_| Error::InternalError)?;
+
+    let mut notified = 0;
+    for hold in queries::list_ready_holds_not_notified(&db)? {
+        let (copy_id, pickup_until) = match (hold.copy_id, hold.pickup_until) {
+            (Some(copy_id), Some(pickup_until)) => (copy_id, pickup_until),
+            _ => continue,
+        };
+        let notification = Notification::HoldReady {
+            hold_id: hold.id,
+            copy_id,
+            pickup_until,
+        };
+
+        match client
+            .notify(context::current(), hold.user_id, notification)
+            .await
+        {
+            Ok(Ok(())) => {
+                queries::mark_hold_notified(hold.id, now, &db)?;
+                notified += 1;
+            }
+            Ok(Err(e)) => {
+                log::error!("Notification for hold '{}' was rejected: {:?}", hold.id, e);
+                continue;
+            }
+            Err(e) => {
+                log::error!("Failed to send notification for hold '{}': {}", hold.id, e);
+                return Err(Error::InternalError);
+            }
+        }
+
+        let announcement = Announcement::HoldReady {
+            hold_id: hold.id,
+            user_id: hold.user_id,
+            copy_id,
+            pickup_until,
+        };
+        match client.announce(context::current(), announcement).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => log::error!("Announcement of hold '{}' was rejected: {:?}", hold.id, e),
+            Err(e) => log::error!("Failed to announce hold '{}': {}", hold.id, e),
+        }
+    }
+
+    Ok(notified)
+}
+
+/// Determines the reminder due for a loan which is due within the reminder lead time.
+///
+/// A loan gets one reminder before its due date, one when it becomes overdue and
+/// another one every `overdue_interval` as long as it stays overdue.
+fn reminder_for(
+    loan: &Loan,
+    now: DateTime<Utc>,
+    overdue_interval: Duration,
+) -> Option<Notification> {
+    if loan.returned_at.is_some() {
+        return None;
+    }
+
+    if loan.due_at > now {
+        return match loan.reminded_at {
+            None => Some(Notification::LoanDueSoon {
+                loan_id: loan.id,
+                copy_id: loan.copy_id,
+                due_at: loan.due_at,
+            }),
+            Some(_) => None,
+        };
+    }
+
+    match loan.reminded_at {
+        Some(reminded_at) if reminded_at >= loan.due_at && reminded_at + overdue_interval > now => {
+            None
+        }
+        _ => Some(Notification::LoanOverdue {
+            loan_id: loan.id,
+            copy_id: loan.copy_id,
+            due_at: loan.due_at,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn loan(due_at: DateTime<Utc>, reminded_at: Option<DateTime<Utc>>) -> Loan {
+        Loan {
+            id: Uuid::new_v4(),
+            copy_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            borrowed_at: due_at - Duration::days(28),
+            due_at,
+            returned_at: None,
+            renewals: 0,
+            overdue_at: None,
+            reminded_at,
+        }
+    }
+
+    #[test]
+    fn uts_reminder_for_due_soon() {
+        let now = Utc::now();
+        let loan = loan(now + Duration::days(2), None);
+
+        let result = reminder_for(&loan, now, Duration::days(7));
+
+        assert_eq!(
+            Some(Notification::LoanDueSoon {
+                loan_id: loan.id,
+                copy_id: loan.copy_id,
+                due_at: loan.due_at,
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn uts_reminder_for_due_soon_already_reminded() {
+        let now = Utc::now();
+        let loan = loan(now + Duration::days(2), Some(now - Duration::days(1)));
+
+        assert_eq!(None, reminder_for(&loan, now, Duration::days(7)));
+    }
+
+    #[test]
+    fn uts_reminder_for_overdue_reminded_before_due_date() {
+        let now = Utc::now();
+        let loan = loan(now - Duration::hours(1), Some(now - Duration::days(2)));
+
+        let result = reminder_for(&loan, now, Duration::days(7));
+
+        assert_eq!(
+            Some(Notification::LoanOverdue {
+                loan_id: loan.id,
+                copy_id: loan.copy_id,
+                due_at: loan.due_at,
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn uts_reminder_for_overdue_within_interval() {
+        let now = Utc::now();
+        let loan = loan(now - Duration::days(3), Some(now - Duration::days(2)));
+
+        assert_eq!(None, reminder_for(&loan, now, Duration::days(7)));
+    }
+
+    #[test]
+    fn uts_reminder_for_overdue_after_interval() {
+        let now = Utc::now();
+        let loan = loan(now - Duration::days(10), Some(now - Duration::days(7)));
+
+        assert!(matches!(
+            reminder_for(&loan, now, Duration::days(7)),
+            Some(Notification::LoanOverdue { .. })
+        ));
+    }
+
+    #[test]
+    fn uts_reminder_for_returned() {
+        let now = Utc::now();
+        let mut loan = loan(now - Duration::days(1), None);
+        loan.returned_at = Some(now);
+
+        assert_eq!(None, reminder_for(&loan, now, Duration::days(7)));
+    }
+}
@@ -3,7 +3,7 @@ use std::{io, sync::Arc};
 #[macro_use]
 extern crate diesel_migrations;
 
-use borrow::{config::get_configuration, db::get_db_pool, rpc::get_rpc_server};
+use borrow::{config::get_configuration, db::get_db_pool, rpc::get_rpc_server, scheduler};
 use helpers::db::run_migration;
 
 #[tokio::main]
@@ -23,13 +23,15 @@ async fn main() -> io::Result<()> {
     embed_migrations!();
     run_migration(embedded_migrations::run, &db_pool);
 
-    let (server, addr) = get_rpc_server(
-        configuration.get_service_socket(),
-        Arc::new(configuration),
-        Arc::new(db_pool),
-    )
-    .await
-    .unwrap();
+    let configuration = Arc::new(configuration);
+    let db_pool = Arc::new(db_pool);
+
+    tokio::spawn(scheduler::run(configuration.clone(), db_pool.clone()));
+    log::info!("Borrow scheduler started");
+
+    let (server, addr) = get_rpc_server(configuration.get_service_socket(), configuration, db_pool)
+        .await
+        .unwrap();
     log::info!("Borrow RPC Server started on {}", addr);
     server.await;
 
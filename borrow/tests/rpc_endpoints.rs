@@ -0,0 +1,1324 @@
+use std::env::set_var;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{Duration, SubsecRound, TimeZone, Utc};
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use tarpc::context;
+use uuid::Uuid;
+
+use borrow::config::Configuration;
+use borrow::db::{get_db_pool, models::LoanAdd, queries, DbPool};
+use borrow::rpc::models::{CopyState, HoldStatus};
+use borrow::rpc::{get_rpc_client, get_rpc_server, service::BorrowServiceClient};
+use borrow::scheduler;
+use helpers::rpc::Error;
+use notification::message::Message;
+use notification::rpc::service::NotificationServiceClient;
+use notification::transport::{Announcer, Recipient, Transport, TransportError};
+
+mod sample_data;
+
+#[macro_use]
+extern crate diesel_migrations;
+
+embed_migrations!("migrations/");
+
+// the notification service shares the database, its tables are needed for sending reminders
+mod notification_migrations {
+    embed_migrations!("../notification/migrations/");
+    pub use embedded_migrations::run;
+}
+
+struct DbTestContext {
+    connection_url: String,
+    db_name: String,
+}
+
+impl DbTestContext {
+    fn new(connection_url: String, db_name: String) -> Self {
+        // connect to database service
+        let conn = PgConnection::establish(&connection_url)
+            .expect("Could not connect to database service");
+
+        // create new database
+        diesel::sql_query(format!("CREATE DATABASE \"{}\"", db_name))
+            .execute(&conn)
+            .unwrap_or_else(|_| panic!("Could not create database: {}", db_name));
+
+        // reconnect to the new database
+        let conn = PgConnection::establish(&format!("{}/{}", connection_url, db_name))
+            .expect("Could not connect to database service");
+
+        // run migration
+        embedded_migrations::run(&conn).expect("Failed to apply database migration");
+
+        // create tables of other services and insert sample data for tests
+        conn.batch_execute(sample_data::EXTERNAL_TABLES)
+            .expect("Error inserting sample data");
+
+        Self {
+            connection_url,
+            db_name,
+        }
+    }
+
+    fn get_connection_url(&self) -> String {
+        format!("{}/{}", self.connection_url, self.db_name)
+    }
+}
+
+impl Drop for DbTestContext {
+    fn drop(&mut self) {
+        let conn = PgConnection::establish(&self.connection_url)
+            .expect("Could not connect to database service");
+
+        diesel::sql_query(format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}'",
+            self.db_name
+        ))
+        .execute(&conn)
+        .unwrap();
+
+        diesel::sql_query(format!("DROP DATABASE \"{}\"", self.db_name))
+            .execute(&conn)
+            .unwrap_or_else(|_| panic!("Could not drop database: {}", self.db_name));
+    }
+}
+
+/// Notification transport, which accepts every message without delivering it
+struct AcceptingTransport;
+
+#[async_trait::async_trait]
+impl Transport for AcceptingTransport {
+    fn channel(&self) -> &'static str {
+        "test"
+    }
+
+    async fn send(&self, _: &Recipient, _: &Message) -> Result<(), TransportError> {
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Announcer for AcceptingTransport {
+    fn channel(&self) -> &'static str {
+        "test"
+    }
+
+    async fn announce(&self, _: &Message) -> Result<(), TransportError> {
+        Ok(())
+    }
+}
+
+/// Starts a notification service on the test database, which accepts every message
+async fn start_notification_service(db_pool: &Arc<DbPool>) -> NotificationServiceClient {
+    notification_migrations::run(&db_pool.get().unwrap())
+        .expect("Failed to apply notification database migration");
+
+    let (server, socket) = notification::rpc::get_rpc_server(
+        get_test_configuration().get_service_socket(),
+        Arc::new(notification::config::Configuration::init().unwrap()),
+        db_pool.clone(),
+        Arc::new(
+            notification::template::Templates::load(Path::new("../notification/templates"), "eng")
+                .unwrap(),
+        ),
+        vec![Arc::new(AcceptingTransport)],
+        Some(Arc::new(AcceptingTransport)),
+    )
+    .await
+    .unwrap();
+    tokio::spawn(server);
+
+    notification::rpc::get_rpc_client(socket).await.unwrap()
+}
+
+/// Kinds of the notifications stored by the notification service
+fn load_notification_kinds(db_pool: &DbPool) -> Vec<String> {
+    use notification::db::schema::notifications::dsl;
+
+    dsl::notifications
+        .select(dsl::kind)
+        .order(dsl::created_at.asc())
+        .load(&db_pool.get().unwrap())
+        .unwrap()
+}
+
+fn get_test_configuration() -> Configuration {
+    set_var("SERVICE_SOCKET", "127.0.0.1:0");
+    set_var("LOAN_DURATION", "28");
+    set_var("NOTIFICATION_SOCKET", "127.0.0.1:8081");
+    set_var("DUE_REMINDER_LEAD_TIME", "3");
+    set_var("OVERDUE_REMINDER_INTERVAL", "7");
+    set_var("HOLD_PICKUP_DURATION", "7");
+
+    Configuration::init().unwrap()
+}
+
+async fn setup(
+    test_context_name: String,
+) -> Result<
+    (
+        impl futures::Future<Output = ()>,
+        BorrowServiceClient,
+        Arc<DbPool>,
+        DbTestContext,
+    ),
+    (),
+> {
+    let configuration = Arc::new(get_test_configuration());
+    let db_test_context = DbTestContext::new(
+        configuration.get_db_connection_base_url(),
+        test_context_name,
+    );
+    let db = Arc::new(get_db_pool(&db_test_context.get_connection_url()));
+    let (server, socket) = get_rpc_server(
+        configuration.get_service_socket(),
+        configuration.clone(),
+        db.clone(),
+    )
+    .await
+    .unwrap();
+    let client = get_rpc_client(socket).await.unwrap();
+
+    Ok((server, client, db, db_test_context))
+}
+
+fn uuid(value: &str) -> Uuid {
+    Uuid::parse_str(value).unwrap()
+}
+
+async fn checkout(client: &BorrowServiceClient, copy_id: &str, user_id: &str) {
+    client
+        .checkout_copy(context::current(), uuid(copy_id), uuid(user_id))
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+async fn place_hold(client: &BorrowServiceClient, user_id: &str) -> Uuid {
+    client
+        .place_hold(context::current(), uuid(sample_data::BOOK_1), uuid(user_id))
+        .await
+        .unwrap()
+        .unwrap()
+        .id
+}
+
+// add a copy of the first book, as the BOOK service does once copies are acquired
+fn insert_copy(db_pool: &DbPool) -> Uuid {
+    let copy_id = Uuid::new_v4();
+    db_pool
+        .get()
+        .unwrap()
+        .batch_execute(&format!(
+            "INSERT INTO copies (id, book_id, copy_id, created_at, created_by) \
+             VALUES ('{}', '{}', 2, now(), '{}')",
+            copy_id,
+            sample_data::BOOK_1,
+            sample_data::USER_ACTIVE_1
+        ))
+        .unwrap();
+    copy_id
+}
+
+fn insert_loan(copy_id: &str, due_in: Duration, db_pool: &DbPool) -> Uuid {
+    let due_at = Utc::now() + due_in;
+    queries::create_loan(
+        LoanAdd {
+            id: Uuid::new_v4(),
+            copy_id: uuid(copy_id),
+            user_id: uuid(sample_data::USER_ACTIVE_1),
+            borrowed_at: due_at - Duration::days(28),
+            due_at,
+        },
+        &db_pool.get().unwrap(),
+    )
+    .unwrap()
+    .id
+}
+
+// checkout an available copy
+#[tokio::test]
+async fn checkout_copy_available() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    let before = Utc::now() - Duration::seconds(1);
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(uuid(sample_data::COPY_1), result.copy_id);
+    assert_eq!(uuid(sample_data::USER_ACTIVE_1), result.user_id);
+    assert!(result.borrowed_at >= before);
+    assert_eq!(Duration::days(28), result.due_at - result.borrowed_at);
+    assert_eq!(None, result.returned_at);
+    assert_eq!(0, result.renewals);
+}
+
+// checkout a copy, which is lent already
+#[tokio::test]
+async fn checkout_copy_already_lent() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::AlreadyExists), result);
+}
+
+// checkout a copy, which does not exist
+#[tokio::test]
+async fn checkout_copy_not_exists() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            Uuid::new_v4(),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// checkout a copy, which was retired
+#[tokio::test]
+async fn checkout_copy_retired() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    db_pool
+        .get()
+        .unwrap()
+        .batch_execute(&format!(
+            "UPDATE copies SET retired_at = now() WHERE id = '{}'",
+            sample_data::COPY_3
+        ))
+        .unwrap();
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_3),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// checkout a copy for an inactive user
+#[tokio::test]
+async fn checkout_copy_user_inactive() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_INACTIVE),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), result);
+}
+
+// return a lent copy and lend it again
+#[tokio::test]
+async fn return_copy_lent() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+    let checkout_result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(loan.id, result.id);
+    assert!(result.returned_at.is_some());
+    assert!(checkout_result.is_ok());
+}
+
+// return a copy, which is not lent
+#[tokio::test]
+async fn return_copy_not_lent() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// renew an open loan
+#[tokio::test]
+async fn renew_loan_open() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .renew_loan(context::current(), loan.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(1, result.renewals);
+    assert_eq!(loan.due_at + Duration::days(28), result.due_at);
+}
+
+// renew a returned loan
+#[tokio::test]
+async fn renew_loan_returned() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .renew_loan(context::current(), loan.id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), result);
+}
+
+// list the open loans of an user
+#[tokio::test]
+async fn list_active_loans_of_user() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    for copy in &[
+        sample_data::COPY_1,
+        sample_data::COPY_2,
+        sample_data::COPY_3,
+    ] {
+        client
+            .checkout_copy(
+                context::current(),
+                uuid(copy),
+                uuid(sample_data::USER_ACTIVE_1),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_2))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .list_active_loans(context::current(), uuid(sample_data::USER_ACTIVE_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        vec![uuid(sample_data::COPY_1), uuid(sample_data::COPY_3)],
+        result
+            .iter()
+            .map(|loan| loan.copy_id)
+            .collect::<Vec<Uuid>>()
+    );
+}
+
+// list the availability of the copies of a book
+#[tokio::test]
+async fn list_copy_availability_of_book() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_2),
+            uuid(sample_data::USER_ACTIVE_1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .list_copy_availability(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        vec![
+            (uuid(sample_data::COPY_1), CopyState::Available),
+            (
+                uuid(sample_data::COPY_2),
+                CopyState::OnLoan {
+                    due_at: loan.due_at
+                }
+            ),
+        ],
+        result
+            .into_iter()
+            .map(|copy| (copy.id, copy.state))
+            .collect::<Vec<(Uuid, CopyState)>>()
+    );
+}
+
+// list the availability of the copies of a book, which were retired
+#[tokio::test]
+async fn list_copy_availability_retired() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    db_pool
+        .get()
+        .unwrap()
+        .batch_execute(&format!(
+            "UPDATE copies SET retired_at = '2021-06-01 00:00:00+00', retirement_reason = 'lost' WHERE id = '{}';
+             UPDATE copies SET retired_at = '2021-06-01 00:00:00+00', retirement_reason = 'damaged' WHERE id = '{}'",
+            sample_data::COPY_1,
+            sample_data::COPY_2
+        ))
+        .unwrap();
+    let retired_at = Utc.ymd(2021, 6, 1).and_hms(0, 0, 0);
+
+    // Act
+    let result = client
+        .list_copy_availability(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        vec![
+            (uuid(sample_data::COPY_1), CopyState::Lost { retired_at }),
+            (uuid(sample_data::COPY_2), CopyState::Retired { retired_at }),
+        ],
+        result
+            .into_iter()
+            .map(|copy| (copy.id, copy.state))
+            .collect::<Vec<(Uuid, CopyState)>>()
+    );
+}
+
+// renew an overdue loan
+#[tokio::test]
+async fn renew_loan_overdue() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan_id = insert_loan(sample_data::COPY_1, Duration::days(-2), &db_pool);
+    scheduler::mark_overdue_loans(&db_pool, Utc::now()).unwrap();
+
+    // Act
+    let result = client
+        .renew_loan(context::current(), loan_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert!(result.due_at > Utc::now());
+    assert_eq!(None, result.overdue_at);
+}
+
+// mark the loans past their due date as overdue
+#[tokio::test]
+async fn scheduler_mark_overdue_loans() {
+    // Arrange
+    let (_server, _client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+
+    let overdue_id = insert_loan(sample_data::COPY_1, Duration::days(-1), &db_pool);
+    let open_id = insert_loan(sample_data::COPY_2, Duration::days(1), &db_pool);
+    let now = Utc::now();
+
+    // Act
+    let first_run = scheduler::mark_overdue_loans(&db_pool, now).unwrap();
+    let second_run = scheduler::mark_overdue_loans(&db_pool, now).unwrap();
+
+    // Assert
+    let db = db_pool.get().unwrap();
+    assert_eq!(
+        vec![overdue_id],
+        first_run.iter().map(|loan| loan.id).collect::<Vec<Uuid>>()
+    );
+    assert!(second_run.is_empty());
+    assert!(queries::get_loan(overdue_id, &db)
+        .unwrap()
+        .overdue_at
+        .is_some());
+    assert_eq!(None, queries::get_loan(open_id, &db).unwrap().overdue_at);
+}
+
+// send reminders for loans due soon and overdue loans, but only once
+#[tokio::test]
+async fn scheduler_send_reminders() {
+    // Arrange
+    let (_server, _client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    let configuration = get_test_configuration();
+    let notification_client = start_notification_service(&db_pool).await;
+
+    let due_soon_id = insert_loan(sample_data::COPY_1, Duration::days(1), &db_pool);
+    let overdue_id = insert_loan(sample_data::COPY_2, Duration::days(-1), &db_pool);
+    let due_later_id = insert_loan(sample_data::COPY_3, Duration::days(10), &db_pool);
+    // the database stores timestamps with microsecond precision
+    let now = Utc::now().trunc_subsecs(6);
+
+    // Act
+    let first_run = scheduler::send_reminders(&configuration, &db_pool, &notification_client, now)
+        .await
+        .unwrap();
+    let second_run = scheduler::send_reminders(&configuration, &db_pool, &notification_client, now)
+        .await
+        .unwrap();
+
+    // Assert
+    let db = db_pool.get().unwrap();
+    assert_eq!(2, first_run);
+    assert_eq!(0, second_run);
+    assert_eq!(
+        Some(now),
+        queries::get_loan(due_soon_id, &db).unwrap().reminded_at
+    );
+    assert_eq!(
+        Some(now),
+        queries::get_loan(overdue_id, &db).unwrap().reminded_at
+    );
+    assert_eq!(
+        None,
+        queries::get_loan(due_later_id, &db).unwrap().reminded_at
+    );
+}
+
+// place a hold on a book, of which all copies are lent
+#[tokio::test]
+async fn place_hold_all_copies_lent() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+
+    // Act
+    let result = client
+        .place_hold(
+            context::current(),
+            uuid(sample_data::BOOK_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(uuid(sample_data::BOOK_1), result.book_id);
+    assert_eq!(uuid(sample_data::USER_ACTIVE_2), result.user_id);
+    assert_eq!(HoldStatus::Waiting, result.status);
+    assert_eq!(None, result.copy_id);
+}
+
+// place a hold on a book, of which a copy is available
+#[tokio::test]
+async fn place_hold_copy_available() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+
+    // Act
+    let result = client
+        .place_hold(
+            context::current(),
+            uuid(sample_data::BOOK_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), result);
+}
+
+// place a second hold of an user on the same book
+#[tokio::test]
+async fn place_hold_already_queued() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    place_hold(&client, sample_data::USER_ACTIVE_2).await;
+
+    // Act
+    let result = client
+        .place_hold(
+            context::current(),
+            uuid(sample_data::BOOK_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::AlreadyExists), result);
+}
+
+// cancel a hold, which is cancelled already
+#[tokio::test]
+async fn cancel_hold_cancelled() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let cancelled = client
+        .cancel_hold(context::current(), hold_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .cancel_hold(context::current(), hold_id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(HoldStatus::Cancelled, cancelled.status);
+    assert_eq!(Err(Error::InvalidInput), result);
+}
+
+// return a copy of a book with holds, which holds the copy for the first user in the queue
+#[tokio::test]
+async fn return_copy_held_for_first_hold() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let first_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let second_hold_id = place_hold(&client, sample_data::USER_ACTIVE_3).await;
+
+    // Act
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    let holds = client
+        .list_holds_by_book(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        vec![
+            (
+                first_hold_id,
+                HoldStatus::Ready,
+                Some(uuid(sample_data::COPY_1))
+            ),
+            (second_hold_id, HoldStatus::Waiting, None),
+        ],
+        holds
+            .iter()
+            .map(|hold| (hold.id, hold.status, hold.copy_id))
+            .collect::<Vec<(Uuid, HoldStatus, Option<Uuid>)>>()
+    );
+
+    let availability = client
+        .list_copy_availability(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        CopyState::Reserved {
+            pickup_until: holds[0].pickup_until.unwrap()
+        },
+        availability[0].state
+    );
+}
+
+// checkout a copy held for another user and by the holder
+#[tokio::test]
+async fn checkout_copy_held() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let other_user = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_3),
+        )
+        .await
+        .unwrap();
+    let holder = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_ACTIVE_2),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), other_user);
+    assert!(holder.is_ok());
+    assert_eq!(
+        Ok(vec![]),
+        client
+            .list_holds_by_user(context::current(), uuid(sample_data::USER_ACTIVE_2))
+            .await
+            .unwrap()
+    );
+}
+
+// cancel a ready hold, which passes the copy on to the next user in the queue
+#[tokio::test]
+async fn cancel_hold_ready() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let first_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    place_hold(&client, sample_data::USER_ACTIVE_3).await;
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    client
+        .cancel_hold(context::current(), first_hold_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    let holds = client
+        .list_holds_by_user(context::current(), uuid(sample_data::USER_ACTIVE_3))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(HoldStatus::Ready, holds[0].status);
+    assert_eq!(Some(uuid(sample_data::COPY_1)), holds[0].copy_id);
+}
+
+// assign an acquired copy to the first hold in the queue
+#[tokio::test]
+async fn assign_available_copies_acquired() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let first_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let second_hold_id = place_hold(&client, sample_data::USER_ACTIVE_3).await;
+    let copy_id = insert_copy(&db_pool);
+
+    // Act
+    let result = client
+        .assign_available_copies(context::current(), uuid(sample_data::BOOK_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        vec![(first_hold_id, HoldStatus::Ready, Some(copy_id))],
+        result
+            .iter()
+            .map(|hold| (hold.id, hold.status, hold.copy_id))
+            .collect::<Vec<(Uuid, HoldStatus, Option<Uuid>)>>()
+    );
+    assert_eq!(
+        HoldStatus::Waiting,
+        queries::get_hold(second_hold_id, &db_pool.get().unwrap())
+            .unwrap()
+            .status
+    );
+}
+
+// checkout another copy than the one held for the user, which fulfills the hold
+#[tokio::test]
+async fn checkout_copy_other_than_held() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let first_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let second_hold_id = place_hold(&client, sample_data::USER_ACTIVE_3).await;
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+    let copy_id = insert_copy(&db_pool);
+
+    // Act
+    checkout(&client, &copy_id.to_string(), sample_data::USER_ACTIVE_2).await;
+
+    // Assert
+    let db = db_pool.get().unwrap();
+    assert_eq!(
+        HoldStatus::Fulfilled,
+        queries::get_hold(first_hold_id, &db).unwrap().status
+    );
+    let second_hold = queries::get_hold(second_hold_id, &db).unwrap();
+    assert_eq!(HoldStatus::Ready, second_hold.status);
+    assert_eq!(Some(uuid(sample_data::COPY_1)), second_hold.copy_id);
+}
+
+// expire holds, which were not picked up in time
+#[tokio::test]
+async fn scheduler_expire_holds() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    let configuration = get_test_configuration();
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let first_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let second_hold_id = place_hold(&client, sample_data::USER_ACTIVE_3).await;
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let before_deadline = scheduler::expire_holds(&configuration, &db_pool, Utc::now()).unwrap();
+    let after_deadline =
+        scheduler::expire_holds(&configuration, &db_pool, Utc::now() + Duration::days(8)).unwrap();
+
+    // Assert
+    let db = db_pool.get().unwrap();
+    assert_eq!(0, before_deadline);
+    assert_eq!(1, after_deadline);
+    assert_eq!(
+        HoldStatus::Expired,
+        queries::get_hold(first_hold_id, &db).unwrap().status
+    );
+    assert_eq!(
+        HoldStatus::Ready,
+        queries::get_hold(second_hold_id, &db).unwrap().status
+    );
+}
+
+// assign available copies to waiting holds, which were missed by the acquisition
+#[tokio::test]
+async fn scheduler_assign_waiting_holds() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    let configuration = get_test_configuration();
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    let hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let copy_id = insert_copy(&db_pool);
+
+    // Act
+    let result = scheduler::assign_waiting_holds(&configuration, &db_pool, Utc::now()).unwrap();
+    let repeated = scheduler::assign_waiting_holds(&configuration, &db_pool, Utc::now()).unwrap();
+
+    // Assert
+    assert_eq!(1, result);
+    assert_eq!(0, repeated);
+    assert_eq!(
+        Some(copy_id),
+        queries::get_hold(hold_id, &db_pool.get().unwrap())
+            .unwrap()
+            .copy_id
+    );
+}
+
+// checkout a copy with the loan duration of the role policy
+#[tokio::test]
+async fn checkout_copy_role_policy_duration() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_GUEST),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(Duration::days(7), result.due_at - result.borrowed_at);
+}
+
+// checkout a copy, while the user is at the open loan limit of the role policy
+#[tokio::test]
+async fn checkout_copy_open_loan_limit() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_GUEST).await;
+
+    // Act
+    let result = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_3),
+            uuid(sample_data::USER_GUEST),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::PolicyViolation), result);
+}
+
+// checkout two copies at once, while the user is one loan below the open loan limit
+#[tokio::test(flavor = "multi_thread")]
+async fn checkout_copy_open_loan_limit_concurrent() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let (first, second) = tokio::join!(
+        client.checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_GUEST),
+        ),
+        client.checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_3),
+            uuid(sample_data::USER_GUEST),
+        )
+    );
+
+    // Assert
+    let mut results = vec![first.unwrap().is_ok(), second.unwrap().is_ok()];
+    results.sort_unstable();
+    assert_eq!(vec![false, true], results);
+}
+
+// renew a loan, which reached the renewal limit of the role policy
+#[tokio::test]
+async fn renew_loan_renewal_limit() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let loan = client
+        .checkout_copy(
+            context::current(),
+            uuid(sample_data::COPY_1),
+            uuid(sample_data::USER_GUEST),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .renew_loan(context::current(), loan.id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::PolicyViolation), result);
+}
+
+// place a hold, while holds are not allowed by the role policy
+#[tokio::test]
+async fn place_hold_not_allowed() {
+    // Arrange
+    let (server, client, _db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+
+    // Act
+    let result = client
+        .place_hold(
+            context::current(),
+            uuid(sample_data::BOOK_1),
+            uuid(sample_data::USER_GUEST),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::PolicyViolation), result);
+}
+
+// announce the loans, which became overdue
+#[tokio::test]
+async fn scheduler_announce_overdue_loans() {
+    // Arrange
+    let (_server, _client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    let notification_client = start_notification_service(&db_pool).await;
+
+    insert_loan(sample_data::COPY_1, Duration::days(-1), &db_pool);
+    let overdue_loans = scheduler::mark_overdue_loans(&db_pool, Utc::now()).unwrap();
+
+    // Act
+    scheduler::announce_overdue_loans(&notification_client, &overdue_loans).await;
+
+    // Assert
+    assert_eq!(vec!["overdue_loans"], load_notification_kinds(&db_pool));
+}
+
+// notify the holder of a ready hold and announce it, but only once
+#[tokio::test]
+async fn scheduler_notify_ready_holds() {
+    // Arrange
+    let (server, client, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+    tokio::spawn(server);
+    let notification_client = start_notification_service(&db_pool).await;
+
+    checkout(&client, sample_data::COPY_1, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_2, sample_data::USER_ACTIVE_1).await;
+    checkout(&client, sample_data::COPY_3, sample_data::USER_ACTIVE_1).await;
+    let ready_hold_id = place_hold(&client, sample_data::USER_ACTIVE_2).await;
+    let waiting_hold_id = place_hold(&client, sample_data::USER_ACTIVE_3).await;
+    client
+        .return_copy(context::current(), uuid(sample_data::COPY_1))
+        .await
+        .unwrap()
+        .unwrap();
+    // the database stores timestamps with microsecond precision
+    let now = Utc::now().trunc_subsecs(6);
+
+    // Act
+    let first_run = scheduler::notify_ready_holds(&db_pool, &notification_client, now)
+        .await
+        .unwrap();
+    let second_run = scheduler::notify_ready_holds(&db_pool, &notification_client, now)
+        .await
+        .unwrap();
+
+    // Assert
+    let db = db_pool.get().unwrap();
+    assert_eq!(1, first_run);
+    assert_eq!(0, second_run);
+    assert_eq!(
+        Some(now),
+        queries::get_hold(ready_hold_id, &db).unwrap().notified_at
+    );
+    assert_eq!(
+        None,
+        queries::get_hold(waiting_hold_id, &db).unwrap().notified_at
+    );
+    assert_eq!(
+        vec!["hold_ready", "hold_ready"],
+        load_notification_kinds(&db_pool)
+    );
+}
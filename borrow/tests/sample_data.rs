@@ -0,0 +1,59 @@
+//! The `copies`, `roles` and `users` tables are migrated by the BOOK and IDENTITY services,
+//! so the tests create a reduced version of them alongside their sample data.
+
+pub const EXTERNAL_TABLES: &str = r#"
+CREATE TABLE copies (
+    id UUID PRIMARY KEY,
+    book_id UUID NOT NULL,
+    copy_id INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_by UUID NOT NULL,
+    retired_at TIMESTAMP WITH TIME ZONE,
+    retirement_reason VARCHAR(20)
+);
+
+CREATE TABLE roles (
+    id UUID PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE
+);
+
+CREATE TABLE users (
+    id UUID PRIMARY KEY,
+    email VARCHAR(50) NOT NULL DEFAULT 'user@code.berlin',
+    given_name VARCHAR(50) NOT NULL DEFAULT 'Given',
+    family_name VARCHAR(50) NOT NULL DEFAULT 'Family',
+    active BOOLEAN NOT NULL,
+    role_id UUID NOT NULL REFERENCES roles
+);
+
+INSERT INTO copies (id, book_id, copy_id, created_at, created_by) VALUES
+    ('5b1c6b2e-3c1f-4f4b-9a0e-2d8e3a0c6d11', '0c2a4e55-1b7d-4c8e-a3f6-7e9d1b2c3a40', 0, '2021-01-01 00:00:00+00', 'd1854dea-c0b7-403c-bbe8-fba377453787'),
+    ('8f3d2a71-6e4b-4d2c-b8a9-1c0e5f7a9b22', '0c2a4e55-1b7d-4c8e-a3f6-7e9d1b2c3a40', 1, '2021-01-01 00:00:00+00', 'd1854dea-c0b7-403c-bbe8-fba377453787'),
+    ('c47e9b10-2f5a-4a8d-9e3b-6d1f0a2b4c33', '7a9e3f12-5d4c-4b1a-8e2f-3c6b9d0a1e51', 0, '2021-01-01 00:00:00+00', 'd1854dea-c0b7-403c-bbe8-fba377453787');
+
+INSERT INTO roles (id, name) VALUES
+    ('1b3f6a2c-7d4e-4f9a-8b1c-0e2d4f6a8c01', 'User'),
+    ('6e8a0c2e-4f1b-4d3a-9c5e-7a9b1d3f5e02', 'Guest');
+
+INSERT INTO users (id, active, role_id) VALUES
+    ('d1854dea-c0b7-403c-bbe8-fba377453787', true, '1b3f6a2c-7d4e-4f9a-8b1c-0e2d4f6a8c01'),
+    ('a930312e-eb70-41e4-bf74-d88bf661d4dd', true, '1b3f6a2c-7d4e-4f9a-8b1c-0e2d4f6a8c01'),
+    ('5f0d8c3e-9b2a-4e61-8c7d-2a4b6e8f0c19', true, '1b3f6a2c-7d4e-4f9a-8b1c-0e2d4f6a8c01'),
+    ('42cf1a7b-b7ca-4baf-9dfa-41f4f454a7cf', false, '1b3f6a2c-7d4e-4f9a-8b1c-0e2d4f6a8c01'),
+    ('0a7c9e1b-3d5f-4a2c-8e6b-9d1f3a5c7e03', true, '6e8a0c2e-4f1b-4d3a-9c5e-7a9b1d3f5e02');
+
+INSERT INTO policies (role_name, loan_duration, max_open_loans, max_renewals, holds_allowed) VALUES
+    ('Guest', 7, 1, 0, false);
+"#;
+
+pub const BOOK_1: &str = "0c2a4e55-1b7d-4c8e-a3f6-7e9d1b2c3a40";
+
+pub const COPY_1: &str = "5b1c6b2e-3c1f-4f4b-9a0e-2d8e3a0c6d11";
+pub const COPY_2: &str = "8f3d2a71-6e4b-4d2c-b8a9-1c0e5f7a9b22";
+pub const COPY_3: &str = "c47e9b10-2f5a-4a8d-9e3b-6d1f0a2b4c33";
+
+pub const USER_ACTIVE_1: &str = "d1854dea-c0b7-403c-bbe8-fba377453787";
+pub const USER_ACTIVE_2: &str = "a930312e-eb70-41e4-bf74-d88bf661d4dd";
+pub const USER_ACTIVE_3: &str = "5f0d8c3e-9b2a-4e61-8c7d-2a4b6e8f0c19";
+pub const USER_GUEST: &str = "0a7c9e1b-3d5f-4a2c-8e6b-9d1f3a5c7e03";
+pub const USER_INACTIVE: &str = "42cf1a7b-b7ca-4baf-9dfa-41f4f454a7cf";
@@ -1,11 +1,52 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Combination of the filters of a kind, a book matches all or any of them
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Match {
+    #[default]
+    All,
+    Any,
+}
+
+/// Inclusive range, which is open at a missing bound
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Range<T> {
+    from: Option<T>,
+    to: Option<T>,
+}
+
+impl<T: Clone> Range<T> {
+    pub fn new(from: Option<T>, to: Option<T>) -> Self {
+        Self { from, to }
+    }
+
+    pub fn get_from(&self) -> Option<T> {
+        self.from.clone()
+    }
+
+    pub fn get_to(&self) -> Option<T> {
+        self.to.clone()
+    }
+}
+
+/// Filters of the book listing, a book matches a list filter with any of its values
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Book {
+    #[serde(rename = "match", default)]
+    matching: Match,
     categories: Option<Vec<String>>,
     publishers: Option<Vec<String>>,
     series: Option<Vec<String>>,
     tags: Option<Vec<String>>,
+    subject_areas: Option<Vec<String>>,
+    languages: Option<Vec<String>>,
+    authors: Option<Vec<Uuid>>,
+    editors: Option<Vec<Uuid>>,
+    release_year: Option<Range<i16>>,
+    pages: Option<Range<i32>>,
+    available: Option<bool>,
 }
 
 impl Book {
@@ -20,9 +61,54 @@ impl Book {
             publishers,
             series,
             tags,
+            ..Self::default()
         }
     }
 
+    pub fn with_matching(mut self, matching: Match) -> Self {
+        self.matching = matching;
+        self
+    }
+
+    pub fn with_subject_areas(mut self, subject_areas: Vec<String>) -> Self {
+        self.subject_areas = Some(subject_areas);
+        self
+    }
+
+    pub fn with_languages(mut self, languages: Vec<String>) -> Self {
+        self.languages = Some(languages);
+        self
+    }
+
+    pub fn with_authors(mut self, authors: Vec<Uuid>) -> Self {
+        self.authors = Some(authors);
+        self
+    }
+
+    pub fn with_editors(mut self, editors: Vec<Uuid>) -> Self {
+        self.editors = Some(editors);
+        self
+    }
+
+    pub fn with_release_year(mut self, release_year: Range<i16>) -> Self {
+        self.release_year = Some(release_year);
+        self
+    }
+
+    pub fn with_pages(mut self, pages: Range<i32>) -> Self {
+        self.pages = Some(pages);
+        self
+    }
+
+    pub fn with_available(mut self, available: bool) -> Self {
+        self.available = Some(available);
+        self
+    }
+
+    pub fn get_matching(&self) -> Match {
+        self.matching
+    }
+
     pub fn get_categories(&self) -> Option<Vec<String>> {
         self.categories.clone()
     }
@@ -38,4 +124,32 @@ impl Book {
     pub fn get_tags(&self) -> Option<Vec<String>> {
         self.tags.clone()
     }
+
+    pub fn get_subject_areas(&self) -> Option<Vec<String>> {
+        self.subject_areas.clone()
+    }
+
+    pub fn get_languages(&self) -> Option<Vec<String>> {
+        self.languages.clone()
+    }
+
+    pub fn get_authors(&self) -> Option<Vec<Uuid>> {
+        self.authors.clone()
+    }
+
+    pub fn get_editors(&self) -> Option<Vec<Uuid>> {
+        self.editors.clone()
+    }
+
+    pub fn get_release_year(&self) -> Option<Range<i16>> {
+        self.release_year.clone()
+    }
+
+    pub fn get_pages(&self) -> Option<Range<i32>> {
+        self.pages.clone()
+    }
+
+    pub fn get_available(&self) -> Option<bool> {
+        self.available
+    }
 }
@@ -0,0 +1,97 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Relation of a book, which can be embedded into it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Relation {
+    Authors,
+    Editors,
+    Category,
+    Language,
+    Publisher,
+    Series,
+    Tags,
+    SubjectAreas,
+}
+
+impl Relation {
+    fn name(&self) -> &'static str {
+        match self {
+            Relation::Authors => "authors",
+            Relation::Editors => "editors",
+            Relation::Category => "category",
+            Relation::Language => "language",
+            Relation::Publisher => "publisher",
+            Relation::Series => "series",
+            Relation::Tags => "tags",
+            Relation::SubjectAreas => "subject_areas",
+        }
+    }
+}
+
+/// Relations to embed into books, e.g. `include=authors,tags` or `expand=authors,tags`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Include {
+    #[serde(default, alias = "expand")]
+    include: Relations,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+struct Relations(Vec<Relation>);
+
+impl Include {
+    pub fn new(relations: Vec<Relation>) -> Self {
+        Self {
+            include: Relations(relations),
+        }
+    }
+
+    pub fn contains(&self, relation: Relation) -> bool {
+        self.include.0.contains(&relation)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.0.is_empty()
+    }
+}
+
+impl TryFrom<String> for Relations {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut relations: Vec<Relation> = vec![];
+        for name in value.split(',').filter(|name| !name.is_empty()) {
+            let relation = match name {
+                "authors" => Relation::Authors,
+                "editors" => Relation::Editors,
+                "category" => Relation::Category,
+                "language" => Relation::Language,
+                "publisher" => Relation::Publisher,
+                "series" => Relation::Series,
+                "tags" => Relation::Tags,
+                "subject_areas" => Relation::SubjectAreas,
+                _ => return Err(format!("Unknown relation '{}'", name)),
+            };
+            if !relations.contains(&relation) {
+                relations.push(relation);
+            }
+        }
+        Ok(Self(relations))
+    }
+}
+
+impl fmt::Display for Relations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.0.iter().map(|relation| relation.name()).collect();
+        write!(f, "{}", names.join(","))
+    }
+}
+
+impl From<Relations> for String {
+    fn from(relations: Relations) -> Self {
+        relations.to_string()
+    }
+}
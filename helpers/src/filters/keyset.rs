@@ -0,0 +1,175 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Items, Paginated, Sort};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Position {
+    After,
+    Before,
+}
+
+/// Value of a sort field of an item
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortValue {
+    Text(String),
+    Integer(i64),
+    Timestamp(DateTime<Utc>),
+}
+
+/// Position after or before an item of a sorted listing, which carries the values of the item
+/// for the sort fields and its id. It is passed to clients as an opaque token.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Keyset {
+    position: Position,
+    sort: Sort,
+    values: Vec<SortValue>,
+    id: Uuid,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Token {
+    position: Position,
+    sort: Sort,
+    values: Vec<SortValue>,
+    id: Uuid,
+}
+
+impl Keyset {
+    pub fn new(position: Position, sort: Sort, values: Vec<SortValue>, id: Uuid) -> Self {
+        Self {
+            position,
+            sort,
+            values,
+            id,
+        }
+    }
+
+    pub fn get_position(&self) -> Position {
+        self.position
+    }
+
+    pub fn get_sort(&self) -> &Sort {
+        &self.sort
+    }
+
+    pub fn get_values(&self) -> &[SortValue] {
+        &self.values
+    }
+
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+}
+
+impl TryFrom<String> for Keyset {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = base64::decode_config(value, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| "Invalid cursor".to_string())?;
+        let token: Token =
+            serde_json::from_slice(&json).map_err(|_| "Invalid cursor".to_string())?;
+        Ok(Self::new(
+            token.position,
+            token.sort,
+            token.values,
+            token.id,
+        ))
+    }
+}
+
+impl From<Keyset> for String {
+    fn from(keyset: Keyset) -> Self {
+        let token = Token {
+            position: keyset.position,
+            sort: keyset.sort,
+            values: keyset.values,
+            id: keyset.id,
+        };
+        base64::encode_config(
+            serde_json::to_vec(&token).expect("Failed to serialize cursor"),
+            base64::URL_SAFE_NO_PAD,
+        )
+    }
+}
+
+/// Page of a sorted listing, which starts at the beginning without a cursor
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct KeysetPage {
+    #[serde(default)]
+    sort: Sort,
+    cursor: Option<Keyset>,
+    #[serde(default)]
+    items: Items,
+    /// Whether the total count of the listing is requested, which is expensive
+    #[serde(default)]
+    total: bool,
+}
+
+impl KeysetPage {
+    pub fn new(sort: Sort, cursor: Option<Keyset>, items: Items) -> Self {
+        Self {
+            sort,
+            cursor,
+            items,
+            total: false,
+        }
+    }
+
+    /// Page of all items from the start, e.g. to count them
+    pub fn all() -> Self {
+        Self::new(Sort::default(), None, Items::new(i64::MAX))
+    }
+
+    pub fn with_total(mut self, total: bool) -> Self {
+        self.total = total;
+        self
+    }
+
+    pub fn get_sort(&self) -> &Sort {
+        &self.sort
+    }
+
+    pub fn get_cursor(&self) -> Option<&Keyset> {
+        self.cursor.as_ref()
+    }
+
+    pub fn get_items(&self) -> i64 {
+        self.items.get()
+    }
+
+    /// Number of items to read, one more than requested tells whether more follow
+    pub fn get_limit(&self) -> u64 {
+        self.items.get().saturating_add(1) as u64
+    }
+
+    pub fn get_total(&self) -> bool {
+        self.total
+    }
+
+    /// Envelope of the items read with the limit of this page, ordered by its sort keys
+    pub fn paginate<T>(
+        &self,
+        items: Vec<T>,
+        keyset: impl Fn(&T, Position, &Sort) -> Keyset,
+    ) -> Paginated<T, Keyset> {
+        let position = self
+            .cursor
+            .as_ref()
+            .map_or(Position::After, |cursor| cursor.get_position());
+        Paginated::new(
+            items,
+            self.get_items(),
+            position,
+            self.cursor.is_none(),
+            |item, position| keyset(item, position, &self.sort),
+        )
+    }
+}
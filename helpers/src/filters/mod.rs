@@ -1,5 +1,13 @@
 pub mod book;
+pub mod include;
+pub mod keyset;
 pub mod page;
+pub mod search;
+pub mod sort;
 
-pub use book::Book;
-pub use page::{Cursor, Items, Page};
+pub use book::{Book, Match, Range};
+pub use include::{Include, Relation};
+pub use keyset::{Keyset, KeysetPage, Position, SortValue};
+pub use page::{Cursor, Items, Page, Paginated};
+pub use search::Search;
+pub use sort::{Direction, Sort, SortField};
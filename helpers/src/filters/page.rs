@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::Position;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Cursor {
     #[serde(rename = "after")]
@@ -28,19 +30,40 @@ impl Items {
     pub fn new(items: i64) -> Self {
         Self(items)
     }
+
+    pub fn get(&self) -> i64 {
+        self.0
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Page {
     #[serde(default)]
     cursor: Cursor,
     #[serde(default)]
     items: Items,
+    /// Whether the total count of the listing is requested, which is expensive
+    #[serde(default)]
+    total: bool,
 }
 
 impl Page {
     pub fn new(cursor: Cursor, items: Items) -> Self {
-        Self { cursor, items }
+        Self {
+            cursor,
+            items,
+            total: false,
+        }
+    }
+
+    /// Page of all items from the start, e.g. to count them
+    pub fn all() -> Self {
+        Self::new(Cursor::default(), Items::new(i64::MAX))
+    }
+
+    pub fn with_total(mut self, total: bool) -> Self {
+        self.total = total;
+        self
     }
 
     pub fn get_cursor(&self) -> Cursor {
@@ -48,6 +71,123 @@ impl Page {
     }
 
     pub fn get_items(&self) -> i64 {
-        self.items.0
+        self.items.get()
+    }
+
+    /// Number of items to read, one more than requested tells whether more follow
+    pub fn get_limit(&self) -> u64 {
+        self.items.get().saturating_add(1) as u64
+    }
+
+    pub fn get_total(&self) -> bool {
+        self.total
+    }
+
+    /// Envelope of the items read with the limit of this page, ordered by their ids
+    pub fn paginate<T>(&self, items: Vec<T>, id: impl Fn(&T) -> Uuid) -> Paginated<T> {
+        let (position, at_start) = match self.cursor {
+            Cursor::After(cursor) => (Position::After, cursor.is_nil()),
+            Cursor::Before(_) => (Position::Before, false),
+        };
+        Paginated::new(
+            items,
+            self.get_items(),
+            position,
+            at_start,
+            |item, position| match position {
+                Position::After => Cursor::After(id(item)),
+                Position::Before => Cursor::Before(id(item)),
+            },
+        )
+    }
+}
+
+/// Items of a page with the cursors of the pages after and before it, which are absent when
+/// the listing is known to end there
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Paginated<T, C = Cursor> {
+    items: Vec<T>,
+    next: Option<C>,
+    previous: Option<C>,
+    /// Whether more items follow in the direction of the cursor
+    has_more: bool,
+    total: Option<i64>,
+}
+
+impl<T, C> Paginated<T, C> {
+    /// Builds the envelope of the items read with one more than the requested number, so that
+    /// the extra item beyond the page tells whether more follow in the direction of the cursor
+    pub(crate) fn new(
+        mut items: Vec<T>,
+        requested: i64,
+        position: Position,
+        at_start: bool,
+        cursor: impl Fn(&T, Position) -> C,
+    ) -> Self {
+        let requested = requested.max(0) as usize;
+        let has_more = items.len() > requested;
+        if has_more {
+            match position {
+                Position::After => items.truncate(requested),
+                Position::Before => {
+                    items.drain(..items.len() - requested);
+                }
+            }
+        }
+        let next = items
+            .last()
+            .filter(|_| position == Position::Before || has_more)
+            .map(|item| cursor(item, Position::After));
+        let previous = items
+            .first()
+            .filter(|_| match position {
+                Position::After => !at_start,
+                Position::Before => has_more,
+            })
+            .map(|item| cursor(item, Position::Before));
+
+        Self {
+            items,
+            next,
+            previous,
+            has_more,
+            total: None,
+        }
+    }
+
+    pub fn with_total(mut self, total: Option<i64>) -> Self {
+        self.total = total;
+        self
+    }
+
+    /// Converts the items of the page, which keeps its cursors
+    pub fn map<U>(self, convert: impl FnOnce(Vec<T>) -> Vec<U>) -> Paginated<U, C> {
+        Paginated {
+            items: convert(self.items),
+            next: self.next,
+            previous: self.previous,
+            has_more: self.has_more,
+            total: self.total,
+        }
+    }
+
+    pub fn get_items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn get_next(&self) -> Option<&C> {
+        self.next.as_ref()
+    }
+
+    pub fn get_previous(&self) -> Option<&C> {
+        self.previous.as_ref()
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    pub fn get_total(&self) -> Option<i64> {
+        self.total
     }
 }
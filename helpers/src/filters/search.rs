@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use super::Items;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Search {
+    q: String,
+    #[serde(default)]
+    items: Items,
+}
+
+impl Search {
+    pub fn new(q: String, items: Items) -> Self {
+        Self { q, items }
+    }
+
+    pub fn get_q(&self) -> &str {
+        &self.q
+    }
+
+    pub fn get_items(&self) -> i64 {
+        self.items.get()
+    }
+}
@@ -0,0 +1,89 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Column of the book listing, which books can be sorted by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortField {
+    Title,
+    ReleaseYear,
+    CreatedAt,
+}
+
+impl SortField {
+    fn name(&self) -> &'static str {
+        match self {
+            SortField::Title => "title",
+            SortField::ReleaseYear => "release_year",
+            SortField::CreatedAt => "created_at",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Order of a listing by the given fields, e.g. `-release_year,title` for the latest
+/// books first and those of the same year by title. Ties are broken by id.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Sort(Vec<(SortField, Direction)>);
+
+impl Sort {
+    pub fn new(keys: Vec<(SortField, Direction)>) -> Self {
+        Self(keys)
+    }
+
+    pub fn get_keys(&self) -> &[(SortField, Direction)] {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Sort {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut keys: Vec<(SortField, Direction)> = vec![];
+        for key in value.split(',').filter(|key| !key.is_empty()) {
+            let (name, direction) = match key.strip_prefix('-') {
+                Some(name) => (name, Direction::Desc),
+                None => (key, Direction::Asc),
+            };
+            let field = match name {
+                "title" => SortField::Title,
+                "release_year" => SortField::ReleaseYear,
+                "created_at" => SortField::CreatedAt,
+                _ => return Err(format!("Unknown sort field '{}'", name)),
+            };
+            if keys.iter().any(|(sorted, _)| *sorted == field) {
+                return Err(format!("Sort field '{}' is repeated", name));
+            }
+            keys.push((field, direction));
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self
+            .0
+            .iter()
+            .map(|(field, direction)| match direction {
+                Direction::Asc => field.name().to_string(),
+                Direction::Desc => format!("-{}", field.name()),
+            })
+            .collect();
+        write!(f, "{}", keys.join(","))
+    }
+}
+
+impl From<Sort> for String {
+    fn from(sort: Sort) -> Self {
+        sort.to_string()
+    }
+}
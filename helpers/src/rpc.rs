@@ -1,4 +1,4 @@
-use diesel::result::Error as DBError;
+use diesel::result::{DatabaseErrorKind, Error as DBError};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -9,6 +9,9 @@ pub enum Error {
     InvalidData,
     InvalidInput,
     NotFound,
+    PolicyViolation,
+    /// The entity is still referenced by others, so that it can't be deleted
+    InUse,
 }
 
 impl From<DBError> for Error {
@@ -17,16 +20,27 @@ impl From<DBError> for Error {
         match e {
             DBError::NotFound => Error::NotFound,
             DBError::QueryBuilderError(_) => Error::InvalidData,
+            DBError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => Error::AlreadyExists,
             _ => Error::InternalError,
         }
     }
 }
 
+/// SQLSTATE codes of PostgreSQL integrity constraint violations
+pub const PG_FOREIGN_KEY_VIOLATION: &str = "23503";
+const PG_UNIQUE_VIOLATION: &str = "23505";
+const PG_CHECK_VIOLATION: &str = "23514";
+
 impl From<sqlx::Error> for Error {
     fn from(e: sqlx::Error) -> Self {
         log::debug!("{}", e);
         match e {
             sqlx::Error::RowNotFound => Error::NotFound,
+            sqlx::Error::Database(e) => match e.code().as_deref() {
+                Some(PG_UNIQUE_VIOLATION) => Error::AlreadyExists,
+                Some(PG_FOREIGN_KEY_VIOLATION) | Some(PG_CHECK_VIOLATION) => Error::InvalidInput,
+                _ => Error::InternalError,
+            },
             _ => Error::InternalError,
         }
     }
@@ -0,0 +1,2 @@
+ALTER TABLE users ADD UNIQUE (sub);
+DROP TABLE user_identities;
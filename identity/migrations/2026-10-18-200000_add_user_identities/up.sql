@@ -0,0 +1,15 @@
+CREATE TABLE user_identities (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  user_id UUID NOT NULL REFERENCES users (id) ON UPDATE CASCADE ON DELETE CASCADE,
+  provider VARCHAR(50) NOT NULL,
+  subject VARCHAR(255) NOT NULL,
+  email VARCHAR(50) NOT NULL,
+  linked_at TIMESTAMP NOT NULL DEFAULT now(),
+  UNIQUE (provider, subject),
+  UNIQUE (user_id, provider)
+);
+
+INSERT INTO user_identities (user_id, provider, subject, email)
+  SELECT id, 'google', sub, email FROM users;
+
+ALTER TABLE users DROP CONSTRAINT users_sub_key;
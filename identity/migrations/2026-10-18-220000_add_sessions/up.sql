@@ -0,0 +1,17 @@
+CREATE TABLE sessions (
+  id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+  user_id UUID NOT NULL REFERENCES users (id) ON UPDATE CASCADE ON DELETE CASCADE,
+  created_at TIMESTAMP NOT NULL DEFAULT now(),
+  revoked_at TIMESTAMP
+);
+
+CREATE INDEX sessions_user_id_idx ON sessions (user_id);
+
+-- each family of refresh tokens issued before belongs to a session
+INSERT INTO sessions (id, user_id, created_at, revoked_at)
+  SELECT family_id, user_id, MIN(issued_at),
+    CASE WHEN bool_and(revoked_at IS NOT NULL) THEN MAX(revoked_at) END
+  FROM refresh_tokens
+  GROUP BY family_id, user_id;
+
+ALTER TABLE refresh_tokens ADD FOREIGN KEY (family_id) REFERENCES sessions ON UPDATE CASCADE ON DELETE CASCADE;
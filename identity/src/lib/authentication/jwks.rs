@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use hyper::{header::CACHE_CONTROL, Body, Client, Request, StatusCode, Uri};
+use hyper_tls::HttpsConnector;
+use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::sync::RwLock;
+
+use super::oauth::Error;
+
+/// Time the keys are cached, unless the provider states it by `Cache-Control: max-age`
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Minimum time between two fetches of the keys, so that tokens with unknown key ids
+/// can't make the identity service flood the provider
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Public key of a JSON Web Key Set
+#[derive(Clone, Debug, Deserialize)]
+pub struct Jwk {
+    pub kid: String,
+    pub kty: String,
+    #[serde(default)]
+    pub n: String,
+    #[serde(default)]
+    pub e: String,
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+struct CachedKeys {
+    keys: HashMap<String, Jwk>,
+    fetched_at: Instant,
+    max_age: Duration,
+}
+
+/// Keys of the OpenID provider, with which it signs its ID tokens.
+/// The keys are fetched from the JWKS endpoint and cached, and fetched again once they
+/// expire or a token names an unknown key, as the provider rotates its keys.
+pub struct KeyStore {
+    url: Uri,
+    min_refresh_interval: Duration,
+    cache: RwLock<Option<CachedKeys>>,
+}
+
+impl KeyStore {
+    pub fn new(url: Uri) -> Self {
+        KeyStore {
+            url,
+            min_refresh_interval: MIN_REFRESH_INTERVAL,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Sets the minimum time between two fetches of the keys
+    pub fn with_min_refresh_interval(self, min_refresh_interval: Duration) -> Self {
+        KeyStore {
+            min_refresh_interval,
+            ..self
+        }
+    }
+
+    /// Returns the claims of a token, whose signature is verified by the key it names and
+    /// whose registered claims are checked by the validation
+    pub async fn verify<T: DeserializeOwned>(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<T, Error> {
+        let kid = decode_header(token)
+            .map_err(|_| Error::IdTokenInvalid)?
+            .kid
+            .ok_or(Error::IdTokenKeyUnknown)?;
+        let key = self.get_key(&kid).await?;
+        if key.kty != "RSA" {
+            return Err(Error::IdTokenKeyUnknown);
+        }
+
+        match decode::<T>(
+            token,
+            &DecodingKey::from_rsa_components(&key.n, &key.e),
+            validation,
+        ) {
+            Ok(val) => Ok(val.claims),
+            Err(e) => {
+                log::debug!("Rejected ID token: {}", e);
+                Err(Error::IdTokenInvalid)
+            }
+        }
+    }
+
+    /// Returns the key with the given id, which is fetched unless it is cached
+    async fn get_key(&self, kid: &str) -> Result<Jwk, Error> {
+        if let Some(cached) = self.cache.read().await.as_ref() {
+            let age = cached.fetched_at.elapsed();
+            match cached.keys.get(kid) {
+                Some(key) if age < cached.max_age => return Ok(key.clone()),
+                None if age < self.min_refresh_interval => return Err(Error::IdTokenKeyUnknown),
+                _ => {}
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // the keys may have been fetched by another request while waiting for the lock
+        if let Some(cached) = cache.as_ref() {
+            if cached.fetched_at.elapsed() < self.min_refresh_interval {
+                return cached
+                    .keys
+                    .get(kid)
+                    .cloned()
+                    .ok_or(Error::IdTokenKeyUnknown);
+            }
+        }
+        let fetched = fetch_keys(self.url.clone()).await?;
+        let key = fetched.keys.get(kid).cloned();
+        *cache = Some(fetched);
+        key.ok_or(Error::IdTokenKeyUnknown)
+    }
+}
+
+async fn fetch_keys(url: Uri) -> Result<CachedKeys, Error> {
+    let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
+    let request = Request::get(url).body(Body::empty()).unwrap();
+
+    let response = match client.request(request).await {
+        Ok(val) if val.status() == StatusCode::OK => val,
+        Ok(_) => return Err(Error::JwksEndpointInvalidResponse),
+        Err(_) => return Err(Error::JwksEndpointNotReachable),
+    };
+    let max_age = response
+        .headers()
+        .get(CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(max_age)
+        .unwrap_or(DEFAULT_MAX_AGE);
+
+    let body = match hyper::body::to_bytes(response).await {
+        Ok(val) => val,
+        Err(_) => return Err(Error::JwksEndpointInvalidResponse),
+    };
+    match serde_json::from_slice::<JwkSet>(&body) {
+        Ok(val) => Ok(CachedKeys {
+            keys: val
+                .keys
+                .into_iter()
+                .map(|key| (key.kid.clone(), key))
+                .collect(),
+            fetched_at: Instant::now(),
+            max_age,
+        }),
+        Err(_) => Err(Error::JwksDeserialization),
+    }
+}
+
+/// Reads the `max-age` directive of a `Cache-Control` header
+fn max_age(cache_control: &str) -> Option<Duration> {
+    cache_control
+        .split(',')
+        .filter_map(|directive| directive.trim().strip_prefix("max-age="))
+        .find_map(|seconds| seconds.parse().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_age_of_cache_control() {
+        assert_eq!(
+            Some(Duration::from_secs(21600)),
+            max_age("public, max-age=21600, must-revalidate, no-transform")
+        );
+        assert_eq!(None, max_age("no-cache"));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod jwks;
 pub mod oauth;
+pub mod provider;
 
 use chrono::{Duration, NaiveDateTime};
 use diesel::result::{Error, QueryResult};
@@ -123,14 +125,15 @@ mod tests {
 
         let id_token = IdToken {
             iss: "".into(),
-            aud: "".into(),
+            aud: vec![],
+            azp: None,
             sub: "sub".into(),
             iat: 0,
             exp: 0,
             email: "email".into(),
             given_name: "name".into(),
             family_name: "name".into(),
-            hd: "".into(),
+            hd: None,
             picture: "picture".into(),
         };
 
@@ -178,14 +181,15 @@ mod tests {
 
         let id_token = IdToken {
             iss: "".into(),
-            aud: "".into(),
+            aud: vec![],
+            azp: None,
             sub: "sub".into(),
             iat: 0,
             exp: 0,
             email: "email".into(),
             given_name: "name".into(),
             family_name: "name".into(),
-            hd: "".into(),
+            hd: None,
             picture: "picture".into(),
         };
 
@@ -1,22 +1,44 @@
-use hyper::{Body, Client, Request, StatusCode, Uri};
+use hyper::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Body, Client, Request, StatusCode, Uri,
+};
 use hyper_tls::HttpsConnector;
-use jsonwebtoken::dangerous_insecure_decode;
-use serde::{Deserialize, Serialize, Serializer};
+use jsonwebtoken::{Algorithm, Validation};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use helpers::rpc::Error as RpcError;
 
+use super::jwks::KeyStore;
+
 type ClientIdentifier = String;
 type ClientSecret = String;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
+    DiscoveryEndpointInvalidResponse,
+    DiscoveryEndpointNotReachable,
+    DiscoveryDeserialization,
+    DiscoveryIssuerMismatch,
+    ProviderUnknown,
     AuthorizationCodeLength,
     AuthorizationCodeInvalidCharacter,
     TokenRequestEndpointInvalidResponse,
     TokenRequestEndpointNotReachable,
     TokenRequestContentInvalid,
     TokenRequestDeserialization,
+    JwksEndpointInvalidResponse,
+    JwksEndpointNotReachable,
+    JwksDeserialization,
     IdTokenInvalid,
+    IdTokenKeyUnknown,
+    IdTokenIssuerInvalid,
+    IdTokenHostedDomainInvalid,
+    IdTokenAuthorizedPartyInvalid,
+    UserInfoEndpointInvalidResponse,
+    UserInfoEndpointNotReachable,
+    UserInfoDeserialization,
+    UserInfoSubjectMismatch,
+    UserInfoEmailMissing,
 }
 
 impl From<Error> for RpcError {
@@ -25,19 +47,101 @@ impl From<Error> for RpcError {
         log::debug!("{:?}", e);
         match e {
             AuthorizationCodeLength | AuthorizationCodeInvalidCharacter => RpcError::InvalidData,
+            IdTokenInvalid
+            | IdTokenKeyUnknown
+            | IdTokenIssuerInvalid
+            | IdTokenHostedDomainInvalid
+            | IdTokenAuthorizedPartyInvalid
+            | UserInfoSubjectMismatch
+            | UserInfoEmailMissing => RpcError::InvalidInput,
+            ProviderUnknown => RpcError::NotFound,
             _ => RpcError::InternalError,
         }
     }
 }
 
-/// Represents the Google Discovery Document
-/// https://accounts.google.com/.well-known/openid-configuration
-pub struct DiscoveryDocument {}
+/// Issuer of Google accounts, whose ID tokens may name it by its host only
+const GOOGLE_ISSUER: &str = "https://accounts.google.com";
+const GOOGLE_ISSUER_HOST: &str = "accounts.google.com";
+
+/// Metadata of an OpenID provider, which is published by its discovery document
+/// https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata
+#[derive(Debug)]
+pub struct DiscoveryDocument {
+    issuer: String,
+    token_endpoint: Uri,
+    jwks_uri: Uri,
+    userinfo_endpoint: Option<Uri>,
+}
+
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    token_endpoint: String,
+    jwks_uri: String,
+    userinfo_endpoint: Option<String>,
+}
 
 impl DiscoveryDocument {
+    /// Loads the discovery document of an issuer, which must name the same issuer
+    pub async fn fetch(issuer: Uri) -> Result<Self, Error> {
+        let issuer = issuer.to_string().trim_end_matches('/').to_string();
+        let url = format!("{}/.well-known/openid-configuration", issuer)
+            .parse::<Uri>()
+            .map_err(|_| Error::DiscoveryEndpointNotReachable)?;
+        let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
+        let request = Request::get(url).body(Body::empty()).unwrap();
+
+        let response = match client.request(request).await {
+            Ok(val) if val.status() == StatusCode::OK => val,
+            Ok(_) => return Err(Error::DiscoveryEndpointInvalidResponse),
+            Err(_) => return Err(Error::DiscoveryEndpointNotReachable),
+        };
+
+        let body = match hyper::body::to_bytes(response).await {
+            Ok(val) => val,
+            Err(_) => return Err(Error::DiscoveryEndpointInvalidResponse),
+        };
+
+        let metadata = match serde_json::from_slice::<ProviderMetadata>(&body) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::DiscoveryDeserialization),
+        };
+        if metadata.issuer.trim_end_matches('/') != issuer {
+            return Err(Error::DiscoveryIssuerMismatch);
+        }
+        let parse = |url: String| {
+            url.parse::<Uri>()
+                .map_err(|_| Error::DiscoveryDeserialization)
+        };
+
+        Ok(DiscoveryDocument {
+            issuer: metadata.issuer,
+            token_endpoint: parse(metadata.token_endpoint)?,
+            jwks_uri: parse(metadata.jwks_uri)?,
+            userinfo_endpoint: metadata.userinfo_endpoint.map(parse).transpose()?,
+        })
+    }
+
     /// Returns the `token_endpoint` key value
-    pub fn get_token_endpoint() -> Uri {
-        "https://oauth2.googleapis.com/token".parse().unwrap()
+    pub fn get_token_endpoint(&self) -> Uri {
+        self.token_endpoint.clone()
+    }
+
+    /// Returns the `jwks_uri` key value
+    pub fn get_jwks_uri(&self) -> Uri {
+        self.jwks_uri.clone()
+    }
+
+    /// Returns the `userinfo_endpoint` key value
+    pub fn get_userinfo_endpoint(&self) -> Option<Uri> {
+        self.userinfo_endpoint.clone()
+    }
+
+    /// Checks the `iss` claim of an ID token, which must name the issuer exactly. Only Google
+    /// also issues it without scheme.
+    pub fn is_issuer(&self, iss: &str) -> bool {
+        iss == self.issuer || (self.issuer == GOOGLE_ISSUER && iss == GOOGLE_ISSUER_HOST)
     }
 }
 
@@ -57,12 +161,12 @@ impl Serialize for AuthorizationCode {
 
 impl AuthorizationCode {
     pub fn new(code: String) -> Result<Self, Error> {
-        if code.is_empty() || code.len() > 256 {
+        if code.is_empty() || code.len() > 2048 {
             return Err(Error::AuthorizationCodeLength);
         }
 
         for i in code.chars() {
-            if !(i.is_ascii_alphanumeric() || i == '-' || i == '/' || i == '_') {
+            if !(i.is_ascii_alphanumeric() || matches!(i, '-' | '/' | '_' | '.' | '~')) {
                 return Err(Error::AuthorizationCodeInvalidCharacter);
             }
         }
@@ -134,7 +238,8 @@ impl TokenRequest {
         let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
 
         let request = Request::post(token_endpoint)
-            .body(Body::from(serde_json::to_vec(self).unwrap()))
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(serde_urlencoded::to_string(self).unwrap()))
             .unwrap();
 
         let response = match client.request(request).await {
@@ -159,31 +264,146 @@ impl TokenRequest {
 pub struct TokenSet {
     pub access_token: String,
     pub expires_in: u32,
+    #[serde(default)]
     pub scope: String,
     pub token_type: String,
     pub refresh_token: Option<String>,
     pub id_token: String,
 }
 
+/// Claims of an ID token, of which the profile ones are optional and filled by the
+/// user info of the provider if missing
 #[derive(Debug, Deserialize)]
 pub struct IdToken {
     pub iss: String,
-    pub aud: String,
+    #[serde(deserialize_with = "audience")]
+    pub aud: Vec<String>,
+    /// Client, to which the token was issued, if it has several audiences
+    #[serde(default)]
+    pub azp: Option<String>,
     pub sub: String,
     pub iat: u64,
     pub exp: u64,
+    #[serde(default)]
     pub email: String,
+    #[serde(default)]
     pub given_name: String,
+    #[serde(default)]
     pub family_name: String,
-    pub hd: String,
+    /// G Suite domain of the account, which is absent for personal accounts
+    #[serde(default)]
+    pub hd: Option<String>,
+    #[serde(default)]
     pub picture: String,
 }
 
+/// Reads the `aud` claim, which is a single audience or a list of them
+fn audience<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Audience {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Audience::deserialize(deserializer)? {
+        Audience::One(audience) => vec![audience],
+        Audience::Many(audiences) => audiences,
+    })
+}
+
 impl IdToken {
-    pub fn new(token: &str) -> Result<Self, Error> {
-        match dangerous_insecure_decode::<Self>(token) {
-            Ok(val) => Ok(val.claims),
-            Err(_) => Err(Error::IdTokenInvalid),
+    /// Returns the claims of an ID token, whose signature is verified by the keys of the
+    /// provider. The token must be issued by the provider for this client and be unexpired,
+    /// and if a hosted domain is configured, it must belong to an account of that domain.
+    pub async fn verify(
+        token: &str,
+        keys: &KeyStore,
+        discovery: &DiscoveryDocument,
+        client_identifier: &str,
+        hosted_domain: Option<&str>,
+    ) -> Result<Self, Error> {
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&[client_identifier]);
+        // tolerates a small clock skew between the provider and this service
+        validation.leeway = 60;
+        let id_token = keys.verify::<Self>(token, &validation).await?;
+
+        if !discovery.is_issuer(&id_token.iss) {
+            return Err(Error::IdTokenIssuerInvalid);
+        }
+        if !id_token.aud.iter().any(|aud| aud == client_identifier) {
+            return Err(Error::IdTokenInvalid);
+        }
+        // a token for several audiences must name this client as the one it was issued to
+        if id_token.aud.len() > 1 && id_token.azp.as_deref() != Some(client_identifier) {
+            return Err(Error::IdTokenAuthorizedPartyInvalid);
+        }
+        if hosted_domain.is_some() && id_token.hd.as_deref() != hosted_domain {
+            return Err(Error::IdTokenHostedDomainInvalid);
+        }
+        Ok(id_token)
+    }
+
+    /// Whether profile claims are missing, which some providers only return as user info
+    pub fn is_incomplete(&self) -> bool {
+        self.email.is_empty() || self.given_name.is_empty() || self.family_name.is_empty()
+    }
+
+    /// Fills the missing profile claims by the user info of the same subject
+    pub fn complete(&mut self, user_info: UserInfo) -> Result<(), Error> {
+        if user_info.sub != self.sub {
+            return Err(Error::UserInfoSubjectMismatch);
+        }
+        let fill = |claim: &mut String, value: Option<String>| {
+            if claim.is_empty() {
+                *claim = value.unwrap_or_default();
+            }
+        };
+        fill(&mut self.email, user_info.email);
+        fill(&mut self.given_name, user_info.given_name);
+        fill(&mut self.family_name, user_info.family_name);
+        fill(&mut self.picture, user_info.picture);
+        Ok(())
+    }
+}
+
+/// Claims about the authenticated user, which the user info endpoint returns
+#[derive(Debug, Deserialize)]
+pub struct UserInfo {
+    pub sub: String,
+    pub email: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub picture: Option<String>,
+}
+
+impl UserInfo {
+    pub async fn fetch(userinfo_endpoint: Uri, access_token: &str) -> Result<Self, Error> {
+        let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
+
+        let request = Request::get(userinfo_endpoint)
+            .header(AUTHORIZATION, format!("Bearer {}", access_token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = match client.request(request).await {
+            Ok(val) if val.status() == StatusCode::OK => val,
+            Ok(_) => return Err(Error::UserInfoEndpointInvalidResponse),
+            Err(_) => return Err(Error::UserInfoEndpointNotReachable),
+        };
+
+        let body = match hyper::body::to_bytes(response).await {
+            Ok(val) => val,
+            Err(_) => return Err(Error::UserInfoEndpointInvalidResponse),
+        };
+
+        match serde_json::from_slice::<UserInfo>(&body) {
+            Ok(val) => Ok(val),
+            Err(_) => Err(Error::UserInfoDeserialization),
         }
     }
 }
@@ -192,6 +412,27 @@ impl IdToken {
 mod test {
     use super::*;
 
+    #[test]
+    fn issuer_without_scheme() {
+        let discovery = DiscoveryDocument {
+            issuer: "https://accounts.google.com".into(),
+            token_endpoint: Uri::from_static("https://oauth2.googleapis.com/token"),
+            jwks_uri: Uri::from_static("https://www.googleapis.com/oauth2/v3/certs"),
+            userinfo_endpoint: None,
+        };
+        assert!(discovery.is_issuer("https://accounts.google.com"));
+        assert!(discovery.is_issuer("accounts.google.com"));
+        assert!(!discovery.is_issuer("https://accounts.example.com"));
+
+        let discovery = DiscoveryDocument {
+            issuer: "https://login.example.com".into(),
+            ..discovery
+        };
+        assert!(discovery.is_issuer("https://login.example.com"));
+        assert!(!discovery.is_issuer("login.example.com"));
+        assert!(!discovery.is_issuer("accounts.google.com"));
+    }
+
     #[test]
     fn authorization_code_length_too_short() {
         let too_short_code = String::new();
@@ -203,7 +444,7 @@ mod test {
 
     #[test]
     fn authorization_code_length_too_long() {
-        let too_long_code = "a".repeat(257);
+        let too_long_code = "a".repeat(2049);
         assert_eq!(
             Result::Err(Error::AuthorizationCodeLength),
             AuthorizationCode::new(too_long_code)
@@ -0,0 +1,98 @@
+use super::jwks::KeyStore;
+use super::oauth::{
+    AuthorizationCode, DiscoveryDocument, Error, GrantType, IdToken, RedirectUri, TokenRequest,
+    TokenSet, UserInfo,
+};
+use crate::config::OauthProvider;
+
+/// OpenID provider, with whose accounts users sign in, together with the endpoints and
+/// keys it published
+pub struct Provider {
+    conf: OauthProvider,
+    discovery: DiscoveryDocument,
+    keys: KeyStore,
+}
+
+impl Provider {
+    /// Loads the discovery document of the provider, whose keys are fetched once needed
+    pub async fn discover(conf: OauthProvider) -> Result<Self, Error> {
+        let discovery = DiscoveryDocument::fetch(conf.get_issuer_url()).await?;
+        let keys = KeyStore::new(discovery.get_jwks_uri());
+        Ok(Provider {
+            conf,
+            discovery,
+            keys,
+        })
+    }
+
+    pub fn get_name(&self) -> String {
+        self.conf.get_name()
+    }
+
+    pub fn get_client_identifier(&self) -> String {
+        self.conf.get_client_identifier()
+    }
+
+    /// Exchanges an authorization code for the tokens of an account and returns its verified
+    /// ID token, whose missing profile claims are completed by the user info
+    pub async fn authenticate(
+        &self,
+        code: AuthorizationCode,
+    ) -> Result<(IdToken, TokenSet), Error> {
+        let request = TokenRequest::new(
+            self.conf.get_client_identifier(),
+            self.conf.get_client_secret(),
+            code,
+            RedirectUri::PostMessage,
+            GrantType::AuthorizationCode,
+        );
+        let token_set = request
+            .exchange_code(self.discovery.get_token_endpoint())
+            .await?;
+
+        let mut id_token = IdToken::verify(
+            &token_set.id_token,
+            &self.keys,
+            &self.discovery,
+            &self.conf.get_client_identifier(),
+            self.conf.get_hosted_domain().as_deref(),
+        )
+        .await?;
+        if id_token.is_incomplete() {
+            if let Some(userinfo_endpoint) = self.discovery.get_userinfo_endpoint() {
+                id_token
+                    .complete(UserInfo::fetch(userinfo_endpoint, &token_set.access_token).await?)?;
+            }
+        }
+        if id_token.email.is_empty() {
+            return Err(Error::UserInfoEmailMissing);
+        }
+        Ok((id_token, token_set))
+    }
+}
+
+/// Configured providers, of which the first one is used unless a client names another
+pub struct Providers(Vec<Provider>);
+
+impl Providers {
+    pub async fn discover(confs: Vec<OauthProvider>) -> Result<Self, Error> {
+        let mut providers = vec![];
+        for conf in confs {
+            providers.push(Provider::discover(conf).await?);
+        }
+        Ok(Providers(providers))
+    }
+
+    /// Returns the provider of the given name or the default one
+    pub fn get(&self, name: Option<&str>) -> Result<&Provider, Error> {
+        match name {
+            Some(name) => self.0.iter().find(|provider| provider.get_name() == name),
+            None => self.0.first(),
+        }
+        .ok_or(Error::ProviderUnknown)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Provider> {
+        self.0.iter()
+    }
+}
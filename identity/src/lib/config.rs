@@ -1,4 +1,5 @@
 use dotenv::dotenv;
+use hyper::Uri;
 
 use std::{
     env::{var, VarError},
@@ -14,11 +15,24 @@ pub struct Configuration {
     db_name: String,
     db_username: String,
     db_password: String,
-    oauth_client_identifier: String,
-    oauth_client_secret: String,
+    oauth_providers: Vec<OauthProvider>,
     jwt_secret: String,
 }
 
+/// Name of the provider, which is configured by the unprefixed `OAUTH_*` variables
+/// unless `OAUTH_PROVIDERS` names the providers
+pub const DEFAULT_OAUTH_PROVIDER: &str = "google";
+
+/// OpenID provider, with whose accounts users sign in
+#[derive(Clone, Debug, PartialEq)]
+pub struct OauthProvider {
+    name: String,
+    client_identifier: String,
+    client_secret: String,
+    issuer_url: Uri,
+    hosted_domain: Option<String>,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ConfigurationError {
     description: String,
@@ -73,8 +87,7 @@ impl Configuration {
             db_name: Configuration::init_db_name()?,
             db_username: Configuration::init_db_username()?,
             db_password: Configuration::init_db_password()?,
-            oauth_client_identifier: Configuration::init_oauth_client_identifier()?,
-            oauth_client_secret: Configuration::init_oauth_client_secret()?,
+            oauth_providers: Configuration::init_oauth_providers()?,
             jwt_secret: Configuration::init_jwt_secret()?,
         })
     }
@@ -161,29 +174,32 @@ impl Configuration {
         }
     }
 
-    fn init_oauth_client_identifier() -> Result<String, ConfigurationError> {
-        let key = "OAUTH_CLIENT_IDENTIFIER";
+    fn init_oauth_providers() -> Result<Vec<OauthProvider>, ConfigurationError> {
+        let key = "OAUTH_PROVIDERS";
         match var(key) {
-            Ok(password) => Ok(password),
-            Err(VarError::NotPresent) => Err(ConfigurationError::new(
-                key.into(),
-                ErrorKind::EnvVarValueRequired,
-            )),
-            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
-                key.into(),
-                ErrorKind::EnvVarValueInvalid,
-            )),
-        }
-    }
-
-    fn init_oauth_client_secret() -> Result<String, ConfigurationError> {
-        let key = "OAUTH_CLIENT_SECRET";
-        match var(key) {
-            Ok(password) => Ok(password),
-            Err(VarError::NotPresent) => Err(ConfigurationError::new(
-                key.into(),
-                ErrorKind::EnvVarValueRequired,
-            )),
+            Ok(names) => {
+                let mut providers: Vec<OauthProvider> = vec![];
+                for name in names.split(',').map(str::trim) {
+                    let valid = !name.is_empty()
+                        && name
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                        && providers.iter().all(|provider| provider.name != name);
+                    if !valid {
+                        return Err(ConfigurationError::new(
+                            key.into(),
+                            ErrorKind::EnvVarValueInvalid,
+                        ));
+                    }
+                    let prefix = format!("OAUTH_{}_", name.to_uppercase());
+                    providers.push(OauthProvider::init(name, &prefix)?);
+                }
+                Ok(providers)
+            }
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(vec![OauthProvider::init(DEFAULT_OAUTH_PROVIDER, "OAUTH_")?])
+            }
             Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
                 key.into(),
                 ErrorKind::EnvVarValueInvalid,
@@ -231,12 +247,8 @@ impl Configuration {
         )
     }
 
-    pub fn get_oauth_client_identifier(&self) -> String {
-        self.oauth_client_identifier.clone()
-    }
-
-    pub fn get_oauth_client_secret(&self) -> String {
-        self.oauth_client_secret.clone()
+    pub fn get_oauth_providers(&self) -> Vec<OauthProvider> {
+        self.oauth_providers.clone()
     }
 
     pub fn get_jwt_secret(&self) -> String {
@@ -244,6 +256,91 @@ impl Configuration {
     }
 }
 
+impl OauthProvider {
+    /// Reads the provider of the given name from the variables with the given prefix
+    fn init(name: &str, prefix: &str) -> Result<Self, ConfigurationError> {
+        Ok(Self {
+            name: name.into(),
+            client_identifier: OauthProvider::init_client_identifier(prefix)?,
+            client_secret: OauthProvider::init_client_secret(prefix)?,
+            issuer_url: OauthProvider::init_issuer_url(prefix)?,
+            hosted_domain: OauthProvider::init_hosted_domain(prefix)?,
+        })
+    }
+
+    fn init_client_identifier(prefix: &str) -> Result<String, ConfigurationError> {
+        let key = format!("{}CLIENT_IDENTIFIER", prefix);
+        match var(&key) {
+            Ok(password) => Ok(password),
+            Err(VarError::NotPresent) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueRequired))
+            }
+            Err(VarError::NotUnicode(_)) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueInvalid))
+            }
+        }
+    }
+
+    fn init_client_secret(prefix: &str) -> Result<String, ConfigurationError> {
+        let key = format!("{}CLIENT_SECRET", prefix);
+        match var(&key) {
+            Ok(password) => Ok(password),
+            Err(VarError::NotPresent) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueRequired))
+            }
+            Err(VarError::NotUnicode(_)) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueInvalid))
+            }
+        }
+    }
+
+    fn init_issuer_url(prefix: &str) -> Result<Uri, ConfigurationError> {
+        let key = format!("{}ISSUER_URL", prefix);
+        match var(&key) {
+            Ok(url) => Ok(Uri::from_str(&url)
+                .map_err(|_| ConfigurationError::new(key, ErrorKind::EnvVarValueInvalid))?),
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Uri::from_static("https://accounts.google.com"))
+            }
+            Err(VarError::NotUnicode(_)) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueInvalid))
+            }
+        }
+    }
+
+    fn init_hosted_domain(prefix: &str) -> Result<Option<String>, ConfigurationError> {
+        let key = format!("{}HOSTED_DOMAIN", prefix);
+        match var(&key) {
+            Ok(domain) => Ok(Some(domain)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => {
+                Err(ConfigurationError::new(key, ErrorKind::EnvVarValueInvalid))
+            }
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_client_identifier(&self) -> String {
+        self.client_identifier.clone()
+    }
+
+    pub fn get_client_secret(&self) -> String {
+        self.client_secret.clone()
+    }
+
+    pub fn get_issuer_url(&self) -> Uri {
+        self.issuer_url.clone()
+    }
+
+    pub fn get_hosted_domain(&self) -> Option<String> {
+        self.hosted_domain.clone()
+    }
+}
+
 pub fn get_configuration() -> Configuration {
     dotenv().ok();
     Configuration::init().expect("Failed to create configuration")
@@ -264,6 +361,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -272,8 +372,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -291,6 +396,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -299,8 +407,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -318,6 +431,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result =
@@ -337,6 +453,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -345,8 +464,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -364,6 +488,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -372,8 +499,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -391,6 +523,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result =
@@ -410,6 +545,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -418,8 +556,13 @@ mod tests {
             db_name: "postgres".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -437,6 +580,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -445,8 +591,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -464,6 +615,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -472,8 +626,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "postgres".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -491,6 +650,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -499,8 +661,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -518,6 +685,9 @@ mod tests {
         remove_var("DB_PASSWORD");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -526,8 +696,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -545,6 +720,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -553,8 +731,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -572,6 +755,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -580,8 +766,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -599,6 +790,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         remove_var("OAUTH_CLIENT_IDENTIFIER");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let result = Configuration::init();
@@ -622,6 +816,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -630,8 +827,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -649,6 +851,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         remove_var("OAUTH_CLIENT_SECRET");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let result = Configuration::init();
@@ -662,6 +867,176 @@ mod tests {
         )
     }
 
+    #[test]
+    #[ignore]
+    fn uts_oauth_issuer_url_and_hosted_domain_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
+        set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        set_var("OAUTH_ISSUER_URL", "http://127.0.0.1:9000/realms/library");
+        set_var("OAUTH_HOSTED_DOMAIN", "example.net");
+        remove_var("OAUTH_PROVIDERS");
+        set_var("JWT_SECRET", "jwt_secret");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("http://127.0.0.1:9000/realms/library"),
+                hosted_domain: Some("example.net".into()),
+            }],
+            jwt_secret: "jwt_secret".into(),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_oauth_issuer_url_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
+        set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        set_var("OAUTH_ISSUER_URL", "http://exa mple.net");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
+        set_var("JWT_SECRET", "jwt_secret");
+
+        let result = Configuration::init();
+
+        assert_eq!(
+            Err(ConfigurationError::new(
+                "OAUTH_ISSUER_URL".into(),
+                ErrorKind::EnvVarValueInvalid
+            )),
+            result
+        )
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_oauth_providers_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("OAUTH_PROVIDERS", "google, campus_sso");
+        set_var("OAUTH_GOOGLE_CLIENT_IDENTIFIER", "google_client_identifier");
+        set_var("OAUTH_GOOGLE_CLIENT_SECRET", "google_client_secret");
+        remove_var("OAUTH_GOOGLE_ISSUER_URL");
+        remove_var("OAUTH_GOOGLE_HOSTED_DOMAIN");
+        set_var(
+            "OAUTH_CAMPUS_SSO_CLIENT_IDENTIFIER",
+            "campus_client_identifier",
+        );
+        set_var("OAUTH_CAMPUS_SSO_CLIENT_SECRET", "campus_client_secret");
+        set_var(
+            "OAUTH_CAMPUS_SSO_ISSUER_URL",
+            "http://127.0.0.1:9000/realms/library",
+        );
+        remove_var("OAUTH_CAMPUS_SSO_HOSTED_DOMAIN");
+        set_var("JWT_SECRET", "jwt_secret");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            oauth_providers: vec![
+                OauthProvider {
+                    name: "google".into(),
+                    client_identifier: "google_client_identifier".into(),
+                    client_secret: "google_client_secret".into(),
+                    issuer_url: Uri::from_static("https://accounts.google.com"),
+                    hosted_domain: None,
+                },
+                OauthProvider {
+                    name: "campus_sso".into(),
+                    client_identifier: "campus_client_identifier".into(),
+                    client_secret: "campus_client_secret".into(),
+                    issuer_url: Uri::from_static("http://127.0.0.1:9000/realms/library"),
+                    hosted_domain: None,
+                },
+            ],
+            jwt_secret: "jwt_secret".into(),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_oauth_providers_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("OAUTH_GOOGLE_CLIENT_IDENTIFIER", "google_client_identifier");
+        set_var("OAUTH_GOOGLE_CLIENT_SECRET", "google_client_secret");
+        set_var("JWT_SECRET", "jwt_secret");
+
+        for providers in ["", "google,google", "google,Campus SSO"] {
+            set_var("OAUTH_PROVIDERS", providers);
+            let result = Configuration::init();
+
+            assert_eq!(
+                Err(ConfigurationError::new(
+                    "OAUTH_PROVIDERS".into(),
+                    ErrorKind::EnvVarValueInvalid
+                )),
+                result,
+                "{}",
+                providers
+            )
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_oauth_provider_client_secret_not_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("OAUTH_PROVIDERS", "campus_sso");
+        set_var(
+            "OAUTH_CAMPUS_SSO_CLIENT_IDENTIFIER",
+            "campus_client_identifier",
+        );
+        remove_var("OAUTH_CAMPUS_SSO_CLIENT_SECRET");
+        set_var("JWT_SECRET", "jwt_secret");
+
+        let result = Configuration::init();
+
+        assert_eq!(
+            Err(ConfigurationError::new(
+                "OAUTH_CAMPUS_SSO_CLIENT_SECRET".into(),
+                ErrorKind::EnvVarValueRequired
+            )),
+            result
+        )
+    }
+
     #[test]
     #[ignore]
     fn uts_jwt_secret_set() {
@@ -672,6 +1047,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         set_var("JWT_SECRET", "jwt_secret");
 
         let expected_result = Configuration {
@@ -680,8 +1058,13 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
-            oauth_client_identifier: "oauth_client_identifier".into(),
-            oauth_client_secret: "oauth_client_secret".into(),
+            oauth_providers: vec![OauthProvider {
+                name: "google".into(),
+                client_identifier: "oauth_client_identifier".into(),
+                client_secret: "oauth_client_secret".into(),
+                issuer_url: Uri::from_static("https://accounts.google.com"),
+                hosted_domain: None,
+            }],
             jwt_secret: "jwt_secret".into(),
         };
         let result = Configuration::init();
@@ -699,6 +1082,9 @@ mod tests {
         set_var("DB_PASSWORD", "db_password");
         set_var("OAUTH_CLIENT_IDENTIFIER", "oauth_client_identifier");
         set_var("OAUTH_CLIENT_SECRET", "oauth_client_secret");
+        remove_var("OAUTH_ISSUER_URL");
+        remove_var("OAUTH_HOSTED_DOMAIN");
+        remove_var("OAUTH_PROVIDERS");
         remove_var("JWT_SECRET");
 
         let result = Configuration::init();
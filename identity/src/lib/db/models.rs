@@ -58,3 +58,70 @@ pub struct Role {
     pub id: Uuid,
     pub name: String,
 }
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub email: String,
+    pub linked_at: NaiveDateTime,
+}
+
+impl From<UserIdentity> for RpcModels::Identity {
+    fn from(identity: UserIdentity) -> Self {
+        RpcModels::Identity {
+            provider: identity.provider,
+            subject: identity.subject,
+            email: identity.email,
+            linked_at: identity.linked_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "user_identities"]
+pub struct UserIdentityAdd {
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub email: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub issued_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "refresh_tokens"]
+pub struct RefreshTokenAdd {
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub issued_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "sessions"]
+pub struct SessionAdd {
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+}
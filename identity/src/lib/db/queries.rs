@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use diesel::result::QueryResult;
 use uuid::Uuid;
@@ -12,16 +13,46 @@ pub fn create_user(user: UserAddUpdate, db: &DbConn) -> QueryResult<User> {
     diesel::insert_into(users).values(&user).get_result(db)
 }
 
+/// Creates a user together with the identity at the provider, by which the user signed up
+pub fn create_user_with_identity(
+    user: UserAddUpdate,
+    provider: &str,
+    db: &DbConn,
+) -> QueryResult<User> {
+    use schema::user_identities::dsl::user_identities;
+    use schema::users::dsl::users;
+
+    db.transaction(|| {
+        let user: User = diesel::insert_into(users).values(&user).get_result(db)?;
+        diesel::insert_into(user_identities)
+            .values(&UserIdentityAdd {
+                user_id: user.id,
+                provider: provider.into(),
+                subject: user.sub.clone(),
+                email: user.email.clone(),
+            })
+            .execute(db)?;
+        Ok(user)
+    })
+}
+
 pub fn get_user(user_id: Uuid, db: &DbConn) -> QueryResult<User> {
     use schema::users::dsl::users;
 
     users.find(user_id).first(db)
 }
 
-pub fn get_user_by_sub(sub: &str, db: &DbConn) -> QueryResult<User> {
-    use schema::users::dsl;
+/// Returns the user, to whom the identity of the subject at the provider is linked
+pub fn get_user_by_identity(provider: &str, subject: &str, db: &DbConn) -> QueryResult<User> {
+    use schema::user_identities::dsl;
+    use schema::users;
 
-    dsl::users.filter(dsl::sub.eq(sub)).get_result(db)
+    users::table
+        .inner_join(dsl::user_identities)
+        .filter(dsl::provider.eq(provider))
+        .filter(dsl::subject.eq(subject))
+        .select(users::all_columns)
+        .get_result(db)
 }
 
 pub fn list_users(
@@ -50,14 +81,61 @@ pub fn update_user(user: User, db: &DbConn) -> QueryResult<User> {
         .get_result(db)
 }
 
-pub fn update_user_by_sub(user: UserAddUpdate, db: &DbConn) -> QueryResult<User> {
-    use schema::users::dsl::*;
+pub fn update_user_by_id(user: UserAddUpdate, db: &DbConn) -> QueryResult<User> {
+    use schema::users::dsl::users;
 
-    diesel::update(users.filter(sub.eq(&user.sub)))
+    diesel::update(users.find(user.id))
         .set(&user)
         .get_result(db)
 }
 
+pub fn list_user_identities(user_id: Uuid, db: &DbConn) -> QueryResult<Vec<UserIdentity>> {
+    use schema::user_identities::dsl;
+
+    dsl::user_identities
+        .filter(dsl::user_id.eq(user_id))
+        .order(dsl::linked_at)
+        .load(db)
+}
+
+pub fn create_user_identity(identity: UserIdentityAdd, db: &DbConn) -> QueryResult<UserIdentity> {
+    use schema::user_identities::dsl::user_identities;
+
+    diesel::insert_into(user_identities)
+        .values(&identity)
+        .get_result(db)
+}
+
+/// Deletes the identity of a user at the provider and returns the number of deleted
+/// identities, which is 0 if it is the last identity, as the user couldn't sign in without
+pub fn delete_user_identity(user_id: Uuid, provider: &str, db: &DbConn) -> QueryResult<usize> {
+    use schema::user_identities::dsl;
+
+    db.transaction(|| {
+        // locks the identities of the user, so that concurrent deletes can't remove all of them
+        let identities: Vec<UserIdentity> = dsl::user_identities
+            .filter(dsl::user_id.eq(user_id))
+            .for_update()
+            .load(db)?;
+        if !identities
+            .iter()
+            .any(|identity| identity.provider == provider)
+        {
+            return Err(diesel::result::Error::NotFound);
+        }
+        if identities.len() == 1 {
+            return Ok(0);
+        }
+
+        diesel::delete(
+            dsl::user_identities
+                .filter(dsl::user_id.eq(user_id))
+                .filter(dsl::provider.eq(provider)),
+        )
+        .execute(db)
+    })
+}
+
 pub fn get_role(role_id: Uuid, db: &DbConn) -> QueryResult<Role> {
     use schema::roles::dsl::roles;
 
@@ -75,3 +153,104 @@ pub fn list_roles(offset: i64, limit: i64, db: &DbConn) -> QueryResult<Vec<Role>
 
     roles.offset(offset).limit(limit).load(db)
 }
+
+pub fn create_refresh_token(token: RefreshTokenAdd, db: &DbConn) -> QueryResult<RefreshToken> {
+    use schema::refresh_tokens::dsl::refresh_tokens;
+
+    diesel::insert_into(refresh_tokens)
+        .values(&token)
+        .get_result(db)
+}
+
+pub fn get_refresh_token_by_hash(token_hash: &str, db: &DbConn) -> QueryResult<RefreshToken> {
+    use schema::refresh_tokens::dsl;
+
+    dsl::refresh_tokens
+        .filter(dsl::token_hash.eq(token_hash))
+        .get_result(db)
+}
+
+/// Marks a refresh token as used and returns the number of marked tokens, which is 0 if
+/// the token was used or revoked before, even by a concurrent request
+pub fn use_refresh_token(token_id: Uuid, time: NaiveDateTime, db: &DbConn) -> QueryResult<usize> {
+    use schema::refresh_tokens::dsl::*;
+
+    diesel::update(
+        refresh_tokens
+            .find(token_id)
+            .filter(used_at.is_null())
+            .filter(revoked_at.is_null()),
+    )
+    .set(used_at.eq(time))
+    .execute(db)
+}
+
+pub fn create_session(session: SessionAdd, db: &DbConn) -> QueryResult<Session> {
+    use schema::sessions::dsl::sessions;
+
+    diesel::insert_into(sessions)
+        .values(&session)
+        .get_result(db)
+}
+
+/// Returns a session, unless it is revoked or its user is inactive
+pub fn get_valid_session(session_id: Uuid, db: &DbConn) -> QueryResult<Session> {
+    use schema::sessions::dsl;
+    use schema::users;
+
+    dsl::sessions
+        .inner_join(users::table)
+        .filter(dsl::id.eq(session_id))
+        .filter(dsl::revoked_at.is_null())
+        .filter(users::active.eq(true))
+        .select(schema::sessions::all_columns)
+        .get_result(db)
+}
+
+/// Revokes a session together with its refresh tokens and returns the number of revoked
+/// sessions, which is 0 if it was revoked before
+pub fn revoke_session(session_id: Uuid, time: NaiveDateTime, db: &DbConn) -> QueryResult<usize> {
+    use schema::refresh_tokens::dsl as refresh_tokens;
+    use schema::sessions::dsl as sessions;
+
+    db.transaction(|| {
+        diesel::update(
+            refresh_tokens::refresh_tokens
+                .filter(refresh_tokens::family_id.eq(session_id))
+                .filter(refresh_tokens::revoked_at.is_null()),
+        )
+        .set(refresh_tokens::revoked_at.eq(time))
+        .execute(db)?;
+        diesel::update(
+            sessions::sessions
+                .find(session_id)
+                .filter(sessions::revoked_at.is_null()),
+        )
+        .set(sessions::revoked_at.eq(time))
+        .execute(db)
+    })
+}
+
+/// Revokes the sessions of a user together with their refresh tokens and returns the number
+/// of revoked sessions
+pub fn revoke_user_sessions(user_id: Uuid, time: NaiveDateTime, db: &DbConn) -> QueryResult<usize> {
+    use schema::refresh_tokens::dsl as refresh_tokens;
+    use schema::sessions::dsl as sessions;
+
+    db.transaction(|| {
+        diesel::update(
+            refresh_tokens::refresh_tokens
+                .filter(refresh_tokens::user_id.eq(user_id))
+                .filter(refresh_tokens::revoked_at.is_null()),
+        )
+        .set(refresh_tokens::revoked_at.eq(time))
+        .execute(db)?;
+        diesel::update(
+            sessions::sessions
+                .filter(sessions::user_id.eq(user_id))
+                .filter(sessions::revoked_at.is_null()),
+        )
+        .set(sessions::revoked_at.eq(time))
+        .execute(db)
+    })
+}
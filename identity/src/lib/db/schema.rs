@@ -1,3 +1,16 @@
+table! {
+    refresh_tokens (id) {
+        id -> Uuid,
+        family_id -> Uuid,
+        user_id -> Uuid,
+        token_hash -> Varchar,
+        issued_at -> Timestamp,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     roles (id) {
         id -> Uuid,
@@ -5,6 +18,26 @@ table! {
     }
 }
 
+table! {
+    sessions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    user_identities (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        provider -> Varchar,
+        subject -> Varchar,
+        email -> Varchar,
+        linked_at -> Timestamp,
+    }
+}
+
 table! {
     users (id) {
         id -> Uuid,
@@ -21,6 +54,10 @@ table! {
     }
 }
 
+joinable!(refresh_tokens -> sessions (family_id));
+joinable!(refresh_tokens -> users (user_id));
+joinable!(sessions -> users (user_id));
+joinable!(user_identities -> users (user_id));
 joinable!(users -> roles (role_id));
 
-allow_tables_to_appear_in_same_query!(roles, users,);
+allow_tables_to_appear_in_same_query!(refresh_tokens, roles, sessions, user_identities, users,);
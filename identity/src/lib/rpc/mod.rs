@@ -11,7 +11,7 @@ use tokio_serde::formats::Json;
 
 use self::server::IdentityServer;
 use self::service::{IdentityService, IdentityServiceClient};
-use crate::{config::Configuration, db::DbPool};
+use crate::{authentication::provider::Providers, config::Configuration, db::DbPool};
 
 pub async fn get_rpc_server(
     addr: SocketAddr,
@@ -20,13 +20,24 @@ pub async fn get_rpc_server(
 ) -> io::Result<(impl Future<Output = ()>, SocketAddr)> {
     let incoming = tarpc::serde_transport::tcp::listen(&addr, Json::default).await?;
     let addr = incoming.local_addr();
+    // the endpoints of the providers and their keys are shared by all channels
+    let providers = Providers::discover(configuration.get_oauth_providers())
+        .await
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("OpenID discovery failed: {:?}", e),
+            )
+        })?;
+    let providers = Arc::new(providers);
 
     let fut = incoming
         .filter_map(|r| future::ready(r.ok()))
         .map(BaseChannel::with_defaults)
         .max_channels_per_key(8, |t| t.as_ref().peer_addr().unwrap().ip())
         .map(move |channel| {
-            let server = IdentityServer::new(configuration.clone(), db_pool.clone());
+            let server =
+                IdentityServer::new(configuration.clone(), db_pool.clone(), providers.clone());
             channel.requests().execute(server.serve())
         })
         .buffer_unordered(10)
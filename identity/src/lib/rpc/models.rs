@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -28,14 +29,36 @@ pub struct OauthClientIdentifier {
     pub identifier: String,
 }
 
+/// Provider, with whose accounts users sign in
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct OauthProvider {
+    pub name: String,
+    pub identifier: String,
+}
+
+/// Account of a user at a provider, by which the user signs in
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Identity {
+    pub provider: String,
+    pub subject: String,
+    pub email: String,
+    pub linked_at: NaiveDateTime,
+}
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct SessionToken {
     pub token: String,
+    /// Seconds until the token expires
+    pub expires_in: i64,
+    /// Token, by which the session is renewed once
+    pub refresh_token: String,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct SessionInfo {
     pub sub: Uuid,
+    /// Id of the session
+    pub jti: Uuid,
     pub given_name: String,
     pub family_name: String,
     pub picture: String,
@@ -47,6 +70,7 @@ impl From<Jwt> for SessionInfo {
     fn from(jwt: Jwt) -> Self {
         Self {
             sub: jwt.sub,
+            jti: jwt.jti,
             given_name: jwt.given_name,
             family_name: jwt.family_name,
             picture: jwt.picture,
@@ -1,31 +1,40 @@
 use std::sync::Arc;
 
 use chrono::{Duration, Utc};
+use diesel::Connection;
 use tarpc::context;
 use uuid::Uuid;
 
 use helpers::rpc::{Error, RpcResult};
 
 use super::{models::*, service::IdentityService};
-use crate::authentication::oauth::{
-    AuthorizationCode, DiscoveryDocument, GrantType, IdToken, RedirectUri, TokenRequest,
-};
 use crate::authentication::{
-    check_account_status, create_user_from_oauth_authentication, AccountStatus,
+    check_account_status, create_user_from_oauth_authentication, oauth::AuthorizationCode,
+    provider::Providers, AccountStatus,
 };
 use crate::config::Configuration;
-use crate::db::{queries, DbConn, DbPool};
-use crate::session::jwt::Jwt;
+use crate::db::{
+    models::{RefreshTokenAdd, SessionAdd, User as DbUser, UserAddUpdate, UserIdentityAdd},
+    queries, DbConn, DbPool,
+};
+use crate::session::{
+    jwt::Jwt, refresh::RefreshToken, REFRESH_TOKEN_VALIDITY, SESSION_TOKEN_VALIDITY,
+};
 
 #[derive(Clone)]
 pub struct IdentityServer {
     conf: Arc<Configuration>,
     db_pool: Arc<DbPool>,
+    providers: Arc<Providers>,
 }
 
 impl IdentityServer {
-    pub fn new(conf: Arc<Configuration>, db_pool: Arc<DbPool>) -> Self {
-        Self { conf, db_pool }
+    pub fn new(conf: Arc<Configuration>, db_pool: Arc<DbPool>, providers: Arc<Providers>) -> Self {
+        Self {
+            conf,
+            db_pool,
+            providers,
+        }
     }
 
     fn get_db(&self) -> DbConn {
@@ -33,6 +42,38 @@ impl IdentityServer {
             .get()
             .expect("Can't retrieve connection from pool")
     }
+
+    /// Returns a session token of the user together with a refresh token, which belongs to
+    /// the family of the tokens issued since the user authenticated, identified by the session
+    fn issue_session_token(&self, user: DbUser, session_id: Uuid) -> RpcResult<SessionToken> {
+        let now = Utc::now();
+        let refresh_token = RefreshToken::generate();
+        queries::create_refresh_token(
+            RefreshTokenAdd {
+                family_id: session_id,
+                user_id: user.id,
+                token_hash: refresh_token.hash,
+                issued_at: now.naive_utc(),
+                expires_at: (now + Duration::seconds(REFRESH_TOKEN_VALIDITY)).naive_utc(),
+            },
+            &self.get_db(),
+        )?;
+
+        Ok(SessionToken {
+            token: Jwt::new(
+                user.id,
+                session_id,
+                user.given_name,
+                user.family_name,
+                user.picture,
+                now,
+                Duration::seconds(SESSION_TOKEN_VALIDITY),
+            )
+            .encode(&self.conf.get_jwt_secret()),
+            expires_in: SESSION_TOKEN_VALIDITY,
+            refresh_token: refresh_token.token,
+        })
+    }
 }
 
 #[tarpc::server]
@@ -102,7 +143,15 @@ impl IdentityService for IdentityServer {
 
     /// Switches the status of an user account between enabled and disabled
     async fn update_user(self, _: context::Context, user_update: User) -> RpcResult<User> {
-        let result = queries::update_user(user_update.into(), &self.get_db());
+        let db = self.get_db();
+        let result = db.transaction::<_, diesel::result::Error, _>(|| {
+            let user = queries::update_user(user_update.into(), &db)?;
+            // a disabled user is signed out of all sessions along with the update
+            if !user.active {
+                queries::revoke_user_sessions(user.id, Utc::now().naive_utc(), &db)?;
+            }
+            Ok(user)
+        });
 
         match result {
             Ok(val) => Ok(User {
@@ -142,45 +191,54 @@ impl IdentityService for IdentityServer {
         }
     }
 
-    /// Returns an OAuth 2.0 client identifier.
+    /// Returns the OAuth 2.0 client identifier of the default provider.
     async fn oauth_client_identifier(
         self,
         _: context::Context,
     ) -> RpcResult<OauthClientIdentifier> {
         Ok(OauthClientIdentifier {
-            identifier: self.conf.get_oauth_client_identifier(),
+            identifier: self.providers.get(None)?.get_client_identifier(),
         })
     }
 
+    /// Returns the providers, with whose accounts users sign in, the default one first
+    async fn oauth_providers(self, _: context::Context) -> RpcResult<Vec<OauthProvider>> {
+        Ok(self
+            .providers
+            .iter()
+            .map(|provider| OauthProvider {
+                name: provider.get_name(),
+                identifier: provider.get_client_identifier(),
+            })
+            .collect())
+    }
+
     /// Returns a session token and creates or updates an user account
     async fn oauth_authentication(
         self,
         _: context::Context,
         code: OauthAuthorizationCode,
+        provider: Option<String>,
     ) -> RpcResult<SessionToken> {
+        let provider = self.providers.get(provider.as_deref())?;
         // Checks if the authorization code has a valid form
         let authorization_code = AuthorizationCode::new(code)?;
 
-        // Creates a TokenRequest instance
-        let request = TokenRequest::new(
-            self.conf.get_oauth_client_identifier(),
-            self.conf.get_oauth_client_secret(),
-            authorization_code,
-            RedirectUri::PostMessage,
-            GrantType::AuthorizationCode,
-        );
-
-        // Exchanges the TokenRequest for a Tokenset
-        let tokenset = request
-            .exchange_code(DiscoveryDocument::get_token_endpoint())
-            .await?;
-
-        // Creates an IdToken from the TokenSet
-        let id_token = IdToken::new(&tokenset.id_token)?;
+        // Exchanges the code for a verified IdToken and the TokenSet
+        let (id_token, tokenset) = provider.authenticate(authorization_code).await?;
 
-        // Checks the status of the user about to authenticate
-        let account_status =
-            check_account_status(queries::get_user_by_sub(&id_token.sub, &self.get_db()))?;
+        // Checks the status of the user, to whom the account at the provider is linked
+        let existing_user =
+            queries::get_user_by_identity(&provider.get_name(), &id_token.sub, &self.get_db());
+        let (user_id, sub, role_id) = match &existing_user {
+            Ok(user) => (user.id, user.sub.clone(), user.role_id),
+            Err(_) => (
+                Uuid::new_v4(),
+                id_token.sub.clone(),
+                queries::get_role_by_name("User", &self.get_db())?.id,
+            ),
+        };
+        let account_status = check_account_status(existing_user)?;
 
         // Checks if the user account is inactive or authentication has missing refresh token for new account
         if account_status == AccountStatus::Inactive {
@@ -192,45 +250,164 @@ impl IdentityService for IdentityServer {
             return Err(Error::InvalidInput);
         }
 
-        // Get user role
-        let user_role = queries::get_role_by_name("User", &self.get_db())?;
-
         // Creates an user model instance from IdToken and TokenSet
         let user = create_user_from_oauth_authentication(
             &id_token,
             &tokenset,
             Utc::now().naive_utc(),
-            Uuid::new_v4(),
-            user_role.id,
+            user_id,
+            role_id,
         );
 
         // Create or update user record in database
         let user = match account_status {
-            AccountStatus::Active => queries::update_user_by_sub(user, &self.get_db())?,
-            AccountStatus::New => queries::create_user(user, &self.get_db())?,
+            AccountStatus::Active => {
+                queries::update_user_by_id(UserAddUpdate { sub, ..user }, &self.get_db())?
+            }
+            AccountStatus::New => {
+                queries::create_user_with_identity(user, &provider.get_name(), &self.get_db())?
+            }
             _ => return Err(Error::InternalError),
         };
         log::info!("Successfully created/updated account '{}'", &id_token.email);
 
-        // Create and return SessionToken
-        Ok(SessionToken {
-            token: Jwt::new(
-                user.id,
-                user.given_name,
-                user.family_name,
-                user.picture,
-                Utc::now(),
-                Duration::seconds(3600),
-            )
-            .encode(&self.conf.get_jwt_secret()),
-        })
+        // Create and return SessionToken of a new session, which starts a new family of
+        // refresh tokens
+        let session = queries::create_session(
+            SessionAdd {
+                user_id: user.id,
+                created_at: Utc::now().naive_utc(),
+            },
+            &self.get_db(),
+        )?;
+        self.issue_session_token(user, session.id)
     }
 
-    /// Returns the validity and content of a session token
+    /// Returns the identities of a user, by which the user signs in
+    async fn list_identities(self, _: context::Context, user_id: Uuid) -> RpcResult<Vec<Identity>> {
+        Ok(queries::list_user_identities(user_id, &self.get_db())?
+            .into_iter()
+            .map(Identity::from)
+            .collect())
+    }
+
+    /// Links the account at a provider to a user, who may then sign in with either account
+    async fn link_identity(
+        self,
+        _: context::Context,
+        user_id: Uuid,
+        provider: String,
+        code: OauthAuthorizationCode,
+    ) -> RpcResult<Identity> {
+        let provider = self.providers.get(Some(&provider))?;
+        let authorization_code = AuthorizationCode::new(code)?;
+        if !queries::get_user(user_id, &self.get_db())?.active {
+            return Err(Error::InvalidInput);
+        }
+
+        let (id_token, _) = provider.authenticate(authorization_code).await?;
+        let identity = queries::create_user_identity(
+            UserIdentityAdd {
+                user_id,
+                provider: provider.get_name(),
+                subject: id_token.sub,
+                email: id_token.email,
+            },
+            &self.get_db(),
+        )?;
+        log::info!(
+            "Linked account '{}' of provider '{}' to user '{}'",
+            &identity.email,
+            &identity.provider,
+            user_id
+        );
+        Ok(identity.into())
+    }
+
+    /// Unlinks the account at a provider from a user, unless the user can't sign in without
+    async fn unlink_identity(
+        self,
+        _: context::Context,
+        user_id: Uuid,
+        provider: String,
+    ) -> RpcResult<()> {
+        match queries::delete_user_identity(user_id, &provider, &self.get_db())? {
+            0 => Err(Error::PolicyViolation),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the validity and content of a session token, which is invalid once its
+    /// session is revoked or its user is inactive
     async fn session_info(self, _: context::Context, token: String) -> RpcResult<SessionInfo> {
-        match Jwt::decode(&self.conf.get_jwt_secret(), &token) {
-            Ok(val) => Ok(val.into()),
-            Err(_) => Err(Error::InvalidData),
+        let jwt = match Jwt::decode(&self.conf.get_jwt_secret(), &token) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::InvalidData),
+        };
+        match queries::get_valid_session(jwt.jti, &self.get_db()) {
+            Ok(_) => Ok(jwt.into()),
+            Err(diesel::result::Error::NotFound) => Err(Error::InvalidData),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Revokes the session of a session token together with its refresh tokens
+    async fn logout(self, _: context::Context, token: String) -> RpcResult<()> {
+        let jwt = match Jwt::decode(&self.conf.get_jwt_secret(), &token) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::InvalidData),
+        };
+        match queries::revoke_session(jwt.jti, Utc::now().naive_utc(), &self.get_db())? {
+            0 => Err(Error::InvalidData),
+            _ => Ok(()),
+        }
+    }
+
+    /// Revokes all sessions of a user together with their refresh tokens
+    async fn logout_everywhere(self, _: context::Context, user_id: Uuid) -> RpcResult<()> {
+        let revoked =
+            queries::revoke_user_sessions(user_id, Utc::now().naive_utc(), &self.get_db())?;
+        log::info!("Revoked {} sessions of user '{}'", revoked, user_id);
+        Ok(())
+    }
+
+    /// Returns a new session token for a refresh token, which is replaced by a new one.
+    /// A refresh token used twice may be stolen, so that its whole session is revoked.
+    async fn refresh_session(
+        self,
+        _: context::Context,
+        refresh_token: String,
+    ) -> RpcResult<SessionToken> {
+        let db = self.get_db();
+        let now = Utc::now().naive_utc();
+        let token =
+            match queries::get_refresh_token_by_hash(&RefreshToken::hash(&refresh_token), &db) {
+                Ok(val) => val,
+                Err(diesel::result::Error::NotFound) => return Err(Error::InvalidData),
+                Err(e) => return Err(e.into()),
+            };
+        if token.revoked_at.is_some() || token.expires_at <= now {
+            return Err(Error::InvalidData);
+        }
+        if token.used_at.is_some() || queries::use_refresh_token(token.id, now, &db)? == 0 {
+            log::warn!(
+                "Reused refresh token of user '{}', revoked its session '{}'",
+                token.user_id,
+                token.family_id
+            );
+            queries::revoke_session(token.family_id, now, &db)?;
+            return Err(Error::InvalidData);
+        }
+
+        let user = queries::get_user(token.user_id, &db)?;
+        if !user.active {
+            log::info!(
+                "Rejected session renewal of inactive account '{}'",
+                &user.email
+            );
+            queries::revoke_session(token.family_id, now, &db)?;
+            return Err(Error::InvalidData);
         }
+        self.issue_session_token(user, token.family_id)
     }
 }
@@ -13,6 +13,20 @@ pub trait IdentityService {
     async fn get_role(role_id: Uuid) -> RpcResult<Role>;
     async fn list_roles(offset: u32, limit: u32) -> RpcResult<Vec<Role>>;
     async fn oauth_client_identifier() -> RpcResult<OauthClientIdentifier>;
-    async fn oauth_authentication(code: OauthAuthorizationCode) -> RpcResult<SessionToken>;
+    async fn oauth_providers() -> RpcResult<Vec<OauthProvider>>;
+    async fn oauth_authentication(
+        code: OauthAuthorizationCode,
+        provider: Option<String>,
+    ) -> RpcResult<SessionToken>;
+    async fn list_identities(user_id: Uuid) -> RpcResult<Vec<Identity>>;
+    async fn link_identity(
+        user_id: Uuid,
+        provider: String,
+        code: OauthAuthorizationCode,
+    ) -> RpcResult<Identity>;
+    async fn unlink_identity(user_id: Uuid, provider: String) -> RpcResult<()>;
     async fn session_info(token: String) -> RpcResult<SessionInfo>;
+    async fn refresh_session(refresh_token: String) -> RpcResult<SessionToken>;
+    async fn logout(token: String) -> RpcResult<()>;
+    async fn logout_everywhere(user_id: Uuid) -> RpcResult<()>;
 }
@@ -6,6 +6,8 @@ use uuid::Uuid;
 #[derive(Serialize, PartialEq, Debug, Deserialize)]
 pub struct Jwt {
     pub sub: Uuid,
+    /// Id of the session, by which the token can be revoked before it expires
+    pub jti: Uuid,
     pub given_name: String,
     pub family_name: String,
     pub picture: String,
@@ -21,6 +23,7 @@ pub enum JwtError {
 impl Jwt {
     pub fn new(
         sub: Uuid,
+        jti: Uuid,
         given_name: String,
         family_name: String,
         picture: String,
@@ -29,6 +32,7 @@ impl Jwt {
     ) -> Jwt {
         Jwt {
             sub,
+            jti,
             given_name,
             family_name,
             picture,
@@ -67,10 +71,12 @@ mod tests {
     #[test]
     fn jwt_creation() {
         let id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
         let creation_time = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
         let validity = Duration::seconds(3600);
         let jwt = Jwt::new(
             id,
+            session_id,
             "given_name".to_string(),
             "family_name".to_string(),
             "picture".to_string(),
@@ -79,6 +85,7 @@ mod tests {
         );
 
         assert_eq!(id, jwt.sub);
+        assert_eq!(session_id, jwt.jti);
         assert_eq!("given_name", jwt.given_name);
         assert_eq!("family_name", jwt.family_name);
         assert_eq!("picture", jwt.picture);
@@ -89,6 +96,7 @@ mod tests {
     #[test]
     fn jwt_encoding_decoding() {
         let jwt = Jwt::new(
+            Uuid::new_v4(),
             Uuid::new_v4(),
             "given_name".to_string(),
             "family_name".to_string(),
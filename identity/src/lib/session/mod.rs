@@ -1 +1,8 @@
 pub mod jwt;
+pub mod refresh;
+
+/// Seconds a session token is valid, before the client renews it by its refresh token
+pub const SESSION_TOKEN_VALIDITY: i64 = 3600;
+
+/// Seconds a refresh token is valid, so that a session ends once it isn't renewed for 30 days
+pub const REFRESH_TOKEN_VALIDITY: i64 = 30 * 24 * 3600;
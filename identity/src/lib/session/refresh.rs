@@ -0,0 +1,48 @@
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+
+/// Opaque token, by which a client renews its session once.
+/// Only the hash of the token is stored, so that the stored tokens can't be used if leaked.
+#[derive(Debug)]
+pub struct RefreshToken {
+    pub token: String,
+    pub hash: String,
+}
+
+impl RefreshToken {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .expect("Can't generate random refresh token");
+        let token = base64::encode_config(bytes, base64::URL_SAFE_NO_PAD);
+
+        RefreshToken {
+            hash: RefreshToken::hash(&token),
+            token,
+        }
+    }
+
+    /// Returns the hash of a token, by which it is stored
+    pub fn hash(token: &str) -> String {
+        base64::encode_config(digest(&SHA256, token.as_bytes()), base64::URL_SAFE_NO_PAD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_token_generation() {
+        let first = RefreshToken::generate();
+        let second = RefreshToken::generate();
+
+        assert_eq!(43, first.token.len());
+        assert_ne!(first.token, second.token);
+        assert_eq!(RefreshToken::hash(&first.token), first.hash);
+        assert_ne!(first.token, first.hash);
+    }
+}
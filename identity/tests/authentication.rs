@@ -0,0 +1,326 @@
+//! Tests of the OpenID authentication against the mock issuer
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use chrono::Utc;
+use hyper::Uri;
+use jsonwebtoken::{Algorithm, Validation};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use identity::authentication::jwks::KeyStore;
+use identity::authentication::oauth::{
+    AuthorizationCode, DiscoveryDocument, Error, GrantType, IdToken, RedirectUri, TokenRequest,
+    UserInfo,
+};
+
+mod common;
+
+use common::{sign, MockIssuer};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Claims {
+    sub: String,
+    exp: i64,
+}
+
+fn claims() -> Claims {
+    Claims {
+        sub: "1".into(),
+        exp: Utc::now().timestamp() + 3600,
+    }
+}
+
+#[tokio::test]
+async fn verify_token_by_cached_key() {
+    let issuer = MockIssuer::start(&["a"]);
+    let keys = KeyStore::new(issuer.jwks_uri());
+    let validation = Validation::new(Algorithm::RS256);
+
+    for _ in 0..2 {
+        let result = keys
+            .verify::<Claims>(&sign(&claims(), "a"), &validation)
+            .await;
+        assert_eq!(Ok(claims().sub), result.map(|claims| claims.sub));
+    }
+    assert_eq!(1, issuer.key_fetches.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn fetch_keys_again_for_rotated_key() {
+    let issuer = MockIssuer::start(&["a"]);
+    let keys = KeyStore::new(issuer.jwks_uri()).with_min_refresh_interval(Duration::from_secs(0));
+    let validation = Validation::new(Algorithm::RS256);
+
+    assert!(keys
+        .verify::<Claims>(&sign(&claims(), "a"), &validation)
+        .await
+        .is_ok());
+    *issuer.kids.lock().unwrap() = vec!["b"];
+    assert!(keys
+        .verify::<Claims>(&sign(&claims(), "b"), &validation)
+        .await
+        .is_ok());
+    assert_eq!(2, issuer.key_fetches.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn reject_unknown_key_within_refresh_interval() {
+    let issuer = MockIssuer::start(&["a"]);
+    let keys = KeyStore::new(issuer.jwks_uri());
+    let validation = Validation::new(Algorithm::RS256);
+
+    assert!(keys
+        .verify::<Claims>(&sign(&claims(), "a"), &validation)
+        .await
+        .is_ok());
+    assert_eq!(
+        Err(Error::IdTokenKeyUnknown),
+        keys.verify::<Claims>(&sign(&claims(), "b"), &validation)
+            .await
+    );
+    assert_eq!(1, issuer.key_fetches.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn reject_token_with_invalid_signature() {
+    let issuer = MockIssuer::start(&["a"]);
+    let keys = KeyStore::new(issuer.jwks_uri());
+    let validation = Validation::new(Algorithm::RS256);
+
+    let token = sign(&claims(), "a");
+    let other = sign(
+        &Claims {
+            sub: "2".into(),
+            ..claims()
+        },
+        "a",
+    );
+    let mut parts: Vec<&str> = token.split('.').collect();
+    parts[1] = other.split('.').nth(1).unwrap();
+    assert_eq!(
+        Err(Error::IdTokenInvalid),
+        keys.verify::<Claims>(&parts.join("."), &validation).await
+    );
+}
+
+fn id_token_claims(issuer: &MockIssuer) -> Value {
+    json!({
+        "iss": format!("http://{}", issuer.issuer.authority().unwrap()),
+        "aud": "client",
+        "sub": "1",
+        "iat": Utc::now().timestamp(),
+        "exp": Utc::now().timestamp() + 3600,
+        "email": "john.doe@example.net",
+        "given_name": "John",
+        "family_name": "Doe",
+        "hd": "example.net",
+        "picture": "https://example.com/avatar.jpg",
+    })
+}
+
+async fn verify(
+    issuer: &MockIssuer,
+    claims: Value,
+    hosted_domain: Option<&str>,
+) -> Result<IdToken, Error> {
+    let discovery = DiscoveryDocument::fetch(issuer.issuer.clone()).await?;
+    let keys = KeyStore::new(discovery.get_jwks_uri());
+    IdToken::verify(
+        &sign(&claims, "a"),
+        &keys,
+        &discovery,
+        "client",
+        hosted_domain,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn discovery_document() {
+    let issuer = MockIssuer::start(&["a"]);
+    let discovery = DiscoveryDocument::fetch(issuer.issuer.clone())
+        .await
+        .unwrap();
+    assert_eq!(issuer.jwks_uri(), discovery.get_jwks_uri());
+    assert_eq!("/token", discovery.get_token_endpoint().path());
+    assert_eq!(
+        Some("/userinfo"),
+        discovery.get_userinfo_endpoint().as_ref().map(Uri::path)
+    );
+
+    let other_issuer = format!("http://localhost:{}", issuer.issuer.port_u16().unwrap());
+    assert_eq!(
+        Error::DiscoveryIssuerMismatch,
+        DiscoveryDocument::fetch(other_issuer.parse().unwrap())
+            .await
+            .unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn id_token_valid() {
+    let issuer = MockIssuer::start(&["a"]);
+    let id_token = verify(&issuer, id_token_claims(&issuer), Some("example.net"))
+        .await
+        .unwrap();
+    assert_eq!("1", id_token.sub);
+    assert_eq!(Some("example.net".to_string()), id_token.hd);
+
+    let mut claims = id_token_claims(&issuer);
+    claims["aud"] = json!(["other-client", "client"]);
+    claims["azp"] = "client".into();
+    claims.as_object_mut().unwrap().remove("hd");
+    let id_token = verify(&issuer, claims, None).await.unwrap();
+    assert_eq!(vec!["other-client", "client"], id_token.aud);
+}
+
+#[tokio::test]
+async fn id_token_audience_invalid() {
+    let issuer = MockIssuer::start(&["a"]);
+    let mut claims = id_token_claims(&issuer);
+    claims["aud"] = "other-client".into();
+    assert_eq!(
+        Err(Error::IdTokenInvalid),
+        verify(&issuer, claims.clone(), None).await.map(|_| ())
+    );
+
+    claims["aud"] = json!(["other-client", "third-client"]);
+    claims["azp"] = "client".into();
+    assert_eq!(
+        Err(Error::IdTokenInvalid),
+        verify(&issuer, claims, None).await.map(|_| ())
+    );
+}
+
+#[tokio::test]
+async fn id_token_authorized_party_invalid() {
+    let issuer = MockIssuer::start(&["a"]);
+    let mut claims = id_token_claims(&issuer);
+    claims["aud"] = json!(["other-client", "client"]);
+    assert_eq!(
+        Err(Error::IdTokenAuthorizedPartyInvalid),
+        verify(&issuer, claims.clone(), None).await.map(|_| ())
+    );
+
+    claims["azp"] = "other-client".into();
+    assert_eq!(
+        Err(Error::IdTokenAuthorizedPartyInvalid),
+        verify(&issuer, claims, None).await.map(|_| ())
+    );
+}
+
+#[tokio::test]
+async fn id_token_expired() {
+    let issuer = MockIssuer::start(&["a"]);
+    let mut claims = id_token_claims(&issuer);
+    claims["exp"] = (Utc::now().timestamp() - 3600).into();
+    assert_eq!(
+        Err(Error::IdTokenInvalid),
+        verify(&issuer, claims, None).await.map(|_| ())
+    );
+}
+
+#[tokio::test]
+async fn id_token_issuer_invalid() {
+    let issuer = MockIssuer::start(&["a"]);
+    let mut claims = id_token_claims(&issuer);
+    claims["iss"] = "https://accounts.example.com".into();
+    assert_eq!(
+        Err(Error::IdTokenIssuerInvalid),
+        verify(&issuer, claims, None).await.map(|_| ())
+    );
+}
+
+#[tokio::test]
+async fn id_token_hosted_domain_invalid() {
+    let issuer = MockIssuer::start(&["a"]);
+    let mut claims = id_token_claims(&issuer);
+    claims["hd"] = "example.com".into();
+    assert_eq!(
+        Err(Error::IdTokenHostedDomainInvalid),
+        verify(&issuer, claims, Some("example.net"))
+            .await
+            .map(|_| ())
+    );
+
+    let mut claims = id_token_claims(&issuer);
+    claims.as_object_mut().unwrap().remove("hd");
+    assert_eq!(
+        Err(Error::IdTokenHostedDomainInvalid),
+        verify(&issuer, claims, Some("example.net"))
+            .await
+            .map(|_| ())
+    );
+}
+
+#[tokio::test]
+async fn exchange_code_and_complete_id_token() {
+    let issuer = MockIssuer::start(&["a"]);
+    let discovery = DiscoveryDocument::fetch(issuer.issuer.clone())
+        .await
+        .unwrap();
+    let mut claims = id_token_claims(&issuer);
+    for claim in ["email", "given_name", "family_name", "picture"] {
+        claims.as_object_mut().unwrap().remove(claim);
+    }
+    *issuer.token_set.lock().unwrap() = json!({
+        "access_token": "access",
+        "expires_in": 300,
+        "token_type": "Bearer",
+        "id_token": sign(&claims, "a"),
+    })
+    .to_string();
+    *issuer.userinfo.lock().unwrap() = json!({
+        "sub": "1",
+        "email": "john.doe@example.net",
+        "given_name": "John",
+        "family_name": "Doe",
+    })
+    .to_string();
+
+    let token_set = TokenRequest::new(
+        "client".into(),
+        "secret".into(),
+        AuthorizationCode::new("4/0Ab.c~d".into()).unwrap(),
+        RedirectUri::PostMessage,
+        GrantType::AuthorizationCode,
+    )
+    .exchange_code(discovery.get_token_endpoint())
+    .await
+    .unwrap();
+    assert_eq!(
+        vec!["client_id=client&client_secret=secret&code=4%2F0Ab.c%7Ed\
+              &redirect_uri=postmessage&grant_type=authorization_code"
+            .to_string()],
+        *issuer.token_requests.lock().unwrap()
+    );
+
+    let keys = KeyStore::new(discovery.get_jwks_uri());
+    let mut id_token = IdToken::verify(&token_set.id_token, &keys, &discovery, "client", None)
+        .await
+        .unwrap();
+    assert!(id_token.is_incomplete());
+    let user_info = UserInfo::fetch(
+        discovery.get_userinfo_endpoint().unwrap(),
+        &token_set.access_token,
+    )
+    .await
+    .unwrap();
+    id_token.complete(user_info).unwrap();
+    assert!(!id_token.is_incomplete());
+    assert_eq!("john.doe@example.net", id_token.email);
+    assert_eq!("", id_token.picture);
+
+    let other_user = UserInfo {
+        sub: "2".into(),
+        email: None,
+        given_name: None,
+        family_name: None,
+        picture: None,
+    };
+    assert_eq!(
+        Err(Error::UserInfoSubjectMismatch),
+        id_token.complete(other_user)
+    );
+}
@@ -7,7 +7,7 @@ use diesel::{prelude::*, result::Error};
 use uuid::Uuid;
 
 use identity::config::Configuration;
-use identity::db::schema::{roles, users::dsl::users};
+use identity::db::schema::{roles, user_identities::dsl::user_identities, users::dsl::users};
 use identity::db::{get_db_pool, models::*, queries};
 
 mod sample_data;
@@ -51,10 +51,15 @@ impl DbTestContext {
             .unwrap();
 
         // insert sample data for tests
+        let sample_users = sample_data::users(user_role.id);
         diesel::insert_into(users)
-            .values(&sample_data::users(user_role.id))
+            .values(&sample_users)
             .execute(&conn)
             .expect("Error inserting 'users' sample data");
+        diesel::insert_into(user_identities)
+            .values(&sample_data::user_identities(&sample_users))
+            .execute(&conn)
+            .expect("Error inserting 'user_identities' sample data");
 
         Self {
             connection_url,
@@ -162,9 +167,9 @@ async fn db_get_user_not_exists() {
     assert_eq!(Err(Error::NotFound), result);
 }
 
-// get an user by its sub
+// get an user by an identity at a provider
 #[tokio::test]
-async fn db_get_user_by_sub() {
+async fn db_get_user_by_identity() {
     // Arrange
     let (_configuration, db_pool, _db_test_context) = setup(stdext::function_name!().into())
         .await
@@ -187,10 +192,14 @@ async fn db_get_user_by_sub() {
     };
 
     // Act
-    let result = queries::get_user_by_sub("2", &db_pool.get().unwrap());
+    let result =
+        queries::get_user_by_identity("campus_sso", "f:campus:jkerr", &db_pool.get().unwrap());
+    let result_other_provider =
+        queries::get_user_by_identity("campus_sso", "2", &db_pool.get().unwrap());
 
     // Assert
     assert_eq!(Ok(expected_result), result);
+    assert_eq!(Err(Error::NotFound), result_other_provider);
 }
 
 // list active users with offset/limit
@@ -301,9 +310,9 @@ async fn db_update_user_verify() {
     assert_eq!(Ok(expected_result), result);
 }
 
-// verify update user by sub
+// verify update user by id
 #[tokio::test]
-async fn db_update_user_by_sub_verify() {
+async fn db_update_user_by_id_verify() {
     // Arrange
     let (_configuration, db_pool, _db_test_context) = setup(stdext::function_name!().into())
         .await
@@ -340,12 +349,135 @@ async fn db_update_user_by_sub_verify() {
     };
 
     // Act
-    let result = queries::update_user_by_sub(expected_change, &db_pool.get().unwrap());
+    let result = queries::update_user_by_id(expected_change, &db_pool.get().unwrap());
 
     // Assert
     assert_eq!(Ok(expected_result), result);
 }
 
+// create an user with the identity, by which the user signed up
+#[tokio::test]
+async fn db_create_user_with_identity() {
+    // Arrange
+    let (_configuration, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+
+    let user_role = queries::get_role_by_name("User", &db_pool.get().unwrap()).unwrap();
+    let new_user = UserAddUpdate {
+        id: Uuid::new_v4(),
+        sub: "2".into(),
+        email: "kim.kerr@campus.example.edu".into(),
+        given_name: "Kim".into(),
+        family_name: "Kerr".into(),
+        picture: "".into(),
+        oauth_access_token: "access_token".into(),
+        oauth_access_token_valid: NaiveDate::from_ymd(2020, 12, 31).and_hms(0, 0, 0),
+        oauth_refresh_token: Some("refresh_token".into()),
+        active: true,
+        role_id: user_role.id,
+    };
+
+    // Act
+    let result =
+        queries::create_user_with_identity(new_user.clone(), "campus_sso", &db_pool.get().unwrap());
+    let duplicate_id = Uuid::new_v4();
+    let duplicate = queries::create_user_with_identity(
+        UserAddUpdate {
+            id: duplicate_id,
+            ..new_user.clone()
+        },
+        "campus_sso",
+        &db_pool.get().unwrap(),
+    );
+
+    // Assert
+    assert_eq!(Ok(new_user.id), result.map(|user| user.id));
+    assert_eq!(
+        Ok(new_user.id),
+        queries::get_user_by_identity("campus_sso", "2", &db_pool.get().unwrap())
+            .map(|user| user.id)
+    );
+    // the user isn't created without the identity
+    assert!(duplicate.is_err());
+    assert_eq!(
+        Err(Error::NotFound),
+        queries::get_user(duplicate_id, &db_pool.get().unwrap())
+    );
+}
+
+// delete the identities of an user, but not the last one
+#[tokio::test]
+async fn db_delete_user_identity() {
+    // Arrange
+    let (_configuration, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+
+    // Act
+    let result_unknown = queries::delete_user_identity(user_id, "gitlab", &db_pool.get().unwrap());
+    let result_linked = queries::delete_user_identity(user_id, "google", &db_pool.get().unwrap());
+    let result_last = queries::delete_user_identity(user_id, "campus_sso", &db_pool.get().unwrap());
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result_unknown);
+    assert_eq!(Ok(1), result_linked);
+    assert_eq!(Ok(0), result_last);
+    assert_eq!(
+        Ok(vec!["campus_sso".to_string()]),
+        queries::list_user_identities(user_id, &db_pool.get().unwrap()).map(|identities| {
+            identities
+                .into_iter()
+                .map(|identity| identity.provider)
+                .collect()
+        })
+    );
+}
+
+// revoke sessions one by one and by user
+#[tokio::test]
+async fn db_revoke_session() {
+    // Arrange
+    let (_configuration, db_pool, _db_test_context) = setup(stdext::function_name!().into())
+        .await
+        .expect("Could not set up test environment");
+
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+    let time = NaiveDate::from_ymd(2021, 11, 1).and_hms(12, 0, 0);
+    let sessions: Vec<Session> = (0..3)
+        .map(|_| {
+            queries::create_session(
+                SessionAdd {
+                    user_id,
+                    created_at: time,
+                },
+                &db_pool.get().unwrap(),
+            )
+            .unwrap()
+        })
+        .collect();
+
+    // Act
+    let result_session = queries::revoke_session(sessions[0].id, time, &db_pool.get().unwrap());
+    let result_revoked = queries::revoke_session(sessions[0].id, time, &db_pool.get().unwrap());
+    let result_valid = queries::get_valid_session(sessions[1].id, &db_pool.get().unwrap());
+    let result_user = queries::revoke_user_sessions(user_id, time, &db_pool.get().unwrap());
+
+    // Assert
+    assert_eq!(Ok(1), result_session);
+    assert_eq!(Ok(0), result_revoked);
+    assert_eq!(Ok(sessions[1].clone()), result_valid);
+    assert_eq!(Ok(2), result_user);
+    for session in sessions {
+        assert_eq!(
+            Err(Error::NotFound),
+            queries::get_valid_session(session.id, &db_pool.get().unwrap())
+        );
+    }
+}
+
 // get a valid role
 #[tokio::test]
 async fn db_get_role_exists() {
@@ -1,20 +1,27 @@
 use std::env::{set_var, var};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use chrono::{Duration, Utc};
 use diesel::prelude::*;
+use serde_json::json;
 use tarpc::context;
 use uuid::Uuid;
 
 use helpers::rpc::Error;
-use identity::db::schema::{roles, users::dsl::users};
+use identity::db::schema::{roles, user_identities::dsl::user_identities, users::dsl::users};
 use identity::db::{get_db_pool, models, queries, DbPool};
-use identity::rpc::models::{Role, SessionInfo, User};
+use identity::rpc::models::{OauthProvider, Role, SessionInfo, SessionToken, User};
 use identity::rpc::{get_rpc_client, get_rpc_server, service::IdentityServiceClient};
-use identity::{config::Configuration, session::jwt::Jwt};
+use identity::{
+    config::Configuration,
+    session::{jwt::Jwt, refresh::RefreshToken},
+};
 
+mod common;
 mod sample_data;
 
+use common::{sign, MockIssuer};
+
 #[macro_use]
 extern crate diesel_migrations;
 
@@ -54,10 +61,15 @@ impl DbTestContext {
             .unwrap();
 
         // insert sample data for tests
+        let sample_users = sample_data::users(user_role.id);
         diesel::insert_into(users)
-            .values(&sample_data::users(user_role.id))
+            .values(&sample_users)
             .execute(&conn)
             .expect("Error inserting 'users' sample data");
+        diesel::insert_into(user_identities)
+            .values(&sample_data::user_identities(&sample_users))
+            .execute(&conn)
+            .expect("Error inserting 'user_identities' sample data");
 
         Self {
             connection_url,
@@ -88,14 +100,68 @@ impl Drop for DbTestContext {
     }
 }
 
+/// Issuer shared by all tests, as the configuration is read from the environment
+static ISSUER: OnceLock<MockIssuer> = OnceLock::new();
+
+fn get_issuer() -> &'static MockIssuer {
+    ISSUER.get_or_init(|| MockIssuer::start(&["a"]))
+}
+
+/// Lets the mock issuer answer the authorization code by the tokens of an account, whose
+/// ID token is issued for the client
+fn issue_token_set(code: &str, client_identifier: &str, sub: &str, email: &str) {
+    let issuer = get_issuer();
+    let id_token = sign(
+        &json!({
+            "iss": issuer.issuer.to_string().trim_end_matches('/'),
+            "sub": sub,
+            "aud": client_identifier,
+            "exp": Utc::now().timestamp() + 3600,
+            "iat": Utc::now().timestamp(),
+            "email": email,
+            "given_name": "Jane",
+            "family_name": "Doe",
+            "picture": "https://example.com/avatar.jpg",
+        }),
+        "a",
+    );
+    let token_set = json!({
+        "access_token": "access",
+        "expires_in": 3599,
+        "refresh_token": "refresh",
+        "scope": "openid email profile",
+        "token_type": "Bearer",
+        "id_token": id_token,
+    });
+    issuer
+        .token_sets_by_code
+        .lock()
+        .unwrap()
+        .insert(code.into(), token_set.to_string());
+}
+
 fn get_test_configuration() -> Configuration {
     set_var("SERVICE_SOCKET", "127.0.0.1:0");
-    if var("OAUTH_CLIENT_IDENTIFIER").is_err() {
-        set_var("OAUTH_CLIENT_IDENTIFIER", "test_oauth_client_identifier");
-    }
-    if var("OAUTH_CLIENT_SECRET").is_err() {
-        set_var("OAUTH_CLIENT_SECRET", "test_oauth_client_secret");
-    }
+    // both providers are served by the mock issuer, but for different clients
+    set_var("OAUTH_PROVIDERS", "google,campus_sso");
+    set_var("OAUTH_GOOGLE_ISSUER_URL", get_issuer().issuer.to_string());
+    set_var(
+        "OAUTH_GOOGLE_CLIENT_IDENTIFIER",
+        "test_oauth_client_identifier",
+    );
+    set_var("OAUTH_GOOGLE_CLIENT_SECRET", "test_oauth_client_secret");
+    set_var(
+        "OAUTH_CAMPUS_SSO_ISSUER_URL",
+        get_issuer().issuer.to_string(),
+    );
+    set_var(
+        "OAUTH_CAMPUS_SSO_CLIENT_IDENTIFIER",
+        "test_campus_client_identifier",
+    );
+    set_var(
+        "OAUTH_CAMPUS_SSO_CLIENT_SECRET",
+        "test_campus_client_secret",
+    );
     if var("JWT_SECRET").is_err() {
         set_var("JWT_SECRET", "test_jwt_secret");
     }
@@ -346,16 +412,42 @@ async fn list_roles_exists() {
     assert_eq!(Ok(expected_result), result);
 }
 
-// test oauth authentication
+// test oauth authentication of a new account against the mock issuer
 #[tokio::test]
 async fn oauth_authentication() {
-    // Check if test requirements are met
-    // This test will silently fail if the envrionment variable "OAUTH_AUTHORIZATION_CODE" is not set
-    let authorization_code = match var("OAUTH_AUTHORIZATION_CODE") {
-        Ok(val) => val,
-        Err(_) => return,
-    };
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
 
+    issue_token_set(
+        "code_new_account",
+        "test_oauth_client_identifier",
+        "mock-sub",
+        "jane.doe@example.com",
+    );
+
+    // Act
+    let result = client
+        .oauth_authentication(context::current(), "code_new_account".into(), None)
+        .await
+        .unwrap();
+
+    // Assert
+    let session = client
+        .session_info(context::current(), result.unwrap().token)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!("Jane", session.given_name);
+    assert_eq!("Doe", session.family_name);
+}
+
+// test oauth authentication by an identity linked to an existing account
+#[tokio::test]
+async fn oauth_authentication_linked_identity() {
     // Arrange
     let (server, client, _configuration, _db_pool, _db_test_context) =
         setup(stdext::function_name!().into())
@@ -363,14 +455,166 @@ async fn oauth_authentication() {
             .expect("Could not set up test environment");
     tokio::spawn(server);
 
+    issue_token_set(
+        "code_linked_identity",
+        "test_campus_client_identifier",
+        "f:campus:jkerr",
+        "jkerr@campus.example.edu",
+    );
+
     // Act
     let result = client
-        .oauth_authentication(context::current(), authorization_code)
+        .oauth_authentication(
+            context::current(),
+            "code_linked_identity".into(),
+            Some("campus_sso".into()),
+        )
+        .await
+        .unwrap();
+    let result_unknown_provider = client
+        .oauth_authentication(
+            context::current(),
+            "code_linked_identity".into(),
+            Some("gitlab".into()),
+        )
         .await
         .unwrap();
 
     // Assert
-    assert!(result.is_ok());
+    let session = client
+        .session_info(context::current(), result.unwrap().token)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap(),
+        session.sub
+    );
+    assert_eq!(Err(Error::NotFound), result_unknown_provider);
+}
+
+// test providers
+#[tokio::test]
+async fn oauth_providers() {
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client.oauth_providers(context::current()).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        Ok(vec![
+            OauthProvider {
+                name: "google".into(),
+                identifier: "test_oauth_client_identifier".into(),
+            },
+            OauthProvider {
+                name: "campus_sso".into(),
+                identifier: "test_campus_client_identifier".into(),
+            },
+        ]),
+        result
+    );
+}
+
+// test linking an identity at another provider
+#[tokio::test]
+async fn link_identity() {
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let user_id = Uuid::parse_str("d1854dea-c0b7-403c-bbe8-fba377453787").unwrap();
+    issue_token_set(
+        "code_link_jdoe",
+        "test_campus_client_identifier",
+        "f:campus:jdoe",
+        "jdoe@campus.example.edu",
+    );
+    issue_token_set(
+        "code_link_jkerr",
+        "test_campus_client_identifier",
+        "f:campus:jkerr",
+        "jkerr@campus.example.edu",
+    );
+
+    // Act
+    let result = client
+        .link_identity(
+            context::current(),
+            user_id,
+            "campus_sso".into(),
+            "code_link_jdoe".into(),
+        )
+        .await
+        .unwrap();
+    // the identity is linked to another user
+    let result_linked_to_other = client
+        .link_identity(
+            context::current(),
+            Uuid::parse_str("42cf1a7b-b7ca-4baf-9dfa-41f4f454a7cf").unwrap(),
+            "campus_sso".into(),
+            "code_link_jkerr".into(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let identity = result.unwrap();
+    assert_eq!("f:campus:jdoe", identity.subject);
+    assert_eq!("jdoe@campus.example.edu", identity.email);
+    assert_eq!(Err(Error::AlreadyExists), result_linked_to_other);
+    assert_eq!(
+        vec!["google".to_string(), "campus_sso".to_string()],
+        client
+            .list_identities(context::current(), user_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|identity| identity.provider)
+            .collect::<Vec<_>>()
+    );
+}
+
+// test unlinking identities, of which the last one is kept
+#[tokio::test]
+async fn unlink_identity() {
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+
+    // Act
+    let result = client
+        .unlink_identity(context::current(), user_id, "google".into())
+        .await
+        .unwrap();
+    let result_last = client
+        .unlink_identity(context::current(), user_id, "campus_sso".into())
+        .await
+        .unwrap();
+    let result_not_linked = client
+        .unlink_identity(context::current(), user_id, "google".into())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(Err(Error::PolicyViolation), result_last);
+    assert_eq!(Err(Error::NotFound), result_not_linked);
 }
 
 // test client identifier
@@ -391,21 +635,31 @@ async fn oauth_client_identifier() {
         .unwrap();
 
     // Assert
-    assert_eq!(var("OAUTH_CLIENT_IDENTIFIER").unwrap(), result.identifier);
+    assert_eq!("test_oauth_client_identifier", result.identifier);
 }
 
 // test session info with valid jwt signature
 #[tokio::test]
 async fn session_info_valid_token() {
     // Arrange
-    let (server, client, configuration, _db_pool, _db_test_context) =
+    let (server, client, configuration, db_pool, _db_test_context) =
         setup(stdext::function_name!().into())
             .await
             .expect("Could not set up test environment");
     tokio::spawn(server);
 
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+    let session = queries::create_session(
+        models::SessionAdd {
+            user_id,
+            created_at: Utc::now().naive_utc(),
+        },
+        &db_pool.get().unwrap(),
+    )
+    .unwrap();
     let token = Jwt::new(
-        Uuid::new_v4(),
+        user_id,
+        session.id,
         "John".into(),
         "Doe".into(),
         "https://example.com/avatar.jpg".into(),
@@ -424,6 +678,7 @@ async fn session_info_valid_token() {
     // Act
     let token = SessionInfo {
         sub: token.sub,
+        jti: token.jti,
         given_name: token.given_name.clone(),
         family_name: token.family_name.clone(),
         picture: token.picture.clone(),
@@ -439,14 +694,24 @@ async fn session_info_valid_token() {
 #[tokio::test]
 async fn session_info_invalid_token() {
     // Arrange
-    let (server, client, configuration, _db_pool, _db_test_context) =
+    let (server, client, configuration, db_pool, _db_test_context) =
         setup(stdext::function_name!().into())
             .await
             .expect("Could not set up test environment");
     tokio::spawn(server);
 
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+    let session = queries::create_session(
+        models::SessionAdd {
+            user_id,
+            created_at: Utc::now().naive_utc(),
+        },
+        &db_pool.get().unwrap(),
+    )
+    .unwrap();
     let token = Jwt::new(
-        Uuid::new_v4(),
+        user_id,
+        session.id,
         "John".into(),
         "Doe".into(),
         "https://example.com/person.jpg".into(),
@@ -466,3 +731,279 @@ async fn session_info_invalid_token() {
     // Assert
     assert_eq!(Err(Error::InvalidData), result);
 }
+
+// sign in by the mock issuer and return the tokens of the session
+async fn sign_in(client: &IdentityServiceClient, code: &str) -> SessionToken {
+    issue_token_set(
+        code,
+        "test_oauth_client_identifier",
+        "f:campus:jkerr",
+        "jkerr@campus.example.edu",
+    );
+    client
+        .oauth_authentication(context::current(), code.into(), None)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+// test renewing a session by rotating refresh tokens
+#[tokio::test]
+async fn refresh_session_rotation() {
+    // Arrange
+    let (server, client, configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let refresh_token = sign_in(&client, "code_refresh_rotation")
+        .await
+        .refresh_token;
+
+    // Act
+    let first = client
+        .refresh_session(context::current(), refresh_token.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let second = client
+        .refresh_session(context::current(), first.refresh_token.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_ne!(refresh_token, first.refresh_token);
+    assert_ne!(first.refresh_token, second.refresh_token);
+    assert_eq!(3600, second.expires_in);
+    assert!(Jwt::decode(&configuration.get_jwt_secret(), &second.token).is_ok());
+}
+
+// test revoking the family of a reused refresh token
+#[tokio::test]
+async fn refresh_session_reuse() {
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let refresh_token = sign_in(&client, "code_refresh_reuse").await.refresh_token;
+    let other_session = sign_in(&client, "code_refresh_reuse_other")
+        .await
+        .refresh_token;
+    let renewed = client
+        .refresh_session(context::current(), refresh_token.clone())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result_reuse = client
+        .refresh_session(context::current(), refresh_token)
+        .await
+        .unwrap();
+    let result_renewed = client
+        .refresh_session(context::current(), renewed.refresh_token)
+        .await
+        .unwrap();
+    let result_other_session = client
+        .refresh_session(context::current(), other_session)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidData), result_reuse);
+    assert_eq!(Err(Error::InvalidData), result_renewed);
+    assert!(result_other_session.is_ok());
+}
+
+// test rejecting unknown and expired refresh tokens
+#[tokio::test]
+async fn refresh_session_invalid_token() {
+    // Arrange
+    let (server, client, _configuration, db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let user_id = Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap();
+    let session = queries::create_session(
+        models::SessionAdd {
+            user_id,
+            created_at: (Utc::now() - Duration::days(31)).naive_utc(),
+        },
+        &db_pool.get().unwrap(),
+    )
+    .unwrap();
+    let expired = RefreshToken::generate();
+    queries::create_refresh_token(
+        models::RefreshTokenAdd {
+            family_id: session.id,
+            user_id,
+            token_hash: expired.hash,
+            issued_at: (Utc::now() - Duration::days(31)).naive_utc(),
+            expires_at: (Utc::now() - Duration::days(1)).naive_utc(),
+        },
+        &db_pool.get().unwrap(),
+    )
+    .unwrap();
+
+    // Act
+    let result_unknown = client
+        .refresh_session(context::current(), RefreshToken::generate().token)
+        .await
+        .unwrap();
+    let result_expired = client
+        .refresh_session(context::current(), expired.token)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidData), result_unknown);
+    assert_eq!(Err(Error::InvalidData), result_expired);
+}
+
+// test revoking a session and its refresh tokens by logging out
+#[tokio::test]
+async fn logout() {
+    // Arrange
+    let (server, client, _configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let session = sign_in(&client, "code_logout").await;
+    let other_session = sign_in(&client, "code_logout_other").await;
+
+    // Act
+    let result = client
+        .logout(context::current(), session.token.clone())
+        .await
+        .unwrap();
+    let result_repeated = client
+        .logout(context::current(), session.token.clone())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(Err(Error::InvalidData), result_repeated);
+    assert_eq!(
+        Err(Error::InvalidData),
+        client
+            .session_info(context::current(), session.token)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        Err(Error::InvalidData),
+        client
+            .refresh_session(context::current(), session.refresh_token)
+            .await
+            .unwrap()
+    );
+    assert!(client
+        .session_info(context::current(), other_session.token)
+        .await
+        .unwrap()
+        .is_ok());
+}
+
+// test revoking all sessions of a user
+#[tokio::test]
+async fn logout_everywhere() {
+    // Arrange
+    let (server, client, configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let sessions = vec![
+        sign_in(&client, "code_logout_everywhere").await,
+        sign_in(&client, "code_logout_everywhere_other").await,
+    ];
+    let user_id = Jwt::decode(&configuration.get_jwt_secret(), &sessions[0].token)
+        .unwrap()
+        .sub;
+
+    // Act
+    let result = client
+        .logout_everywhere(context::current(), user_id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    for session in sessions {
+        assert_eq!(
+            Err(Error::InvalidData),
+            client
+                .session_info(context::current(), session.token)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Err(Error::InvalidData),
+            client
+                .refresh_session(context::current(), session.refresh_token)
+                .await
+                .unwrap()
+        );
+    }
+}
+
+// test ending the sessions of a deactivated user
+#[tokio::test]
+async fn session_info_inactive_user() {
+    // Arrange
+    let (server, client, configuration, _db_pool, _db_test_context) =
+        setup(stdext::function_name!().into())
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let session = sign_in(&client, "code_session_inactive_user").await;
+    let user_id = Jwt::decode(&configuration.get_jwt_secret(), &session.token)
+        .unwrap()
+        .sub;
+    let user = client
+        .get_user(context::current(), user_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    client
+        .update_user(
+            context::current(),
+            User {
+                active: false,
+                ..user
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        Err(Error::InvalidData),
+        client
+            .session_info(context::current(), session.token)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        Err(Error::InvalidData),
+        client
+            .refresh_session(context::current(), session.refresh_token)
+            .await
+            .unwrap()
+    );
+}
@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use uuid::Uuid;
 
-use identity::db::models::UserAddUpdate;
+use identity::db::models::{UserAddUpdate, UserIdentityAdd};
 
 pub fn users(role_id: Uuid) -> Vec<UserAddUpdate> {
     vec![
@@ -150,3 +150,23 @@ pub fn users(role_id: Uuid) -> Vec<UserAddUpdate> {
         },
     ]
 }
+
+/// Identities of the users at the default provider, and one more of Jack Kerr
+pub fn user_identities(users: &[UserAddUpdate]) -> Vec<UserIdentityAdd> {
+    let mut identities: Vec<UserIdentityAdd> = users
+        .iter()
+        .map(|user| UserIdentityAdd {
+            user_id: user.id,
+            provider: "google".into(),
+            subject: user.sub.clone(),
+            email: user.email.clone(),
+        })
+        .collect();
+    identities.push(UserIdentityAdd {
+        user_id: Uuid::parse_str("a930312e-eb70-41e4-bf74-d88bf661d4dd").unwrap(),
+        provider: "campus_sso".into(),
+        subject: "f:campus:jkerr".into(),
+        email: "jkerr@campus.example.edu".into(),
+    });
+    identities
+}
@@ -0,0 +1,5 @@
+DROP INDEX delivery_attempts_notification_id_idx;
+DROP INDEX notifications_recipient_idx;
+
+DROP TABLE delivery_attempts;
+DROP TABLE notifications;
@@ -0,0 +1,23 @@
+-- The recipient is an user of the IDENTITY service, which migrates the shared
+-- database on its own schedule, so it is not referenced by a foreign key.
+CREATE TABLE notifications (
+    id UUID,
+    recipient UUID NOT NULL,
+    kind VARCHAR NOT NULL,
+    parameters JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (id)
+);
+
+-- Every try to deliver a notification over a channel, error is NULL on success
+CREATE TABLE delivery_attempts (
+    id UUID,
+    notification_id UUID NOT NULL REFERENCES notifications ON DELETE CASCADE,
+    channel VARCHAR NOT NULL,
+    attempted_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    error TEXT,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX notifications_recipient_idx ON notifications (recipient);
+CREATE INDEX delivery_attempts_notification_id_idx ON delivery_attempts (notification_id);
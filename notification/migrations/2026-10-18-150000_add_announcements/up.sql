@@ -0,0 +1,2 @@
+-- Announcements are posted to a shared channel and have no recipient
+ALTER TABLE notifications ALTER COLUMN recipient DROP NOT NULL;
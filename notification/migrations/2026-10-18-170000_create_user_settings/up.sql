@@ -0,0 +1,7 @@
+-- Notification settings of the users of the IDENTITY service, users without
+-- settings get their notifications in the default language
+CREATE TABLE user_settings (
+    user_id UUID,
+    language VARCHAR(3) NOT NULL,
+    PRIMARY KEY (user_id)
+);
@@ -0,0 +1,11 @@
+DROP INDEX notifications_dead_idx;
+DROP INDEX notifications_pending_next_attempt_at_idx;
+
+ALTER TABLE notifications
+    DROP COLUMN next_attempt_at,
+    DROP COLUMN attempts,
+    DROP COLUMN status,
+    DROP COLUMN html,
+    DROP COLUMN body,
+    DROP COLUMN subject,
+    DROP COLUMN channel;
@@ -0,0 +1,37 @@
+-- Notifications are the outbox of the service: they are stored with their rendered
+-- message and delivered asynchronously over their channel. A PENDING notification is
+-- attempted at next_attempt_at, it ends DELIVERED or DEAD once all attempts failed.
+ALTER TABLE notifications
+    ADD COLUMN channel VARCHAR,
+    ADD COLUMN subject TEXT NOT NULL DEFAULT '',
+    ADD COLUMN body TEXT NOT NULL DEFAULT '',
+    ADD COLUMN html TEXT,
+    ADD COLUMN status VARCHAR NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'delivered', 'dead')),
+    ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN next_attempt_at TIMESTAMP WITH TIME ZONE;
+
+-- notifications stored before were delivered right away
+UPDATE notifications SET
+    channel = CASE WHEN recipient IS NULL THEN 'slack' ELSE 'email' END,
+    status = CASE
+        WHEN EXISTS (
+            SELECT 1 FROM delivery_attempts
+            WHERE delivery_attempts.notification_id = notifications.id AND error IS NULL
+        ) THEN 'delivered'
+        ELSE 'dead'
+    END,
+    attempts = (
+        SELECT COUNT(*) FROM delivery_attempts
+        WHERE delivery_attempts.notification_id = notifications.id
+    );
+
+ALTER TABLE notifications
+    ALTER COLUMN channel SET NOT NULL,
+    ALTER COLUMN subject DROP DEFAULT,
+    ALTER COLUMN body DROP DEFAULT,
+    ADD CHECK (status <> 'pending' OR next_attempt_at IS NOT NULL);
+
+CREATE INDEX notifications_pending_next_attempt_at_idx ON notifications (next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX notifications_dead_idx ON notifications (created_at) WHERE status = 'dead';
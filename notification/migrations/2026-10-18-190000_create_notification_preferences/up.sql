@@ -0,0 +1,18 @@
+-- Channel, on which an user gets the notifications of a kind, users without a
+-- preference for a kind get its notifications by email
+CREATE TABLE notification_preferences (
+    user_id UUID,
+    kind VARCHAR NOT NULL,
+    channel VARCHAR NOT NULL CHECK (channel IN ('email', 'slack_dm', 'none')),
+    PRIMARY KEY (user_id, kind)
+);
+
+-- Daily window in the time zone of an user, in which notifications are held back
+CREATE TABLE quiet_hours (
+    user_id UUID,
+    starts_at TIME NOT NULL,
+    ends_at TIME NOT NULL,
+    time_zone VARCHAR NOT NULL,
+    PRIMARY KEY (user_id),
+    CHECK (starts_at <> ends_at)
+);
@@ -1,10 +1,14 @@
 use dotenv::dotenv;
+use hyper::Uri;
+use lettre::message::Mailbox;
 
 use std::{
     env::{var, VarError},
     fmt,
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
+    path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 
 #[derive(Debug, PartialEq)]
@@ -14,6 +18,28 @@ pub struct Configuration {
     db_name: String,
     db_username: String,
     db_password: String,
+    smtp_host: String,
+    smtp_port: u16,
+    smtp_tls: SmtpTls,
+    smtp_username: Option<String>,
+    smtp_password: Option<String>,
+    mail_from: Mailbox,
+    slack_webhook_url: Option<Uri>,
+    template_dir: PathBuf,
+    default_language: String,
+    outbox_poll_interval: Duration,
+    outbox_retry_delay: Duration,
+    outbox_max_attempts: u16,
+    slack_bot_token: Option<String>,
+    slack_api_url: Uri,
+}
+
+/// Encryption of the connection to the SMTP server
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SmtpTls {
+    None,
+    StartTls,
+    Tls,
 }
 
 #[derive(Debug, PartialEq)]
@@ -68,6 +94,20 @@ impl Configuration {
             db_name: Configuration::init_db_name()?,
             db_username: Configuration::init_db_username()?,
             db_password: Configuration::init_db_password()?,
+            smtp_host: Configuration::init_smtp_host()?,
+            smtp_port: Configuration::init_smtp_port()?,
+            smtp_tls: Configuration::init_smtp_tls()?,
+            smtp_username: Configuration::init_smtp_username()?,
+            smtp_password: Configuration::init_smtp_password()?,
+            mail_from: Configuration::init_mail_from()?,
+            slack_webhook_url: Configuration::init_slack_webhook_url()?,
+            template_dir: Configuration::init_template_dir()?,
+            default_language: Configuration::init_default_language()?,
+            outbox_poll_interval: Configuration::init_outbox_poll_interval()?,
+            outbox_retry_delay: Configuration::init_outbox_retry_delay()?,
+            outbox_max_attempts: Configuration::init_outbox_max_attempts()?,
+            slack_bot_token: Configuration::init_slack_bot_token()?,
+            slack_api_url: Configuration::init_slack_api_url()?,
         })
     }
 
@@ -153,6 +193,258 @@ impl Configuration {
         }
     }
 
+    fn init_smtp_host() -> Result<String, ConfigurationError> {
+        let key = "SMTP_HOST";
+        match var(key) {
+            Ok(host) => Ok(host),
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(String::from("localhost"))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_smtp_port() -> Result<u16, ConfigurationError> {
+        let key = "SMTP_PORT";
+        match var(key) {
+            Ok(port) => match port.parse::<u16>() {
+                Ok(port) if port > 0 => Ok(port),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(25)
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_smtp_tls() -> Result<SmtpTls, ConfigurationError> {
+        let key = "SMTP_TLS";
+        match var(key) {
+            Ok(tls) => match tls.as_str() {
+                "none" => Ok(SmtpTls::None),
+                "starttls" => Ok(SmtpTls::StartTls),
+                "tls" => Ok(SmtpTls::Tls),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(SmtpTls::None)
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_smtp_username() -> Result<Option<String>, ConfigurationError> {
+        let key = "SMTP_USERNAME";
+        match var(key) {
+            Ok(username) => Ok(Some(username)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_smtp_password() -> Result<Option<String>, ConfigurationError> {
+        let key = "SMTP_PASSWORD";
+        match var(key) {
+            Ok(password) => Ok(Some(password)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_mail_from() -> Result<Mailbox, ConfigurationError> {
+        let key = "MAIL_FROM";
+        match var(key) {
+            Ok(mailbox) => Ok(mailbox
+                .parse()
+                .map_err(|_| ConfigurationError::new(key.into(), ErrorKind::EnvVarValueInvalid))?),
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok("CODE Library <library@localhost>".parse().unwrap())
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_slack_webhook_url() -> Result<Option<Uri>, ConfigurationError> {
+        let key = "SLACK_WEBHOOK_URL";
+        match var(key) {
+            Ok(url) => match url.parse::<Uri>() {
+                Ok(url) if url.scheme().is_some() && url.host().is_some() => Ok(Some(url)),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_template_dir() -> Result<PathBuf, ConfigurationError> {
+        let key = "TEMPLATE_DIR";
+        match var(key) {
+            Ok(directory) => Ok(PathBuf::from(directory)),
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(PathBuf::from("templates"))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_default_language() -> Result<String, ConfigurationError> {
+        let key = "DEFAULT_LANGUAGE";
+        match var(key) {
+            Ok(language)
+                if language.len() == 3 && language.chars().all(|c| c.is_ascii_lowercase()) =>
+            {
+                Ok(language)
+            }
+            Ok(_) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(String::from("eng"))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_outbox_poll_interval() -> Result<Duration, ConfigurationError> {
+        let key = "OUTBOX_POLL_INTERVAL";
+        match var(key) {
+            Ok(seconds) => match seconds.parse::<u32>() {
+                Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::from_secs(10))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_outbox_retry_delay() -> Result<Duration, ConfigurationError> {
+        let key = "OUTBOX_RETRY_DELAY";
+        match var(key) {
+            Ok(seconds) => match seconds.parse::<u32>() {
+                Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds.into())),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Duration::from_secs(60))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_outbox_max_attempts() -> Result<u16, ConfigurationError> {
+        let key = "OUTBOX_MAX_ATTEMPTS";
+        match var(key) {
+            Ok(attempts) => match attempts.parse::<u16>() {
+                Ok(attempts) if attempts > 0 => Ok(attempts),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(5)
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_slack_bot_token() -> Result<Option<String>, ConfigurationError> {
+        let key = "SLACK_BOT_TOKEN";
+        match var(key) {
+            Ok(token) => Ok(Some(token)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
+    fn init_slack_api_url() -> Result<Uri, ConfigurationError> {
+        let key = "SLACK_API_URL";
+        match var(key) {
+            Ok(url) => match url.trim_end_matches('/').parse::<Uri>() {
+                Ok(url) if url.scheme().is_some() && url.host().is_some() => Ok(url),
+                _ => Err(ConfigurationError::new(
+                    key.into(),
+                    ErrorKind::EnvVarValueInvalid,
+                )),
+            },
+            Err(VarError::NotPresent) => {
+                log::debug!("Environment variable {} uses default value.", key);
+                Ok(Uri::from_static("https://slack.com/api"))
+            }
+            Err(VarError::NotUnicode(_)) => Err(ConfigurationError::new(
+                key.into(),
+                ErrorKind::EnvVarValueInvalid,
+            )),
+        }
+    }
+
     pub fn get_service_socket(&self) -> SocketAddr {
         self.service_socket
     }
@@ -167,6 +459,72 @@ impl Configuration {
             self.db_name
         )
     }
+
+    pub fn get_db_connection_base_url(&self) -> String {
+        format!(
+            "postgres://{}:{}@{}:{}",
+            self.db_username,
+            self.db_password,
+            self.db_socket.ip(),
+            self.db_socket.port()
+        )
+    }
+
+    pub fn get_smtp_host(&self) -> &str {
+        &self.smtp_host
+    }
+
+    pub fn get_smtp_port(&self) -> u16 {
+        self.smtp_port
+    }
+
+    pub fn get_smtp_tls(&self) -> SmtpTls {
+        self.smtp_tls
+    }
+
+    /// Returns the SMTP credentials, if both username and password are set
+    pub fn get_smtp_credentials(&self) -> Option<(String, String)> {
+        match (&self.smtp_username, &self.smtp_password) {
+            (Some(username), Some(password)) => Some((username.clone(), password.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn get_mail_from(&self) -> Mailbox {
+        self.mail_from.clone()
+    }
+
+    pub fn get_slack_webhook_url(&self) -> Option<Uri> {
+        self.slack_webhook_url.clone()
+    }
+
+    pub fn get_template_dir(&self) -> PathBuf {
+        self.template_dir.clone()
+    }
+
+    pub fn get_default_language(&self) -> String {
+        self.default_language.clone()
+    }
+
+    pub fn get_outbox_poll_interval(&self) -> Duration {
+        self.outbox_poll_interval
+    }
+
+    pub fn get_outbox_retry_delay(&self) -> Duration {
+        self.outbox_retry_delay
+    }
+
+    pub fn get_outbox_max_attempts(&self) -> u16 {
+        self.outbox_max_attempts
+    }
+
+    pub fn get_slack_bot_token(&self) -> Option<String> {
+        self.slack_bot_token.clone()
+    }
+
+    pub fn get_slack_api_url(&self) -> Uri {
+        self.slack_api_url.clone()
+    }
 }
 
 pub fn get_configuration() -> Configuration {
@@ -187,6 +545,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -194,6 +566,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -208,6 +594,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000),
@@ -215,6 +615,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -229,6 +643,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result =
             ConfigurationError::new("SERVICE_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -245,6 +673,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -252,6 +694,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -266,6 +722,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -273,6 +743,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -287,6 +771,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result =
             ConfigurationError::new("DB_SOCKET".into(), ErrorKind::SocketAddrInvalid);
@@ -303,6 +801,20 @@ mod tests {
         remove_var("DB_NAME");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -310,6 +822,20 @@ mod tests {
             db_name: "postgres".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -324,6 +850,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -331,6 +871,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -345,6 +899,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         remove_var("DB_USERNAME");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -352,6 +920,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "postgres".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -366,6 +948,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -373,6 +969,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -387,6 +997,20 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         remove_var("DB_PASSWORD");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -394,6 +1018,20 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
@@ -408,6 +1046,197 @@ mod tests {
         set_var("DB_NAME", "db_name");
         set_var("DB_USERNAME", "db_username");
         set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_host_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        set_var("SMTP_HOST", "smtp.example.com");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "smtp.example.com".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_port_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        set_var("SMTP_PORT", "587");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 587,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_port_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        set_var("SMTP_PORT", "0");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("SMTP_PORT".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_tls_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        set_var("SMTP_TLS", "starttls");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
 
         let expected_result = Configuration {
             service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
@@ -415,9 +1244,752 @@ mod tests {
             db_name: "db_name".into(),
             db_username: "db_username".into(),
             db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::StartTls,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
         };
         let result = Configuration::init();
 
         assert_eq!(Ok(expected_result), result)
     }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_tls_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        set_var("SMTP_TLS", "ssl");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("SMTP_TLS".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_smtp_credentials_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        set_var("SMTP_USERNAME", "smtp_username");
+        set_var("SMTP_PASSWORD", "smtp_password");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: Some("smtp_username".into()),
+            smtp_password: Some("smtp_password".into()),
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_mail_from_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        set_var("MAIL_FROM", "Library <library@example.com>");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "Library <library@example.com>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_mail_from_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        set_var("MAIL_FROM", "library");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("MAIL_FROM".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+    #[test]
+    #[ignore]
+    fn uts_slack_webhook_url_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        set_var(
+            "SLACK_WEBHOOK_URL",
+            "https://hooks.slack.com/services/T0/B0/X",
+        );
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: Some("https://hooks.slack.com/services/T0/B0/X".parse().unwrap()),
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_slack_webhook_url_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        set_var("SLACK_WEBHOOK_URL", "hooks.slack.com");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("SLACK_WEBHOOK_URL".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+    #[test]
+    #[ignore]
+    fn uts_template_dir_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        set_var("TEMPLATE_DIR", "/etc/notification/templates");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("/etc/notification/templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_default_language_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        set_var("DEFAULT_LANGUAGE", "ger");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "ger".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_default_language_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        set_var("DEFAULT_LANGUAGE", "de");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("DEFAULT_LANGUAGE".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+    #[test]
+    #[ignore]
+    fn uts_outbox_poll_interval_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        set_var("OUTBOX_POLL_INTERVAL", "30");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(30),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_outbox_poll_interval_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        set_var("OUTBOX_POLL_INTERVAL", "0");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("OUTBOX_POLL_INTERVAL".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+    #[test]
+    #[ignore]
+    fn uts_outbox_retry_delay_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        set_var("OUTBOX_RETRY_DELAY", "120");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(120),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_outbox_retry_delay_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        set_var("OUTBOX_RETRY_DELAY", "soon");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("OUTBOX_RETRY_DELAY".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_outbox_max_attempts_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        set_var("OUTBOX_MAX_ATTEMPTS", "10");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 10,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_outbox_max_attempts_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        set_var("OUTBOX_MAX_ATTEMPTS", "0");
+        remove_var("SLACK_BOT_TOKEN");
+        remove_var("SLACK_API_URL");
+
+        let expected_result =
+            ConfigurationError::new("OUTBOX_MAX_ATTEMPTS".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_slack_bot_token_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        set_var("SLACK_BOT_TOKEN", "xoxb-token");
+        remove_var("SLACK_API_URL");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: Some("xoxb-token".into()),
+            slack_api_url: Uri::from_static("https://slack.com/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_slack_api_url_set() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        set_var("SLACK_API_URL", "http://localhost:8000/api/");
+
+        let expected_result = Configuration {
+            service_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+            db_socket: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 5432),
+            db_name: "db_name".into(),
+            db_username: "db_username".into(),
+            db_password: "db_password".into(),
+            smtp_host: "localhost".into(),
+            smtp_port: 25,
+            smtp_tls: SmtpTls::None,
+            smtp_username: None,
+            smtp_password: None,
+            mail_from: "CODE Library <library@localhost>".parse().unwrap(),
+            slack_webhook_url: None,
+            template_dir: PathBuf::from("templates"),
+            default_language: "eng".into(),
+            outbox_poll_interval: Duration::from_secs(10),
+            outbox_retry_delay: Duration::from_secs(60),
+            outbox_max_attempts: 5,
+            slack_bot_token: None,
+            slack_api_url: Uri::from_static("http://localhost:8000/api"),
+        };
+        let result = Configuration::init();
+
+        assert_eq!(Ok(expected_result), result)
+    }
+
+    #[test]
+    #[ignore]
+    fn uts_slack_api_url_input_invalid() {
+        set_var("SERVICE_SOCKET", "127.0.0.1:8080");
+        set_var("DB_SOCKET", "127.0.0.1:5432");
+        set_var("DB_NAME", "db_name");
+        set_var("DB_USERNAME", "db_username");
+        set_var("DB_PASSWORD", "db_password");
+        remove_var("SMTP_HOST");
+        remove_var("SMTP_PORT");
+        remove_var("SMTP_TLS");
+        remove_var("SMTP_USERNAME");
+        remove_var("SMTP_PASSWORD");
+        remove_var("MAIL_FROM");
+        remove_var("SLACK_WEBHOOK_URL");
+        remove_var("TEMPLATE_DIR");
+        remove_var("DEFAULT_LANGUAGE");
+        remove_var("OUTBOX_POLL_INTERVAL");
+        remove_var("OUTBOX_RETRY_DELAY");
+        remove_var("OUTBOX_MAX_ATTEMPTS");
+        remove_var("SLACK_BOT_TOKEN");
+        set_var("SLACK_API_URL", "slack.com/api");
+
+        let expected_result =
+            ConfigurationError::new("SLACK_API_URL".into(), ErrorKind::EnvVarValueInvalid);
+        let result = Configuration::init();
+
+        assert_eq!(Err(expected_result), result)
+    }
 }
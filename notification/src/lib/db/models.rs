@@ -0,0 +1,166 @@
+use std::io::Write;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::schema::*;
+
+#[derive(AsExpression, Clone, Copy, Debug, Deserialize, FromSqlRow, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[sql_type = "Text"]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Dead,
+}
+
+impl ToSql<Text, Pg> for DeliveryStatus {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        let status: &[u8] = match self {
+            DeliveryStatus::Pending => b"pending",
+            DeliveryStatus::Delivered => b"delivered",
+            DeliveryStatus::Dead => b"dead",
+        };
+        out.write_all(status)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for DeliveryStatus {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"pending" => Ok(DeliveryStatus::Pending),
+            b"delivered" => Ok(DeliveryStatus::Delivered),
+            b"dead" => Ok(DeliveryStatus::Dead),
+            _ => Err("Unrecognized delivery status".into()),
+        }
+    }
+}
+
+/// Channel, on which an user prefers to get the notifications of a kind
+#[derive(AsExpression, Clone, Copy, Debug, Deserialize, FromSqlRow, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[sql_type = "Text"]
+pub enum Channel {
+    Email,
+    SlackDm,
+    None,
+}
+
+impl Channel {
+    /// Name of the channel, as reported by its transport
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Email => "email",
+            Channel::SlackDm => "slack_dm",
+            Channel::None => "none",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Channel {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Channel {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"email" => Ok(Channel::Email),
+            b"slack_dm" => Ok(Channel::SlackDm),
+            b"none" => Ok(Channel::None),
+            _ => Err("Unrecognized channel".into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct Notification {
+    pub id: Uuid,
+    pub recipient: Option<Uuid>,
+    pub kind: String,
+    pub parameters: Value,
+    pub created_at: DateTime<Utc>,
+    pub channel: String,
+    pub subject: String,
+    pub body: String,
+    pub html: Option<String>,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "notifications"]
+pub struct NotificationAdd {
+    pub id: Uuid,
+    pub recipient: Option<Uuid>,
+    pub kind: String,
+    pub parameters: Value,
+    pub created_at: DateTime<Utc>,
+    pub channel: String,
+    pub subject: String,
+    pub body: String,
+    pub html: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct DeliveryAttempt {
+    pub id: Uuid,
+    pub notification_id: Uuid,
+    pub channel: String,
+    pub attempted_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[table_name = "delivery_attempts"]
+pub struct DeliveryAttemptAdd {
+    pub id: Uuid,
+    pub notification_id: Uuid,
+    pub channel: String,
+    pub attempted_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Queryable)]
+pub struct User {
+    pub id: Uuid,
+    pub email: String,
+    pub given_name: String,
+    pub family_name: String,
+    pub language: Option<String>,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq, Queryable)]
+#[table_name = "user_settings"]
+pub struct UserSettings {
+    pub user_id: Uuid,
+    pub language: String,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq, Queryable)]
+#[table_name = "notification_preferences"]
+pub struct NotificationPreference {
+    pub user_id: Uuid,
+    pub kind: String,
+    pub channel: Channel,
+}
+
+#[derive(Clone, Debug, Insertable, PartialEq, Queryable)]
+#[table_name = "quiet_hours"]
+pub struct QuietHours {
+    pub user_id: Uuid,
+    pub starts_at: NaiveTime,
+    pub ends_at: NaiveTime,
+    pub time_zone: String,
+}
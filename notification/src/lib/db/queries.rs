@@ -0,0 +1,291 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::result::QueryResult;
+use uuid::Uuid;
+
+use super::models::*;
+use super::schema;
+use super::DbConn;
+
+pub fn create_notification(
+    notification: NotificationAdd,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl::notifications;
+
+    diesel::insert_into(notifications)
+        .values(&notification)
+        .get_result(db)
+}
+
+pub fn get_notification(notification_id: Uuid, db: &DbConn) -> QueryResult<Notification> {
+    use schema::notifications::dsl::notifications;
+
+    notifications.find(notification_id).first(db)
+}
+
+/// Locks the pending notifications due for an attempt, rows locked by other dispatchers are skipped
+pub fn list_due_notifications_for_update(
+    now: DateTime<Utc>,
+    limit: i64,
+    db: &DbConn,
+) -> QueryResult<Vec<Notification>> {
+    use schema::notifications::dsl;
+
+    dsl::notifications
+        .filter(dsl::status.eq(DeliveryStatus::Pending))
+        .filter(dsl::next_attempt_at.le(now))
+        .order(dsl::next_attempt_at.asc())
+        .limit(limit)
+        .for_update()
+        .skip_locked()
+        .load(db)
+}
+
+pub fn schedule_notification_attempt(
+    notification_id: Uuid,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl;
+
+    diesel::update(dsl::notifications.find(notification_id))
+        .set((
+            dsl::attempts.eq(attempts),
+            dsl::next_attempt_at.eq(next_attempt_at),
+        ))
+        .get_result(db)
+}
+
+/// Ends the delivery of a pending notification with the given status
+pub fn close_notification(
+    notification_id: Uuid,
+    status: DeliveryStatus,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl;
+
+    diesel::update(
+        dsl::notifications
+            .find(notification_id)
+            .filter(dsl::status.eq(DeliveryStatus::Pending)),
+    )
+    .set((
+        dsl::status.eq(status),
+        dsl::next_attempt_at.eq(None::<DateTime<Utc>>),
+    ))
+    .get_result(db)
+}
+
+pub fn list_dead_notifications(
+    offset: i64,
+    limit: i64,
+    db: &DbConn,
+) -> QueryResult<Vec<Notification>> {
+    use schema::notifications::dsl;
+
+    dsl::notifications
+        .filter(dsl::status.eq(DeliveryStatus::Dead))
+        .order(dsl::created_at.asc())
+        .offset(offset)
+        .limit(limit)
+        .load(db)
+}
+
+/// Puts a dead notification back into the outbox with all its attempts available again
+pub fn requeue_notification(
+    notification_id: Uuid,
+    now: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl;
+
+    diesel::update(
+        dsl::notifications
+            .find(notification_id)
+            .filter(dsl::status.eq(DeliveryStatus::Dead)),
+    )
+    .set((
+        dsl::status.eq(DeliveryStatus::Pending),
+        dsl::attempts.eq(0),
+        dsl::next_attempt_at.eq(now),
+    ))
+    .get_result(db)
+}
+
+/// Replaces the rendered message of a notification
+pub fn update_notification_message(
+    notification_id: Uuid,
+    subject: &str,
+    body: &str,
+    html: Option<&str>,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl;
+
+    diesel::update(dsl::notifications.find(notification_id))
+        .set((
+            dsl::subject.eq(subject),
+            dsl::body.eq(body),
+            dsl::html.eq(html),
+        ))
+        .get_result(db)
+}
+
+pub fn create_delivery_attempt(
+    attempt: DeliveryAttemptAdd,
+    db: &DbConn,
+) -> QueryResult<DeliveryAttempt> {
+    use schema::delivery_attempts::dsl::delivery_attempts;
+
+    diesel::insert_into(delivery_attempts)
+        .values(&attempt)
+        .get_result(db)
+}
+
+pub fn list_delivery_attempts_by_notification(
+    notification_id: Uuid,
+    db: &DbConn,
+) -> QueryResult<Vec<DeliveryAttempt>> {
+    use schema::delivery_attempts::dsl;
+
+    dsl::delivery_attempts
+        .filter(dsl::notification_id.eq(notification_id))
+        .order(dsl::attempted_at.asc())
+        .load(db)
+}
+
+/// Last delivery attempt of each of the given notifications
+pub fn list_last_delivery_attempts(
+    notification_ids: &[Uuid],
+    db: &DbConn,
+) -> QueryResult<Vec<DeliveryAttempt>> {
+    use schema::delivery_attempts::dsl;
+
+    dsl::delivery_attempts
+        .filter(dsl::notification_id.eq_any(notification_ids))
+        .distinct_on(dsl::notification_id)
+        .order((dsl::notification_id, dsl::attempted_at.desc()))
+        .load(db)
+}
+
+/// Users are loaded with the language of their settings
+pub fn get_user(user_id: Uuid, db: &DbConn) -> QueryResult<User> {
+    use schema::{user_settings, users};
+
+    users::table
+        .left_join(user_settings::table)
+        .filter(users::id.eq(user_id))
+        .select((
+            users::id,
+            users::email,
+            users::given_name,
+            users::family_name,
+            user_settings::language.nullable(),
+        ))
+        .first(db)
+}
+
+pub fn get_users(user_ids: &[Uuid], db: &DbConn) -> QueryResult<Vec<User>> {
+    use schema::{user_settings, users};
+
+    users::table
+        .left_join(user_settings::table)
+        .filter(users::id.eq_any(user_ids))
+        .select((
+            users::id,
+            users::email,
+            users::given_name,
+            users::family_name,
+            user_settings::language.nullable(),
+        ))
+        .load(db)
+}
+
+pub fn upsert_user_settings(settings: UserSettings, db: &DbConn) -> QueryResult<UserSettings> {
+    use schema::user_settings::dsl;
+
+    diesel::insert_into(dsl::user_settings)
+        .values(&settings)
+        .on_conflict(dsl::user_id)
+        .do_update()
+        .set(dsl::language.eq(&settings.language))
+        .get_result(db)
+}
+
+pub fn list_notification_preferences(
+    user_id: Uuid,
+    db: &DbConn,
+) -> QueryResult<Vec<NotificationPreference>> {
+    use schema::notification_preferences::dsl;
+
+    dsl::notification_preferences
+        .filter(dsl::user_id.eq(user_id))
+        .order(dsl::kind.asc())
+        .load(db)
+}
+
+pub fn get_notification_preference(
+    user_id: Uuid,
+    kind: &str,
+    db: &DbConn,
+) -> QueryResult<Option<NotificationPreference>> {
+    use schema::notification_preferences::dsl;
+
+    dsl::notification_preferences
+        .find((user_id, kind))
+        .first(db)
+        .optional()
+}
+
+/// Replaces all notification preferences of an user
+pub fn replace_notification_preferences(
+    user_id: Uuid,
+    preferences: &[NotificationPreference],
+    db: &DbConn,
+) -> QueryResult<Vec<NotificationPreference>> {
+    use schema::notification_preferences::dsl;
+
+    diesel::delete(dsl::notification_preferences.filter(dsl::user_id.eq(user_id))).execute(db)?;
+    diesel::insert_into(dsl::notification_preferences)
+        .values(preferences)
+        .get_results(db)
+}
+
+pub fn get_quiet_hours(user_id: Uuid, db: &DbConn) -> QueryResult<Option<QuietHours>> {
+    use schema::quiet_hours::dsl::quiet_hours;
+
+    quiet_hours.find(user_id).first(db).optional()
+}
+
+/// Replaces the quiet hours of an user, `None` removes them
+pub fn replace_quiet_hours(
+    user_id: Uuid,
+    hours: Option<QuietHours>,
+    db: &DbConn,
+) -> QueryResult<Option<QuietHours>> {
+    use schema::quiet_hours::dsl;
+
+    diesel::delete(dsl::quiet_hours.find(user_id)).execute(db)?;
+    match hours {
+        Some(hours) => diesel::insert_into(dsl::quiet_hours)
+            .values(&hours)
+            .get_result(db)
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Holds back a pending notification without counting an attempt
+pub fn defer_notification(
+    notification_id: Uuid,
+    next_attempt_at: DateTime<Utc>,
+    db: &DbConn,
+) -> QueryResult<Notification> {
+    use schema::notifications::dsl;
+
+    diesel::update(dsl::notifications.find(notification_id))
+        .set(dsl::next_attempt_at.eq(next_attempt_at))
+        .get_result(db)
+}
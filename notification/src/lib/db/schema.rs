@@ -0,0 +1,74 @@
+table! {
+    delivery_attempts (id) {
+        id -> Uuid,
+        notification_id -> Uuid,
+        channel -> Varchar,
+        attempted_at -> Timestamptz,
+        error -> Nullable<Text>,
+    }
+}
+
+table! {
+    notifications (id) {
+        id -> Uuid,
+        recipient -> Nullable<Uuid>,
+        kind -> Varchar,
+        parameters -> Jsonb,
+        created_at -> Timestamptz,
+        channel -> Varchar,
+        subject -> Text,
+        body -> Text,
+        html -> Nullable<Text>,
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    notification_preferences (user_id, kind) {
+        user_id -> Uuid,
+        kind -> Varchar,
+        channel -> Varchar,
+    }
+}
+
+table! {
+    quiet_hours (user_id) {
+        user_id -> Uuid,
+        starts_at -> Time,
+        ends_at -> Time,
+        time_zone -> Varchar,
+    }
+}
+
+table! {
+    user_settings (user_id) {
+        user_id -> Uuid,
+        language -> Varchar,
+    }
+}
+
+// Owned by the IDENTITY service, only read by this service
+table! {
+    users (id) {
+        id -> Uuid,
+        email -> Varchar,
+        given_name -> Varchar,
+        family_name -> Varchar,
+    }
+}
+
+joinable!(delivery_attempts -> notifications (notification_id));
+joinable!(notification_preferences -> users (user_id));
+joinable!(quiet_hours -> users (user_id));
+joinable!(user_settings -> users (user_id));
+
+allow_tables_to_appear_in_same_query!(
+    delivery_attempts,
+    notification_preferences,
+    notifications,
+    quiet_hours,
+    user_settings,
+    users,
+);
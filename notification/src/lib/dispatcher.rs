@@ -0,0 +1,273 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+use diesel::Connection;
+use uuid::Uuid;
+
+use helpers::rpc::{Error, RpcResult};
+
+use crate::config::Configuration;
+use crate::db::{
+    models::{DeliveryAttemptAdd, DeliveryStatus, Notification, QuietHours},
+    queries, DbPool,
+};
+use crate::message::Message;
+use crate::transport::{Announcer, Recipient, Transport, TransportError};
+
+/// Maximum number of notifications attempted per run
+const BATCH_SIZE: i64 = 50;
+
+/// Delivers the notifications of the outbox over their channels.
+///
+/// Every attempt is scheduled before it is made, so a notification, whose attempt was
+/// interrupted, is attempted again after the retry delay. The delay doubles with every
+/// attempt and the notification is dead-lettered once the last attempt failed.
+/// Notifications due in the quiet hours of their recipient are held back until these end.
+pub struct Dispatcher {
+    conf: Arc<Configuration>,
+    db_pool: Arc<DbPool>,
+    transports: Vec<Arc<dyn Transport>>,
+    announcer: Option<Arc<dyn Announcer>>,
+}
+
+impl Dispatcher {
+    pub fn new(
+        conf: Arc<Configuration>,
+        db_pool: Arc<DbPool>,
+        transports: Vec<Arc<dyn Transport>>,
+        announcer: Option<Arc<dyn Announcer>>,
+    ) -> Self {
+        Self {
+            conf,
+            db_pool,
+            transports,
+            announcer,
+        }
+    }
+
+    /// Periodically attempts the due notifications
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.conf.get_outbox_poll_interval());
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.dispatch(Utc::now()).await {
+                log::error!("Failed to dispatch notifications: {:?}", e);
+            }
+        }
+    }
+
+    /// Attempts every notification due at the given time once and returns the number delivered
+    pub async fn dispatch(&self, now: DateTime<Utc>) -> RpcResult<usize> {
+        let db = self.db_pool.get().map_err(|_| Error::InternalError)?;
+        let retry_delay = Duration::from_std(self.conf.get_outbox_retry_delay())
+            .map_err(|_| Error::InternalError)?;
+
+        let claimed = db.transaction::<_, Error, _>(|| {
+            let mut claimed = vec![];
+            for notification in queries::list_due_notifications_for_update(now, BATCH_SIZE, &db)? {
+                let quiet_hours = match notification.recipient {
+                    Some(user_id) => queries::get_quiet_hours(user_id, &db)?,
+                    None => None,
+                };
+                if let Some(until) = quiet_hours.and_then(|hours| quiet_until(&hours, now)) {
+                    queries::defer_notification(notification.id, until, &db)?;
+                    log::debug!(
+                        "Notification '{}' held back until {} by quiet hours",
+                        notification.id,
+                        until
+                    );
+                    continue;
+                }
+
+                let attempts = notification.attempts + 1;
+                claimed.push(queries::schedule_notification_attempt(
+                    notification.id,
+                    attempts,
+                    now + backoff(retry_delay, attempts),
+                    &db,
+                )?);
+            }
+            Ok(claimed)
+        })?;
+
+        let mut delivered = 0;
+        for notification in claimed {
+            let recipient = match notification.recipient {
+                Some(user_id) => match queries::get_user(user_id, &db) {
+                    Ok(user) => Some(user.into()),
+                    Err(diesel::result::Error::NotFound) => None,
+                    Err(e) => return Err(e.into()),
+                },
+                None => None,
+            };
+            let result = self.deliver(&notification, recipient).await;
+
+            queries::create_delivery_attempt(
+                DeliveryAttemptAdd {
+                    id: Uuid::new_v4(),
+                    notification_id: notification.id,
+                    channel: notification.channel.clone(),
+                    attempted_at: Utc::now(),
+                    error: result.as_ref().err().map(|e| e.to_string()),
+                },
+                &db,
+            )?;
+
+            match result {
+                Ok(()) => {
+                    queries::close_notification(notification.id, DeliveryStatus::Delivered, &db)?;
+                    log::info!(
+                        "Notification '{}' delivered by {}",
+                        notification.id,
+                        notification.channel
+                    );
+                    delivered += 1;
+                }
+                Err(e) if notification.attempts >= self.conf.get_outbox_max_attempts().into() => {
+                    queries::close_notification(notification.id, DeliveryStatus::Dead, &db)?;
+                    log::error!(
+                        "Notification '{}' dead-lettered after {} attempts: {}",
+                        notification.id,
+                        notification.attempts,
+                        e
+                    );
+                }
+                Err(e) => log::warn!(
+                    "Attempt {} of notification '{}' failed, retrying at {:?}: {}",
+                    notification.attempts,
+                    notification.id,
+                    notification.next_attempt_at,
+                    e
+                ),
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    async fn deliver(
+        &self,
+        notification: &Notification,
+        recipient: Option<Recipient>,
+    ) -> Result<(), TransportError> {
+        let message = Message {
+            subject: notification.subject.clone(),
+            body: notification.body.clone(),
+            html: notification.html.clone(),
+        };
+
+        if let Some(transport) = self
+            .transports
+            .iter()
+            .find(|transport| notification.channel == transport.channel())
+        {
+            let recipient = recipient.ok_or_else(|| {
+                TransportError::new(format!("Recipient {:?} not found", notification.recipient))
+            })?;
+            return transport.send(&recipient, &message).await;
+        }
+        match &self.announcer {
+            Some(announcer) if notification.channel == announcer.channel() => {
+                announcer.announce(&message).await
+            }
+            _ => Err(TransportError::new(format!(
+                "No transport for channel '{}'",
+                notification.channel
+            ))),
+        }
+    }
+}
+
+/// Delay after the given attempt, the retry delay doubles with every attempt
+fn backoff(retry_delay: Duration, attempts: i32) -> Duration {
+    retry_delay * 2i32.pow((attempts - 1).clamp(0, 16) as u32)
+}
+
+/// End of the quiet hours, if the given time is within them
+fn quiet_until(hours: &QuietHours, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let time_zone: Tz = hours.time_zone.parse().ok()?;
+    let local = now.with_timezone(&time_zone);
+    let time = local.time();
+
+    let quiet = if hours.starts_at < hours.ends_at {
+        hours.starts_at <= time && time < hours.ends_at
+    } else {
+        hours.starts_at <= time || time < hours.ends_at
+    };
+    if !quiet {
+        return None;
+    }
+
+    let mut date = local.date().naive_local();
+    if time >= hours.ends_at {
+        date = date.succ();
+    }
+    // an end skipped by a daylight saving time change ends the quiet hours an hour later
+    let end = date.and_time(hours.ends_at);
+    time_zone
+        .from_local_datetime(&end)
+        .earliest()
+        .or_else(|| {
+            time_zone
+                .from_local_datetime(&(end + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|end| end.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use uuid::Uuid;
+
+    fn quiet_hours(starts_at: (u32, u32), ends_at: (u32, u32)) -> QuietHours {
+        QuietHours {
+            user_id: Uuid::new_v4(),
+            starts_at: NaiveTime::from_hms(starts_at.0, starts_at.1, 0),
+            ends_at: NaiveTime::from_hms(ends_at.0, ends_at.1, 0),
+            time_zone: "Europe/Berlin".into(),
+        }
+    }
+
+    #[test]
+    fn uts_backoff() {
+        let retry_delay = Duration::seconds(60);
+
+        assert_eq!(Duration::seconds(60), backoff(retry_delay, 1));
+        assert_eq!(Duration::seconds(120), backoff(retry_delay, 2));
+        assert_eq!(Duration::seconds(480), backoff(retry_delay, 4));
+    }
+
+    #[test]
+    fn uts_quiet_until_within_day() {
+        let hours = quiet_hours((12, 0), (14, 0));
+
+        // 13:30 in Berlin, which is UTC+1 in winter
+        let result = quiet_until(&hours, Utc.ymd(2021, 3, 1).and_hms(12, 30, 0));
+
+        assert_eq!(Some(Utc.ymd(2021, 3, 1).and_hms(13, 0, 0)), result);
+    }
+
+    #[test]
+    fn uts_quiet_until_over_midnight() {
+        let hours = quiet_hours((22, 0), (7, 0));
+
+        let before_midnight = quiet_until(&hours, Utc.ymd(2021, 3, 1).and_hms(22, 0, 0));
+        let after_midnight = quiet_until(&hours, Utc.ymd(2021, 3, 2).and_hms(1, 0, 0));
+
+        assert_eq!(Some(Utc.ymd(2021, 3, 2).and_hms(6, 0, 0)), before_midnight);
+        assert_eq!(Some(Utc.ymd(2021, 3, 2).and_hms(6, 0, 0)), after_midnight);
+    }
+
+    #[test]
+    fn uts_quiet_until_outside() {
+        let hours = quiet_hours((22, 0), (7, 0));
+
+        let result = quiet_until(&hours, Utc.ymd(2021, 3, 1).and_hms(12, 0, 0));
+
+        assert_eq!(None, result);
+    }
+}
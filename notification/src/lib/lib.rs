@@ -1,5 +1,10 @@
 pub mod config;
 pub mod db;
+pub mod dispatcher;
+pub mod message;
 pub mod rpc;
+pub mod template;
+pub mod transport;
 
+#[macro_use]
 extern crate diesel;
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::rpc::models::Announcement;
+use crate::transport::Recipient;
+
+/// Rendered content of a notification, the body is plain text with an optional HTML alternative
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub subject: String,
+    pub body: String,
+    pub html: Option<String>,
+}
+
+impl Message {
+    /// Renders an announcement, mentioned users are named if they are known
+    pub fn announcement(announcement: &Announcement, users: &HashMap<Uuid, Recipient>) -> Self {
+        let name = |user_id: &Uuid| match users.get(user_id) {
+            Some(user) => format!("{} {}", user.given_name, user.family_name),
+            None => user_id.to_string(),
+        };
+
+        match announcement {
+            Announcement::OverdueLoans { loans } => Self {
+                subject: format!("{} overdue loan(s)", loans.len()),
+                body: loans
+                    .iter()
+                    .map(|loan| {
+                        format!(
+                            "• Copy {} borrowed by {}, due on {}",
+                            loan.copy_id,
+                            name(&loan.user_id),
+                            loan.due_at.format("%Y-%m-%d")
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                html: None,
+            },
+            Announcement::NewAcquisition { title, copies, .. } => Self {
+                subject: "New acquisition".into(),
+                body: format!("{} copy(ies) of \"{}\" joined the library.", copies, title),
+                html: None,
+            },
+            Announcement::HoldReady {
+                user_id,
+                copy_id,
+                pickup_until,
+                ..
+            } => Self {
+                subject: "Hold ready for pickup".into(),
+                body: format!(
+                    "Copy {} is held for {} until {}.",
+                    copy_id,
+                    name(user_id),
+                    pickup_until.format("%Y-%m-%d")
+                ),
+                html: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::models::OverdueLoan;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn uts_announcement_overdue_loans() {
+        let user_id = Uuid::new_v4();
+        let unknown_user_id = Uuid::new_v4();
+        let users = vec![(
+            user_id,
+            Recipient {
+                user_id,
+                email: "ada@example.com".into(),
+                given_name: "Ada".into(),
+                family_name: "Lovelace".into(),
+                language: None,
+            },
+        )]
+        .into_iter()
+        .collect::<HashMap<Uuid, Recipient>>();
+        let loan = |user_id| OverdueLoan {
+            loan_id: Uuid::new_v4(),
+            copy_id: Uuid::new_v4(),
+            user_id,
+            due_at: Utc.ymd(2021, 3, 1).and_hms(12, 0, 0),
+        };
+        let announcement = Announcement::OverdueLoans {
+            loans: vec![loan(user_id), loan(unknown_user_id)],
+        };
+
+        let result = Message::announcement(&announcement, &users);
+
+        assert_eq!("2 overdue loan(s)", result.subject);
+        assert_eq!(2, result.body.lines().count());
+        assert!(result
+            .body
+            .contains("borrowed by Ada Lovelace, due on 2021-03-01"));
+        assert!(result
+            .body
+            .contains(&format!("borrowed by {}", unknown_user_id)));
+    }
+
+    #[test]
+    fn uts_announcement_new_acquisition() {
+        let announcement = Announcement::NewAcquisition {
+            book_id: Uuid::new_v4(),
+            title: "Dune".into(),
+            copies: 3,
+        };
+
+        let result = Message::announcement(&announcement, &HashMap::new());
+
+        assert_eq!("New acquisition", result.subject);
+        assert_eq!("3 copy(ies) of \"Dune\" joined the library.", result.body);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod models;
 pub mod server;
 pub mod service;
 
@@ -10,12 +11,20 @@ use tokio_serde::formats::Json;
 
 use self::server::NotificationServer;
 use self::service::{NotificationService, NotificationServiceClient};
-use crate::{config::Configuration, db::DbPool};
+use crate::{
+    config::Configuration,
+    db::DbPool,
+    template::Templates,
+    transport::{Announcer, Transport},
+};
 
 pub async fn get_rpc_server(
     addr: SocketAddr,
     configuration: Arc<Configuration>,
     db_pool: Arc<DbPool>,
+    templates: Arc<Templates>,
+    transports: Vec<Arc<dyn Transport>>,
+    announcer: Option<Arc<dyn Announcer>>,
 ) -> io::Result<(impl Future<Output = ()>, SocketAddr)> {
     let incoming = tarpc::serde_transport::tcp::listen(&addr, Json::default).await?;
     let addr = incoming.local_addr();
@@ -25,7 +34,13 @@ pub async fn get_rpc_server(
         .map(BaseChannel::with_defaults)
         .max_channels_per_key(1, |t| t.as_ref().peer_addr().unwrap().ip())
         .map(move |channel| {
-            let server = NotificationServer::new(configuration.clone(), db_pool.clone());
+            let server = NotificationServer::new(
+                configuration.clone(),
+                db_pool.clone(),
+                templates.clone(),
+                transports.clone(),
+                announcer.clone(),
+            );
             channel.requests().execute(server.serve())
         })
         .buffer_unordered(10)
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use crate::db::models::Channel;
+
+/// Kinds of the notifications sent to users
+pub const NOTIFICATION_KINDS: [&str; 4] = [
+    "loan_due_soon",
+    "loan_overdue",
+    "hold_ready",
+    "account_disabled",
+];
+
+/// Event to notify a user about, carrying the data needed to render the message
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Notification {
+    LoanDueSoon {
+        loan_id: Uuid,
+        copy_id: Uuid,
+        due_at: DateTime<Utc>,
+    },
+    LoanOverdue {
+        loan_id: Uuid,
+        copy_id: Uuid,
+        due_at: DateTime<Utc>,
+    },
+    HoldReady {
+        hold_id: Uuid,
+        copy_id: Uuid,
+        pickup_until: DateTime<Utc>,
+    },
+    AccountDisabled,
+}
+
+impl Notification {
+    /// Name of the notification kind, as used in its serialized form
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Notification::LoanDueSoon { .. } => "loan_due_soon",
+            Notification::LoanOverdue { .. } => "loan_overdue",
+            Notification::HoldReady { .. } => "hold_ready",
+            Notification::AccountDisabled => "account_disabled",
+        }
+    }
+}
+
+/// Event to post to the shared channels of the library, like Slack
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Announcement {
+    OverdueLoans {
+        loans: Vec<OverdueLoan>,
+    },
+    NewAcquisition {
+        book_id: Uuid,
+        title: String,
+        copies: u32,
+    },
+    HoldReady {
+        hold_id: Uuid,
+        user_id: Uuid,
+        copy_id: Uuid,
+        pickup_until: DateTime<Utc>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OverdueLoan {
+    pub loan_id: Uuid,
+    pub user_id: Uuid,
+    pub copy_id: Uuid,
+    pub due_at: DateTime<Utc>,
+}
+
+impl Announcement {
+    /// Name of the announcement kind, as used in its serialized form
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Announcement::OverdueLoans { .. } => "overdue_loans",
+            Announcement::NewAcquisition { .. } => "new_acquisition",
+            Announcement::HoldReady { .. } => "hold_ready",
+        }
+    }
+
+    /// Users mentioned in the announcement
+    pub fn user_ids(&self) -> Vec<Uuid> {
+        match self {
+            Announcement::OverdueLoans { loans } => loans.iter().map(|loan| loan.user_id).collect(),
+            Announcement::NewAcquisition { .. } => vec![],
+            Announcement::HoldReady { user_id, .. } => vec![*user_id],
+        }
+    }
+}
+
+/// Notification, whose delivery was given up after all attempts failed
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FailedDelivery {
+    pub notification_id: Uuid,
+    pub recipient: Option<Uuid>,
+    pub kind: String,
+    pub channel: String,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i32,
+    pub last_attempted_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl FailedDelivery {
+    pub fn new(
+        notification: crate::db::models::Notification,
+        last_attempt: Option<crate::db::models::DeliveryAttempt>,
+    ) -> Self {
+        FailedDelivery {
+            notification_id: notification.id,
+            recipient: notification.recipient,
+            kind: notification.kind,
+            channel: notification.channel,
+            created_at: notification.created_at,
+            attempts: notification.attempts,
+            last_attempted_at: last_attempt.as_ref().map(|attempt| attempt.attempted_at),
+            last_error: last_attempt.and_then(|attempt| attempt.error),
+        }
+    }
+}
+
+/// Daily window, in which no notifications are delivered to an user.
+/// The window may span midnight, e.g. from 22:00 to 07:00.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct QuietHours {
+    pub starts_at: NaiveTime,
+    pub ends_at: NaiveTime,
+    /// IANA time zone of the window, e.g. `Europe/Berlin`
+    pub time_zone: String,
+}
+
+impl From<crate::db::models::QuietHours> for QuietHours {
+    fn from(hours: crate::db::models::QuietHours) -> Self {
+        QuietHours {
+            starts_at: hours.starts_at,
+            ends_at: hours.ends_at,
+            time_zone: hours.time_zone,
+        }
+    }
+}
+
+/// How an user wants to be notified
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct NotificationPreferences {
+    /// Channel per notification kind, kinds without a channel are sent by email
+    pub channels: BTreeMap<String, Channel>,
+    pub quiet_hours: Option<QuietHours>,
+}
@@ -1,26 +1,375 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
+use chrono::Utc;
+use chrono_tz::Tz;
+use diesel::Connection;
 use tarpc::context;
+use uuid::Uuid;
 
-use super::service::NotificationService;
+use super::models::{
+    Announcement, Channel, FailedDelivery, Notification, NotificationPreferences, QuietHours,
+    NOTIFICATION_KINDS,
+};
+use super::service::{Error, NotificationService, RpcResult};
 use crate::config::Configuration;
-use crate::db::DbPool;
+use crate::db::{
+    models::{
+        DeliveryAttempt, DeliveryStatus, NotificationAdd, NotificationPreference, UserSettings,
+    },
+    queries, DbConn, DbPool,
+};
+use crate::message::Message;
+use crate::template::Templates;
+use crate::transport::{Announcer, Recipient, Transport};
 
 #[derive(Clone)]
 pub struct NotificationServer {
     conf: Arc<Configuration>,
     db_pool: Arc<DbPool>,
+    templates: Arc<Templates>,
+    transports: Vec<Arc<dyn Transport>>,
+    announcer: Option<Arc<dyn Announcer>>,
 }
 
 impl NotificationServer {
-    pub fn new(conf: Arc<Configuration>, db_pool: Arc<DbPool>) -> Self {
-        Self { conf, db_pool }
+    pub fn new(
+        conf: Arc<Configuration>,
+        db_pool: Arc<DbPool>,
+        templates: Arc<Templates>,
+        transports: Vec<Arc<dyn Transport>>,
+        announcer: Option<Arc<dyn Announcer>>,
+    ) -> Self {
+        Self {
+            conf,
+            db_pool,
+            templates,
+            transports,
+            announcer,
+        }
+    }
+
+    fn has_transport(&self, channel: Channel) -> bool {
+        self.transports
+            .iter()
+            .any(|transport| transport.channel() == channel.as_str())
+    }
+
+    /// Renders the message of a stored notification again from its parameters
+    fn render_stored(
+        &self,
+        notification: &crate::db::models::Notification,
+        db: &DbConn,
+    ) -> RpcResult<Message> {
+        match notification.recipient {
+            Some(recipient) => {
+                let parameters: Notification =
+                    serde_json::from_value(notification.parameters.clone())
+                        .map_err(|_| Error::InvalidData)?;
+                let recipient: Recipient = queries::get_user(recipient, db)?.into();
+                self.templates.render(&parameters, &recipient).map_err(|e| {
+                    log::error!("Failed to render '{}': {}", parameters.kind(), e);
+                    Error::InternalError
+                })
+            }
+            None => {
+                let announcement: Announcement =
+                    serde_json::from_value(notification.parameters.clone())
+                        .map_err(|_| Error::InvalidData)?;
+                let users = get_mentioned_users(&announcement, db)?;
+                Ok(Message::announcement(&announcement, &users))
+            }
+        }
+    }
+
+    fn get_db(&self) -> DbConn {
+        self.db_pool
+            .get()
+            .expect("Can't retrieve connection from pool")
     }
 }
 
 #[tarpc::server]
 impl NotificationService for NotificationServer {
-    async fn notification(self, _: context::Context) {
-        unimplemented!();
+    /// Renders a notification for an user and puts it into the outbox for delivery
+    /// on the channel, which the user prefers for its kind
+    async fn notify(
+        self,
+        _: context::Context,
+        recipient: Uuid,
+        notification: Notification,
+    ) -> RpcResult<()> {
+        let db = self.get_db();
+
+        let recipient: Recipient = queries::get_user(recipient, &db)?.into();
+        let channel = match queries::get_notification_preference(
+            recipient.user_id,
+            notification.kind(),
+            &db,
+        )? {
+            Some(preference) if preference.channel == Channel::None => {
+                log::debug!(
+                    "User '{}' opted out of '{}', skipping it",
+                    recipient.user_id,
+                    notification.kind()
+                );
+                return Ok(());
+            }
+            Some(preference) if self.has_transport(preference.channel) => preference.channel,
+            Some(preference) => {
+                log::warn!(
+                    "Channel '{}' preferred by user '{}' isn't configured, falling back to email",
+                    preference.channel.as_str(),
+                    recipient.user_id
+                );
+                Channel::Email
+            }
+            None => Channel::Email,
+        };
+        let message = self
+            .templates
+            .render(&notification, &recipient)
+            .map_err(|e| {
+                log::error!("Failed to render '{}': {}", notification.kind(), e);
+                Error::InternalError
+            })?;
+        let stored = queries::create_notification(
+            outbox_entry(
+                Some(recipient.user_id),
+                notification.kind(),
+                serde_json::to_value(&notification).map_err(|_| Error::InternalError)?,
+                channel.as_str(),
+                message,
+            ),
+            &db,
+        )?;
+        log::debug!(
+            "Notification '{}' for user '{}' queued",
+            stored.id,
+            recipient.user_id
+        );
+
+        Ok(())
+    }
+
+    /// Renders an announcement and puts it into the outbox for the shared channel,
+    /// if one is configured
+    async fn announce(self, _: context::Context, announcement: Announcement) -> RpcResult<()> {
+        let announcer = match &self.announcer {
+            Some(announcer) => announcer,
+            None => {
+                log::debug!(
+                    "No channel for announcements configured, skipping '{}'",
+                    announcement.kind()
+                );
+                return Ok(());
+            }
+        };
+        let db = self.get_db();
+
+        let users = get_mentioned_users(&announcement, &db)?;
+        let stored = queries::create_notification(
+            outbox_entry(
+                None,
+                announcement.kind(),
+                serde_json::to_value(&announcement).map_err(|_| Error::InternalError)?,
+                announcer.channel(),
+                Message::announcement(&announcement, &users),
+            ),
+            &db,
+        )?;
+        log::debug!("Announcement '{}' queued", stored.id);
+
+        Ok(())
+    }
+
+    /// Lists the notifications, which were dead-lettered after all delivery attempts failed
+    async fn list_failed_deliveries(
+        self,
+        _: context::Context,
+        offset: u32,
+        limit: u32,
+    ) -> RpcResult<Vec<FailedDelivery>> {
+        let db = self.get_db();
+
+        let notifications = queries::list_dead_notifications(offset.into(), limit.into(), &db)?;
+        let ids: Vec<Uuid> = notifications.iter().map(|n| n.id).collect();
+        let mut last_attempts: HashMap<Uuid, DeliveryAttempt> =
+            queries::list_last_delivery_attempts(&ids, &db)?
+                .into_iter()
+                .map(|attempt| (attempt.notification_id, attempt))
+                .collect();
+
+        Ok(notifications
+            .into_iter()
+            .map(|notification| {
+                let last_attempt = last_attempts.remove(&notification.id);
+                FailedDelivery::new(notification, last_attempt)
+            })
+            .collect())
+    }
+
+    /// Puts a dead-lettered notification back into the outbox
+    async fn requeue_delivery(self, _: context::Context, notification_id: Uuid) -> RpcResult<()> {
+        let db = self.get_db();
+
+        let notification = queries::get_notification(notification_id, &db)?;
+        if notification.status != DeliveryStatus::Dead {
+            return Err(Error::InvalidInput);
+        }
+        // notifications stored before the outbox have no message, it is rendered again
+        let message = match notification.body.is_empty() {
+            true => Some(self.render_stored(&notification, &db)?),
+            false => None,
+        };
+        db.transaction::<_, Error, _>(|| {
+            if let Some(message) = &message {
+                queries::update_notification_message(
+                    notification_id,
+                    &message.subject,
+                    &message.body,
+                    message.html.as_deref(),
+                    &db,
+                )?;
+            }
+            queries::requeue_notification(notification_id, Utc::now(), &db)?;
+            Ok(())
+        })?;
+        log::info!("Notification '{}' requeued", notification_id);
+
+        Ok(())
+    }
+
+    /// Sets the language, in which an user gets notifications
+    async fn set_user_language(
+        self,
+        _: context::Context,
+        user_id: Uuid,
+        language: String,
+    ) -> RpcResult<()> {
+        if !self.templates.get_languages().contains(&language) {
+            return Err(Error::InvalidInput);
+        }
+        let db = self.get_db();
+
+        queries::get_user(user_id, &db)?;
+        queries::upsert_user_settings(UserSettings { user_id, language }, &db)?;
+
+        Ok(())
+    }
+
+    /// Returns the channel of every notification kind and the quiet hours of an user
+    async fn get_notification_preferences(
+        self,
+        _: context::Context,
+        user_id: Uuid,
+    ) -> RpcResult<NotificationPreferences> {
+        let db = self.get_db();
+
+        queries::get_user(user_id, &db)?;
+        let mut channels: BTreeMap<String, Channel> = NOTIFICATION_KINDS
+            .iter()
+            .map(|kind| (kind.to_string(), Channel::Email))
+            .collect();
+        for preference in queries::list_notification_preferences(user_id, &db)? {
+            channels.insert(preference.kind, preference.channel);
+        }
+
+        Ok(NotificationPreferences {
+            channels,
+            quiet_hours: queries::get_quiet_hours(user_id, &db)?.map(QuietHours::from),
+        })
+    }
+
+    /// Replaces the preferences of an user, kinds left out are sent by email again
+    async fn update_notification_preferences(
+        self,
+        context: context::Context,
+        user_id: Uuid,
+        preferences: NotificationPreferences,
+    ) -> RpcResult<NotificationPreferences> {
+        for (kind, channel) in preferences.channels.iter() {
+            if !NOTIFICATION_KINDS.contains(&kind.as_str()) {
+                return Err(Error::InvalidInput);
+            }
+            if *channel != Channel::None && !self.has_transport(*channel) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        if let Some(hours) = &preferences.quiet_hours {
+            if hours.starts_at == hours.ends_at || hours.time_zone.parse::<Tz>().is_err() {
+                return Err(Error::InvalidInput);
+            }
+        }
+        let db = self.get_db();
+
+        queries::get_user(user_id, &db)?;
+        db.transaction::<_, Error, _>(|| {
+            queries::replace_notification_preferences(
+                user_id,
+                &preferences
+                    .channels
+                    .iter()
+                    .map(|(kind, channel)| NotificationPreference {
+                        user_id,
+                        kind: kind.clone(),
+                        channel: *channel,
+                    })
+                    .collect::<Vec<_>>(),
+                &db,
+            )?;
+            queries::replace_quiet_hours(
+                user_id,
+                preferences
+                    .quiet_hours
+                    .map(|hours| crate::db::models::QuietHours {
+                        user_id,
+                        starts_at: hours.starts_at,
+                        ends_at: hours.ends_at,
+                        time_zone: hours.time_zone,
+                    }),
+                &db,
+            )?;
+            Ok(())
+        })?;
+
+        self.get_notification_preferences(context, user_id).await
+    }
+}
+
+/// Loads the users mentioned by an announcement, keyed by their id
+fn get_mentioned_users(
+    announcement: &Announcement,
+    db: &DbConn,
+) -> RpcResult<HashMap<Uuid, Recipient>> {
+    Ok(queries::get_users(&announcement.user_ids(), db)?
+        .into_iter()
+        .map(|user| (user.id, user.into()))
+        .collect())
+}
+
+/// Notification in the outbox, which is due for its first delivery attempt right away
+fn outbox_entry(
+    recipient: Option<Uuid>,
+    kind: &str,
+    parameters: serde_json::Value,
+    channel: &str,
+    message: Message,
+) -> NotificationAdd {
+    let now = Utc::now();
+
+    NotificationAdd {
+        id: Uuid::new_v4(),
+        recipient,
+        kind: kind.into(),
+        parameters,
+        created_at: now,
+        channel: channel.into(),
+        subject: message.subject,
+        body: message.body,
+        html: message.html,
+        next_attempt_at: Some(now),
     }
 }
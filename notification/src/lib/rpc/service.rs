@@ -1,4 +1,19 @@
+use uuid::Uuid;
+
+pub use helpers::rpc::{Error, RpcResult};
+
+use super::models::{Announcement, FailedDelivery, Notification, NotificationPreferences};
+
 #[tarpc::service]
 pub trait NotificationService {
-    async fn notification();
+    async fn notify(recipient: Uuid, notification: Notification) -> RpcResult<()>;
+    async fn announce(announcement: Announcement) -> RpcResult<()>;
+    async fn set_user_language(user_id: Uuid, language: String) -> RpcResult<()>;
+    async fn get_notification_preferences(user_id: Uuid) -> RpcResult<NotificationPreferences>;
+    async fn update_notification_preferences(
+        user_id: Uuid,
+        preferences: NotificationPreferences,
+    ) -> RpcResult<NotificationPreferences>;
+    async fn list_failed_deliveries(offset: u32, limit: u32) -> RpcResult<Vec<FailedDelivery>>;
+    async fn requeue_delivery(notification_id: Uuid) -> RpcResult<()>;
 }
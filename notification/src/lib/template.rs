@@ -0,0 +1,380 @@
+use std::{fmt, fs, path::Path};
+
+use handlebars::Handlebars;
+use serde::Serialize;
+
+use crate::message::Message;
+use crate::rpc::models::{Notification, NOTIFICATION_KINDS};
+use crate::transport::Recipient;
+
+/// Parts of a message and the file extension of their templates
+const PARTS: [(&str, &str); 3] = [
+    ("subject", "subject.hbs"),
+    ("text", "text.hbs"),
+    ("html", "html.hbs"),
+];
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, PartialEq)]
+pub struct TemplateError {
+    description: String,
+}
+
+impl TemplateError {
+    pub fn new(description: String) -> Self {
+        TemplateError { description }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+#[derive(Serialize)]
+struct LoanParameters {
+    given_name: String,
+    family_name: String,
+    due_date: String,
+}
+
+#[derive(Serialize)]
+struct HoldReadyParameters {
+    given_name: String,
+    family_name: String,
+    pickup_until: String,
+}
+
+#[derive(Serialize)]
+struct AccountParameters {
+    given_name: String,
+    family_name: String,
+}
+
+/// Typed parameters of a notification kind, which are available to its templates
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Parameters {
+    Loan(LoanParameters),
+    HoldReady(HoldReadyParameters),
+    Account(AccountParameters),
+}
+
+impl Parameters {
+    fn of(notification: &Notification, recipient: &Recipient) -> Self {
+        let given_name = recipient.given_name.clone();
+        let family_name = recipient.family_name.clone();
+
+        match notification {
+            Notification::LoanDueSoon { due_at, .. } | Notification::LoanOverdue { due_at, .. } => {
+                Parameters::Loan(LoanParameters {
+                    given_name,
+                    family_name,
+                    due_date: due_at.format(DATE_FORMAT).to_string(),
+                })
+            }
+            Notification::HoldReady { pickup_until, .. } => {
+                Parameters::HoldReady(HoldReadyParameters {
+                    given_name,
+                    family_name,
+                    pickup_until: pickup_until.format(DATE_FORMAT).to_string(),
+                })
+            }
+            Notification::AccountDisabled => Parameters::Account(AccountParameters {
+                given_name,
+                family_name,
+            }),
+        }
+    }
+
+    /// Parameters with placeholder values, which are used to validate the templates of a kind
+    fn sample(kind: &str) -> Self {
+        let given_name = "Ada".to_string();
+        let family_name = "Lovelace".to_string();
+
+        match kind {
+            "loan_due_soon" | "loan_overdue" => Parameters::Loan(LoanParameters {
+                given_name,
+                family_name,
+                due_date: "2021-03-01".into(),
+            }),
+            "hold_ready" => Parameters::HoldReady(HoldReadyParameters {
+                given_name,
+                family_name,
+                pickup_until: "2021-03-01".into(),
+            }),
+            _ => Parameters::Account(AccountParameters {
+                given_name,
+                family_name,
+            }),
+        }
+    }
+}
+
+/// Localized templates of the notifications sent to users.
+///
+/// The templates are read from a directory with a subdirectory per ISO 639-2 language code,
+/// containing a subject, text and HTML template for every notification kind, e.g.
+/// `eng/loan_overdue.subject.hbs`, `eng/loan_overdue.text.hbs` and `eng/loan_overdue.html.hbs`.
+pub struct Templates {
+    text: Handlebars<'static>,
+    html: Handlebars<'static>,
+    languages: Vec<String>,
+    default_language: String,
+}
+
+impl Templates {
+    /// Loads and validates all templates, so that missing templates or placeholders,
+    /// which are not parameters of their notification kind, fail at startup
+    pub fn load(directory: &Path, default_language: &str) -> Result<Self, TemplateError> {
+        let mut text = Handlebars::new();
+        text.set_strict_mode(true);
+        text.register_escape_fn(handlebars::no_escape);
+        let mut html = Handlebars::new();
+        html.set_strict_mode(true);
+
+        let mut languages = vec![];
+        let entries = fs::read_dir(directory).map_err(|e| {
+            TemplateError::new(format!("Can't read {}: {}", directory.display(), e))
+        })?;
+        for entry in entries {
+            let path = entry.map_err(|e| TemplateError::new(e.to_string()))?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let language = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| is_language_code(name))
+                .ok_or_else(|| {
+                    TemplateError::new(format!(
+                        "{} is not named by an ISO 639-2 language code",
+                        path.display()
+                    ))
+                })?
+                .to_string();
+
+            for kind in NOTIFICATION_KINDS.iter() {
+                for (part, extension) in PARTS.iter() {
+                    let file = path.join(format!("{}.{}", kind, extension));
+                    let source = fs::read_to_string(&file).map_err(|e| {
+                        TemplateError::new(format!("Can't read {}: {}", file.display(), e))
+                    })?;
+                    let registry = if *part == "html" {
+                        &mut html
+                    } else {
+                        &mut text
+                    };
+                    registry
+                        .register_template_string(&name(&language, kind, part), source)
+                        .map_err(|e| {
+                            TemplateError::new(format!("Invalid {}: {}", file.display(), e))
+                        })?;
+                }
+            }
+            languages.push(language);
+        }
+
+        if !languages
+            .iter()
+            .any(|language| language == default_language)
+        {
+            return Err(TemplateError::new(format!(
+                "No templates for the default language '{}'",
+                default_language
+            )));
+        }
+        languages.sort();
+
+        let templates = Self {
+            text,
+            html,
+            languages,
+            default_language: default_language.into(),
+        };
+        for language in templates.languages.iter() {
+            for kind in NOTIFICATION_KINDS.iter() {
+                templates.render_parts(language, kind, &Parameters::sample(kind))?;
+            }
+        }
+
+        Ok(templates)
+    }
+
+    /// Languages with templates as ISO 639-2 codes
+    pub fn get_languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Renders a notification in the language of the recipient,
+    /// falling back to the default language if there are no templates for it
+    pub fn render(
+        &self,
+        notification: &Notification,
+        recipient: &Recipient,
+    ) -> Result<Message, TemplateError> {
+        let language = match &recipient.language {
+            Some(language) if self.languages.contains(language) => language,
+            _ => &self.default_language,
+        };
+
+        self.render_parts(
+            language,
+            notification.kind(),
+            &Parameters::of(notification, recipient),
+        )
+    }
+
+    fn render_parts(
+        &self,
+        language: &str,
+        kind: &str,
+        parameters: &Parameters,
+    ) -> Result<Message, TemplateError> {
+        let render = |registry: &Handlebars, part| {
+            registry
+                .render(&name(language, kind, part), parameters)
+                .map_err(|e| {
+                    TemplateError::new(format!(
+                        "Can't render {} of '{}' in '{}': {}",
+                        part, kind, language, e
+                    ))
+                })
+        };
+
+        Ok(Message {
+            subject: render(&self.text, "subject")?.trim().to_string(),
+            body: render(&self.text, "text")?,
+            html: Some(render(&self.html, "html")?),
+        })
+    }
+}
+
+fn name(language: &str, kind: &str, part: &str) -> String {
+    format!("{}/{}.{}", language, kind, part)
+}
+
+fn is_language_code(name: &str) -> bool {
+    name.len() == 3 && name.chars().all(|c| c.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn recipient(language: Option<&str>) -> Recipient {
+        Recipient {
+            user_id: Uuid::new_v4(),
+            email: "ada@example.com".into(),
+            given_name: "Ada <3".into(),
+            family_name: "Lovelace".into(),
+            language: language.map(String::from),
+        }
+    }
+
+    fn loan_overdue() -> Notification {
+        Notification::LoanOverdue {
+            loan_id: Uuid::new_v4(),
+            copy_id: Uuid::new_v4(),
+            due_at: Utc.ymd(2021, 3, 1).and_hms(12, 0, 0),
+        }
+    }
+
+    fn template_directory() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("templates")
+    }
+
+    /// Copies the shipped templates, so that single files can be changed
+    fn copy_templates() -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("templates-{}", Uuid::new_v4()));
+        for language in fs::read_dir(template_directory()).unwrap() {
+            let language = language.unwrap().path();
+            let target = directory.join(language.file_name().unwrap());
+            fs::create_dir_all(&target).unwrap();
+            for file in fs::read_dir(&language).unwrap() {
+                let file = file.unwrap().path();
+                fs::copy(&file, target.join(file.file_name().unwrap())).unwrap();
+            }
+        }
+        directory
+    }
+
+    #[test]
+    fn uts_load_shipped_templates() {
+        let templates = Templates::load(&template_directory(), "eng").unwrap();
+
+        assert_eq!(vec!["eng", "ger"], templates.get_languages());
+    }
+
+    #[test]
+    fn uts_load_default_language_missing() {
+        let result = Templates::load(&template_directory(), "fre");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn uts_load_template_missing() {
+        let directory = copy_templates();
+        fs::remove_file(directory.join("ger/hold_ready.html.hbs")).unwrap();
+
+        let result = Templates::load(&directory, "eng");
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn uts_load_placeholder_unknown() {
+        let directory = copy_templates();
+        fs::write(
+            directory.join("eng/loan_overdue.text.hbs"),
+            "Hello {{given_name}}, it was due on {{pickup_until}}.",
+        )
+        .unwrap();
+
+        let result = Templates::load(&directory, "eng");
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(matches!(result, Err(e) if e.to_string().contains("loan_overdue")));
+    }
+
+    #[test]
+    fn uts_render_language_of_recipient() {
+        let templates = Templates::load(&template_directory(), "eng").unwrap();
+
+        let result = templates
+            .render(&loan_overdue(), &recipient(Some("ger")))
+            .unwrap();
+
+        assert_eq!("Deine Ausleihe ist überfällig", result.subject);
+        assert!(result.body.starts_with("Hallo Ada <3,"));
+        assert!(result.body.contains("2021-03-01"));
+    }
+
+    #[test]
+    fn uts_render_default_language() {
+        let templates = Templates::load(&template_directory(), "eng").unwrap();
+
+        let result = templates
+            .render(&loan_overdue(), &recipient(Some("fre")))
+            .unwrap();
+
+        assert_eq!("Your loan is overdue", result.subject);
+        assert!(result.body.starts_with("Hello Ada <3,"));
+    }
+
+    #[test]
+    fn uts_render_html_escaped() {
+        let templates = Templates::load(&template_directory(), "eng").unwrap();
+
+        let result = templates.render(&loan_overdue(), &recipient(None)).unwrap();
+
+        assert!(result.html.unwrap().contains("Hello Ada &lt;3,"));
+    }
+}
@@ -0,0 +1,68 @@
+pub mod slack;
+pub mod slack_dm;
+pub mod smtp;
+
+use std::fmt;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::message::Message;
+
+/// User, which receives a notification
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recipient {
+    pub user_id: Uuid,
+    pub email: String,
+    pub given_name: String,
+    pub family_name: String,
+    /// ISO 639-2 code of the preferred language
+    pub language: Option<String>,
+}
+
+impl From<crate::db::models::User> for Recipient {
+    fn from(user: crate::db::models::User) -> Self {
+        Recipient {
+            user_id: user.id,
+            email: user.email,
+            given_name: user.given_name,
+            family_name: user.family_name,
+            language: user.language,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TransportError {
+    description: String,
+}
+
+impl TransportError {
+    pub fn new(description: String) -> Self {
+        TransportError { description }
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+/// Channel over which notifications are delivered to their recipients
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Name of the channel, recorded with every delivery attempt
+    fn channel(&self) -> &'static str;
+
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), TransportError>;
+}
+
+/// Shared channel of the library, to which announcements are posted
+#[async_trait]
+pub trait Announcer: Send + Sync {
+    /// Name of the channel, recorded with every delivery attempt
+    fn channel(&self) -> &'static str;
+
+    async fn announce(&self, message: &Message) -> Result<(), TransportError>;
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use serde::Serialize;
+
+use super::{Announcer, TransportError};
+use crate::message::Message;
+
+#[derive(Serialize)]
+struct WebhookPayload {
+    text: String,
+}
+
+/// Posts announcements to a Slack channel through an incoming webhook
+pub struct SlackTransport {
+    client: Client<HttpsConnector<HttpConnector>>,
+    webhook_url: Uri,
+}
+
+impl SlackTransport {
+    /// Failed posts are not retried here, the dispatcher attempts them again from the outbox
+    pub fn new(webhook_url: Uri) -> Self {
+        Self {
+            client: Client::builder().build::<_, Body>(HttpsConnector::new()),
+            webhook_url,
+        }
+    }
+}
+
+/// Formats a message with Slack markup
+pub(super) fn format(message: &Message) -> String {
+    format!("*{}*\n{}", message.subject, message.body)
+}
+
+#[async_trait]
+impl Announcer for SlackTransport {
+    fn channel(&self) -> &'static str {
+        "slack"
+    }
+
+    async fn announce(&self, message: &Message) -> Result<(), TransportError> {
+        let payload = serde_json::to_vec(&WebhookPayload {
+            text: format(message),
+        })
+        .map_err(|e| TransportError::new(e.to_string()))?;
+        let request = Request::post(self.webhook_url.clone())
+            .header("Content-Type", "application/json")
+            .body(Body::from(payload))
+            .map_err(|e| TransportError::new(e.to_string()))?;
+
+        match self.client.request(request).await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => Err(TransportError::new(format!(
+                "Slack webhook responded with {}",
+                response.status()
+            ))),
+            Err(e) => Err(TransportError::new(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uts_format() {
+        let message = Message {
+            subject: "Subject".into(),
+            body: "Body".into(),
+            html: None,
+        };
+
+        assert_eq!("*Subject*\nBody", format(&message));
+    }
+}
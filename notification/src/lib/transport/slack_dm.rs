@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request, Uri};
+use hyper_tls::HttpsConnector;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{slack, Recipient, Transport, TransportError};
+use crate::message::Message;
+
+#[derive(Serialize)]
+struct LookupByEmailRequest<'a> {
+    email: &'a str,
+}
+
+#[derive(Deserialize)]
+struct LookupByEmailResponse {
+    user: SlackUser,
+}
+
+#[derive(Deserialize)]
+struct SlackUser {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct PostMessageRequest<'a> {
+    channel: &'a str,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct PostMessageResponse {}
+
+/// Envelope of every Web API response, failed calls are answered with `ok` unset
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    content: Option<T>,
+}
+
+/// Sends notifications as direct messages of a Slack app to the Slack user
+/// with the email address of the recipient
+pub struct SlackDmTransport {
+    client: Client<HttpsConnector<HttpConnector>>,
+    api_url: Uri,
+    bot_token: String,
+}
+
+impl SlackDmTransport {
+    pub fn new(api_url: Uri, bot_token: String) -> Self {
+        Self {
+            client: Client::builder().build::<_, Body>(HttpsConnector::new()),
+            api_url,
+            bot_token,
+        }
+    }
+
+    /// Calls a method of the Slack Web API
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> Result<T, TransportError> {
+        let request = Request::post(format!(
+            "{}/{}",
+            self.api_url.to_string().trim_end_matches('/'),
+            method
+        ))
+        .header("Authorization", format!("Bearer {}", self.bot_token))
+        .header("Content-Type", content_type)
+        .body(Body::from(body))
+        .map_err(|e| TransportError::new(e.to_string()))?;
+
+        let response = self
+            .client
+            .request(request)
+            .await
+            .map_err(|e| TransportError::new(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(TransportError::new(format!(
+                "Slack {} responded with {}",
+                method,
+                response.status()
+            )));
+        }
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|e| TransportError::new(e.to_string()))?;
+        let response: ApiResponse<T> = serde_json::from_slice(&body)
+            .map_err(|e| TransportError::new(format!("Invalid response of {}: {}", method, e)))?;
+
+        match response {
+            ApiResponse {
+                ok: true,
+                content: Some(content),
+                ..
+            } => Ok(content),
+            ApiResponse { error, .. } => Err(TransportError::new(format!(
+                "Slack {} failed: {}",
+                method,
+                error.unwrap_or_else(|| "unknown_error".into())
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for SlackDmTransport {
+    fn channel(&self) -> &'static str {
+        "slack_dm"
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), TransportError> {
+        let lookup = serde_urlencoded::to_string(LookupByEmailRequest {
+            email: &recipient.email,
+        })
+        .map_err(|e| TransportError::new(e.to_string()))?;
+        let user: LookupByEmailResponse = self
+            .call(
+                "users.lookupByEmail",
+                "application/x-www-form-urlencoded",
+                lookup.into_bytes(),
+            )
+            .await?;
+
+        let post = serde_json::to_vec(&PostMessageRequest {
+            channel: &user.user.id,
+            text: slack::format(message),
+        })
+        .map_err(|e| TransportError::new(e.to_string()))?;
+        self.call::<PostMessageResponse>(
+            "chat.postMessage",
+            "application/json; charset=utf-8",
+            post,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
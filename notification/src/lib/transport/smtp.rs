@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use super::{Recipient, Transport, TransportError};
+use crate::config::{Configuration, SmtpTls};
+use crate::message::Message;
+
+/// Delivers notifications as email through a SMTP server
+pub struct SmtpTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpTransport {
+    pub fn new(
+        host: &str,
+        port: u16,
+        tls: SmtpTls,
+        credentials: Option<(String, String)>,
+        from: Mailbox,
+    ) -> Result<Self, TransportError> {
+        let builder = match tls {
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| TransportError::new(e.to_string()))?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+                .map_err(|e| TransportError::new(e.to_string()))?,
+        }
+        .port(port);
+
+        let builder = match credentials {
+            Some((username, password)) => builder.credentials(Credentials::new(username, password)),
+            None => builder,
+        };
+
+        Ok(Self {
+            mailer: builder.build(),
+            from,
+        })
+    }
+
+    pub fn from_configuration(conf: &Configuration) -> Result<Self, TransportError> {
+        Self::new(
+            conf.get_smtp_host(),
+            conf.get_smtp_port(),
+            conf.get_smtp_tls(),
+            conf.get_smtp_credentials(),
+            conf.get_mail_from(),
+        )
+    }
+}
+
+#[async_trait]
+impl Transport for SmtpTransport {
+    fn channel(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, recipient: &Recipient, message: &Message) -> Result<(), TransportError> {
+        let to = Mailbox::new(
+            Some(format!(
+                "{} {}",
+                recipient.given_name, recipient.family_name
+            )),
+            recipient.email.parse().map_err(|_| {
+                TransportError::new(format!("Invalid address: {}", recipient.email))
+            })?,
+        );
+
+        let builder = lettre::Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject.clone());
+        let email = match &message.html {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                message.body.clone(),
+                html.clone(),
+            )),
+            None => builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.body.clone()),
+        }
+        .map_err(|e| TransportError::new(e.to_string()))?;
+
+        self.mailer
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| TransportError::new(e.to_string()))
+    }
+}
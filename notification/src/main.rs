@@ -4,7 +4,17 @@ use std::{io, sync::Arc};
 extern crate diesel_migrations;
 
 use helpers::db::run_migration;
-use notification::{config::get_configuration, db::get_db_pool, rpc::get_rpc_server};
+use notification::{
+    config::get_configuration,
+    db::get_db_pool,
+    dispatcher::Dispatcher,
+    rpc::get_rpc_server,
+    template::Templates,
+    transport::{
+        slack::SlackTransport, slack_dm::SlackDmTransport, smtp::SmtpTransport, Announcer,
+        Transport,
+    },
+};
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
@@ -23,10 +33,43 @@ async fn main() -> io::Result<()> {
     embed_migrations!();
     run_migration(embedded_migrations::run, &db_pool);
 
+    let templates = Templates::load(
+        &configuration.get_template_dir(),
+        &configuration.get_default_language(),
+    )
+    .expect("Failed to load notification templates");
+
+    let mut transports: Vec<Arc<dyn Transport>> = vec![Arc::new(
+        SmtpTransport::from_configuration(&configuration).expect("Failed to create SMTP transport"),
+    )];
+    if let Some(bot_token) = configuration.get_slack_bot_token() {
+        transports.push(Arc::new(SlackDmTransport::new(
+            configuration.get_slack_api_url(),
+            bot_token,
+        )));
+    }
+    let announcer = configuration
+        .get_slack_webhook_url()
+        .map(|webhook_url| Arc::new(SlackTransport::new(webhook_url)) as Arc<dyn Announcer>);
+
+    let configuration = Arc::new(configuration);
+    let db_pool = Arc::new(db_pool);
+
+    let dispatcher = Dispatcher::new(
+        configuration.clone(),
+        db_pool.clone(),
+        transports.clone(),
+        announcer.clone(),
+    );
+    tokio::spawn(dispatcher.run());
+
     let (server, addr) = get_rpc_server(
         configuration.get_service_socket(),
-        Arc::new(configuration),
-        Arc::new(db_pool),
+        configuration,
+        db_pool,
+        Arc::new(templates),
+        transports,
+        announcer,
     )
     .await
     .unwrap();
@@ -0,0 +1,1156 @@
+use std::env::set_var;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use chrono::{NaiveTime, Utc};
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Response, Server, StatusCode, Uri};
+use tarpc::context;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+use helpers::rpc::Error;
+use notification::config::{Configuration, SmtpTls};
+use notification::db::{
+    get_db_pool,
+    models::{DeliveryAttempt, DeliveryStatus},
+    schema, DbPool,
+};
+use notification::dispatcher::Dispatcher;
+use notification::rpc::{
+    get_rpc_client, get_rpc_server,
+    models::{
+        Announcement, Channel, Notification, NotificationPreferences, OverdueLoan, QuietHours,
+    },
+    service::NotificationServiceClient,
+};
+use notification::template::Templates;
+use notification::transport::{
+    slack::SlackTransport, slack_dm::SlackDmTransport, smtp::SmtpTransport, Announcer, Transport,
+};
+
+mod sample_data;
+
+#[macro_use]
+extern crate diesel_migrations;
+
+embed_migrations!("migrations/");
+
+struct DbTestContext {
+    connection_url: String,
+    db_name: String,
+}
+
+impl DbTestContext {
+    fn new(connection_url: String, db_name: String) -> Self {
+        // connect to database service
+        let conn = PgConnection::establish(&connection_url)
+            .expect("Could not connect to database service");
+
+        // create new database
+        diesel::sql_query(format!("CREATE DATABASE \"{}\"", db_name))
+            .execute(&conn)
+            .unwrap_or_else(|_| panic!("Could not create database: {}", db_name));
+
+        // reconnect to the new database
+        let conn = PgConnection::establish(&format!("{}/{}", connection_url, db_name))
+            .expect("Could not connect to database service");
+
+        // run migration
+        embedded_migrations::run(&conn).expect("Failed to apply database migration");
+
+        // create tables of other services and insert sample data for tests
+        conn.batch_execute(sample_data::EXTERNAL_TABLES)
+            .expect("Error inserting sample data");
+
+        Self {
+            connection_url,
+            db_name,
+        }
+    }
+
+    fn get_connection_url(&self) -> String {
+        format!("{}/{}", self.connection_url, self.db_name)
+    }
+}
+
+impl Drop for DbTestContext {
+    fn drop(&mut self) {
+        let conn = PgConnection::establish(&self.connection_url)
+            .expect("Could not connect to database service");
+
+        diesel::sql_query(format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}'",
+            self.db_name
+        ))
+        .execute(&conn)
+        .unwrap();
+
+        diesel::sql_query(format!("DROP DATABASE \"{}\"", self.db_name))
+            .execute(&conn)
+            .unwrap_or_else(|_| panic!("Could not drop database: {}", self.db_name));
+    }
+}
+
+/// Minimal SMTP server, which accepts every mail and keeps its content
+struct SmtpSink {
+    socket: SocketAddr,
+    mails: Arc<Mutex<Vec<String>>>,
+}
+
+impl SmtpSink {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let socket = listener.local_addr().unwrap();
+        let mails = Arc::new(Mutex::new(Vec::new()));
+
+        let sink_mails = mails.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mails = sink_mails.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    writer.write_all(b"220 sink\r\n").await.unwrap();
+
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let reply: &[u8] = match line.to_uppercase().as_str() {
+                            "DATA" => {
+                                writer.write_all(b"354 go ahead\r\n").await.unwrap();
+                                let mut mail = String::new();
+                                while let Ok(Some(line)) = lines.next_line().await {
+                                    if line == "." {
+                                        break;
+                                    }
+                                    mail.push_str(&line);
+                                    mail.push('\n');
+                                }
+                                mails.lock().unwrap().push(mail);
+                                b"250 queued\r\n"
+                            }
+                            "QUIT" => {
+                                writer.write_all(b"221 bye\r\n").await.unwrap();
+                                break;
+                            }
+                            _ => b"250 ok\r\n",
+                        };
+                        writer.write_all(reply).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        Self { socket, mails }
+    }
+
+    fn mails(&self) -> Vec<String> {
+        self.mails.lock().unwrap().clone()
+    }
+}
+
+/// Stand-in for the Slack webhook and Web API, which answers with the given status codes
+/// in turn and keeps the body of every request
+struct WebhookStandIn {
+    url: Uri,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl WebhookStandIn {
+    fn start(statuses: Vec<StatusCode>) -> Self {
+        Self::start_with_body(statuses, "")
+    }
+
+    /// Answers every request with the given body
+    fn start_with_body(statuses: Vec<StatusCode>, response: &'static str) -> Self {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let statuses = Arc::new(Mutex::new(statuses.into_iter()));
+
+        let stand_in_requests = requests.clone();
+        let make_service = make_service_fn(move |_| {
+            let requests = stand_in_requests.clone();
+            let statuses = statuses.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request| {
+                    let requests = requests.clone();
+                    let status = statuses.lock().unwrap().next().unwrap_or(StatusCode::OK);
+                    async move {
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        requests
+                            .lock()
+                            .unwrap()
+                            .push(String::from_utf8(body.to_vec()).unwrap());
+                        Response::builder()
+                            .status(status)
+                            .body(Body::from(response))
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let url = format!("http://{}/services/T0/B0/X", server.local_addr())
+            .parse()
+            .unwrap();
+        tokio::spawn(server);
+
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn get_test_configuration() -> Configuration {
+    set_var("SERVICE_SOCKET", "127.0.0.1:0");
+
+    Configuration::init().unwrap()
+}
+
+async fn setup(
+    test_context_name: String,
+    smtp_socket: SocketAddr,
+    webhook_url: Option<Uri>,
+    slack_api_url: Option<Uri>,
+) -> Result<
+    (
+        impl futures::Future<Output = ()>,
+        NotificationServiceClient,
+        Dispatcher,
+        Arc<DbPool>,
+        DbTestContext,
+    ),
+    (),
+> {
+    let configuration = Arc::new(get_test_configuration());
+    let db_test_context = DbTestContext::new(
+        configuration.get_db_connection_base_url(),
+        test_context_name,
+    );
+    let db = Arc::new(get_db_pool(&db_test_context.get_connection_url()));
+    let transport = SmtpTransport::new(
+        &smtp_socket.ip().to_string(),
+        smtp_socket.port(),
+        SmtpTls::None,
+        None,
+        "CODE Library <library@code.berlin>".parse().unwrap(),
+    )
+    .unwrap();
+    let announcer = webhook_url
+        .map(|webhook_url| Arc::new(SlackTransport::new(webhook_url)) as Arc<dyn Announcer>);
+    let templates = Templates::load(
+        &configuration.get_template_dir(),
+        &configuration.get_default_language(),
+    )
+    .unwrap();
+    let mut transports: Vec<Arc<dyn Transport>> = vec![Arc::new(transport)];
+    if let Some(slack_api_url) = slack_api_url {
+        transports.push(Arc::new(SlackDmTransport::new(
+            slack_api_url,
+            "xoxb-token".into(),
+        )));
+    }
+    let dispatcher = Dispatcher::new(
+        configuration.clone(),
+        db.clone(),
+        transports.clone(),
+        announcer.clone(),
+    );
+    let (server, socket) = get_rpc_server(
+        configuration.get_service_socket(),
+        configuration.clone(),
+        db.clone(),
+        Arc::new(templates),
+        transports,
+        announcer,
+    )
+    .await
+    .unwrap();
+    let client = get_rpc_client(socket).await.unwrap();
+
+    Ok((server, client, dispatcher, db, db_test_context))
+}
+
+fn uuid(value: &str) -> Uuid {
+    Uuid::parse_str(value).unwrap()
+}
+
+fn loan_overdue() -> Notification {
+    Notification::LoanOverdue {
+        loan_id: Uuid::new_v4(),
+        copy_id: Uuid::new_v4(),
+        due_at: Utc::now(),
+    }
+}
+
+fn overdue_loans() -> Announcement {
+    Announcement::OverdueLoans {
+        loans: vec![OverdueLoan {
+            loan_id: Uuid::new_v4(),
+            user_id: uuid(sample_data::USER_1),
+            copy_id: uuid("00000000-0000-0000-0000-000000000001"),
+            due_at: Utc::now(),
+        }],
+    }
+}
+
+fn load_delivery_attempts(db_pool: &DbPool) -> Vec<DeliveryAttempt> {
+    schema::delivery_attempts::table
+        .load(&db_pool.get().unwrap())
+        .unwrap()
+}
+
+fn load_notification_states(db_pool: &DbPool) -> Vec<(Uuid, DeliveryStatus, i32)> {
+    use schema::notifications::dsl;
+
+    dsl::notifications
+        .select((dsl::id, dsl::status, dsl::attempts))
+        .load(&db_pool.get().unwrap())
+        .unwrap()
+}
+
+// notify an user by email
+#[tokio::test]
+async fn notify_email_delivered() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap();
+    let delivered = dispatcher.dispatch(Utc::now()).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(1, delivered);
+
+    let mails = smtp_sink.mails();
+    assert_eq!(1, mails.len());
+    assert!(mails[0].contains("To: \"Ada Lovelace\" <ada.lovelace@code.berlin>"));
+    assert!(mails[0].contains("Subject: Your loan is overdue"));
+    assert!(mails[0].contains("Content-Type: multipart/alternative"));
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(1, attempts.len());
+    assert_eq!("email", attempts[0].channel);
+    assert_eq!(None, attempts[0].error);
+
+    let states = load_notification_states(&db_pool);
+    assert_eq!(DeliveryStatus::Delivered, states[0].1);
+}
+
+// notify an user, while the SMTP server is not reachable
+#[tokio::test]
+async fn notify_email_failed() {
+    // Arrange
+    let unused_socket = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let (server, client, dispatcher, db_pool, _db_test_context) =
+        setup(stdext::function_name!().into(), unused_socket, None, None)
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let delivered = dispatcher.dispatch(now).await.unwrap();
+    // the next attempt is not due before the retry delay passed
+    let delivered_before_retry = dispatcher.dispatch(now).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(0, delivered);
+    assert_eq!(0, delivered_before_retry);
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(1, attempts.len());
+    assert!(attempts[0].error.is_some());
+
+    let states = load_notification_states(&db_pool);
+    assert_eq!(DeliveryStatus::Pending, states[0].1);
+    assert_eq!(1, states[0].2);
+}
+
+// dead-letter a notification after all delivery attempts failed and requeue it
+#[tokio::test]
+async fn notify_email_dead_lettered() {
+    // Arrange
+    let unused_socket = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let (server, client, dispatcher, db_pool, _db_test_context) =
+        setup(stdext::function_name!().into(), unused_socket, None, None)
+            .await
+            .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    for day in 0..6 {
+        dispatcher
+            .dispatch(Utc::now() + chrono::Duration::days(day))
+            .await
+            .unwrap();
+    }
+    let failed = client
+        .list_failed_deliveries(context::current(), 0, 10)
+        .await
+        .unwrap()
+        .unwrap();
+    let requeued = client
+        .requeue_delivery(context::current(), failed[0].notification_id)
+        .await
+        .unwrap();
+    let requeued_again = client
+        .requeue_delivery(context::current(), failed[0].notification_id)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(5, load_delivery_attempts(&db_pool).len());
+
+    assert_eq!(1, failed.len());
+    assert_eq!(Some(uuid(sample_data::USER_1)), failed[0].recipient);
+    assert_eq!("loan_overdue", failed[0].kind);
+    assert_eq!("email", failed[0].channel);
+    assert_eq!(5, failed[0].attempts);
+    assert!(failed[0].last_error.is_some());
+
+    assert_eq!(Ok(()), requeued);
+    assert_eq!(Err(Error::InvalidInput), requeued_again);
+    let states = load_notification_states(&db_pool);
+    assert_eq!(DeliveryStatus::Pending, states[0].1);
+    assert_eq!(0, states[0].2);
+}
+
+// requeue a notification, which was dead-lettered before messages were stored in the outbox
+#[tokio::test]
+async fn requeue_delivery_legacy() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let notification_id = Uuid::new_v4();
+    diesel::insert_into(schema::notifications::table)
+        .values((
+            schema::notifications::id.eq(notification_id),
+            schema::notifications::recipient.eq(uuid(sample_data::USER_1)),
+            schema::notifications::kind.eq("loan_overdue"),
+            schema::notifications::parameters.eq(serde_json::to_value(loan_overdue()).unwrap()),
+            schema::notifications::created_at.eq(Utc::now()),
+            schema::notifications::channel.eq("email"),
+            schema::notifications::subject.eq(""),
+            schema::notifications::body.eq(""),
+            schema::notifications::status.eq(DeliveryStatus::Dead),
+        ))
+        .execute(&db_pool.get().unwrap())
+        .unwrap();
+
+    // Act
+    let requeued = client
+        .requeue_delivery(context::current(), notification_id)
+        .await
+        .unwrap();
+    let delivered = dispatcher.dispatch(Utc::now()).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), requeued);
+    assert_eq!(1, delivered);
+
+    let mails = smtp_sink.mails();
+    assert_eq!(1, mails.len());
+    assert!(mails[0].contains("Subject: Your loan is overdue"));
+}
+
+// requeue a notification, which does not exist
+#[tokio::test]
+async fn requeue_delivery_not_exists() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .requeue_delivery(context::current(), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// notify an user, which does not exist
+#[tokio::test]
+async fn notify_user_not_exists() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .notify(context::current(), Uuid::new_v4(), loan_overdue())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+    assert!(load_notification_states(&db_pool).is_empty());
+}
+
+// set the language of an user, in which notifications are sent
+#[tokio::test]
+async fn set_user_language_valid() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .set_user_language(context::current(), uuid(sample_data::USER_1), "ger".into())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+
+    client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    dispatcher.dispatch(Utc::now()).await.unwrap();
+    let mails = smtp_sink.mails();
+    assert_eq!(1, mails.len());
+    assert!(!mails[0].contains("Subject: Your loan is overdue"));
+
+    let languages: Vec<String> = schema::user_settings::table
+        .select(schema::user_settings::language)
+        .load(&db_pool.get().unwrap())
+        .unwrap();
+    assert_eq!(vec!["ger"], languages);
+}
+
+// set a language of an user, for which there are no templates
+#[tokio::test]
+async fn set_user_language_unsupported() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .set_user_language(context::current(), uuid(sample_data::USER_1), "fre".into())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), result);
+}
+
+// set the language of an user, which does not exist
+#[tokio::test]
+async fn set_user_language_user_not_exists() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .set_user_language(context::current(), Uuid::new_v4(), "ger".into())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// announce overdue loans on Slack
+#[tokio::test]
+async fn announce_slack_posted() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let webhook = WebhookStandIn::start(vec![]);
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        Some(webhook.url.clone()),
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .announce(context::current(), overdue_loans())
+        .await
+        .unwrap();
+    let delivered = dispatcher.dispatch(Utc::now()).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(1, delivered);
+
+    let requests = webhook.requests();
+    assert_eq!(1, requests.len());
+    assert!(requests[0].starts_with(r#"{"text":"*1 overdue loan(s)*"#));
+    assert!(requests[0].contains("borrowed by Ada Lovelace"));
+    assert!(smtp_sink.mails().is_empty());
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(1, attempts.len());
+    assert_eq!("slack", attempts[0].channel);
+    assert_eq!(None, attempts[0].error);
+}
+
+// announce on Slack, while the webhook fails temporarily, which is retried from the outbox
+#[tokio::test]
+async fn announce_slack_retried() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let webhook = WebhookStandIn::start(vec![StatusCode::TOO_MANY_REQUESTS]);
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        Some(webhook.url.clone()),
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .announce(context::current(), overdue_loans())
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let delivered = dispatcher.dispatch(now).await.unwrap();
+    let delivered_after_retry = dispatcher
+        .dispatch(now + chrono::Duration::hours(1))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(0, delivered);
+    assert_eq!(1, delivered_after_retry);
+    assert_eq!(2, webhook.requests().len());
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(2, attempts.len());
+    assert_eq!(1, attempts.iter().filter(|a| a.error.is_none()).count());
+}
+
+// announce on Slack, while the webhook rejects the post
+#[tokio::test]
+async fn announce_slack_rejected() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let webhook = WebhookStandIn::start(vec![StatusCode::NOT_FOUND]);
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        Some(webhook.url.clone()),
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .announce(context::current(), overdue_loans())
+        .await
+        .unwrap();
+    let delivered = dispatcher.dispatch(Utc::now()).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(0, delivered);
+    assert_eq!(1, webhook.requests().len());
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(1, attempts.len());
+    assert_eq!(
+        Some("Slack webhook responded with 404 Not Found".into()),
+        attempts[0].error
+    );
+}
+
+// announce without a configured Slack channel
+#[tokio::test]
+async fn announce_slack_disabled() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .announce(context::current(), overdue_loans())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert!(load_notification_states(&db_pool).is_empty());
+}
+
+fn preferences(
+    channels: &[(&str, Channel)],
+    quiet_hours: Option<QuietHours>,
+) -> NotificationPreferences {
+    NotificationPreferences {
+        channels: channels
+            .iter()
+            .map(|(kind, channel)| (kind.to_string(), *channel))
+            .collect(),
+        quiet_hours,
+    }
+}
+
+// get the preferences of an user, which never changed them
+#[tokio::test]
+async fn get_notification_preferences_default() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .get_notification_preferences(context::current(), uuid(sample_data::USER_1))
+        .await
+        .unwrap();
+
+    // Assert
+    let expected = preferences(
+        &[
+            ("account_disabled", Channel::Email),
+            ("hold_ready", Channel::Email),
+            ("loan_due_soon", Channel::Email),
+            ("loan_overdue", Channel::Email),
+        ],
+        None,
+    );
+    assert_eq!(Ok(expected), result);
+}
+
+// update the preferences of an user
+#[tokio::test]
+async fn update_notification_preferences_valid() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let quiet_hours = QuietHours {
+        starts_at: NaiveTime::from_hms(22, 0, 0),
+        ends_at: NaiveTime::from_hms(7, 0, 0),
+        time_zone: "Europe/Berlin".into(),
+    };
+
+    // Act
+    let result = client
+        .update_notification_preferences(
+            context::current(),
+            uuid(sample_data::USER_1),
+            preferences(
+                &[("loan_due_soon", Channel::None)],
+                Some(quiet_hours.clone()),
+            ),
+        )
+        .await
+        .unwrap();
+    let reset = client
+        .update_notification_preferences(
+            context::current(),
+            uuid(sample_data::USER_1),
+            preferences(&[("hold_ready", Channel::None)], None),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    let expected = preferences(
+        &[
+            ("account_disabled", Channel::Email),
+            ("hold_ready", Channel::Email),
+            ("loan_due_soon", Channel::None),
+            ("loan_overdue", Channel::Email),
+        ],
+        Some(quiet_hours),
+    );
+    assert_eq!(Ok(expected), result);
+
+    let expected = preferences(
+        &[
+            ("account_disabled", Channel::Email),
+            ("hold_ready", Channel::None),
+            ("loan_due_soon", Channel::Email),
+            ("loan_overdue", Channel::Email),
+        ],
+        None,
+    );
+    assert_eq!(Ok(expected), reset);
+}
+
+// update the preferences of an user with unknown kinds, channels or time zones
+#[tokio::test]
+async fn update_notification_preferences_invalid() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let user_id = uuid(sample_data::USER_1);
+    let quiet_hours = |time_zone: &str, ends_at| QuietHours {
+        starts_at: NaiveTime::from_hms(22, 0, 0),
+        ends_at,
+        time_zone: time_zone.into(),
+    };
+
+    // Act
+    let unknown_kind = client
+        .update_notification_preferences(
+            context::current(),
+            user_id,
+            preferences(&[("book_returned", Channel::Email)], None),
+        )
+        .await
+        .unwrap();
+    let channel_not_configured = client
+        .update_notification_preferences(
+            context::current(),
+            user_id,
+            preferences(&[("loan_overdue", Channel::SlackDm)], None),
+        )
+        .await
+        .unwrap();
+    let unknown_time_zone = client
+        .update_notification_preferences(
+            context::current(),
+            user_id,
+            preferences(
+                &[],
+                Some(quiet_hours("Europe/Atlantis", NaiveTime::from_hms(7, 0, 0))),
+            ),
+        )
+        .await
+        .unwrap();
+    let empty_window = client
+        .update_notification_preferences(
+            context::current(),
+            user_id,
+            preferences(
+                &[],
+                Some(quiet_hours("Europe/Berlin", NaiveTime::from_hms(22, 0, 0))),
+            ),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::InvalidInput), unknown_kind);
+    assert_eq!(Err(Error::InvalidInput), channel_not_configured);
+    assert_eq!(Err(Error::InvalidInput), unknown_time_zone);
+    assert_eq!(Err(Error::InvalidInput), empty_window);
+}
+
+// update the preferences of an user, which does not exist
+#[tokio::test]
+async fn update_notification_preferences_user_not_exists() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, _db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    // Act
+    let result = client
+        .update_notification_preferences(context::current(), Uuid::new_v4(), preferences(&[], None))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Err(Error::NotFound), result);
+}
+
+// notify an user, which opted out of the notification kind
+#[tokio::test]
+async fn notify_opted_out() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, _dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    client
+        .update_notification_preferences(
+            context::current(),
+            uuid(sample_data::USER_1),
+            preferences(&[("loan_overdue", Channel::None)], None),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert!(load_notification_states(&db_pool).is_empty());
+}
+
+// notify an user during the quiet hours
+#[tokio::test]
+async fn notify_quiet_hours_deferred() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    let now = Utc::now();
+    client
+        .update_notification_preferences(
+            context::current(),
+            uuid(sample_data::USER_1),
+            preferences(
+                &[],
+                Some(QuietHours {
+                    starts_at: (now - chrono::Duration::hours(1)).time(),
+                    ends_at: (now + chrono::Duration::hours(1)).time(),
+                    time_zone: "UTC".into(),
+                }),
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let delivered_quiet = dispatcher.dispatch(Utc::now()).await.unwrap();
+    let delivered_after = dispatcher
+        .dispatch(now + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(0, delivered_quiet);
+    assert_eq!(1, delivered_after);
+    assert_eq!(1, load_delivery_attempts(&db_pool).len());
+    assert_eq!(1, smtp_sink.mails().len());
+
+    let states = load_notification_states(&db_pool);
+    assert_eq!(DeliveryStatus::Delivered, states[0].1);
+    assert_eq!(1, states[0].2);
+}
+
+// notify an user, which prefers direct messages on Slack
+#[tokio::test]
+async fn notify_slack_dm_delivered() {
+    // Arrange
+    let smtp_sink = SmtpSink::start().await;
+    let slack_api = WebhookStandIn::start_with_body(vec![], r#"{"ok":true,"user":{"id":"U0ADA"}}"#);
+    let (server, client, dispatcher, db_pool, _db_test_context) = setup(
+        stdext::function_name!().into(),
+        smtp_sink.socket,
+        None,
+        Some(slack_api.url.clone()),
+    )
+    .await
+    .expect("Could not set up test environment");
+    tokio::spawn(server);
+
+    client
+        .update_notification_preferences(
+            context::current(),
+            uuid(sample_data::USER_1),
+            preferences(&[("loan_overdue", Channel::SlackDm)], None),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Act
+    let result = client
+        .notify(
+            context::current(),
+            uuid(sample_data::USER_1),
+            loan_overdue(),
+        )
+        .await
+        .unwrap();
+    let delivered = dispatcher.dispatch(Utc::now()).await.unwrap();
+
+    // Assert
+    assert_eq!(Ok(()), result);
+    assert_eq!(1, delivered);
+    assert!(smtp_sink.mails().is_empty());
+
+    let requests = slack_api.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!("email=ada.lovelace%40code.berlin", requests[0]);
+    assert!(requests[1].starts_with(r#"{"channel":"U0ADA","text":"*Your loan is overdue*"#));
+
+    let attempts = load_delivery_attempts(&db_pool);
+    assert_eq!(1, attempts.len());
+    assert_eq!("slack_dm", attempts[0].channel);
+    assert_eq!(None, attempts[0].error);
+}
@@ -0,0 +1,16 @@
+//! The `users` table is migrated by the IDENTITY service,
+//! so the tests create a reduced version of it alongside its sample data.
+
+pub const EXTERNAL_TABLES: &str = r#"
+CREATE TABLE users (
+    id UUID PRIMARY KEY,
+    email VARCHAR(50) NOT NULL,
+    given_name VARCHAR(50) NOT NULL,
+    family_name VARCHAR(50) NOT NULL
+);
+
+INSERT INTO users (id, email, given_name, family_name) VALUES
+    ('d1854dea-c0b7-403c-bbe8-fba377453787', 'ada.lovelace@code.berlin', 'Ada', 'Lovelace');
+"#;
+
+pub const USER_1: &str = "d1854dea-c0b7-403c-bbe8-fba377453787";